@@ -12,6 +12,8 @@ fn main() {
         println!("cargo:rustc-cfg=rust_1_34");
     }if Version::new(1, 36, 0) <= rver {
         println!("cargo:rustc-cfg=rust_1_36");
+    }if Version::new(1, 39, 0) <= rver {
+        println!("cargo:rustc-cfg=rust_1_39");
     }
 
     // skeptic::generate_doc_tests(&["../readme.md"]);
@@ -3,17 +3,30 @@ types and traits related to abi stability.
 */
 
 pub(crate) mod abi_checking;
+pub mod extra_checks;
+pub mod fingerprint;
+pub mod layout_cache;
+pub mod layout_diff;
+pub mod layout_snapshot;
 pub mod get_static_equivalent;
 pub mod stable_abi_trait;
+pub mod stable_type_id;
 
 
 mod layout_tests;
 
 pub use self::{
-    abi_checking::exported_check_layout_compatibility as check_layout_compatibility,
+    abi_checking::{
+        exported_check_layout_compatibility as check_layout_compatibility,
+        AbiInstabilityErrors,AbiInstabilityError,
+        AbiInstability,AbiInstability_NE,AbiInstability_Storage,AbiInstability_Interface,
+        ExpectedFound,
+    },
+    fingerprint::LayoutFingerprint,
     get_static_equivalent::{GetStaticEquivalent_,GetStaticEquivalent},
     stable_abi_trait::{
         AbiInfo, AbiInfoWrapper, StableAbi,
         SharedStableAbi,
     },
+    stable_type_id::StableTypeId,
 };
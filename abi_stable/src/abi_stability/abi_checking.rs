@@ -2,7 +2,7 @@
 Functions and types related to the layout checking.
 */
 
-use std::{cmp::Ordering, fmt,mem};
+use std::{cmp::Ordering, fmt,mem,ptr};
 
 #[allow(unused_imports)]
 use core_extensions::{prelude::*,matches};
@@ -13,16 +13,21 @@ use std::{
 };
 // use std::collections::HashSet;
 
-use super::{AbiInfo, AbiInfoWrapper};
+use super::{
+    AbiInfo, AbiInfoWrapper,
+    extra_checks::{ExtraChecks,ExtraAbiErrors},
+    layout_cache::{CheckedPair,LayoutCheckCache,LayoutPath,PathStep},
+};
 use crate::{
+    nonexhaustive_enum::NonExhaustive,
     sabi_types::{ParseVersionError, VersionStrings},
-    prefix_type::{FieldAccessibility,IsConditional},
-    std_types::{RVec, StaticSlice, StaticStr,utypeid::UTypeId,RBoxError,RResult},
+    prefix_type::IsConditional,
+    std_types::{RVec, RStr, StaticSlice, StaticStr,utypeid::UTypeId,RResult,RSome},
     traits::IntoReprC,
     type_layout::{
         TypeLayout, TLData, TLDataDiscriminant, TLField, 
         FullType, ReprAttr, TLDiscriminant,TLPrimitive,
-        TLEnum,IsExhaustive,IncompatibleWithNonExhaustive,TLNonExhaustive,
+        TLEnum,IsExhaustive,IncompatibleWithNonExhaustive,TLNonExhaustive,TLPrefixType,
         TLFieldOrFunction, TLFunction,
         tagging::TagErrors,
     },
@@ -30,11 +35,17 @@ use crate::{
 };
 
 /// All the errors from checking the layout of every nested type in AbiInfo.
-#[derive(Clone, PartialEq)]
+///
+/// This is ffi-safe,so that the layout checker of the executable can return it 
+/// to dynamic libraries that load other dynamic libraries.
+#[derive(Clone, PartialEq,StableAbi)]
 #[repr(C)]
 pub struct AbiInstabilityErrors {
+    /// The expected layout.
     pub interface: &'static AbiInfo,
+    /// The layout that was found.
     pub implementation: &'static AbiInfo,
+    /// The errors for every nested type that had an incompatible layout.
     pub errors: RVec<AbiInstabilityError>,
     _priv:(),
 }
@@ -43,17 +54,82 @@ pub struct AbiInstabilityErrors {
 ///
 /// Error that happen lower or higher on the stack are stored in separate
 ///  `AbiInstabilityError`s.
-#[derive(Debug,Clone, PartialEq)]
+#[derive(Debug,Clone, PartialEq,StableAbi)]
 #[repr(C)]
 pub struct AbiInstabilityError {
+    /// The path (through fields/functions) from the root type to the checked type.
     pub stack_trace: RVec<ExpectedFound<TLFieldOrFunction>>,
-    pub errs: RVec<AbiInstability>,
+    /// The errors for the checked type,
+    /// each wrapped in a `NonExhaustive` since more kinds of errors can be added 
+    /// in minor versions.
+    pub errs: RVec<AbiInstability_NE>,
     pub index: usize,
     _priv:(),
 }
 
-/// An individual error from checking the layout of some type.
-#[derive(Debug, PartialEq,Clone)]
+/**
+An individual error from checking the layout of some type.
+
+This is stored in the `AbiInstability_NE` nonexhaustive enum,
+which can be converted back into this enum with `AbiInstability_NE::as_enum`.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::{AbiInstability,check_layout_compatibility},
+};
+
+mod v1{
+    use abi_stable::StableAbi;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Foo{
+        pub x:u32,
+    }
+}
+
+mod v2{
+    use abi_stable::StableAbi;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Foo{
+        pub x:u64,
+    }
+}
+
+# fn main(){
+
+let errors=check_layout_compatibility(v1::Foo::ABI_INFO,v2::Foo::ABI_INFO)
+    .into_result()
+    .unwrap_err();
+
+let mismatched_size=errors.flattened_errors()
+    .filter_map(|err| err.as_enum().ok() )
+    .any(|err|{
+        match err {
+            AbiInstability::Size(x)=>x.expected==4 && x.found==8,
+            _=>false,
+        }
+    });
+
+assert!(mismatched_size);
+
+# }
+
+```
+
+*/
+#[repr(u8)]
+#[derive(Debug, PartialEq,Clone,StableAbi)]
+#[sabi(kind(WithNonExhaustive(
+    size="[usize;48]",
+    traits(Debug,Display,Clone,PartialEq),
+    assert_nonexhaustive="AbiInstability",
+)))]
 pub enum AbiInstability {
     IsPrefix(ExpectedFound<bool>),
     NonZeroness(ExpectedFound<bool>),
@@ -81,26 +157,36 @@ pub enum AbiInstability {
     TagError{
         err:TagErrors,
     },
+    ExtraCheckError(ExtraAbiErrors),
 }
 
 
 
 use self::AbiInstability as AI;
 
+
+impl AbiInstability{
+    /// Wraps every error in a `NonExhaustive`.
+    fn into_nonexhaustive(errs:RVec<AbiInstability>)->RVec<AbiInstability_NE>{
+        errs.into_iter().map(NonExhaustive::new).collect()
+    }
+}
+
 #[allow(dead_code)]
 impl AbiInstabilityErrors {
     #[cfg(test)]
     pub fn flatten_errors(&self) -> RVec<AbiInstability> {
         self.flattened_errors()
+            .filter_map(|x| x.as_enum().ok() )
+            .cloned()
             .collect::<RVec<AbiInstability>>()
     }
 
-    #[cfg(test)]
-    pub fn flattened_errors<'a>(&'a self) -> impl Iterator<Item=AbiInstability>+'a {
+    /// Iterates over all the individual errors for every nested type.
+    pub fn flattened_errors<'a>(&'a self) -> impl Iterator<Item=&'a AbiInstability_NE>+'a {
         self.errors
             .iter()
             .flat_map(|x| &x.errs )
-            .cloned()
     }
 }
 
@@ -124,10 +210,10 @@ impl fmt::Display for AbiInstabilityErrors {
     }
 }
 
+impl std::error::Error for AbiInstabilityErrors{}
+
 impl fmt::Display for AbiInstabilityError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut extra_err=None::<String>;
-
         write!(f, "{} error(s)", self.errs.len())?;
         if self.stack_trace.is_empty() {
             writeln!(f,".")?;
@@ -149,81 +235,84 @@ impl fmt::Display for AbiInstabilityError {
         writeln!(f)?;
 
         for err in &self.errs {
-            let pair = match err {
-                AI::IsPrefix(v) => ("mismatched prefixness", v.debug_str()),
-                AI::NonZeroness(v) => ("mismatched non-zeroness", v.display_str()),
-                AI::Name(v) => ("mismatched type", v.display_str()),
-                AI::Package(v) => ("mismatched package", v.display_str()),
-                AI::PackageVersionParseError(v) => {
-                    let expected = "a valid version string".to_string();
-                    let found = format!("{:#?}", v);
-
-                    (
-                        "could not parse version string",
-                        Some(ExpectedFound { expected, found }),
-                    )
-                }
-                AI::PackageVersion(v) => ("incompatible package versions", v.display_str()),
-                AI::MismatchedPrefixSize(v) => 
-                    (
-                        "prefix-types have a different prefix", 
-                        v.display_str()
-                    ),
-                AI::Size(v) => ("incompatible type size", v.display_str()),
-                AI::Alignment(v) => ("incompatible type alignment", v.display_str()),
-                AI::GenericParamCount(v) => (
-                    "incompatible ammount of generic parameters",
-                    v.display_str(),
-                ),
-
-                AI::TLDataDiscriminant(v) => ("incompatible data ", v.debug_str()),
-                AI::MismatchedPrimitive(v) => ("incompatible primitive", v.debug_str()),
-                AI::FieldCountMismatch(v) => ("too many fields", v.display_str()),
-                AI::FnLifetimeMismatch(v) => {
-                    ("function pointers reference different lifetimes", v.display_str())
-                }
-                AI::FieldLifetimeMismatch(v) => {
-                    ("field references different lifetimes", v.display_str())
-                }
-                AI::UnexpectedField(v) => ("unexpected field", v.display_str()),
-                AI::TooManyVariants(v) => ("too many variants", v.display_str()),
-                AI::MismatchedPrefixConditionality(v)=>(
-                    "prefix fields differ in whether they are conditional",
-                    v.debug_str()
-                ),
-                AI::MismatchedExhaustiveness(v)=>(
-                    "enums differ in whether they are exhaustive",
-                    v.debug_str()
-                ),
-                AI::UnexpectedVariant(v) => ("unexpected variant", v.debug_str()),
-                AI::ReprAttr(v)=>("incompatible repr attributes",v.debug_str()),
-                AI::EnumDiscriminant(v)=>("different discriminants",v.debug_str()),
-                AI::IncompatibleWithNonExhaustive(e)=>{
-                    extra_err=Some(e.to_string());
-
-                    ("",None)
-                }
-                AI::TagError{err} => {
-                    extra_err=Some(err.to_string());
-
-                    ("", None)
-                },
-            };
+            fmt::Display::fmt(err, f)?;
+        }
+        Ok(())
+    }
+}
 
-            let (error_msg, expected_err):(&'static str, Option<ExpectedFound<String>>)=pair;
+impl fmt::Display for AbiInstability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pair = match self {
+            AI::IsPrefix(v) => ("mismatched prefixness", v.debug_str()),
+            AI::NonZeroness(v) => ("mismatched non-zeroness", v.display_str()),
+            AI::Name(v) => ("mismatched type", v.display_str()),
+            AI::Package(v) => ("mismatched package", v.display_str()),
+            AI::PackageVersionParseError(v) => {
+                let expected = "a valid version string".to_string();
+                let found = format!("{:#?}", v);
 
-            if let Some(expected_err)=expected_err{
-                writeln!(
-                    f,
-                    "\nError:{}\nExpected:\n{}\nFound:\n{}",
-                    error_msg,
-                    expected_err.expected.left_padder(4),
-                    expected_err.found   .left_padder(4),
-                )?;
+                (
+                    "could not parse version string",
+                    Some(ExpectedFound { expected, found }),
+                )
             }
-            if let Some(extra_err)=&extra_err {
-                writeln!(f,"\nExtra:\n{}\n",extra_err.left_padder(4))?;
+            AI::PackageVersion(v) => ("incompatible package versions", v.display_str()),
+            AI::MismatchedPrefixSize(v) => 
+                (
+                    "prefix-types have a different prefix", 
+                    v.display_str()
+                ),
+            AI::Size(v) => ("incompatible type size", v.display_str()),
+            AI::Alignment(v) => ("incompatible type alignment", v.display_str()),
+            AI::GenericParamCount(v) => (
+                "incompatible ammount of generic parameters",
+                v.display_str(),
+            ),
+
+            AI::TLDataDiscriminant(v) => ("incompatible data ", v.debug_str()),
+            AI::MismatchedPrimitive(v) => ("incompatible primitive", v.debug_str()),
+            AI::FieldCountMismatch(v) => ("too many fields", v.display_str()),
+            AI::FnLifetimeMismatch(v) => {
+                ("function pointers reference different lifetimes", v.display_str())
+            }
+            AI::FieldLifetimeMismatch(v) => {
+                ("field references different lifetimes", v.display_str())
+            }
+            AI::UnexpectedField(v) => ("unexpected field", v.display_str()),
+            AI::TooManyVariants(v) => ("too many variants", v.display_str()),
+            AI::MismatchedPrefixConditionality(v)=>(
+                "prefix fields differ in whether they are conditional",
+                v.debug_str()
+            ),
+            AI::MismatchedExhaustiveness(v)=>(
+                "enums differ in whether they are exhaustive",
+                v.debug_str()
+            ),
+            AI::UnexpectedVariant(v) => ("unexpected variant", v.debug_str()),
+            AI::ReprAttr(v)=>("incompatible repr attributes",v.debug_str()),
+            AI::EnumDiscriminant(v)=>("different discriminants",v.debug_str()),
+            AI::IncompatibleWithNonExhaustive(e)=>{
+                return writeln!(f,"\nExtra:\n{}\n",e.to_string().left_padder(4));
             }
+            AI::TagError{err} => {
+                return writeln!(f,"\nExtra:\n{}\n",err.to_string().left_padder(4));
+            },
+            AI::ExtraCheckError(errs) => {
+                return writeln!(f,"\nExtra checks:\n{}\n",errs.to_string().left_padder(4));
+            },
+        };
+
+        let (error_msg, expected_err):(&'static str, Option<ExpectedFound<String>>)=pair;
+
+        if let Some(expected_err)=expected_err{
+            writeln!(
+                f,
+                "\nError:{}\nExpected:\n{}\nFound:\n{}",
+                error_msg,
+                expected_err.expected.left_padder(4),
+                expected_err.found   .left_padder(4),
+            )?;
         }
         Ok(())
     }
@@ -247,14 +336,14 @@ enum FieldContext{
 
 #[derive(Debug, PartialEq,Eq,Ord,PartialOrd,Hash)]
 #[repr(C)]
-struct CheckingUTypeId{
+pub(crate) struct CheckingUTypeId{
     type_id:UTypeId,
     name:StaticStr,
     package:StaticStr,
 }
 
 impl CheckingUTypeId{
-    fn new(this: &'static AbiInfo)->Self{
+    pub(crate) fn new(this: &'static AbiInfo)->Self{
         let layout=this.layout;
         Self{
             type_id:(this.type_id.function)(),
@@ -268,7 +357,7 @@ impl CheckingUTypeId{
 //////
 
 /// Represents an error where a value was expected,but another value was found.
-#[derive(Debug, PartialEq,Clone)]
+#[derive(Debug, PartialEq,Clone,StableAbi)]
 #[repr(C)]
 pub struct ExpectedFound<T> {
     pub expected: T,
@@ -351,17 +440,38 @@ pub struct CheckedNonExhaustiveEnums{
 }
 
 
+/// A pair of types whose user-defined extra checks passed.
+#[derive(Debug,Copy,Clone)]
+#[repr(C)]
+pub struct CheckedExtraChecks{
+    this:&'static AbiInfo,
+    other:&'static AbiInfo,
+    extra_checks:ExtraChecks,
+}
+
+
 ///////////////////////////////////////////////
 
 struct AbiChecker {
     stack_trace: RVec<ExpectedFound<TLFieldOrFunction>>,
+    /// The path from the root types to the types being checked,
+    /// with the same steps as `stack_trace`.
+    path: LayoutPath,
+    /// The paths to the types that must be checked against other loaded types,
+    /// which is what the layout checking cache stores.
+    checked_paths: Vec<LayoutPath>,
     checked_prefix_types:RVec<CheckedPrefixTypes>,
     checked_nonexhaustive_enums:RVec<CheckedNonExhaustiveEnums>,
+    checked_extra_checks:RVec<CheckedExtraChecks>,
 
     visited: HashSet<(CheckingUTypeId,CheckingUTypeId)>,
     errors: RVec<AbiInstabilityError>,
 
     error_index: usize,
+
+    /// Whether an extra check returned a fatal error,
+    /// after which no more types are checked.
+    stop_checking: bool,
 }
 
 ///////////////////////////////////////////////
@@ -370,12 +480,16 @@ impl AbiChecker {
     fn new() -> Self {
         Self {
             stack_trace: RVec::new(),
+            path: Vec::new(),
+            checked_paths: Vec::new(),
             checked_prefix_types:RVec::new(),
             checked_nonexhaustive_enums:RVec::new(),
+            checked_extra_checks:RVec::new(),
 
             visited: HashSet::default(),
             errors: RVec::new(),
             error_index: 0,
+            stop_checking: false,
         }
     }
 
@@ -415,10 +529,10 @@ impl AbiChecker {
             (Ordering::Equal, _) | (Ordering::Less, true) => {}
         }
         
-        let acc_fields:Option<(FieldAccessibility,FieldAccessibility)>=
+        let acc_fields:Option<(&TLPrefixType,&TLPrefixType)>=
             match (&t_lay.data,&o_lay.data) {
                 (TLData::PrefixType(t_prefix), TLData::PrefixType(o_prefix))=>
-                    Some((t_prefix.accessible_fields, o_prefix.accessible_fields)),
+                    Some((t_prefix, o_prefix)),
                 _=>None,
             };
 
@@ -451,10 +565,16 @@ impl AbiChecker {
                     expected:(*this_f).into(),
                     found:(*other_f).into(),
                 });
+                self.path.push(match ctx {
+                    FieldContext::Fields=>PathStep::Field(field_i),
+                    FieldContext::Subfields=>PathStep::Param(field_i),
+                    FieldContext::PhantomFields=>PathStep::PhantomField(field_i),
+                });
 
                 let sf_ctx=FieldContext::Subfields;
 
-                for (t_func,o_func) in this_f.function_range.iter().zip(other_f.function_range) {
+                let functions=this_f.function_range.iter().zip(other_f.function_range);
+                for (func_i,(t_func,o_func)) in functions.enumerate() {
                     self.error_index += 1;
                     let errs_index = self.error_index;
                     let mut errs_ = RVec::<AbiInstability>::new();
@@ -464,6 +584,7 @@ impl AbiChecker {
                         expected:t_func.into(),
                         found:o_func.into(),
                     });
+                    self.path.push(PathStep::Function(func_i));
 
                     if t_func.paramret_lifetime_indices != o_func.paramret_lifetime_indices {
                         push_err(errs, t_func, o_func, |x| x, AI::FnLifetimeMismatch);
@@ -481,18 +602,20 @@ impl AbiChecker {
                     if !errs_.is_empty() {
                         self.errors.push(AbiInstabilityError {
                             stack_trace: self.stack_trace.clone(),
-                            errs: errs_,
+                            errs: AbiInstability::into_nonexhaustive(errs_),
                             index: errs_index,
                             _priv:(),
                         });
                     }
 
                     self.stack_trace.pop();
+                    self.path.pop();
                 }
 
 
                 self.check_inner(t_field_abi, o_field_abi);
                 self.stack_trace.pop();
+                self.path.pop();
             }else{
                 self.stack_trace.push(ExpectedFound{
                     expected:(*this_f).into(),
@@ -520,12 +643,20 @@ impl AbiChecker {
     }
 
     fn check_inner(&mut self, this: &'static AbiInfo, other: &'static AbiInfo) {
+        if self.stop_checking {
+            return;
+        }
+
         let t_cuti=CheckingUTypeId::new(this );
         let o_cuti=CheckingUTypeId::new(other);
         if !self.visited.insert((t_cuti,o_cuti)) {
             return;
         }
 
+        if needs_global_checks(this) {
+            self.checked_paths.push(self.path.clone());
+        }
+
         self.error_index += 1;
         let errs_index = self.error_index;
         let mut errs_ = RVec::<AbiInstability>::new();
@@ -627,6 +758,24 @@ impl AbiChecker {
                 });
             }
 
+            if let RSome(extra_checks)=t_lay.extra_checks {
+                match extra_checks.call(this,other) {
+                    Ok(())=>{
+                        self.checked_extra_checks.push(
+                            CheckedExtraChecks{this,other,extra_checks}
+                        );
+                    }
+                    Err(e)=>{
+                        let is_fatal=e.is_fatal();
+                        errs.push(AI::ExtraCheckError(e));
+                        if is_fatal {
+                            self.stop_checking=true;
+                            return;
+                        }
+                    }
+                }
+            }
+
             match (t_lay.data, o_lay.data) {
                 (TLData::Opaque{..}, _) => {
                     // No checks are necessary
@@ -668,22 +817,7 @@ impl AbiChecker {
                 
                 ( TLData::Enum(t_enum),TLData::Enum(o_enum)  ) => {
                     self.check_enum(errs,this,other,t_enum,o_enum);
-                    let t_as_ne=t_enum.exhaustiveness.as_nonexhaustive();
-                    let o_as_ne=o_enum.exhaustiveness.as_nonexhaustive();
-                    if let (Some(this_ne),Some(other_ne))=(t_as_ne,o_as_ne) {
-                        self.checked_nonexhaustive_enums.push(CheckedNonExhaustiveEnums{
-                            this:NonExhaustiveEnumWithContext{
-                                abi_info:this,
-                                enum_:t_enum,
-                                nonexhaustive:this_ne,
-                            },
-                            other:NonExhaustiveEnumWithContext{
-                                abi_info:other,
-                                enum_:o_enum,
-                                nonexhaustive:other_ne,
-                            },
-                        });
-                    }
+                    self.push_nonexhaustive_enums(this,other,t_enum,o_enum);
                 }
                 (TLData::Enum { .. }, _) => {}
                 
@@ -707,7 +841,7 @@ impl AbiChecker {
         if !errs_.is_empty() {
             self.errors.push(AbiInstabilityError {
                 stack_trace: self.stack_trace.clone(),
-                errs: errs_,
+                errs: AbiInstability::into_nonexhaustive(errs_),
                 index: errs_index,
                 _priv:(),
             });
@@ -841,6 +975,95 @@ impl AbiChecker {
     }
 
 
+    /// Remembers a pair of enums if they are both nonexhaustive,
+    /// to check them against the other loaded versions of the same enum.
+    fn push_nonexhaustive_enums(
+        &mut self,
+        this: &'static AbiInfo,other: &'static AbiInfo,
+        t_enum:&'static TLEnum,o_enum:&'static TLEnum,
+    ){
+        let t_as_ne=t_enum.exhaustiveness.as_nonexhaustive();
+        let o_as_ne=o_enum.exhaustiveness.as_nonexhaustive();
+        if let (Some(this_ne),Some(other_ne))=(t_as_ne,o_as_ne) {
+            self.checked_nonexhaustive_enums.push(CheckedNonExhaustiveEnums{
+                this:NonExhaustiveEnumWithContext{
+                    abi_info:this,
+                    enum_:t_enum,
+                    nonexhaustive:this_ne,
+                },
+                other:NonExhaustiveEnumWithContext{
+                    abi_info:other,
+                    enum_:o_enum,
+                    nonexhaustive:other_ne,
+                },
+            });
+        }
+    }
+
+    /// The pairs of types that must also be checked against the other loaded versions
+    /// of the same types,after this layout check is done.
+    fn checked_pairs(&self)->Vec<CheckedPair>{
+        let mut pairs=Vec::<CheckedPair>::new();
+        let prefix_types=self.checked_prefix_types.iter().map(|x| (x.this,x.other) );
+        let enums=self.checked_nonexhaustive_enums.iter()
+            .map(|x| (x.this.abi_info,x.other.abi_info) );
+        let extra_checks=self.checked_extra_checks.iter().map(|x| (x.this,x.other) );
+        for (this,other) in prefix_types.chain(enums).chain(extra_checks) {
+            let is_new=pairs.iter()
+                .all(|&(t,o)| !ptr::eq(t,this) || !ptr::eq(o,other) );
+            if is_new {
+                pairs.push((this,other));
+            }
+        }
+        pairs
+    }
+
+    /// The paths to the pairs of types returned by `checked_pairs`,
+    /// which is what the layout checking cache stores.
+    fn checked_paths(&self)->Vec<LayoutPath>{
+        self.checked_paths.clone()
+    }
+
+    /// Adds a pair returned by `checked_pairs` in a previous layout check,
+    /// when the result of that layout check was cached.
+    ///
+    /// This runs the user-defined extra checks of the pair,
+    /// since those are arbitrary code that can't be cached.
+    fn add_cached_pair(
+        &mut self,
+        errs: &mut RVec<AbiInstability>,
+        this:&'static AbiInfo,
+        other:&'static AbiInfo,
+    ){
+        let t_lay=this.layout;
+        let o_lay=other.layout;
+        match (t_lay.data,o_lay.data) {
+            (TLData::PrefixType(t_prefix),TLData::PrefixType(o_prefix))=>{
+                self.checked_prefix_types.push(CheckedPrefixTypes{
+                    this,
+                    this_prefix:PrefixTypeMetadata::with_prefix_layout(t_prefix,t_lay),
+                    other,
+                    other_prefix:PrefixTypeMetadata::with_prefix_layout(o_prefix,o_lay),
+                });
+            }
+            (TLData::Enum(t_enum),TLData::Enum(o_enum))=>{
+                self.push_nonexhaustive_enums(this,other,t_enum,o_enum);
+            }
+            _=>{}
+        }
+        if let RSome(extra_checks)=t_lay.extra_checks {
+            match extra_checks.call(this,other) {
+                Ok(())=>{
+                    self.checked_extra_checks.push(CheckedExtraChecks{this,other,extra_checks});
+                }
+                Err(e)=>{
+                    errs.push(AI::ExtraCheckError(e));
+                }
+            }
+        }
+    }
+
+
     fn final_prefix_type_checks(
         &mut self,
         globals:&CheckingGlobals
@@ -929,7 +1152,7 @@ impl AbiChecker {
         }else{
             Err(AbiInstabilityError {
                 stack_trace: self.stack_trace.clone(),
-                errs: errs_,
+                errs: AbiInstability::into_nonexhaustive(errs_),
                 index: self.error_index,
                 _priv:(),
             })
@@ -1029,7 +1252,7 @@ impl AbiChecker {
         }else{
             Err(AbiInstabilityError {
                 stack_trace: self.stack_trace.clone(),
-                errs: errs_,
+                errs: AbiInstability::into_nonexhaustive(errs_),
                 index: self.error_index,
                 _priv:(),
             })
@@ -1038,6 +1261,116 @@ impl AbiChecker {
 
     
 
+    /// Checks the types with user-defined extra checks against
+    /// the previously accepted implementations of the same types,
+    /// remembering the new implementations if they pass.
+    fn final_extra_checks(
+        &mut self,
+        globals:&CheckingGlobals
+    )->Result<(),AbiInstabilityError>{
+        self.error_index += 1;
+        let mut errs_ = RVec::<AbiInstability>::new();
+        let errs =&mut errs_;
+
+        let mut extra_checks_map=globals.extra_checks_map.lock().unwrap();
+
+        for pair in mem::replace(&mut self.checked_extra_checks,Default::default()) {
+            let CheckedExtraChecks{this,other,extra_checks}=pair;
+
+            let t_utid=this .get_utypeid();
+            let o_utid=other.get_utypeid();
+
+            let t_index=extra_checks_map.get_index(&t_utid);
+            let mut o_index=extra_checks_map.get_index(&o_utid);
+
+            if t_index==o_index{
+                o_index=None;
+            }
+
+            {
+                let accepted_with=|index:Option<MapIndex>,abi_info:&'static AbiInfo|{
+                    let mut accepted=index
+                        .and_then(|i| extra_checks_map.get_with_index(i) )
+                        .cloned()
+                        .unwrap_or_default();
+                    push_accepted_layout(&mut accepted,abi_info);
+                    accepted
+                };
+                let t_accepted=accepted_with(t_index,this);
+                let o_accepted=accepted_with(o_index,other);
+
+                for &t_abi in &t_accepted {
+                    for &o_abi in &o_accepted {
+                        let already_checked=
+                            t_abi.get_utypeid()==o_abi.get_utypeid()||
+                            ptr::eq(t_abi,this)&&ptr::eq(o_abi,other);
+                        if already_checked {
+                            continue;
+                        }
+                        if let Err(e)=extra_checks.call(t_abi,o_abi) {
+                            errs.push(AI::ExtraCheckError(e));
+                        }
+                    }
+                }
+            }
+
+            if !errs.is_empty() { break; }
+
+            match (t_index,o_index) {
+                (None,None)=>{
+                    let mut accepted=RVec::new();
+                    push_accepted_layout(&mut accepted,this);
+                    push_accepted_layout(&mut accepted,other);
+
+                    let i=extra_checks_map
+                        .get_or_insert(t_utid,accepted)
+                        .into_inner()
+                        .index;
+                    extra_checks_map.associate_key(o_utid,i);
+                }
+                (Some(im_index),None)|(None,Some(im_index))=>{
+                    let accepted=extra_checks_map.get_mut_with_index(im_index).unwrap();
+                    push_accepted_layout(accepted,this);
+                    push_accepted_layout(accepted,other);
+
+                    extra_checks_map.associate_key(t_utid,im_index);
+                    extra_checks_map.associate_key(o_utid,im_index);
+                }
+                (Some(l_index),Some(r_index))=>{
+                    let (l_accepted,r_accepted)=
+                        extra_checks_map.get2_mut_with_index(l_index,r_index);
+                    let l_accepted=l_accepted.unwrap();
+                    for &abi_info in r_accepted.unwrap().iter() {
+                        push_accepted_layout(l_accepted,abi_info);
+                    }
+
+                    extra_checks_map.replace_with_index(r_index,l_index);
+                }
+            }
+        }
+
+        if errs_.is_empty() {
+            Ok(())
+        }else{
+            Err(AbiInstabilityError {
+                stack_trace: self.stack_trace.clone(),
+                errs: AbiInstability::into_nonexhaustive(errs_),
+                index: self.error_index,
+                _priv:(),
+            })
+        }
+    }
+
+}
+
+
+/// Adds `abi_info` to the accepted implementations of a type with extra checks,
+/// if it isn't already there.
+fn push_accepted_layout(accepted:&mut RVec<&'static AbiInfo>,abi_info:&'static AbiInfo){
+    let utid=abi_info.get_utypeid();
+    if accepted.iter().all(|x| x.get_utypeid()!=utid ) {
+        accepted.push(abi_info);
+    }
 }
 
 
@@ -1063,10 +1396,35 @@ pub(super) fn check_layout_compatibility(
 }
 
 
+pub(crate) fn check_layout_compatibility_with_globals(
+    interface: &'static AbiInfoWrapper,
+    implementation: &'static AbiInfoWrapper,
+    globals:&CheckingGlobals,
+) -> Result<(), AbiInstabilityErrors> {
+    check_layout_compatibility_inner(interface,implementation,None,globals)
+}
+
+
+/// Checks the layout of the root module of a dynamic library,
+/// using the layout checking cache if it is enabled.
+///
+/// `library` identifies the contents of the dynamic library,
+/// it's the value returned by `layout_cache::library_id`.
+pub(crate) fn check_library_layout_with_globals(
+    interface: &'static AbiInfoWrapper,
+    implementation: &'static AbiInfoWrapper,
+    library:&str,
+    globals:&CheckingGlobals,
+) -> Result<(), AbiInstabilityErrors> {
+    check_layout_compatibility_inner(interface,implementation,Some(library),globals)
+}
+
+
 #[inline(never)]
-pub(super) fn check_layout_compatibility_with_globals(
+fn check_layout_compatibility_inner(
     interface: &'static AbiInfoWrapper,
     implementation: &'static AbiInfoWrapper,
+    library:Option<&str>,
     globals:&CheckingGlobals,
 ) -> Result<(), AbiInstabilityErrors> {
     let mut errors: RVec<AbiInstabilityError>;
@@ -1081,14 +1439,45 @@ pub(super) fn check_layout_compatibility_with_globals(
                 expected: false,
                 found: true,
             })]
-            .into(),
+            .piped(RVec::from)
+            .piped(AbiInstability::into_nonexhaustive),
             index: 0,
             _priv:(),
         }]
         .into();
     } else {
         let mut checker = AbiChecker::new();
-        checker.check_inner(interface, implementation);
+
+        let cached=library.and_then(|library|{
+            globals.layout_cache.lock().unwrap()
+                .as_mut()
+                .map(|cache| cache.lookup(interface,implementation,library) )
+        });
+
+        let mut paths_to_cache=None;
+        match cached.as_ref().and_then(|x| x.checked_pairs.as_ref() ) {
+            Some(checked_pairs)=>{
+                let mut errs_=RVec::<AbiInstability>::new();
+                for &(this,other) in checked_pairs {
+                    checker.add_cached_pair(&mut errs_,this,other);
+                }
+                if !errs_.is_empty() {
+                    checker.errors.push(AbiInstabilityError {
+                        stack_trace: RVec::new(),
+                        errs: AbiInstability::into_nonexhaustive(errs_),
+                        index: checker.error_index,
+                        _priv:(),
+                    });
+                }
+            }
+            None=>{
+                checker.check_inner(interface, implementation);
+                if cached.is_some() {
+                    paths_to_cache=Some(checker.checked_paths());
+                }
+            }
+        }
+
         if checker.errors.is_empty() {
             if let Err(e)=checker.final_prefix_type_checks(globals) {
                 checker.errors.push(e);
@@ -1096,7 +1485,17 @@ pub(super) fn check_layout_compatibility_with_globals(
             if let Err(e)=checker.final_non_exhaustive_enum_checks(globals) {
                 checker.errors.push(e);
             }
+            if let Err(e)=checker.final_extra_checks(globals) {
+                checker.errors.push(e);
+            }
+        }
+
+        if let (Some(lookup),Some(paths),true)=(&cached,paths_to_cache,checker.errors.is_empty()){
+            if let Some(cache)=&mut *globals.layout_cache.lock().unwrap() {
+                cache.insert(lookup,paths);
+            }
         }
+
         errors = checker.errors;
     }
 
@@ -1114,16 +1513,60 @@ pub(super) fn check_layout_compatibility_with_globals(
     }
 }
 
+/// Returns the pairs of prefix types,nonexhaustive enums,and types with extra checks
+/// that are compared when checking the layout of `interface` against `implementation`.
+pub(crate) fn checked_pairs_of(
+    interface: &'static AbiInfoWrapper,
+    implementation: &'static AbiInfoWrapper,
+)->Vec<CheckedPair>{
+    let mut checker = AbiChecker::new();
+    checker.check_inner(interface.get(), implementation.get());
+    checker.checked_pairs()
+}
+
+
+/// Whether the type must be checked against the other loaded versions of the same type
+/// after its layout is checked.
+fn needs_global_checks(abi_info:&'static AbiInfo)->bool{
+    let layout=abi_info.layout;
+    layout.extra_checks.is_rsome() ||
+    match layout.data {
+        TLData::PrefixType{..}=>true,
+        TLData::Enum(enum_)=>enum_.exhaustiveness.is_nonexhaustive(),
+        _=>false,
+    }
+}
+
+
+/**
+Checks that the layout of the root module of a dynamic library is compatible,
+using the layout checking cache if it is enabled.
+*/
+pub(crate) extern fn check_library_layout_for_ffi(
+    interface: &'static AbiInfoWrapper,
+    implementation: &'static AbiInfoWrapper,
+    library:RStr<'_>,
+) -> RResult<(), AbiInstabilityErrors> {
+    extern_fn_panic_handling!{
+        check_library_layout_with_globals(
+            interface,
+            implementation,
+            library.as_str(),
+            get_checking_globals(),
+        ).into_c()
+    }
+}
+
+
 /**
 Checks that the layout of `interface` is compatible with `implementation`,
 */
 pub(crate) extern fn check_layout_compatibility_for_ffi(
     interface: &'static AbiInfoWrapper,
     implementation: &'static AbiInfoWrapper,
-) -> RResult<(), RBoxError> {
+) -> RResult<(), AbiInstabilityErrors> {
     extern_fn_panic_handling!{
         check_layout_compatibility(interface,implementation)
-            .map_err(RBoxError::from_fmt)
             .into_c()
     }
 }
@@ -1150,9 +1593,9 @@ and the second must be actual layout.
 pub extern fn exported_check_layout_compatibility(
     interface: &'static AbiInfoWrapper,
     implementation: &'static AbiInfoWrapper,
-) -> RResult<(), RBoxError> {
+) -> RResult<(), AbiInstabilityErrors> {
     extern_fn_panic_handling!{
-        (crate::globals::initialized_globals().layout_checking)
+        crate::globals::initialized_globals().layout_checking()
             (interface,implementation)
     }
 }
@@ -1165,7 +1608,7 @@ use std::sync::Mutex;
 
 use crate::{
     sabi_types::LateStaticRef,
-    multikey_map::MultiKeyMap,
+    multikey_map::{MultiKeyMap,MapIndex},
     prefix_type::PrefixTypeMetadata,
     utils::leak_value,
 };
@@ -1174,6 +1617,10 @@ use crate::{
 pub struct CheckingGlobals{
     pub(crate) prefix_type_map:Mutex<MultiKeyMap<UTypeId,PrefixTypeMetadata>>,
     pub(crate) nonexhaustive_map:Mutex<MultiKeyMap<UTypeId,NonExhaustiveEnumWithContext>>,
+    /// The implementations of types with extra checks that were accepted so far.
+    pub(crate) extra_checks_map:Mutex<MultiKeyMap<UTypeId,RVec<&'static AbiInfo>>>,
+    /// The on-disk cache of layout checks,enabled with `enable_layout_check_cache`.
+    pub(crate) layout_cache:Mutex<Option<LayoutCheckCache>>,
 }
 
 impl CheckingGlobals{
@@ -1181,6 +1628,8 @@ impl CheckingGlobals{
         CheckingGlobals{
             prefix_type_map:MultiKeyMap::new().piped(Mutex::new),
             nonexhaustive_map:MultiKeyMap::new().piped(Mutex::new),
+            extra_checks_map:MultiKeyMap::new().piped(Mutex::new),
+            layout_cache:Mutex::new(None),
         }
     }
 }
@@ -1,75 +1,145 @@
-/*
+/*!
+User-defined layout checks,
+for invariants that can't be expressed with the layout of a type.
 
-The untested prototype of a way for users to add extra layout checks.
+These are declared with the `#[sabi(extra_checks="function_path")]`
+attribute of the `StableAbi` derive macro,
+or with `TypeLayout::set_extra_checks` for manual implementations of `StableAbi`.
 
-The main reasons this is not an accessible module are:
+# Which checks are run
 
-- I haven't figured out what to do with respect to merging 
-    TagVariant::Ignored in the tag of a TypeLayout,
-    in the case that someone wants to load multiple 
-    dynamic libraries with the same interface,
-    which have to be compatible to be loaded.
+The extra checks of the interface (the expected type layout) are the ones that are run,
+passing the interface and implementation `AbiInfo`s to them.
 
+The extra checks of the implementation are ignored.
 
+# Multiple implementations of the same interface
+
+When multiple dynamic libraries use the same type,
+every implementation of the type that passes the extra checks is remembered,
+and is also checked against each implementation loaded afterwards,
+passing the previously loaded implementation as the interface.
+
+This means that the extra checks of a type must accept a pair of implementations
+that can be used together,not only an interface and an implementation.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::{
+        check_layout_compatibility,
+        extra_checks::{ExtraChecksParams,ExtraAbiErrors,ExtraAbiError,ErrorSeriousness},
+    },
+    std_types::{RResult,ROk,RErr},
+};
+
+/// A buffer allocated by the global allocator of the library that created it,
+/// which is only known to be the same allocator if the package versions are equal.
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(extra_checks="same_package_version")]
+pub struct Buffer{
+    ptr:*mut u8,
+    len:usize,
+}
+
+extern "C" fn same_package_version(params:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    let (_,interface_version)=params.interface().layout.package_and_version();
+    let (_,impl_version)=params.implementation().layout.package_and_version();
+
+    if interface_version==impl_version {
+        ROk(())
+    }else{
+        let error=ExtraAbiError::new(
+            "different package versions",
+            format!("expected version {},found {}",interface_version,impl_version),
+            ErrorSeriousness::Fatal,
+        );
+        RErr(error.into())
+    }
+}
+
+# fn main(){
+
+assert!( check_layout_compatibility(Buffer::ABI_INFO,Buffer::ABI_INFO).is_ok() );
+
+# }
+
+```
 
 */
 
+use std::{
+    error::Error as ErrorTrait,
+    fmt::{self,Display},
+};
+
+use core_extensions::StringExt;
+
 use crate::{
-    erased_types::trait_objects::DebugDisplayObject,
+    abi_stability::AbiInfo,
+    std_types::{RResult,RString,RVec},
 };
 
+
 /**
 Extra layout checks,passed by the user in either:
 
-- the StableAbi derive macro:
-    in the `#[sabi(extra_checks=" funtion_name::<type_params> ")]` helper attribute.
+- The `StableAbi` derive macro:
+    in the `#[sabi(extra_checks="function_path")]` helper attribute.
 
-- the `extra_checks` field of `TypeLayoutParams`,
+- `TypeLayout::set_extra_checks`.
+
+The function must not panic,
+use the `extern_fn_panic_handling` macro if it can panic.
 
 */
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, PartialEq,StableAbi)]
 pub struct ExtraChecks{
-    pub func:extern "C" fn(ExtraChecksParams)->RResult<(),ExtraAbiErrors>
+    pub check:extern "C" fn(ExtraChecksParams)->RResult<(),ExtraAbiErrors>,
 }
 
+
+/// The parameters passed to `ExtraChecks.check`.
 #[repr(C)]
-#[derive(Debug, PartialEq,Eq,StableAbi)]
-pub struct ExtraChecksParams<'tag>{
-    interface    :&'static AbiInfo,
-    interface_tag:&'tag CheckableTag,
-    impl_   :&'static AbiInfo,
-    impl_tag:&'tag CheckableTag,
+#[derive(Debug, Copy, Clone, PartialEq,StableAbi)]
+pub struct ExtraChecksParams{
+    interface:&'static AbiInfo,
+    implementation:&'static AbiInfo,
 }
 
 
-
-/// All the errors returned by `ExtraChecks.func`,as well as how serious they are.
+/// All the errors returned by `ExtraChecks.check`,as well as how serious they are.
 #[repr(C)]
-#[derive(Debug, PartialEq,Eq,StableAbi)]
+#[derive(Debug, Clone, PartialEq,Eq,StableAbi)]
 pub struct ExtraAbiErrors{
     pub errors:RVec<ExtraAbiError>,
 }
 
 
-/// An error returned by `ExtraChecks.func`.
+/// An error returned by `ExtraChecks.check`.
 #[repr(C)]
-#[derive(Debug, PartialEq,Eq,StableAbi)]
+#[derive(Debug, Clone, PartialEq,Eq,StableAbi)]
 pub struct ExtraAbiError{
+    /// A short description of the kind of error.
     pub name:RString,
+    /// A description of the error,including the values involved.
     pub description:RString,
-    pub error:DebugDisplayObject,
     pub seriousness:ErrorSeriousness,
 }
 
 
-/// How serious an error returned from `ExtraChecks.func`,
+/// How serious an error returned from `ExtraChecks.check`,
 /// determining whether layout checking immediately returns an error or
 /// collects a few more errors.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq,Eq,StableAbi)]
 pub enum ErrorSeriousness{
-    /// An error that stops layout checking immediately.
+    /// An error that stops layout checking immediately,
+    /// so no more types are checked.
     Fatal,
 
     /**
@@ -81,3 +151,92 @@ Eg:If this error comes from checking a field,
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+
+
+impl ExtraChecks{
+    /// Calls the check function with the interface and implementation.
+    pub fn call(&self,interface:&'static AbiInfo,implementation:&'static AbiInfo)
+    ->Result<(),ExtraAbiErrors>
+    {
+        (self.check)(ExtraChecksParams{interface,implementation})
+            .into_result()
+    }
+}
+
+
+impl ExtraChecksParams{
+    /// The expected layout,
+    /// or a previously loaded implementation when checking multiple implementations.
+    pub fn interface(&self)->&'static AbiInfo{
+        self.interface
+    }
+
+    /// The layout that was found.
+    pub fn implementation(&self)->&'static AbiInfo{
+        self.implementation
+    }
+}
+
+
+impl ExtraAbiErrors{
+    /// Constructs an ExtraAbiErrors from a list of errors.
+    pub fn new(errors:RVec<ExtraAbiError>)->Self{
+        Self{errors}
+    }
+
+    /// Whether any of the errors is fatal.
+    pub fn is_fatal(&self)->bool{
+        self.errors.iter().any(|e| e.seriousness==ErrorSeriousness::Fatal )
+    }
+}
+
+
+impl From<ExtraAbiError> for ExtraAbiErrors{
+    fn from(error:ExtraAbiError)->Self{
+        Self::new(vec![error].into())
+    }
+}
+
+
+impl ExtraAbiError{
+    /// Constructs an ExtraAbiError.
+    pub fn new<N,D>(name:N,description:D,seriousness:ErrorSeriousness)->Self
+    where
+        N:Into<RString>,
+        D:Into<RString>,
+    {
+        Self{
+            name:name.into(),
+            description:description.into(),
+            seriousness,
+        }
+    }
+}
+
+
+impl Display for ExtraAbiErrors{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        for error in &self.errors {
+            Display::fmt(error,f)?;
+        }
+        Ok(())
+    }
+}
+
+impl ErrorTrait for ExtraAbiErrors{}
+
+
+impl Display for ExtraAbiError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        writeln!(
+            f,
+            "\n{:?} error:{}\n{}",
+            self.seriousness,
+            self.name,
+            self.description.as_str().left_padder(4),
+        )
+    }
+}
+
+impl ErrorTrait for ExtraAbiError{}
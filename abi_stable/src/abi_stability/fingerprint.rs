@@ -0,0 +1,419 @@
+/*!
+Stable fingerprints of the type layouts of a type and every type it references,
+used to cache the results of layout checking.
+*/
+
+use std::{
+    collections::HashMap,
+    fmt::{self,Debug,Display,Write},
+    num::ParseIntError,
+};
+
+use super::{
+    AbiInfo,AbiInfoWrapper,
+    abi_checking::CheckingUTypeId,
+};
+
+use crate::type_layout::{TLData,TLField,TLFunction};
+
+
+/**
+A hash of the `TypeLayout` of a type and every type it references,
+which is the same in every process and in every build of a dynamic library
+(as long as the types don't change).
+
+This is computed from everything that layout checking compares,
+as well as the version of abi_stable,
+so two types with the same fingerprint are either both compatible
+with another type or both incompatible.
+
+It does not include the user-defined extra checks,
+since they are arbitrary code.
+
+Computing the fingerprint walks every type referenced by the type,
+so it costs about as much as checking the layout of the type.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::LayoutFingerprint,
+    std_types::RString,
+};
+
+let fingerprint=LayoutFingerprint::of(<RString as StableAbi>::ABI_INFO);
+
+assert_eq!(fingerprint,LayoutFingerprint::of(<RString as StableAbi>::ABI_INFO));
+assert_ne!(fingerprint,LayoutFingerprint::of(<u32 as StableAbi>::ABI_INFO));
+
+// The fingerprint can be converted to and from a hexadecimal string.
+let hex=fingerprint.to_string();
+assert_eq!(LayoutFingerprint::from_hex(&hex),Ok(fingerprint));
+
+```
+
+*/
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Ord,PartialOrd,Hash)]
+pub struct LayoutFingerprint{
+    hash:u128,
+}
+
+
+impl LayoutFingerprint{
+    /// Computes the fingerprint of the type and every type it references.
+    pub fn of(abi_info:&'static AbiInfoWrapper)->Self{
+        Self::of_abi_info(abi_info.get())
+    }
+
+    pub(crate) fn of_abi_info(abi_info:&'static AbiInfo)->Self{
+        LayoutGraph::new(abi_info).fingerprint
+    }
+
+    /// Parses the fingerprint from the 32 hexadecimal digits that it is displayed as.
+    pub fn from_hex(hex:&str)->Result<Self,ParseIntError>{
+        u128::from_str_radix(hex,16).map(|hash| Self{hash} )
+    }
+
+    pub(crate) fn hash(self)->u128{
+        self.hash
+    }
+}
+
+
+impl Display for LayoutFingerprint{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        write!(f,"{:032x}",self.hash)
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// The fingerprint of a type,and all the types it references,
+/// indexed in the order they are first found.
+#[derive(Debug)]
+pub(crate) struct LayoutGraph{
+    pub(crate) fingerprint:LayoutFingerprint,
+    nodes:Vec<&'static AbiInfo>,
+    indices:HashMap<CheckingUTypeId,usize>,
+}
+
+
+impl LayoutGraph{
+    pub(crate) fn new(root:&'static AbiInfo)->Self{
+        let mut builder=GraphBuilder{
+            hasher:FingerprintHasher::new(),
+            nodes:Vec::new(),
+            indices:HashMap::new(),
+        };
+        builder.hash_abi_info(root);
+        Self{
+            fingerprint:LayoutFingerprint{hash:builder.hasher.state},
+            nodes:builder.nodes,
+            indices:builder.indices,
+        }
+    }
+
+    /// Gets the index of the type in this graph.
+    pub(crate) fn index_of(&self,abi_info:&'static AbiInfo)->Option<usize>{
+        self.indices.get(&CheckingUTypeId::new(abi_info)).cloned()
+    }
+
+    /// Gets every type in the graph,in the order they were first found.
+    pub(crate) fn nodes(&self)->&[&'static AbiInfo]{
+        &self.nodes
+    }
+
+    /// Gets the type at the index.
+    pub(crate) fn get(&self,index:usize)->Option<&'static AbiInfo>{
+        self.nodes.get(index).cloned()
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// Changing how fingerprints are computed requires changing this string,
+/// so that fingerprints from different versions are never equal.
+const FINGERPRINT_SEED:&str="abi_stable 0.7 layout fingerprint,format 1";
+
+/// A 128 bit FNV-1a hasher,which unlike `DefaultHasher` is guaranteed to be the same
+/// in every version of Rust.
+pub(crate) struct FingerprintHasher{
+    pub(crate) state:u128,
+}
+
+impl FingerprintHasher{
+    const OFFSET_BASIS:u128=0x6c62272e07bb014262b821756295c58d;
+    const PRIME:u128=0x0000000001000000000000000000013B;
+
+    fn new()->Self{
+        Self::with_seed(FINGERPRINT_SEED)
+    }
+
+    /// Constructs a hasher whose hashes are different for every `seed`.
+    pub(crate) fn with_seed(seed:&str)->Self{
+        let mut this=Self{state:Self::OFFSET_BASIS};
+        this.hash_str(seed);
+        this
+    }
+
+    pub(crate) fn hash_bytes(&mut self,bytes:&[u8]){
+        for &byte in bytes {
+            self.state^=byte as u128;
+            self.state=self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    pub(crate) fn hash_u64(&mut self,n:u64){
+        self.hash_bytes(&[
+            n as u8,(n>>8) as u8,(n>>16) as u8,(n>>24) as u8,
+            (n>>32) as u8,(n>>40) as u8,(n>>48) as u8,(n>>56) as u8,
+        ]);
+    }
+
+    fn hash_usize(&mut self,n:usize){
+        self.hash_u64(n as u64);
+    }
+
+    fn hash_bool(&mut self,b:bool){
+        self.hash_bytes(&[b as u8]);
+    }
+
+    pub(crate) fn hash_str(&mut self,s:&str){
+        self.hash_usize(s.len());
+        self.hash_bytes(s.as_bytes());
+    }
+
+    /// Hashes the Debug representation of a value that doesn't contain pointers.
+    fn hash_debug<T:Debug>(&mut self,value:&T){
+        let _=write!(self,"{:?}",value);
+        // 0xFF never appears in utf-8,so this separates consecutive values.
+        self.hash_bytes(&[0xFF]);
+    }
+}
+
+impl Write for FingerprintHasher{
+    fn write_str(&mut self,s:&str)->fmt::Result{
+        self.hash_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+
+struct GraphBuilder{
+    hasher:FingerprintHasher,
+    nodes:Vec<&'static AbiInfo>,
+    indices:HashMap<CheckingUTypeId,usize>,
+}
+
+impl GraphBuilder{
+    /// Hashes a type,
+    /// only hashing the index of the type if it was already hashed,
+    /// so that recursive types can be hashed.
+    fn hash_abi_info(&mut self,abi_info:&'static AbiInfo){
+        let next_index=self.nodes.len();
+        let index=*self.indices.entry(CheckingUTypeId::new(abi_info)).or_insert(next_index);
+        if index!=next_index {
+            self.hasher.hash_bytes(b"r");
+            self.hasher.hash_usize(index);
+            return;
+        }
+        self.nodes.push(abi_info);
+        self.hasher.hash_bytes(b"n");
+
+        let layout=abi_info.layout;
+        let (package,version)=layout.package_and_version();
+        let hasher=&mut self.hasher;
+
+        hasher.hash_debug(&abi_info.kind);
+        hasher.hash_bool(abi_info.prefix_kind);
+        hasher.hash_bool(abi_info.is_nonzero);
+        hasher.hash_str(layout.name.as_str());
+        hasher.hash_str(package.as_str());
+        hasher.hash_str(version.version.as_str());
+        hasher.hash_usize(layout.full_type.generics.lifetime.len());
+        hasher.hash_usize(layout.size);
+        hasher.hash_usize(layout.alignment);
+        hasher.hash_debug(&layout.repr_attr);
+        hasher.hash_debug(&layout.tag);
+        hasher.hash_bool(layout.extra_checks.is_rsome());
+
+        self.hash_fields(layout.phantom_fields.as_slice().iter().cloned());
+
+        self.hasher.hash_debug(&layout.data.as_discriminant());
+        match layout.data {
+            TLData::Primitive(prim)=>{
+                self.hasher.hash_debug(&prim);
+            }
+            TLData::Opaque=>{}
+            TLData::Struct{fields}|TLData::Union{fields}=>{
+                self.hash_fields(fields.get_fields());
+            }
+            TLData::Enum(enum_)=>{
+                self.hasher.hash_str(enum_.variant_names.as_str());
+                self.hasher.hash_debug(&enum_.exhaustiveness);
+                self.hasher.hash_debug(&enum_.discriminants);
+                self.hasher.hash_debug(&enum_.field_count);
+                self.hash_fields(enum_.fields.get_fields());
+            }
+            TLData::PrefixType(prefix)=>{
+                self.hasher.hash_usize(prefix.first_suffix_field);
+                self.hasher.hash_debug(&prefix.accessible_fields);
+                // Only hashed when there are more than 64 fields,
+                // so that the fingerprint of smaller prefix types doesn't change.
+                let more_accessible_fields=prefix.more_accessible_fields.as_slice();
+                if !more_accessible_fields.is_empty() {
+                    self.hasher.hash_debug(&more_accessible_fields);
+                }
+                self.hasher.hash_debug(&prefix.conditional_prefix_fields);
+                self.hash_fields(prefix.fields.get_fields());
+            }
+        }
+    }
+
+    fn hash_fields<I>(&mut self,fields:I)
+    where
+        I:ExactSizeIterator<Item=TLField>,
+    {
+        self.hasher.hash_usize(fields.len());
+        for field in fields {
+            self.hash_field(&field);
+        }
+    }
+
+    fn hash_field(&mut self,field:&TLField){
+        self.hasher.hash_str(field.name.as_str());
+        self.hasher.hash_debug(&field.lifetime_indices);
+        self.hasher.hash_debug(&field.field_accessor);
+        self.hasher.hash_bool(field.is_function);
+
+        self.hasher.hash_usize(field.function_range.len());
+        for function in field.function_range {
+            self.hash_function(&function);
+        }
+
+        self.hash_abi_info(field.abi_info.get());
+    }
+
+    fn hash_function(&mut self,function:&TLFunction){
+        self.hasher.hash_str(function.name.as_str());
+        self.hasher.hash_str(function.bound_lifetimes.as_str());
+        self.hasher.hash_debug(&function.paramret_lifetime_indices);
+        self.hash_fields(function.get_params_ret_iter());
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::{
+        StableAbi,
+        std_types::{RString,RVec,ROption},
+    };
+
+    mod a{
+        use super::*;
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Recursive{
+            pub value:u32,
+            pub next:ROption<&'static Recursive>,
+        }
+    }
+
+    mod b{
+        use super::*;
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Recursive{
+            pub value:u32,
+            pub next:ROption<&'static Recursive>,
+        }
+    }
+
+    mod c{
+        use super::*;
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Recursive{
+            pub value:i32,
+            pub next:ROption<&'static Recursive>,
+        }
+    }
+
+    mod returns_u8{
+        use super::*;
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Callback{
+            pub function:extern "C" fn(u8)->u8,
+        }
+    }
+
+    mod returns_u16{
+        use super::*;
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Callback{
+            pub function:extern "C" fn(u8)->u16,
+        }
+    }
+
+    #[test]
+    fn same_and_different_types(){
+        let a=LayoutFingerprint::of(a::Recursive::ABI_INFO);
+        let b=LayoutFingerprint::of(b::Recursive::ABI_INFO);
+        let c=LayoutFingerprint::of(c::Recursive::ABI_INFO);
+
+        // Types with the same definition have the same fingerprint.
+        assert_eq!(a,b);
+        assert_ne!(a,c);
+
+        let list=vec![
+            LayoutFingerprint::of(<RString as StableAbi>::ABI_INFO),
+            LayoutFingerprint::of(<RVec<u8> as StableAbi>::ABI_INFO),
+            LayoutFingerprint::of(<RVec<u16> as StableAbi>::ABI_INFO),
+            LayoutFingerprint::of(returns_u8::Callback::ABI_INFO),
+            LayoutFingerprint::of(returns_u16::Callback::ABI_INFO),
+            a,
+            c,
+        ];
+        for (i,l) in list.iter().enumerate() {
+            for (j,r) in list.iter().enumerate() {
+                assert_eq!(i==j,l==r,"{} {}",i,j);
+            }
+        }
+    }
+
+    #[test]
+    fn graph_indices(){
+        let abi_info=a::Recursive::ABI_INFO.get();
+        let graph=LayoutGraph::new(abi_info);
+
+        assert_eq!(graph.index_of(abi_info),Some(0));
+        assert_eq!(graph.get(0).map(|x| x.layout.name ),Some(abi_info.layout.name));
+
+        for index in 0..graph.nodes.len() {
+            assert_eq!(graph.index_of(graph.get(index).unwrap()),Some(index));
+        }
+        assert_eq!(graph.index_of(c::Recursive::ABI_INFO.get()),None);
+    }
+
+    #[test]
+    fn hex_roundtrip(){
+        let fingerprint=LayoutFingerprint::of(a::Recursive::ABI_INFO);
+        let hex=fingerprint.to_string();
+        assert_eq!(hex.len(),32);
+        assert_eq!(LayoutFingerprint::from_hex(&hex),Ok(fingerprint));
+        assert!(LayoutFingerprint::from_hex("not hex").is_err());
+    }
+}
@@ -0,0 +1,380 @@
+/*!
+An optional on-disk cache of the results of layout checking,
+so that loading a dynamic library that was already checked in a previous process
+does not compare every type it references.
+
+The cache is keyed by the `LayoutFingerprint`s of the expected and found layouts
+(computed once per process for every root module type),
+and the path,size,and modification time of the dynamic library file.
+The fingerprint of the found layout is what ensures that the library has the cached layout,
+the path,size,and modification time of the file are only used to skip the lookup
+for library files that were never checked.
+Only successful checks are stored.
+
+Only the libraries loaded with `RootModule::load_from` (and related functions)
+and `PluginRegistry` use the cache.
+
+Loading a library with a cached layout still checks it against
+other versions of the same prefix types/nonexhaustive enums/types with extra checks
+that were loaded in the same process,
+and still runs the user-defined extra checks of the types in the library.
+
+# Example
+
+```
+use abi_stable::abi_stability::layout_cache;
+
+let path=std::env::temp_dir().join("abi_stable_layout_cache_example.txt");
+
+// This must be called in the executable,before loading any dynamic library.
+layout_cache::enable_layout_check_cache(&path).unwrap();
+
+// Load dynamic libraries here.
+
+layout_cache::disable_layout_check_cache();
+
+# let _=std::fs::remove_file(&path);
+```
+
+*/
+
+use std::{
+    collections::{HashMap,HashSet},
+    fs::{self,OpenOptions},
+    io::{self,Write},
+    path::{Path,PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use super::{
+    AbiInfo,
+    abi_checking::get_checking_globals,
+    fingerprint::LayoutFingerprint,
+};
+
+use crate::type_layout::{TLData,TLField,TLFunction};
+
+
+/**
+Enables the layout checking cache,stored in the file at `path`.
+
+The file is created if it doesn't exist,
+and every successful layout check that wasn't already cached is appended to it.
+
+This must be called in the executable before loading any dynamic library,
+since the executable does the layout checking of every dynamic library.
+
+# Errors
+
+This returns an error if the file could not be read or created.
+
+*/
+pub fn enable_layout_check_cache(path:&Path)->io::Result<()>{
+    let cache=LayoutCheckCache::load(path)?;
+    *get_checking_globals().layout_cache.lock().unwrap()=Some(cache);
+    Ok(())
+}
+
+
+/// Disables the layout checking cache,which is disabled by default.
+pub fn disable_layout_check_cache(){
+    *get_checking_globals().layout_cache.lock().unwrap()=None;
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// A pair of expected and found types in the same position of their type graphs.
+pub(crate) type CheckedPair=(&'static AbiInfo,&'static AbiInfo);
+
+
+/// A step in the path from the root type to a type it references.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub(crate) enum PathStep{
+    /// The nth field of the type
+    /// (the fields of a struct,union,prefix type,or of all the variants of an enum).
+    Field(usize),
+    /// The nth phantom field of the type.
+    PhantomField(usize),
+    /// The nth function pointer in the field.
+    Function(usize),
+    /// The nth parameter of the function,the return type being the last one.
+    Param(usize),
+}
+
+
+/// The path from the root type to a type it references.
+pub(crate) type LayoutPath=Vec<PathStep>;
+
+
+/// The layout checks that were successful in previous processes.
+#[derive(Debug)]
+pub(crate) struct LayoutCheckCache{
+    path:PathBuf,
+    /// The pairs of types that need to be checked against other loaded types,
+    /// stored as the path to them from the root types.
+    entries:HashMap<CacheKey,Vec<LayoutPath>>,
+    /// The interfaces and library files of the entries,
+    /// used to avoid computing the fingerprint of the implementation
+    /// when it can't be in the cache.
+    libraries:HashSet<(LayoutFingerprint,String)>,
+    /// The fingerprints of the layouts computed so far,
+    /// keyed by the address of the AbiInfo.
+    fingerprints:HashMap<usize,LayoutFingerprint>,
+}
+
+
+/// Identifies the pair of layouts that was checked.
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub(crate) struct CacheKey{
+    interface:LayoutFingerprint,
+    implementation:LayoutFingerprint,
+    /// The value returned by `library_id`.
+    library:String,
+}
+
+
+/// The result of looking up a pair of layouts in the cache.
+pub(crate) struct CacheLookup{
+    interface:LayoutFingerprint,
+    implementation:&'static AbiInfo,
+    library:String,
+    /// The pairs of types that need to be checked against other loaded types,
+    /// None if the layouts weren't in the cache.
+    pub(crate) checked_pairs:Option<Vec<CheckedPair>>,
+}
+
+
+/**
+Identifies the contents of the dynamic library at `path` without reading it,
+with its canonical path,size,and modification time.
+
+Returns None if the metadata of the file could not be read.
+*/
+pub(crate) fn library_id(path:&Path)->Option<String>{
+    let path=fs::canonicalize(path).ok()?;
+    let metadata=fs::metadata(&path).ok()?;
+    let modified=metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    let path=path.to_str()?;
+    // The fields of a cache entry are separated with tabs.
+    if path.contains(|c| c=='\t'||c=='\n'||c=='\r' ) {
+        return None;
+    }
+    Some(format!(
+        "{} {}.{:09} {}",
+        metadata.len(),
+        modified.as_secs(),
+        modified.subsec_nanos(),
+        path,
+    ))
+}
+
+
+impl LayoutCheckCache{
+    pub(crate) fn load(path:&Path)->io::Result<Self>{
+        let contents=match fs::read_to_string(path) {
+            Ok(x)=>x,
+            Err(ref e) if e.kind()==io::ErrorKind::NotFound=>String::new(),
+            Err(e)=>return Err(e),
+        };
+
+        OpenOptions::new().create(true).append(true).open(path)?;
+
+        // Lines that can't be parsed are ignored,
+        // they could have been partially written by a process that was killed.
+        let entries=contents.lines()
+            .filter_map(parse_entry)
+            .collect::<HashMap<CacheKey,Vec<LayoutPath>>>();
+
+        Ok(Self{
+            path:path.to_owned(),
+            libraries:entries.keys().map(|k| (k.interface,k.library.clone()) ).collect(),
+            entries,
+            fingerprints:HashMap::new(),
+        })
+    }
+
+    fn fingerprint(&mut self,abi_info:&'static AbiInfo)->LayoutFingerprint{
+        *self.fingerprints.entry(abi_info as *const AbiInfo as usize)
+            .or_insert_with(|| LayoutFingerprint::of_abi_info(abi_info) )
+    }
+
+    pub(crate) fn lookup(
+        &mut self,
+        interface:&'static AbiInfo,
+        implementation:&'static AbiInfo,
+        library:&str,
+    )->CacheLookup{
+        let interface_fp=self.fingerprint(interface);
+        let library=library.to_string();
+
+        let checked_pairs=if self.libraries.contains(&(interface_fp,library.clone())) {
+            let key=CacheKey{
+                interface:interface_fp,
+                implementation:self.fingerprint(implementation),
+                library:library.clone(),
+            };
+            self.entries.get(&key)
+        }else{
+            None
+        };
+
+        let checked_pairs=checked_pairs
+            .and_then(|paths|{
+                paths.iter()
+                    .map(|path|Some((
+                        resolve_path(interface,path)?,
+                        resolve_path(implementation,path)?,
+                    )))
+                    .collect::<Option<Vec<CheckedPair>>>()
+            });
+        CacheLookup{
+            interface:interface_fp,
+            implementation,
+            library,
+            checked_pairs,
+        }
+    }
+
+    /// Stores a successful layout check,
+    /// ignoring errors writing to the file since the cache is only an optimization.
+    pub(crate) fn insert(&mut self,lookup:&CacheLookup,paths:Vec<LayoutPath>){
+        let key=CacheKey{
+            interface:lookup.interface,
+            implementation:self.fingerprint(lookup.implementation),
+            library:lookup.library.clone(),
+        };
+
+        let mut line=format!("{}\t{}\t{}\t",key.interface,key.implementation,key.library);
+        for path in &paths {
+            line.push_str(&format_path(path));
+            line.push(' ');
+        }
+        line.push('\n');
+
+        let _=OpenOptions::new().append(true).open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()) );
+
+        self.libraries.insert((key.interface,key.library.clone()));
+        self.entries.insert(key,paths);
+    }
+}
+
+
+/// Gets the type at the end of `path`,starting from `root`.
+fn resolve_path(root:&'static AbiInfo,path:&[PathStep])->Option<&'static AbiInfo>{
+    #[derive(Copy,Clone)]
+    enum Cursor{
+        Type(&'static AbiInfo),
+        Field(TLField),
+        Function(TLFunction),
+    }
+
+    fn nth_field<I>(mut iter:I,n:usize)->Option<TLField>
+    where I:Iterator<Item=TLField>
+    {
+        iter.nth(n)
+    }
+
+    let mut cursor=Cursor::Type(root);
+    for &step in path {
+        let abi_info=match cursor {
+            Cursor::Type(x)=>Some(x),
+            Cursor::Field(field)=>Some(field.abi_info.get()),
+            Cursor::Function(_)=>None,
+        };
+
+        cursor=match (step,abi_info,cursor) {
+            (PathStep::Field(n),Some(abi_info),_)=>{
+                let field=match abi_info.layout.data {
+                    TLData::Primitive{..}|TLData::Opaque=>None,
+                    TLData::Struct{fields}|TLData::Union{fields}=>
+                        nth_field(fields.get_fields(),n),
+                    TLData::Enum(enum_)=>
+                        nth_field(enum_.fields.get_fields(),n),
+                    TLData::PrefixType(prefix)=>
+                        nth_field(prefix.fields.get_fields(),n),
+                }?;
+                Cursor::Field(field)
+            }
+            (PathStep::PhantomField(n),Some(abi_info),_)=>{
+                Cursor::Field(*abi_info.layout.phantom_fields.as_slice().get(n)?)
+            }
+            (PathStep::Function(n),_,Cursor::Field(field))=>{
+                Cursor::Function(field.function_range.iter().nth(n)?)
+            }
+            (PathStep::Param(n),_,Cursor::Function(function))=>{
+                Cursor::Field(function.get_params_ret_iter().nth(n)?)
+            }
+            _=>return None,
+        };
+    }
+
+    match cursor {
+        Cursor::Type(x)=>Some(x),
+        Cursor::Field(field)=>Some(field.abi_info.get()),
+        Cursor::Function(_)=>None,
+    }
+}
+
+
+/// Formats a path as its steps separated by `.`,starting with a `.`,
+/// where each step is a letter followed by the index.
+fn format_path(path:&[PathStep])->String{
+    let mut out=String::new();
+    for step in path {
+        let (letter,n)=match *step {
+            PathStep::Field(n)=>('f',n),
+            PathStep::PhantomField(n)=>('p',n),
+            PathStep::Function(n)=>('n',n),
+            PathStep::Param(n)=>('a',n),
+        };
+        out.push('.');
+        out.push(letter);
+        out.push_str(&n.to_string());
+    }
+    if out.is_empty() {
+        out.push('.');
+    }
+    out
+}
+
+
+fn parse_path(word:&str)->Option<LayoutPath>{
+    if !word.starts_with('.') {
+        return None;
+    }
+    word[1..].split('.')
+        .filter(|step| !step.is_empty() )
+        .map(|step|{
+            let n=step.get(1..)?.parse::<usize>().ok()?;
+            match step.as_bytes()[0] {
+                b'f'=>Some(PathStep::Field(n)),
+                b'p'=>Some(PathStep::PhantomField(n)),
+                b'n'=>Some(PathStep::Function(n)),
+                b'a'=>Some(PathStep::Param(n)),
+                _=>None,
+            }
+        })
+        .collect()
+}
+
+
+/// Parses a line of the cache file,
+/// with the format 
+/// `<interface fingerprint>\t<implementation fingerprint>\t<library id>\t<path>*`.
+fn parse_entry(line:&str)->Option<(CacheKey,Vec<LayoutPath>)>{
+    let mut fields=line.split('\t');
+    let interface=LayoutFingerprint::from_hex(fields.next()?).ok()?;
+    let implementation=LayoutFingerprint::from_hex(fields.next()?).ok()?;
+    let library=fields.next()?.to_string();
+    let paths=fields.next()?
+        .split_whitespace()
+        .map(parse_path)
+        .collect::<Option<Vec<LayoutPath>>>()?;
+    if fields.next().is_some() {
+        return None;
+    }
+    Some((CacheKey{interface,implementation,library},paths))
+}
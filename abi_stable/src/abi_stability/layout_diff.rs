@@ -0,0 +1,170 @@
+/*!
+Compares the layouts of two versions of a type,
+reporting both the breaking and the compatible changes between them.
+*/
+
+use std::fmt::{self,Display};
+
+use super::{
+    AbiInfo,AbiInfoWrapper,
+    abi_checking::{
+        AbiInstabilityErrors,CheckingGlobals,
+        check_layout_compatibility_with_globals,checked_pairs_of,
+    },
+};
+
+use crate::type_layout::TLData;
+
+
+/**
+The differences between an older (the interface) and newer (the implementation)
+version of a type,and every type it references.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::layout_diff::{CompatibleChange,diff_layouts},
+};
+
+mod v1{
+    use abi_stable::StableAbi;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Module")))]
+    pub struct ModuleVal{
+        #[sabi(last_prefix_field)]
+        pub add:extern "C" fn(u32,u32)->u32,
+    }
+}
+
+mod v2{
+    use abi_stable::StableAbi;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Module")))]
+    pub struct ModuleVal{
+        #[sabi(last_prefix_field)]
+        pub add:extern "C" fn(u32,u32)->u32,
+        pub sub:extern "C" fn(u32,u32)->u32,
+    }
+}
+
+# fn main(){
+let diff=diff_layouts(<&v1::Module>::ABI_INFO,<&v2::Module>::ABI_INFO);
+assert!(!diff.is_breaking());
+assert_eq!(diff.compatible_changes.len(),1);
+match &diff.compatible_changes[0] {
+    CompatibleChange::AddedPrefixFields{fields,..}=>assert_eq!(fields,&["sub".to_string()]),
+    x=>panic!("unexpected change:{}",x),
+}
+
+// Removing a field is a breaking change.
+let diff=diff_layouts(<&v2::Module>::ABI_INFO,<&v1::Module>::ABI_INFO);
+assert!(diff.is_breaking());
+# }
+```
+
+*/
+#[derive(Debug)]
+pub struct LayoutDiff{
+    /// The errors that `check_layout_compatibility` would return,
+    /// None if the newer version is compatible with the older one.
+    pub errors:Option<AbiInstabilityErrors>,
+    /// The changes that don't break compatibility with the older version.
+    pub compatible_changes:Vec<CompatibleChange>,
+}
+
+
+/// A change between two versions of a type that doesn't break compatibility.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum CompatibleChange{
+    /// The newer version of a prefix type has more fields.
+    AddedPrefixFields{
+        type_name:String,
+        fields:Vec<String>,
+    },
+    /// The newer version of a nonexhaustive enum has more variants.
+    AddedVariants{
+        type_name:String,
+        variants:Vec<String>,
+    },
+}
+
+
+impl LayoutDiff{
+    /// Whether the newer version is incompatible with the older one.
+    pub fn is_breaking(&self)->bool{
+        self.errors.is_some()
+    }
+}
+
+
+/**
+Compares the layout of `interface` (the older version of a type)
+with `implementation` (the newer version).
+
+This does the same checks as `check_layout_compatibility`,
+without comparing the types against the ones in dynamic libraries loaded before.
+*/
+pub fn diff_layouts(
+    interface:&'static AbiInfoWrapper,
+    implementation:&'static AbiInfoWrapper,
+)->LayoutDiff{
+    let errors=check_layout_compatibility_with_globals(
+        interface,
+        implementation,
+        &CheckingGlobals::new(),
+    ).err();
+
+    let compatible_changes=checked_pairs_of(interface,implementation)
+        .into_iter()
+        .filter_map(|(this,other)| compatible_change(this,other) )
+        .collect();
+
+    LayoutDiff{errors,compatible_changes}
+}
+
+
+fn compatible_change(this:&'static AbiInfo,other:&'static AbiInfo)->Option<CompatibleChange>{
+    let type_name=||other.layout.full_type.to_string();
+    match (this.layout.data,other.layout.data) {
+        (TLData::PrefixType(t_prefix),TLData::PrefixType(o_prefix))=>{
+            let fields=o_prefix.fields.get_fields()
+                .skip(t_prefix.fields.len())
+                .map(|field| field.name.to_string() )
+                .collect::<Vec<String>>();
+            if fields.is_empty() {
+                return None;
+            }
+            Some(CompatibleChange::AddedPrefixFields{type_name:type_name(),fields})
+        }
+        (TLData::Enum(t_enum),TLData::Enum(o_enum))=>{
+            if !t_enum.exhaustiveness.is_nonexhaustive() {
+                return None;
+            }
+            let variants=o_enum.variant_names_iter()
+                .skip(t_enum.variant_count())
+                .map(|name| name.to_string() )
+                .collect::<Vec<String>>();
+            if variants.is_empty() {
+                return None;
+            }
+            Some(CompatibleChange::AddedVariants{type_name:type_name(),variants})
+        }
+        _=>None,
+    }
+}
+
+
+impl Display for CompatibleChange{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self {
+            CompatibleChange::AddedPrefixFields{type_name,fields}=>
+                write!(f,"added fields to prefix type '{}':{}",type_name,fields.join(",")),
+            CompatibleChange::AddedVariants{type_name,variants}=>
+                write!(f,"added variants to nonexhaustive enum '{}':{}",type_name,variants.join(",")),
+        }
+    }
+}
@@ -0,0 +1,961 @@
+/*!
+Owned and serializable snapshots of the layout of a type and every type it references,
+which can be stored in a file and later checked against the layout of a type,
+without having to build the version of the type that the snapshot was taken of.
+*/
+
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{self,Display},
+    sync::Mutex,
+};
+
+use super::{
+    AbiInfo,AbiInfoWrapper,
+    abi_checking::{AbiInstabilityErrors,CheckingGlobals,check_layout_compatibility_with_globals},
+    extra_checks::{ErrorSeriousness,ExtraAbiError,ExtraAbiErrors,ExtraChecks,ExtraChecksParams},
+    fingerprint::LayoutGraph,
+    layout_diff::{LayoutDiff,diff_layouts},
+    stable_abi_trait::{GetAbiInfo,TypeKind},
+};
+
+use crate::{
+    prefix_type::{FieldAccessibility,IsAccessible,IsConditional},
+    reflection::ModReflMode,
+    sabi_types::{CmpIgnored,LateStaticRef},
+    std_types::{RErr,RNone,RResult,RSome,StaticSlice,StaticStr},
+    type_layout::{
+        CompTLFunction,CustomPrimitive,DiscriminantRepr,FieldAccessor,FullType,GenericParams,
+        IsExhaustive,ItemInfo,LifetimeIndex,ModPath,ReprAttr,StartLen,Tag,TLData,
+        TLDiscriminants,TLEnum,TLField,TLFunction,TLFunctionRange,TLFunctions,TLNonExhaustive,
+        TLPrimitive,TypeLayout,
+        tagging::{CheckableTag,CTVariant,Primitive},
+    },
+    utils::leak_value,
+};
+
+
+mod type_slots;
+
+use self::type_slots::{TypeSlot,TYPE_SLOT_CAPACITY,reserve_type_slots};
+
+
+/// The version of the format of `LayoutSnapshot`,
+/// which is incremented whenever the format changes.
+pub const SNAPSHOT_FORMAT_VERSION:u32=2;
+
+
+/**
+An owned snapshot of the layout of a type and every type it references,
+that can be serialized and deserialized with serde.
+
+Recursive types are represented by referencing types by their index in `types`.
+
+# Checking
+
+`LayoutSnapshot::check_layout` reconstructs the layout that the snapshot was taken of,
+and checks it against the type the same way that `check_layout_compatibility` does,
+where the snapshot is the interface and the type is the implementation,
+without comparing them against the types in dynamic libraries loaded before.
+
+User-defined extra checks(declared with `#[sabi(extra_checks)]`) can't be stored in a snapshot,
+so the extra checks of the implementation are run in place of those of the snapshot.
+
+# Example
+
+This is how a snapshot can be used in a test,to fail when a type changes incompatibly.
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::layout_snapshot::LayoutSnapshot,
+    std_types::RString,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Point{
+    pub x:u32,
+    pub y:u32,
+    pub name:RString,
+}
+
+# fn main(){
+// This would be a file committed along with the crate.
+let json=serde_json::to_string_pretty(&LayoutSnapshot::new(Point::ABI_INFO)).unwrap();
+
+let snapshot:LayoutSnapshot=serde_json::from_str(&json).unwrap();
+
+snapshot.check_layout(Point::ABI_INFO).unwrap();
+
+assert!( snapshot.check_layout(<RString>::ABI_INFO).is_err() );
+# }
+```
+
+*/
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct LayoutSnapshot{
+    /// The `SNAPSHOT_FORMAT_VERSION` of the abi_stable that created this snapshot.
+    pub format_version:u32,
+    /// The `LayoutFingerprint` of the type,as a hexadecimal string.
+    pub fingerprint:String,
+    /// The type the snapshot was taken of,followed by every type it references.
+    pub types:Vec<TypeSnapshot>,
+}
+
+
+/// The snapshot of the layout of a single type.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct TypeSnapshot{
+    pub name:String,
+    pub full_type:FullTypeSnapshot,
+    pub package:String,
+    pub version:String,
+    /// The line in the file where the type was defined.
+    pub line:u32,
+    pub mod_path:ModPathSnapshot,
+    pub is_prefix:bool,
+    pub is_nonzero:bool,
+    pub size:usize,
+    pub alignment:usize,
+    pub repr_attr:ReprAttr,
+    pub mod_refl_mode:ModReflMode,
+    pub tag:TagSnapshot,
+    /// Whether the type has extra checks,which are not stored in the snapshot.
+    pub has_extra_checks:bool,
+    pub phantom_fields:Vec<FieldSnapshot>,
+    pub data:DataSnapshot,
+}
+
+
+/// The snapshot of a `FullType`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct FullTypeSnapshot{
+    pub name:String,
+    pub primitive:Option<PrimitiveSnapshot>,
+    pub lifetimes:Vec<String>,
+    pub types:Vec<FullTypeSnapshot>,
+    pub consts:Vec<String>,
+}
+
+
+/// The snapshot of a `ModPath`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub enum ModPathSnapshot{
+    NoPath,
+    In(String),
+    Prelude,
+}
+
+
+/// The snapshot of a `TLData`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+#[serde(tag="variant")]
+pub enum DataSnapshot{
+    Primitive{
+        primitive:PrimitiveSnapshot,
+    },
+    Opaque,
+    Struct{
+        fields:Vec<FieldSnapshot>,
+    },
+    Union{
+        fields:Vec<FieldSnapshot>,
+    },
+    Enum(EnumSnapshot),
+    PrefixType(PrefixSnapshot),
+}
+
+
+/**
+The snapshot of a `TLPrimitive`.
+
+Custom primitives are compared by address,
+so a custom primitive from a snapshot is never equal to the one in a type.
+*/
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub enum PrimitiveSnapshot{
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    Usize,
+    Isize,
+    Bool,
+    SharedRef,
+    MutRef,
+    ConstPtr,
+    MutPtr,
+    Array{
+        len:usize,
+    },
+    Custom{
+        typename:String,
+        start_gen:String,
+        ty_sep:String,
+        end_gen:String,
+    },
+}
+
+
+/// The snapshot of a `TLEnum`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct EnumSnapshot{
+    /// A ';' separated list of all variant names.
+    pub variant_names:String,
+    /// The ammount of fields in each variant.
+    pub field_counts:Vec<u8>,
+    pub discriminant_repr:DiscriminantRepr,
+    pub discriminants:Vec<String>,
+    /// The size and alignment of the enum,if it is nonexhaustive.
+    pub nonexhaustive:Option<TLNonExhaustive>,
+    /// The fields of all the variants.
+    pub fields:Vec<FieldSnapshot>,
+}
+
+
+/// The snapshot of a `TLPrefixType`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct PrefixSnapshot{
+    pub first_suffix_field:usize,
+    /// Whether each field is accessible.
+    pub accessible_fields:Vec<bool>,
+    /// Whether each field in the prefix is conditionally accessible.
+    pub conditional_prefix_fields:Vec<bool>,
+    pub fields:Vec<FieldSnapshot>,
+}
+
+
+/// The snapshot of a `TLField`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct FieldSnapshot{
+    pub name:String,
+    /// The index of the type of the field in `LayoutSnapshot::types`.
+    pub type_index:usize,
+    pub lifetime_indices:Vec<LifetimeIndex>,
+    pub field_accessor:FieldAccessorSnapshot,
+    pub is_function:bool,
+    pub functions:Vec<FunctionSnapshot>,
+}
+
+
+/// The snapshot of a `FieldAccessor`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub enum FieldAccessorSnapshot{
+    Direct,
+    Method{
+        name:Option<String>,
+    },
+    MethodOption,
+    Opaque,
+}
+
+
+/// The snapshot of a `TLFunction`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub struct FunctionSnapshot{
+    pub name:String,
+    pub bound_lifetimes:String,
+    pub paramret_lifetime_indices:Vec<LifetimeIndex>,
+    pub params:Vec<FieldSnapshot>,
+    pub returns:FieldSnapshot,
+}
+
+
+/// The snapshot of a `Tag`,after it's converted to a `CheckableTag`.
+#[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+pub enum TagSnapshot{
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Str(String),
+    Ignored(Box<TagSnapshot>),
+    Array(Vec<TagSnapshot>),
+    Set(Vec<TagSnapshot>),
+    Map(Vec<(TagSnapshot,TagSnapshot)>),
+}
+
+
+/// The error returned when a type can't be checked against a `LayoutSnapshot`,
+/// or is incompatible with it.
+#[derive(Debug,Clone,PartialEq)]
+pub enum SnapshotError{
+    /// The snapshot was created with a different `SNAPSHOT_FORMAT_VERSION`.
+    FormatVersion{
+        expected:u32,
+        found:u32,
+    },
+    /// The snapshot can't be converted back to a layout,
+    /// eg:because it references a type that it doesn't contain.
+    Malformed(String),
+    /// The layouts of more types were reconstructed from snapshots
+    /// than this process supports.
+    TooManyTypes{
+        capacity:usize,
+    },
+    /// The type is incompatible with the snapshot.
+    Incompatible(AbiInstabilityErrors),
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+impl LayoutSnapshot{
+    /// Takes a snapshot of the layout of a type and every type it references.
+    pub fn new(abi_info:&'static AbiInfoWrapper)->Self{
+        let graph=LayoutGraph::new(abi_info.get());
+        Self{
+            format_version:SNAPSHOT_FORMAT_VERSION,
+            fingerprint:graph.fingerprint.to_string(),
+            types:graph.nodes().iter().map(|&x| TypeSnapshot::new(x,&graph) ).collect(),
+        }
+    }
+
+    /**
+Checks that the layout of the type is compatible with this snapshot.
+
+# Errors
+
+This returns an error if the type is not compatible with this snapshot,
+or if this snapshot can't be converted back to a layout (see `to_abi_info`).
+    */
+    pub fn check_layout(&self,abi_info:&'static AbiInfoWrapper)->Result<(),SnapshotError>{
+        check_layout_compatibility_with_globals(
+            self.to_abi_info()?,
+            abi_info,
+            &CheckingGlobals::new(),
+        ).map_err(SnapshotError::Incompatible)
+    }
+
+    /**
+Compares this snapshot with a newer version of the type,
+reporting both the breaking and the compatible changes.
+
+# Errors
+
+This returns an error if this snapshot can't be converted back to a layout
+(see `to_abi_info`).
+    */
+    pub fn diff(&self,implementation:&'static AbiInfoWrapper)->Result<LayoutDiff,SnapshotError>{
+        Ok(diff_layouts(self.to_abi_info()?,implementation))
+    }
+
+    /**
+Reconstructs the layout of the type that this snapshot was taken of.
+
+The reconstructed layouts are leaked,once for every distinct snapshot.
+
+# Errors
+
+This returns an error if this snapshot has a different `SNAPSHOT_FORMAT_VERSION`,
+if it is malformed,
+or if the layouts of more than 4096 types were reconstructed in this process.
+    */
+    pub fn to_abi_info(&self)->Result<&'static AbiInfoWrapper,SnapshotError>{
+        if self.format_version!=SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::FormatVersion{
+                expected:SNAPSHOT_FORMAT_VERSION,
+                found:self.format_version,
+            });
+        }
+        if self.types.is_empty() {
+            return Err(SnapshotError::Malformed("the snapshot contains no types".into()));
+        }
+
+        let mut reconstructed=RECONSTRUCTED_SNAPSHOTS
+            .init(|| leak_value(Mutex::new(Vec::new())) )
+            .lock()
+            .unwrap();
+
+        if let Some(&(_,abi_info))=reconstructed.iter().find(|(snapshot,_)| snapshot==self ) {
+            return Ok(abi_info);
+        }
+
+        let slots=reserve_type_slots(self.types.len())
+            .ok_or(SnapshotError::TooManyTypes{capacity:TYPE_SLOT_CAPACITY})?;
+        let abi_info=LayoutBuilder{snapshot:self,slots}.build()?;
+
+        reconstructed.push((self.clone(),abi_info));
+        Ok(abi_info)
+    }
+}
+
+
+/// The snapshots whose layouts were reconstructed,
+/// so that the layout of each snapshot is only leaked once.
+static RECONSTRUCTED_SNAPSHOTS:
+    LateStaticRef<Mutex<Vec<(LayoutSnapshot,&'static AbiInfoWrapper)>>>=
+    LateStaticRef::new();
+
+
+impl TypeSnapshot{
+    fn new(abi_info:&'static AbiInfo,graph:&LayoutGraph)->Self{
+        let layout=abi_info.layout;
+        let (package,version)=layout.package_and_version();
+        let fields=|fields:&mut dyn Iterator<Item=TLField>|{
+            fields.map(|f| FieldSnapshot::new(&f,graph) ).collect::<Vec<FieldSnapshot>>()
+        };
+
+        let data=match layout.data {
+            TLData::Primitive(prim)=>
+                DataSnapshot::Primitive{primitive:PrimitiveSnapshot::new(prim)},
+            TLData::Opaque=>
+                DataSnapshot::Opaque,
+            TLData::Struct{fields:x}=>
+                DataSnapshot::Struct{fields:fields(&mut x.get_fields())},
+            TLData::Union{fields:x}=>
+                DataSnapshot::Union{fields:fields(&mut x.get_fields())},
+            TLData::Enum(enum_)=>{
+                DataSnapshot::Enum(EnumSnapshot{
+                    variant_names:enum_.variant_names.to_string(),
+                    field_counts:enum_.field_count.as_slice().to_vec(),
+                    discriminant_repr:enum_.discriminants.discriminant_repr(),
+                    discriminants:enum_.discriminants.to_string_vec(),
+                    nonexhaustive:enum_.exhaustiveness.as_nonexhaustive().cloned(),
+                    fields:fields(&mut enum_.fields.get_fields()),
+                })
+            }
+            TLData::PrefixType(prefix)=>{
+                let field_count=prefix.fields.len();
+                DataSnapshot::PrefixType(PrefixSnapshot{
+                    first_suffix_field:prefix.first_suffix_field,
+                    accessible_fields:(0..field_count)
+                        .map(|i| prefix.is_accessible(i) )
+                        .collect(),
+                    conditional_prefix_fields:prefix.conditional_prefix_fields.as_slice().iter()
+                        .map(|&x| x==IsConditional::Yes )
+                        .collect(),
+                    fields:fields(&mut prefix.fields.get_fields()),
+                })
+            }
+        };
+
+        Self{
+            name:layout.name.to_string(),
+            full_type:FullTypeSnapshot::new(&layout.full_type),
+            package:package.to_string(),
+            version:version.version.to_string(),
+            line:layout.item_info.line,
+            mod_path:ModPathSnapshot::new(layout.item_info.mod_path),
+            is_prefix:abi_info.prefix_kind,
+            is_nonzero:abi_info.is_nonzero,
+            size:layout.size,
+            alignment:layout.alignment,
+            repr_attr:layout.repr_attr,
+            mod_refl_mode:layout.mod_refl_mode,
+            tag:TagSnapshot::new(&layout.tag.to_checkable()),
+            has_extra_checks:layout.extra_checks.is_rsome(),
+            phantom_fields:fields(&mut layout.phantom_fields.as_slice().iter().cloned()),
+            data,
+        }
+    }
+}
+
+
+impl FullTypeSnapshot{
+    fn new(full_type:&FullType)->Self{
+        let generics=&full_type.generics;
+        Self{
+            name:full_type.name.to_string(),
+            primitive:full_type.primitive.into_option().map(PrimitiveSnapshot::new),
+            lifetimes:generics.lifetime.iter().map(|x| x.to_string() ).collect(),
+            types:generics.type_.iter().map(|x| FullTypeSnapshot::new(&x.full_type) ).collect(),
+            consts:generics.const_.iter().map(|x| x.to_string() ).collect(),
+        }
+    }
+
+    fn to_full_type(&self)->FullType{
+        FullType::new(
+            leak_str(&self.name),
+            self.primitive.as_ref().map(PrimitiveSnapshot::to_primitive).into(),
+            GenericParams::new(
+                leak_vec(self.lifetimes.iter().map(|x| StaticStr::new(leak_str(x)) ).collect()),
+                leak_vec(self.types.iter().map(|x| leak_value(x.to_type_parameter()) ).collect()),
+                leak_vec(self.consts.iter().map(|x| StaticStr::new(leak_str(x)) ).collect()),
+            ),
+        )
+    }
+
+    /// Constructs the layout of a type parameter,
+    /// which is only used to print the type it is a parameter of.
+    fn to_type_parameter(&self)->TypeLayout{
+        TypeLayout{
+            name:StaticStr::new(leak_str(&self.name)),
+            item_info:CmpIgnored::new(ItemInfo::new("",0,ModPath::NoPath)),
+            size:0,
+            alignment:1,
+            data:TLData::Opaque,
+            full_type:self.to_full_type(),
+            phantom_fields:StaticSlice::new(&[]),
+            reflection_tag:Tag::null(),
+            private_tag:Tag::null(),
+            tag:Tag::null(),
+            repr_attr:ReprAttr::c(),
+            mod_refl_mode:ModReflMode::Opaque,
+            extra_checks:RNone,
+        }
+    }
+}
+
+
+impl ModPathSnapshot{
+    fn new(mod_path:ModPath)->Self{
+        match mod_path {
+            ModPath::NoPath=>ModPathSnapshot::NoPath,
+            ModPath::In(path)=>ModPathSnapshot::In(path.to_string()),
+            ModPath::Prelude=>ModPathSnapshot::Prelude,
+        }
+    }
+
+    fn to_mod_path(&self)->ModPath{
+        match self {
+            ModPathSnapshot::NoPath=>ModPath::NoPath,
+            ModPathSnapshot::In(path)=>ModPath::inside(leak_str(path)),
+            ModPathSnapshot::Prelude=>ModPath::Prelude,
+        }
+    }
+}
+
+
+macro_rules! declare_primitive_conversions {
+    ( $($variant:ident)* ) => (
+        impl PrimitiveSnapshot{
+            fn new(primitive:TLPrimitive)->Self{
+                match primitive {
+                    $( TLPrimitive::$variant=>PrimitiveSnapshot::$variant, )*
+                    TLPrimitive::Array{len}=>PrimitiveSnapshot::Array{len},
+                    TLPrimitive::Custom(custom)=>PrimitiveSnapshot::Custom{
+                        typename:custom.typename.to_string(),
+                        start_gen:custom.start_gen.to_string(),
+                        ty_sep:custom.ty_sep.to_string(),
+                        end_gen:custom.end_gen.to_string(),
+                    },
+                }
+            }
+
+            fn to_primitive(&self)->TLPrimitive{
+                match self {
+                    $( PrimitiveSnapshot::$variant=>TLPrimitive::$variant, )*
+                    PrimitiveSnapshot::Array{len}=>TLPrimitive::Array{len:*len},
+                    PrimitiveSnapshot::Custom{typename,start_gen,ty_sep,end_gen}=>
+                        TLPrimitive::Custom(leak_value(CustomPrimitive{
+                            typename:StaticStr::new(leak_str(typename)),
+                            start_gen:StaticStr::new(leak_str(start_gen)),
+                            ty_sep:StaticStr::new(leak_str(ty_sep)),
+                            end_gen:StaticStr::new(leak_str(end_gen)),
+                        })),
+                }
+            }
+        }
+    )
+}
+
+declare_primitive_conversions!{
+    U8 I8 U16 I16 U32 I32 U64 I64 Usize Isize Bool
+    SharedRef MutRef ConstPtr MutPtr
+}
+
+
+impl FieldSnapshot{
+    fn new(field:&TLField,graph:&LayoutGraph)->Self{
+        Self{
+            name:field.name.to_string(),
+            type_index:type_index(graph,field),
+            lifetime_indices:field.lifetime_indices.as_slice().to_vec(),
+            field_accessor:FieldAccessorSnapshot::new(field.field_accessor),
+            is_function:field.is_function,
+            functions:field.function_range.iter()
+                .map(|f| FunctionSnapshot::new(&f,graph) )
+                .collect(),
+        }
+    }
+}
+
+
+impl FieldAccessorSnapshot{
+    fn new(field_accessor:FieldAccessor)->Self{
+        match field_accessor {
+            FieldAccessor::Direct=>FieldAccessorSnapshot::Direct,
+            FieldAccessor::Method{name}=>
+                FieldAccessorSnapshot::Method{name:name.map(|x| x.to_string() )},
+            FieldAccessor::MethodOption=>FieldAccessorSnapshot::MethodOption,
+            FieldAccessor::Opaque=>FieldAccessorSnapshot::Opaque,
+        }
+    }
+
+    fn to_field_accessor(&self)->FieldAccessor{
+        match self {
+            FieldAccessorSnapshot::Direct=>FieldAccessor::Direct,
+            FieldAccessorSnapshot::Method{name}=>FieldAccessor::Method{
+                name:name.as_ref().map(|x| leak_value(StaticStr::new(leak_str(x))) ),
+            },
+            FieldAccessorSnapshot::MethodOption=>FieldAccessor::MethodOption,
+            FieldAccessorSnapshot::Opaque=>FieldAccessor::Opaque,
+        }
+    }
+}
+
+
+impl FunctionSnapshot{
+    fn new(function:&TLFunction,graph:&LayoutGraph)->Self{
+        Self{
+            name:function.name.to_string(),
+            bound_lifetimes:function.bound_lifetimes.to_string(),
+            paramret_lifetime_indices:function.paramret_lifetime_indices.as_slice().to_vec(),
+            params:function.get_params().map(|f| FieldSnapshot::new(&f,graph) ).collect(),
+            returns:FieldSnapshot::new(&function.get_return(),graph),
+        }
+    }
+}
+
+
+impl TagSnapshot{
+    fn new(tag:&CheckableTag)->Self{
+        match tag.variant() {
+            CTVariant::Primitive(Primitive::Null)=>TagSnapshot::Null,
+            CTVariant::Primitive(Primitive::Bool(x))=>TagSnapshot::Bool(*x),
+            CTVariant::Primitive(Primitive::Int(x))=>TagSnapshot::Int(*x),
+            CTVariant::Primitive(Primitive::UInt(x))=>TagSnapshot::UInt(*x),
+            CTVariant::Primitive(Primitive::String_(x))=>TagSnapshot::Str(x.to_string()),
+            CTVariant::Ignored(x)=>TagSnapshot::Ignored(Box::new(TagSnapshot::new(x))),
+            CTVariant::Array(x)=>TagSnapshot::Array(x.iter().map(TagSnapshot::new).collect()),
+            CTVariant::Set(x)=>
+                TagSnapshot::Set(x.iter().map(|kv| TagSnapshot::new(&kv.key) ).collect()),
+            CTVariant::Map(x)=>TagSnapshot::Map(
+                x.iter()
+                    .map(|kv| (TagSnapshot::new(&kv.key),TagSnapshot::new(&kv.value)) )
+                    .collect()
+            ),
+        }
+    }
+
+    fn to_tag(&self)->Tag{
+        match self {
+            TagSnapshot::Null=>Tag::null(),
+            TagSnapshot::Bool(x)=>Tag::bool_(*x),
+            TagSnapshot::Int(x)=>Tag::int(*x),
+            TagSnapshot::UInt(x)=>Tag::uint(*x),
+            TagSnapshot::Str(x)=>Tag::str(leak_str(x)),
+            TagSnapshot::Ignored(x)=>Tag::ignored(leak_value(x.to_tag())),
+            TagSnapshot::Array(x)=>Tag::arr(leak_vec(x.iter().map(Self::to_tag).collect())),
+            TagSnapshot::Set(x)=>Tag::set(leak_vec(x.iter().map(Self::to_tag).collect())),
+            TagSnapshot::Map(x)=>Tag::map(leak_vec(
+                x.iter()
+                    .map(|(key,value)| Tag::kv(key.to_tag(),value.to_tag()) )
+                    .collect()
+            )),
+        }
+    }
+}
+
+
+fn type_index(graph:&LayoutGraph,field:&TLField)->usize{
+    graph.index_of(field.abi_info.get())
+        .expect("every type referenced by a field must be in the LayoutGraph")
+}
+
+
+fn leak_str(s:&str)->&'static str{
+    leak_value(s.to_string()).as_str()
+}
+
+fn leak_vec<T>(vec:Vec<T>)->&'static [T]{
+    Box::leak(vec.into_boxed_slice())
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// Reconstructs the layouts of the types in a snapshot,
+/// where each type is returned by the function of its `TypeSlot`.
+struct LayoutBuilder<'a>{
+    snapshot:&'a LayoutSnapshot,
+    slots:Vec<&'static TypeSlot>,
+}
+
+
+impl<'a> LayoutBuilder<'a>{
+    fn build(&self)->Result<&'static AbiInfoWrapper,SnapshotError>{
+        let mut abi_infos=Vec::with_capacity(self.slots.len());
+        for (ty,slot) in self.snapshot.types.iter().zip(&self.slots) {
+            let abi_info=AbiInfo{
+                kind:if ty.is_prefix { TypeKind::Prefix }else{ TypeKind::Value },
+                prefix_kind:ty.is_prefix,
+                type_id:slot.type_id(),
+                is_nonzero:ty.is_nonzero,
+                layout:leak_value(self.type_layout(ty)?),
+            };
+            // The snapshot was taken from the layout that the AbiInfo of the type described.
+            abi_infos.push(leak_value(unsafe{ AbiInfoWrapper::new_unchecked(abi_info) }));
+        }
+
+        for (abi_info,slot) in abi_infos.iter().zip(&self.slots) {
+            slot.set(abi_info.get());
+        }
+
+        Ok(abi_infos[0])
+    }
+
+    fn type_layout(&self,ty:&TypeSnapshot)->Result<TypeLayout,SnapshotError>{
+        let data=match &ty.data {
+            DataSnapshot::Primitive{primitive}=>
+                TLData::Primitive(primitive.to_primitive()),
+            DataSnapshot::Opaque=>
+                TLData::Opaque,
+            DataSnapshot::Struct{fields}=>
+                TLData::struct_(self.fields(fields)?),
+            DataSnapshot::Union{fields}=>
+                TLData::union_(self.fields(fields)?),
+            DataSnapshot::Enum(enum_)=>{
+                let exhaustiveness=match enum_.nonexhaustive {
+                    Some(nonexhaustive)=>IsExhaustive::nonexhaustive(leak_value(nonexhaustive)),
+                    None=>IsExhaustive::exhaustive(),
+                };
+                TLData::Enum(leak_value(TLEnum::new(
+                    leak_str(&enum_.variant_names),
+                    exhaustiveness,
+                    self.fields(&enum_.fields)?,
+                    discriminants(enum_.discriminant_repr,&enum_.discriminants)?,
+                    leak_vec(enum_.field_counts.clone()),
+                )))
+            }
+            DataSnapshot::PrefixType(prefix)=>{
+                let mut accessible_fields=FieldAccessibility::empty();
+                let mut more_accessible_fields=Vec::new();
+                for (i,&is_accessible) in prefix.accessible_fields.iter().enumerate() {
+                    accessible_fields.set_accessibility_with_more(
+                        &mut more_accessible_fields,
+                        i,
+                        IsAccessible::new(is_accessible),
+                    );
+                }
+                TLData::prefix_type(
+                    prefix.first_suffix_field,
+                    accessible_fields,
+                    leak_vec(more_accessible_fields),
+                    leak_vec(prefix.conditional_prefix_fields.iter().cloned().map(IsConditional::new).collect()),
+                    self.fields(&prefix.fields)?,
+                )
+            }
+        };
+
+        let package_and_version=format!("{};{}",ty.package,ty.version);
+
+        Ok(TypeLayout{
+            name:StaticStr::new(leak_str(&ty.name)),
+            item_info:CmpIgnored::new(ItemInfo::new(
+                leak_str(&package_and_version),
+                ty.line,
+                ty.mod_path.to_mod_path(),
+            )),
+            size:ty.size,
+            alignment:ty.alignment,
+            data,
+            full_type:ty.full_type.to_full_type(),
+            phantom_fields:StaticSlice::new(self.fields(&ty.phantom_fields)?),
+            reflection_tag:Tag::null(),
+            private_tag:Tag::null(),
+            tag:ty.tag.to_tag(),
+            repr_attr:ty.repr_attr,
+            mod_refl_mode:ty.mod_refl_mode,
+            extra_checks:if ty.has_extra_checks { RSome(SNAPSHOT_EXTRA_CHECKS) }else{ RNone },
+        })
+    }
+
+    fn fields(&self,fields:&[FieldSnapshot])->Result<&'static [TLField],SnapshotError>{
+        let fields=fields.iter()
+            .map(|field| self.field(field) )
+            .collect::<Result<Vec<TLField>,SnapshotError>>()?;
+        Ok(leak_vec(fields))
+    }
+
+    fn field(&self,field:&FieldSnapshot)->Result<TLField,SnapshotError>{
+        Ok(TLField{
+            name:StaticStr::new(leak_str(&field.name)),
+            lifetime_indices:StaticSlice::new(leak_vec(field.lifetime_indices.clone())),
+            abi_info:self.get_abi_info(field.type_index)?,
+            function_range:self.functions(&field.functions)?,
+            is_function:field.is_function,
+            field_accessor:field.field_accessor.to_field_accessor(),
+        })
+    }
+
+    /// Constructs the `TLFunctions` that the function pointers in a field are stored in.
+    fn functions(&self,functions:&[FunctionSnapshot])->Result<TLFunctionRange,SnapshotError>{
+        if functions.is_empty() {
+            return Ok(TLFunctionRange::EMPTY);
+        }
+
+        let mut strings=String::new();
+        let mut comp_functions=Vec::with_capacity(functions.len());
+        let mut abi_infos=Vec::new();
+        let mut lifetime_indices=Vec::new();
+
+        for function in functions {
+            let param_names=function.params.iter()
+                .map(|param| param.name.as_str() )
+                .collect::<Vec<&str>>()
+                .join(";");
+            let name=push_str(&mut strings,&function.name)?;
+            let bound_lifetimes=push_str(&mut strings,&function.bound_lifetimes)?;
+            let param_names=push_str(&mut strings,&param_names)?;
+
+            let params_start=abi_infos.len();
+            for param in &function.params {
+                abi_infos.push(self.get_abi_info(param.type_index)?);
+            }
+            let param_abi_infos=start_len(params_start,abi_infos.len())?;
+
+            let return_abi_info=to_u16(abi_infos.len())?;
+            abi_infos.push(self.get_abi_info(function.returns.type_index)?);
+
+            let lifetimes_start=lifetime_indices.len();
+            lifetime_indices.extend_from_slice(&function.paramret_lifetime_indices);
+            let paramret_lifetime_indices=start_len(lifetimes_start,lifetime_indices.len())?;
+
+            comp_functions.push(CompTLFunction::new(
+                name,
+                bound_lifetimes,
+                param_names,
+                param_abi_infos,
+                paramret_lifetime_indices,
+                RSome(return_abi_info),
+            ));
+        }
+
+        let fn_range=start_len(0,comp_functions.len())?;
+        let functions=TLFunctions::new(
+            leak_str(&strings),
+            leak_vec(comp_functions),
+            &[],
+            leak_vec(abi_infos),
+            leak_vec(lifetime_indices),
+        );
+        Ok(TLFunctionRange::new(fn_range,Some(leak_value(functions))))
+    }
+
+    fn get_abi_info(&self,type_index:usize)->Result<GetAbiInfo,SnapshotError>{
+        match self.slots.get(type_index) {
+            Some(slot)=>Ok(slot.get_abi_info()),
+            None=>Err(SnapshotError::Malformed(format!(
+                "type index out of bounds:{} (there are {} types)",
+                type_index,
+                self.slots.len(),
+            ))),
+        }
+    }
+}
+
+
+fn discriminants(
+    repr:DiscriminantRepr,
+    discriminants:&[String],
+)->Result<TLDiscriminants,SnapshotError>{
+    macro_rules! parse_discriminants {
+        ( $constructor:ident ) => ({
+            let parsed=discriminants.iter()
+                .map(|discr|{
+                    discr.parse().map_err(|_|{
+                        SnapshotError::Malformed(format!(
+                            "invalid {:?} enum discriminant:{}",
+                            repr,
+                            discr,
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>,SnapshotError>>()?;
+            TLDiscriminants::$constructor(leak_vec(parsed))
+        })
+    }
+
+    Ok(match repr {
+        DiscriminantRepr::U8=>parse_discriminants!(from_u8_slice),
+        DiscriminantRepr::I8=>parse_discriminants!(from_i8_slice),
+        DiscriminantRepr::U16=>parse_discriminants!(from_u16_slice),
+        DiscriminantRepr::I16=>parse_discriminants!(from_i16_slice),
+        DiscriminantRepr::U32=>parse_discriminants!(from_u32_slice),
+        DiscriminantRepr::I32=>parse_discriminants!(from_i32_slice),
+        DiscriminantRepr::U64=>parse_discriminants!(from_u64_slice),
+        DiscriminantRepr::I64=>parse_discriminants!(from_i64_slice),
+        DiscriminantRepr::Usize=>parse_discriminants!(from_usize_slice),
+        DiscriminantRepr::Isize=>parse_discriminants!(from_isize_slice),
+        DiscriminantRepr::U128|DiscriminantRepr::I128=>{
+            return Err(SnapshotError::Malformed(format!(
+                "unsupported enum discriminant representation:{:?}",
+                repr,
+            )));
+        }
+    })
+}
+
+
+fn push_str(strings:&mut String,s:&str)->Result<StartLen,SnapshotError>{
+    let start=strings.len();
+    strings.push_str(s);
+    start_len(start,strings.len())
+}
+
+fn start_len(start:usize,end:usize)->Result<StartLen,SnapshotError>{
+    Ok(StartLen::new(to_u16(start)?,to_u16(end-start)?))
+}
+
+fn to_u16(n:usize)->Result<u16,SnapshotError>{
+    u16::try_from(n).map_err(|_|{
+        SnapshotError::Malformed(format!("too many function pointers in a field:{}",n))
+    })
+}
+
+
+/// The extra checks of reconstructed types which had extra checks when the snapshot was taken.
+const SNAPSHOT_EXTRA_CHECKS:ExtraChecks=ExtraChecks{check:snapshot_extra_checks};
+
+/// Runs the extra checks of the implementation,since functions can't be stored in a snapshot.
+extern "C" fn snapshot_extra_checks(params:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    let (interface,implementation)=(params.interface(),params.implementation());
+    match implementation.layout.extra_checks {
+        // Both types were reconstructed from snapshots.
+        RSome(extra_checks) if extra_checks==SNAPSHOT_EXTRA_CHECKS=>Ok(()).into(),
+        RSome(extra_checks)=>extra_checks.call(interface,implementation).into(),
+        RNone=>RErr(ExtraAbiError::new(
+            "removed extra checks",
+            "The type had extra checks when the snapshot was taken,and now it doesn't.",
+            ErrorSeriousness::NonFatal,
+        ).into()),
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+impl Display for SnapshotError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self {
+            SnapshotError::FormatVersion{expected,found}=>write!(
+                f,
+                "The snapshot has an incompatible format version.\n\
+                 expected:{}\n\
+                 found:{}",
+                expected,
+                found,
+            ),
+            SnapshotError::Malformed(e)=>write!(f,"Malformed layout snapshot:{}",e),
+            SnapshotError::TooManyTypes{capacity}=>write!(
+                f,
+                "Cannot reconstruct the layouts of more than {} types from snapshots.",
+                capacity,
+            ),
+            SnapshotError::Incompatible(e)=>Display::fmt(e,f),
+        }
+    }
+}
+
+impl Error for SnapshotError{}
@@ -0,0 +1,121 @@
+/*!
+A fixed set of `extern "C" fn()->&'static AbiInfo` functions,
+which reference the types reconstructed from a `LayoutSnapshot`,
+since `GetAbiInfo` can only be constructed from a function pointer.
+*/
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicPtr,AtomicUsize,Ordering},
+};
+
+use crate::{
+    abi_stability::{AbiInfo,stable_abi_trait::GetAbiInfo},
+    sabi_types::ReturnValueEquality,
+    std_types::UTypeId,
+};
+
+
+const ROWS:usize=64;
+const COLUMNS:usize=64;
+
+/// The ammount of types that can be reconstructed from snapshots in a process.
+pub(super) const TYPE_SLOT_CAPACITY:usize=ROWS*COLUMNS;
+
+
+/// A function that returns the `AbiInfo` of a reconstructed type,
+/// with a `UTypeId` that is unique to the slot.
+pub(super) struct TypeSlot{
+    abi_info:&'static AtomicPtr<AbiInfo>,
+    get_abi_info:extern "C" fn()->&'static AbiInfo,
+    type_id:extern "C" fn()->UTypeId,
+}
+
+
+impl TypeSlot{
+    /// Gets the function that returns the type in this slot.
+    pub(super) fn get_abi_info(&self)->GetAbiInfo{
+        GetAbiInfo::from_fn(self.get_abi_info)
+    }
+
+    pub(super) fn type_id(&self)->ReturnValueEquality<UTypeId>{
+        ReturnValueEquality{function:self.type_id}
+    }
+
+    /// Sets the type in this slot,
+    /// which must be done before the function returned by `get_abi_info` is called.
+    pub(super) fn set(&self,abi_info:&'static AbiInfo){
+        self.abi_info.store(abi_info as *const AbiInfo as *mut AbiInfo,Ordering::Release);
+    }
+}
+
+
+/// Reserves `count` unused slots,
+/// returning None if there aren't enough unused slots left.
+pub(super) fn reserve_type_slots(count:usize)->Option<Vec<&'static TypeSlot>>{
+    let mut start=NEXT_SLOT.load(Ordering::Relaxed);
+    loop {
+        if TYPE_SLOT_CAPACITY-start < count {
+            return None;
+        }
+        match NEXT_SLOT.compare_exchange_weak(start,start+count,Ordering::Relaxed,Ordering::Relaxed) {
+            Ok(_)=>break,
+            Err(current)=>start=current,
+        }
+    }
+    Some(
+        (start..start+count)
+            .map(|i| &TYPE_SLOTS[i/COLUMNS][i%COLUMNS] )
+            .collect()
+    )
+}
+
+
+static NEXT_SLOT:AtomicUsize=AtomicUsize::new(0);
+
+
+macro_rules! type_slots {
+    ( rows[ $($row:tt)* ] columns $columns:tt ) => (
+        [ $( type_slots!(@row $row $columns), )* ]
+    );
+    (@row $row:tt [ $($column:tt)* ] ) => (
+        [ $( type_slots!(@slot $row $column), )* ]
+    );
+    (@slot $row:tt $column:tt) => ({
+        struct Slot;
+
+        static ABI_INFO:AtomicPtr<AbiInfo>=AtomicPtr::new(ptr::null_mut());
+
+        extern "C" fn get_abi_info()->&'static AbiInfo{
+            // Slots are only handed out by `reserve_type_slots`,
+            // and are set before the `GetAbiInfo`s referencing them are used.
+            unsafe{ &*ABI_INFO.load(Ordering::Acquire) }
+        }
+
+        extern "C" fn type_id()->UTypeId{
+            UTypeId::new::<Slot>()
+        }
+
+        TypeSlot{
+            abi_info:&ABI_INFO,
+            get_abi_info,
+            type_id,
+        }
+    });
+}
+
+
+static TYPE_SLOTS:[[TypeSlot;COLUMNS];ROWS]=type_slots!{
+    rows[
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+        16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+        32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+        48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+    ]
+    columns[
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+        16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+        32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+        48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+    ]
+};
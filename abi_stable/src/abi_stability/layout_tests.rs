@@ -17,5 +17,17 @@ mod sabi_trait;
 #[cfg(all(test,not(feature="only_new_tests")))]
 mod nonexhaustive_enums;
 
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod extra_checks;
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod layout_cache;
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod layout_diff;
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod layout_snapshot;
+
 #[cfg(test)]
 mod get_static_equivalent;
@@ -0,0 +1,234 @@
+use crate::{
+    StableAbi,
+    abi_stability::{
+        abi_checking::{
+            AbiInstability,AbiInstabilityErrors,
+            check_layout_compatibility_with_globals,
+            CheckingGlobals,
+        },
+        extra_checks::{ExtraChecksParams,ExtraAbiErrors,ExtraAbiError,ErrorSeriousness},
+        AbiInfoWrapper,
+    },
+    std_types::{RResult,ROk,RErr},
+    type_layout::Tag,
+};
+
+use core_extensions::matches;
+
+
+/// Requires that the tags are equal,unless the interface has the empty set tag.
+extern "C" fn check_tags(params:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    let interface=params.interface().layout.tag;
+    let implementation=params.implementation().layout.tag;
+    if interface==Tag::set(&[]) || interface==implementation {
+        ROk(())
+    }else{
+        RErr(ExtraAbiError::new(
+            "different tags",
+            format!("interface:{}\nimplementation:{}",interface,implementation),
+            ErrorSeriousness::NonFatal,
+        ).into())
+    }
+}
+
+extern "C" fn always_fatal(_:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    RErr(ExtraAbiError::new("fatal","always fails",ErrorSeriousness::Fatal).into())
+}
+
+extern "C" fn always_non_fatal(_:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    RErr(ExtraAbiError::new("non-fatal","always fails",ErrorSeriousness::NonFatal).into())
+}
+
+
+mod interface{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[])",extra_checks="check_tags")]
+    pub struct Foo{
+        pub x:u32,
+    }
+}
+
+mod impl_a{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[Tag::str(\"a\")])",extra_checks="check_tags")]
+    pub struct Foo{
+        pub x:u32,
+    }
+}
+
+mod impl_a_copy{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[Tag::str(\"a\")])",extra_checks="check_tags")]
+    pub struct Foo{
+        pub x:u32,
+    }
+}
+
+mod impl_b{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[Tag::str(\"b\")])",extra_checks="check_tags")]
+    pub struct Foo{
+        pub x:u32,
+    }
+}
+
+
+mod fatal_a{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="always_fatal")]
+    pub struct Inner{
+        pub x:u32,
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Outer{
+        pub inner:Inner,
+        pub y:u32,
+    }
+}
+
+mod fatal_b{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="always_fatal")]
+    pub struct Inner{
+        pub x:u32,
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Outer{
+        pub inner:Inner,
+        pub y:i32,
+    }
+}
+
+mod non_fatal_a{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="always_non_fatal")]
+    pub struct Inner{
+        pub x:u32,
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Outer{
+        pub inner:Inner,
+        pub y:u32,
+    }
+}
+
+mod non_fatal_b{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="always_non_fatal")]
+    pub struct Inner{
+        pub x:u32,
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Outer{
+        pub inner:Inner,
+        pub y:i32,
+    }
+}
+
+
+fn check(
+    interface:&'static AbiInfoWrapper,
+    implementation:&'static AbiInfoWrapper,
+    globals:&CheckingGlobals,
+)->Result<(),AbiInstabilityErrors>{
+    check_layout_compatibility_with_globals(interface,implementation,globals)
+}
+
+fn extra_check_errors(errs:&AbiInstabilityErrors)->Vec<ExtraAbiErrors>{
+    errs.flatten_errors()
+        .into_iter()
+        .filter_map(|e| match e {
+            AbiInstability::ExtraCheckError(e)=>Some(e),
+            _=>None,
+        })
+        .collect()
+}
+
+
+#[test]
+fn passing_checks(){
+    let globals=CheckingGlobals::new();
+    check(interface::Foo::ABI_INFO,interface::Foo::ABI_INFO,&globals).unwrap();
+    check(interface::Foo::ABI_INFO,impl_a::Foo::ABI_INFO,&globals).unwrap();
+    check(impl_a::Foo::ABI_INFO,impl_a_copy::Foo::ABI_INFO,&globals).unwrap();
+}
+
+#[test]
+fn failing_checks(){
+    let globals=CheckingGlobals::new();
+    let errs=check(impl_a::Foo::ABI_INFO,impl_b::Foo::ABI_INFO,&globals).unwrap_err();
+    let extra_errs=extra_check_errors(&errs);
+    assert_eq!(extra_errs.len(),1);
+    assert_eq!(extra_errs[0].errors[0].name.as_str(),"different tags");
+    assert!(!extra_errs[0].is_fatal());
+}
+
+#[test]
+fn fatal_stops_checking(){
+    let globals=CheckingGlobals::new();
+    let errs=check(fatal_a::Outer::ABI_INFO,fatal_b::Outer::ABI_INFO,&globals).unwrap_err();
+    let flattened=errs.flatten_errors();
+    // The `y` field is not checked after the fatal error in the `inner` field.
+    assert_eq!(flattened.len(),1,"{:#?}",flattened);
+    assert!(extra_check_errors(&errs)[0].is_fatal());
+}
+
+#[test]
+fn non_fatal_continues_checking(){
+    let globals=CheckingGlobals::new();
+    let errs=check(non_fatal_a::Outer::ABI_INFO,non_fatal_b::Outer::ABI_INFO,&globals)
+        .unwrap_err();
+    let flattened=errs.flatten_errors();
+    assert_eq!(extra_check_errors(&errs).len(),1);
+    assert!(
+        flattened.iter().any(|e| matches!(AbiInstability::Name{..}=e) ),
+        "{:#?}",
+        flattened,
+    );
+}
+
+#[test]
+fn merging_implementations(){
+    let globals=CheckingGlobals::new();
+
+    check(interface::Foo::ABI_INFO,impl_a::Foo::ABI_INFO,&globals).unwrap();
+
+    // Compatible with the interface,but not with the implementation loaded before.
+    let errs=check(interface::Foo::ABI_INFO,impl_b::Foo::ABI_INFO,&globals).unwrap_err();
+    assert_eq!(extra_check_errors(&errs).len(),1);
+
+    check(interface::Foo::ABI_INFO,impl_a_copy::Foo::ABI_INFO,&globals).unwrap();
+
+    // Each group is compatible by itself.
+    let globals=CheckingGlobals::new();
+    check(interface::Foo::ABI_INFO,impl_b::Foo::ABI_INFO,&globals).unwrap();
+    check(impl_a::Foo::ABI_INFO,impl_a_copy::Foo::ABI_INFO,&globals).unwrap();
+
+    // Merging both groups checks impl_b against impl_a and impl_a_copy.
+    let errs=check(interface::Foo::ABI_INFO,impl_a::Foo::ABI_INFO,&globals).unwrap_err();
+    assert_eq!(extra_check_errors(&errs).len(),2);
+}
@@ -0,0 +1,250 @@
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicBool,Ordering},
+};
+
+use crate::{
+    StableAbi,
+    abi_stability::{
+        abi_checking::{
+            AbiInstability,AbiInstabilityErrors,
+            CheckingGlobals,check_library_layout_with_globals,
+        },
+        extra_checks::{ExtraChecksParams,ExtraAbiErrors,ExtraAbiError,ErrorSeriousness},
+        layout_cache::LayoutCheckCache,
+        AbiInfoWrapper,
+    },
+    std_types::{RResult,ROk,RErr},
+};
+
+
+mod prefix0 {
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+    }
+}
+
+mod prefix1 {
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        pub field1: u16,
+    }
+}
+
+
+mod callback0 {
+    use super::prefix0::Prefix;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Callback {
+        pub function: extern "C" fn(u8,&'static Prefix)->u8,
+    }
+}
+
+mod callback1 {
+    use super::prefix1::Prefix;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Callback {
+        pub function: extern "C" fn(u8,&'static Prefix)->u8,
+    }
+}
+
+
+static FAIL_EXTRA_CHECKS:AtomicBool=AtomicBool::new(false);
+
+extern "C" fn fail_if_requested(_:ExtraChecksParams)->RResult<(),ExtraAbiErrors>{
+    if FAIL_EXTRA_CHECKS.load(Ordering::SeqCst) {
+        RErr(ExtraAbiError::new("requested","failed",ErrorSeriousness::NonFatal).into())
+    }else{
+        ROk(())
+    }
+}
+
+mod extra_checks0 {
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="fail_if_requested")]
+    pub struct Checked {
+        pub x: u32,
+    }
+}
+
+mod extra_checks1 {
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(extra_checks="fail_if_requested")]
+    pub struct Checked {
+        pub x: u32,
+    }
+}
+
+
+static PREF_0:&AbiInfoWrapper = <&prefix0::Prefix>::ABI_INFO;
+static PREF_1:&AbiInfoWrapper = <&prefix1::Prefix>::ABI_INFO;
+
+const LIBRARY:&str="100 1.000000000 /libraries/library.so";
+
+
+fn check(
+    interface:&'static AbiInfoWrapper,
+    implementation:&'static AbiInfoWrapper,
+    globals:&CheckingGlobals,
+)->Result<(),AbiInstabilityErrors>{
+    check_library_layout_with_globals(interface,implementation,LIBRARY,globals)
+}
+
+
+fn cache_path(name:&str)->PathBuf{
+    let path=std::env::temp_dir()
+        .join(format!("abi_stable_layout_cache_{}_{}.txt",name,std::process::id()));
+    let _=fs::remove_file(&path);
+    path
+}
+
+fn globals_with_cache(path:&PathBuf)->CheckingGlobals{
+    let globals=CheckingGlobals::new();
+    *globals.layout_cache.lock().unwrap()=Some(LayoutCheckCache::load(path).unwrap());
+    globals
+}
+
+fn cached_lines(path:&PathBuf)->usize{
+    fs::read_to_string(path).unwrap().lines().count()
+}
+
+
+#[test]
+fn successful_checks_are_cached(){
+    let path=cache_path("successful");
+
+    let globals=globals_with_cache(&path);
+    check(PREF_0,PREF_1,&globals).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    // Already in the cache,so nothing is written.
+    check(PREF_0,PREF_1,&globals).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    // The same library file,but not the same interface.
+    check(PREF_1,PREF_0,&globals).unwrap_err();
+    assert_eq!(cached_lines(&path),1);
+
+    // A different library file,with the same interface.
+    let other_library="100 2.000000000 /libraries/library.so";
+    check_library_layout_with_globals(PREF_0,PREF_1,other_library,&globals).unwrap();
+    assert_eq!(cached_lines(&path),2);
+
+    let _=fs::remove_file(&path);
+}
+
+
+#[test]
+fn library_with_the_same_metadata_is_checked(){
+    let path=cache_path("same_metadata");
+
+    let globals=globals_with_cache(&path);
+    check(PREF_0,PREF_1,&globals).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    // A library rebuilt in place,keeping the size and modification time,
+    // with a different layout.
+    let globals=globals_with_cache(&path);
+    check(PREF_0,<u32>::ABI_INFO,&globals).unwrap_err();
+    assert_eq!(cached_lines(&path),1);
+
+    let _=fs::remove_file(&path);
+}
+
+
+#[test]
+fn cache_hits_still_merge_prefix_types(){
+    let path=cache_path("merge");
+
+    check(PREF_0,PREF_1,&globals_with_cache(&path)).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    // Loaded as if it was another process.
+    let globals=globals_with_cache(&path);
+    assert_eq!(globals.prefix_type_map.lock().unwrap().key_len(),0);
+    check(PREF_0,PREF_1,&globals).unwrap();
+    assert_eq!(cached_lines(&path),1);
+    assert_ne!(globals.prefix_type_map.lock().unwrap().key_len(),0);
+
+    let _=fs::remove_file(&path);
+}
+
+
+#[test]
+fn cache_hits_find_prefix_types_in_function_parameters(){
+    let path=cache_path("function_parameters");
+
+    let interface=callback0::Callback::ABI_INFO;
+    let implementation=callback1::Callback::ABI_INFO;
+
+    check(interface,implementation,&globals_with_cache(&path)).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    let globals=globals_with_cache(&path);
+    check(interface,implementation,&globals).unwrap();
+    assert_eq!(cached_lines(&path),1);
+    assert_ne!(globals.prefix_type_map.lock().unwrap().key_len(),0);
+
+    // The prefix type of the cached check is merged with this one.
+    check(PREF_1,PREF_0,&globals).unwrap_err();
+
+    let _=fs::remove_file(&path);
+}
+
+
+#[test]
+fn cache_hits_run_extra_checks(){
+    let path=cache_path("extra_checks");
+
+    let interface=extra_checks0::Checked::ABI_INFO;
+    let implementation=extra_checks1::Checked::ABI_INFO;
+
+    check(interface,implementation,&globals_with_cache(&path)).unwrap();
+    assert_eq!(cached_lines(&path),1);
+
+    FAIL_EXTRA_CHECKS.store(true,Ordering::SeqCst);
+    let result=check(interface,implementation,&globals_with_cache(&path));
+    FAIL_EXTRA_CHECKS.store(false,Ordering::SeqCst);
+
+    let errs=result.unwrap_err().flatten_errors();
+    assert!(
+        errs.iter().any(|e| match e { AbiInstability::ExtraCheckError{..}=>true,_=>false } ),
+        "{:#?}",
+        errs,
+    );
+
+    let _=fs::remove_file(&path);
+}
+
+
+#[test]
+fn corrupted_lines_are_ignored(){
+    let path=cache_path("corrupted");
+    fs::write(&path,"not a fingerprint\n0123\t4567\tlibrary\t.f0.x\n0123\tlibrary\t.\n")
+        .unwrap();
+
+    let globals=globals_with_cache(&path);
+    check(PREF_0,PREF_1,&globals).unwrap();
+    assert_eq!(cached_lines(&path),4);
+
+    let _=fs::remove_file(&path);
+}
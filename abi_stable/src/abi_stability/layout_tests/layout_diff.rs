@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+
+use crate::{
+    StableAbi,
+    abi_stability::layout_diff::{CompatibleChange,diff_layouts},
+    nonexhaustive_enum::examples::{command_a,command_b},
+};
+
+
+mod prefix0 {
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        pub command: &'static command_a::Foo_NE,
+    }
+}
+
+mod prefix1 {
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        pub command: &'static command_b::Foo_NE,
+        pub field2: u16,
+        pub field3: u32,
+    }
+}
+
+
+#[test]
+fn compatible_changes(){
+    let diff=diff_layouts(<&prefix0::Prefix>::ABI_INFO,<&prefix1::Prefix>::ABI_INFO);
+    assert!(!diff.is_breaking(),"{:#?}",diff.errors);
+
+    let mut found_fields=false;
+    let mut found_variants=false;
+    for change in &diff.compatible_changes {
+        match change {
+            CompatibleChange::AddedPrefixFields{fields,..}=>{
+                assert_eq!(fields,&["field2","field3"]);
+                found_fields=true;
+            }
+            CompatibleChange::AddedVariants{variants,..}=>{
+                assert_eq!(variants,&["C"]);
+                found_variants=true;
+            }
+        }
+    }
+    assert!(found_fields && found_variants,"{:#?}",diff.compatible_changes);
+}
+
+
+#[test]
+fn breaking_changes(){
+    let diff=diff_layouts(<&prefix1::Prefix>::ABI_INFO,<&prefix0::Prefix>::ABI_INFO);
+    assert!(diff.is_breaking());
+    assert_eq!(diff.compatible_changes,vec![]);
+
+    let diff=diff_layouts(<&prefix0::Prefix>::ABI_INFO,<&prefix0::Prefix>::ABI_INFO);
+    assert!(!diff.is_breaking());
+    assert_eq!(diff.compatible_changes,vec![]);
+}
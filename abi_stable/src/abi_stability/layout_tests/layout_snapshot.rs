@@ -0,0 +1,275 @@
+#![allow(dead_code)]
+
+use std::{num,sync::atomic};
+
+use crate::{
+    StableAbi,
+    abi_stability::{
+        abi_checking::{AbiInstability,CheckingGlobals,check_layout_compatibility_with_globals},
+        layout_diff::CompatibleChange,
+        layout_snapshot::{LayoutSnapshot,SnapshotError,SNAPSHOT_FORMAT_VERSION},
+        AbiInfoWrapper,
+    },
+    nonexhaustive_enum::examples::{
+        command_a,
+        command_a_exhaustive,
+        command_b,
+        command_c,
+        command_c_mismatched_field,
+        command_h,
+        command_h_mismatched_discriminant,
+    },
+    std_types::*,
+    type_layout::Tag,
+};
+
+
+mod regular{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Rectangle{
+        pub x:u32,
+        pub y:u32,
+        pub name:RString,
+        pub next:ROption<&'static Rectangle>,
+    }
+}
+
+mod changed_field_type{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct Rectangle{
+        pub x:u32,
+        pub y:u32,
+        pub name:RVec<u8>,
+        pub next:ROption<&'static Rectangle>,
+    }
+}
+
+mod tagged_a{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[Tag::str(\"a\")])")]
+    pub struct Tagged{
+        pub x:u32,
+    }
+}
+
+mod tagged_ab{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(tag="Tag::set(&[Tag::str(\"a\"),Tag::str(\"b\")])")]
+    pub struct Tagged{
+        pub x:u32,
+    }
+}
+
+mod prefix0{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal{
+        #[sabi(last_prefix_field)]
+        pub field0:u8,
+        pub callback:extern "C" fn(RStr<'_>)->u32,
+    }
+}
+
+mod prefix1{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal{
+        #[sabi(last_prefix_field)]
+        pub field0:u8,
+        pub callback:extern "C" fn(RStr<'_>)->u32,
+        pub field2:u16,
+    }
+}
+
+mod prefix1_changed_callback{
+    use super::*;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal{
+        #[sabi(last_prefix_field)]
+        pub field0:u8,
+        pub callback:extern "C" fn(RStr<'_>)->u64,
+        pub field2:u16,
+    }
+}
+
+
+fn snapshot_roundtrip(abi_info:&'static AbiInfoWrapper)->LayoutSnapshot{
+    let json=serde_json::to_string(&LayoutSnapshot::new(abi_info)).unwrap();
+    serde_json::from_str::<LayoutSnapshot>(&json).unwrap()
+}
+
+
+#[test]
+fn serialization_roundtrip(){
+    for &abi_info in &[regular::Rectangle::ABI_INFO,<&prefix1::Prefix>::ABI_INFO] {
+        let snapshot=LayoutSnapshot::new(abi_info);
+        assert_eq!(snapshot,snapshot_roundtrip(abi_info));
+        assert_eq!(snapshot.format_version,SNAPSHOT_FORMAT_VERSION);
+    }
+}
+
+
+#[test]
+fn recursive_types(){
+    let snapshot=LayoutSnapshot::new(regular::Rectangle::ABI_INFO);
+    // The recursive reference to Rectangle is stored as an index into the types.
+    let rectangles=snapshot.types.iter().filter(|x| x.name=="Rectangle" ).count();
+    assert_eq!(rectangles,1);
+    snapshot.check_layout(regular::Rectangle::ABI_INFO).unwrap();
+}
+
+
+#[test]
+fn reconstructed_layout(){
+    for &abi_info in &[regular::Rectangle::ABI_INFO,<&prefix1::Prefix>::ABI_INFO] {
+        let snapshot=snapshot_roundtrip(abi_info);
+        let reconstructed=snapshot.to_abi_info().unwrap();
+        // Each snapshot is only reconstructed once.
+        assert!(std::ptr::eq(reconstructed,snapshot.to_abi_info().unwrap()));
+        assert_eq!(LayoutSnapshot::new(reconstructed),snapshot);
+    }
+}
+
+
+#[test]
+fn error_paths(){
+    let errs=match snapshot_roundtrip(regular::Rectangle::ABI_INFO)
+        .check_layout(changed_field_type::Rectangle::ABI_INFO)
+    {
+        Err(SnapshotError::Incompatible(errs))=>errs,
+        x=>panic!("expected an incompatibility error:{:?}",x),
+    };
+    assert!(
+        errs.flatten_errors().iter().any(|e| match e {
+            AbiInstability::Name(ef)=>ef.expected.name.as_str()=="RString",
+            _=>false,
+        }),
+        "{}",
+        errs,
+    );
+
+    let errs=match snapshot_roundtrip(<&prefix1::Prefix>::ABI_INFO)
+        .check_layout(<&prefix1_changed_callback::Prefix>::ABI_INFO)
+    {
+        Err(SnapshotError::Incompatible(errs))=>errs,
+        x=>panic!("expected an incompatibility error:{:?}",x),
+    };
+    assert!(
+        errs.errors.iter()
+            .flat_map(|e| &e.stack_trace )
+            .any(|x| x.expected.to_string().contains("callback") ),
+        "{}",
+        errs,
+    );
+}
+
+
+#[test]
+fn compatible_changes(){
+    let diff=snapshot_roundtrip(<&prefix0::Prefix>::ABI_INFO)
+        .diff(<&prefix1::Prefix>::ABI_INFO)
+        .unwrap();
+    assert!(!diff.is_breaking(),"{:#?}",diff);
+    match &diff.compatible_changes[..] {
+        [CompatibleChange::AddedPrefixFields{fields,..}]=>assert_eq!(fields,&["field2"]),
+        x=>panic!("unexpected changes:{:#?}",x),
+    }
+
+    let diff=snapshot_roundtrip(<&command_a::Foo_NE>::ABI_INFO)
+        .diff(<&command_b::Foo_NE>::ABI_INFO)
+        .unwrap();
+    assert!(!diff.is_breaking(),"{:#?}",diff);
+    match &diff.compatible_changes[..] {
+        [CompatibleChange::AddedVariants{variants,..}]=>assert_eq!(variants,&["C"]),
+        x=>panic!("unexpected changes:{:#?}",x),
+    }
+}
+
+
+#[test]
+fn incompatible_format_version(){
+    let mut snapshot=LayoutSnapshot::new(<u32>::ABI_INFO);
+    snapshot.format_version+=1;
+    match snapshot.check_layout(<u32>::ABI_INFO) {
+        Err(SnapshotError::FormatVersion{expected,found})=>{
+            assert_eq!(expected,SNAPSHOT_FORMAT_VERSION);
+            assert_eq!(found,SNAPSHOT_FORMAT_VERSION+1);
+        }
+        x=>panic!("expected a format version error:{:?}",x),
+    }
+}
+
+
+/// Checking against a snapshot must give the same result as checking against the type.
+#[test]
+fn same_result_as_layout_checking(){
+    let list=vec![
+        <u8>::ABI_INFO,
+        <u32>::ABI_INFO,
+        <i32>::ABI_INFO,
+        <bool>::ABI_INFO,
+        <[u32;3]>::ABI_INFO,
+        <[u32;4]>::ABI_INFO,
+        <&u32>::ABI_INFO,
+        <*const u32>::ABI_INFO,
+        <num::NonZeroU32>::ABI_INFO,
+        <atomic::AtomicUsize>::ABI_INFO,
+        <Option<&u32>>::ABI_INFO,
+        <Option<extern "C" fn()>>::ABI_INFO,
+        <ROption<u32>>::ABI_INFO,
+        <RResult<u32,RString>>::ABI_INFO,
+        <RString>::ABI_INFO,
+        <RVec<u32>>::ABI_INFO,
+        <RVec<RString>>::ABI_INFO,
+        <RHashMap<RString,u32>>::ABI_INFO,
+        <RSlice<'_,u32>>::ABI_INFO,
+        regular::Rectangle::ABI_INFO,
+        changed_field_type::Rectangle::ABI_INFO,
+        tagged_a::Tagged::ABI_INFO,
+        tagged_ab::Tagged::ABI_INFO,
+        <&prefix0::Prefix>::ABI_INFO,
+        <&prefix1::Prefix>::ABI_INFO,
+        <&prefix1_changed_callback::Prefix>::ABI_INFO,
+        <command_a::Foo_NE>::ABI_INFO,
+        <command_a_exhaustive::Foo>::ABI_INFO,
+        <command_b::Foo_NE>::ABI_INFO,
+        <command_c::Foo_NE>::ABI_INFO,
+        <command_c_mismatched_field::Foo_NE>::ABI_INFO,
+        <command_h::Foo_NE>::ABI_INFO,
+        <command_h_mismatched_discriminant::Foo_NE>::ABI_INFO,
+    ];
+
+    for (i,&interface) in list.iter().enumerate() {
+        let snapshot=snapshot_roundtrip(interface);
+        for (j,&implementation) in list.iter().enumerate() {
+            let is_compatible=check_layout_compatibility_with_globals(
+                interface,
+                implementation,
+                &CheckingGlobals::new(),
+            ).is_ok();
+            let snapshot_res=snapshot.check_layout(implementation);
+            assert_eq!(
+                is_compatible,
+                snapshot_res.is_ok(),
+                "\ninterface:{} implementation:{}\n{:?}\n",
+                i,
+                j,
+                snapshot_res,
+            );
+        }
+    }
+}
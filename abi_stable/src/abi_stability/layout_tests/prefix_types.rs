@@ -295,8 +295,7 @@ fn check_interface_impl_pair(
         for (field_i,(l_field,r_field)) in 
             t_prefix.fields.get_fields().zip(pre.fields.get_fields()).enumerate() 
         {
-            if t_prefix.accessible_fields.is_accessible(field_i)
-                &&o_prefix.accessible_fields.is_accessible(field_i)
+            if t_prefix.is_accessible(field_i)&&o_prefix.is_accessible(field_i)
             {
                 assert_eq!(l_field,r_field,"\nleft:{:#?}\n\nright:{:#?}\n",l_field,r_field);
             }
@@ -1053,3 +1052,624 @@ fn prefix_on_conditional_fields() {
         }
     }
 }
+
+
+/////////////////////////////////////////////////////////////////////////
+
+
+mod many_fields_66 {
+    use crate::marker_type::UnsafeIgnoredType;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(
+        kind(Prefix(prefix_struct="Prefix")),
+        unsafe_unconstrained(C),
+    )]
+    pub struct PrefixVal<C> {
+        pub _marker:UnsafeIgnoredType<C>,
+        #[sabi(last_prefix_field)]
+        pub field1: u8,
+        pub field2: u8,
+        pub field3: u8,
+        pub field4: u8,
+        pub field5: u8,
+        pub field6: u8,
+        pub field7: u8,
+        pub field8: u8,
+        pub field9: u8,
+        pub field10: u8,
+        pub field11: u8,
+        pub field12: u8,
+        pub field13: u8,
+        pub field14: u8,
+        pub field15: u8,
+        pub field16: u8,
+        pub field17: u8,
+        pub field18: u8,
+        pub field19: u8,
+        pub field20: u8,
+        pub field21: u8,
+        pub field22: u8,
+        pub field23: u8,
+        pub field24: u8,
+        pub field25: u8,
+        pub field26: u8,
+        pub field27: u8,
+        pub field28: u8,
+        pub field29: u8,
+        pub field30: u8,
+        pub field31: u8,
+        pub field32: u8,
+        pub field33: u8,
+        pub field34: u8,
+        pub field35: u8,
+        pub field36: u8,
+        pub field37: u8,
+        pub field38: u8,
+        pub field39: u8,
+        pub field40: u8,
+        pub field41: u8,
+        pub field42: u8,
+        pub field43: u8,
+        pub field44: u8,
+        pub field45: u8,
+        pub field46: u8,
+        pub field47: u8,
+        pub field48: u8,
+        pub field49: u8,
+        pub field50: u8,
+        pub field51: u8,
+        pub field52: u8,
+        pub field53: u8,
+        pub field54: u8,
+        pub field55: u8,
+        pub field56: u8,
+        pub field57: u8,
+        pub field58: u8,
+        pub field59: u8,
+        pub field60: u8,
+        pub field61: u8,
+        pub field62: u8,
+        pub field63: u8,
+        pub field64: u8,
+        pub field65: u8,
+    }
+}
+
+mod many_fields_70 {
+    use crate::marker_type::UnsafeIgnoredType;
+    use super::EnabledFields;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(
+        kind(Prefix(prefix_struct="Prefix")),
+        prefix_bound="C:EnabledFields",
+        unsafe_unconstrained(C),
+        missing_field(option),
+    )]
+    pub struct PrefixVal<C,T=u8> {
+        pub _marker:UnsafeIgnoredType<C>,
+        #[sabi(last_prefix_field)]
+        pub field1: u8,
+        pub field2: u8,
+        pub field3: u8,
+        pub field4: u8,
+        pub field5: u8,
+        pub field6: u8,
+        pub field7: u8,
+        pub field8: u8,
+        pub field9: u8,
+        pub field10: u8,
+        pub field11: u8,
+        pub field12: u8,
+        pub field13: u8,
+        pub field14: u8,
+        pub field15: u8,
+        pub field16: u8,
+        pub field17: u8,
+        pub field18: u8,
+        pub field19: u8,
+        pub field20: u8,
+        pub field21: u8,
+        pub field22: u8,
+        pub field23: u8,
+        pub field24: u8,
+        pub field25: u8,
+        pub field26: u8,
+        pub field27: u8,
+        pub field28: u8,
+        pub field29: u8,
+        pub field30: u8,
+        pub field31: u8,
+        pub field32: u8,
+        pub field33: u8,
+        pub field34: u8,
+        pub field35: u8,
+        pub field36: u8,
+        pub field37: u8,
+        pub field38: u8,
+        pub field39: u8,
+        pub field40: u8,
+        pub field41: u8,
+        pub field42: u8,
+        pub field43: u8,
+        pub field44: u8,
+        pub field45: u8,
+        pub field46: u8,
+        pub field47: u8,
+        pub field48: u8,
+        pub field49: u8,
+        pub field50: u8,
+        pub field51: u8,
+        pub field52: u8,
+        pub field53: u8,
+        pub field54: u8,
+        pub field55: u8,
+        pub field56: u8,
+        pub field57: u8,
+        pub field58: u8,
+        pub field59: u8,
+        pub field60: u8,
+        pub field61: u8,
+        pub field62: u8,
+        pub field63: u8,
+        #[sabi(accessible_if=" <C as EnabledFields>::ENABLE_FIELD_0 ")]
+        pub field64: u8,
+        pub field65: u8,
+        #[sabi(accessible_if=" <C as EnabledFields>::ENABLE_FIELD_1 ")]
+        #[sabi(field_bound="Copy")]
+        pub field66: T,
+        pub field67: u8,
+        pub field68: u8,
+        pub field69: u8,
+    }
+}
+
+
+#[cfg_attr(not(miri),test)]
+fn prefix_with_more_than_64_fields() {
+    use crate::{
+        type_level::bools::{True as T,False as F},
+        marker_type::UnsafeIgnoredType,
+    };
+
+    type Prefix70<AF,U>=many_fields_70::Prefix<AF,U>;
+
+    let prefix66=many_fields_66::PrefixVal{
+        _marker:UnsafeIgnoredType::<(T,T,T,T)>::DEFAULT,
+        field1:1, field2:2, field3:3, field4:4, field5:5, field6:6, field7:7, field8:8,
+        field9:9, field10:10, field11:11, field12:12, field13:13, field14:14, field15:15, field16:16,
+        field17:17, field18:18, field19:19, field20:20, field21:21, field22:22, field23:23, field24:24,
+        field25:25, field26:26, field27:27, field28:28, field29:29, field30:30, field31:31, field32:32,
+        field33:33, field34:34, field35:35, field36:36, field37:37, field38:38, field39:39, field40:40,
+        field41:41, field42:42, field43:43, field44:44, field45:45, field46:46, field47:47, field48:48,
+        field49:49, field50:50, field51:51, field52:52, field53:53, field54:54, field55:55, field56:56,
+        field57:57, field58:58, field59:59, field60:60, field61:61, field62:62, field63:63, field64:64,
+        field65:65,
+    }.leak_into_prefix();
+
+    let prefix70=many_fields_70::PrefixVal{
+        _marker:UnsafeIgnoredType::<(T,T,T,T)>::DEFAULT,
+        field1:1, field2:2, field3:3, field4:4, field5:5, field6:6, field7:7, field8:8,
+        field9:9, field10:10, field11:11, field12:12, field13:13, field14:14, field15:15, field16:16,
+        field17:17, field18:18, field19:19, field20:20, field21:21, field22:22, field23:23, field24:24,
+        field25:25, field26:26, field27:27, field28:28, field29:29, field30:30, field31:31, field32:32,
+        field33:33, field34:34, field35:35, field36:36, field37:37, field38:38, field39:39, field40:40,
+        field41:41, field42:42, field43:43, field44:44, field45:45, field46:46, field47:47, field48:48,
+        field49:49, field50:50, field51:51, field52:52, field53:53, field54:54, field55:55, field56:56,
+        field57:57, field58:58, field59:59, field60:60, field61:61, field62:62, field63:63, field64:64,
+        field65:65, field66:666, field67:67, field68:68, field69:69,
+    }.leak_into_prefix();
+
+    {// The fields after the 66th don't exist.
+        let value:&Prefix70<(T,T,T,T),u8>=unsafe{ transmute_reference(prefix66) };
+
+        assert_eq!(value.field1(),1);
+        assert_eq!(value.field63(),Some(63));
+        assert_eq!(value.field64(),Some(64));
+        assert_eq!(value.field65(),Some(65));
+        assert_eq!(value.field66(),None);
+        assert_eq!(value.field69(),None);
+    }
+    {
+        let value:&Prefix70<(T,T,T,T),u16>=prefix70;
+
+        assert_eq!(value.field64(),Some(64));
+        assert_eq!(value.field65(),Some(65));
+        assert_eq!(value.field66(),Some(666));
+        assert_eq!(value.field69(),Some(69));
+    }
+    {// The fields after the 64th can be conditionally disabled.
+        let value:&Prefix70<(F,T,T,T),u16>=unsafe{ transmute_reference(prefix70) };
+
+        assert_eq!(value.field64(),None);
+        assert_eq!(value.field65(),Some(65));
+        assert_eq!(value.field66(),Some(666));
+    }
+    {
+        let value:&Prefix70<(T,F,T,T),u16>=unsafe{ transmute_reference(prefix70) };
+
+        assert_eq!(value.field64(),Some(64));
+        assert_eq!(value.field66(),None);
+        assert_eq!(value.field69(),Some(69));
+    }
+}
+
+
+#[cfg_attr(not(miri),test)]
+fn prefix_with_more_than_64_fields_layout() {
+    use crate::type_level::bools::{True as T,False as F};
+
+    let fields_66=<&many_fields_66::Prefix<(T,T,T,T)>>::ABI_INFO;
+    let all_u8  =<&many_fields_70::Prefix<(T,T,T,T),u8>>::ABI_INFO;
+    let all_u16 =<&many_fields_70::Prefix<(T,T,T,T),u16>>::ABI_INFO;
+    let all_i16 =<&many_fields_70::Prefix<(T,T,T,T),i16>>::ABI_INFO;
+    let disabled_66_i16=<&many_fields_70::Prefix<(T,F,T,T),i16>>::ABI_INFO;
+
+    let valid=vec![
+        (fields_66,all_u8),
+        (fields_66,all_u16),
+        (all_u16,all_u16),
+        (disabled_66_i16,all_u16),
+        (all_u16,disabled_66_i16),
+    ];
+    for (interf,impl_) in valid {
+        let globals=CheckingGlobals::new();
+        check_layout_compatibility_with_globals(interf,impl_,&globals).unwrap();
+    }
+
+    // `field66` is accessible in both,so its type is checked.
+    // The interface can't have more fields than the implementation.
+    let invalid=vec![
+        (all_u16,all_i16),
+        (all_i16,all_u16),
+        (all_u8,fields_66),
+    ];
+    for (interf,impl_) in invalid {
+        let globals=CheckingGlobals::new();
+        check_layout_compatibility_with_globals(interf,impl_,&globals).unwrap_err();
+    }
+}
+
+
+mod many_fields_258 {
+    use crate::marker_type::UnsafeIgnoredType;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(
+        kind(Prefix(prefix_struct="Prefix")),
+        unsafe_unconstrained(C),
+        missing_field(option),
+    )]
+    pub struct PrefixVal<C> {
+        pub _marker:UnsafeIgnoredType<C>,
+        #[sabi(last_prefix_field)]
+        pub field0: u16,
+        pub field1: u16, pub field2: u16, pub field3: u16, pub field4: u16, pub field5: u16,
+        pub field6: u16, pub field7: u16, pub field8: u16,
+        pub field9: u16, pub field10: u16, pub field11: u16, pub field12: u16, pub field13: u16,
+        pub field14: u16, pub field15: u16, pub field16: u16,
+        pub field17: u16, pub field18: u16, pub field19: u16, pub field20: u16, pub field21: u16,
+        pub field22: u16, pub field23: u16, pub field24: u16,
+        pub field25: u16, pub field26: u16, pub field27: u16, pub field28: u16, pub field29: u16,
+        pub field30: u16, pub field31: u16, pub field32: u16,
+        pub field33: u16, pub field34: u16, pub field35: u16, pub field36: u16, pub field37: u16,
+        pub field38: u16, pub field39: u16, pub field40: u16,
+        pub field41: u16, pub field42: u16, pub field43: u16, pub field44: u16, pub field45: u16,
+        pub field46: u16, pub field47: u16, pub field48: u16,
+        pub field49: u16, pub field50: u16, pub field51: u16, pub field52: u16, pub field53: u16,
+        pub field54: u16, pub field55: u16, pub field56: u16,
+        pub field57: u16, pub field58: u16, pub field59: u16, pub field60: u16, pub field61: u16,
+        pub field62: u16, pub field63: u16, pub field64: u16,
+        pub field65: u16, pub field66: u16, pub field67: u16, pub field68: u16, pub field69: u16,
+        pub field70: u16, pub field71: u16, pub field72: u16,
+        pub field73: u16, pub field74: u16, pub field75: u16, pub field76: u16, pub field77: u16,
+        pub field78: u16, pub field79: u16, pub field80: u16,
+        pub field81: u16, pub field82: u16, pub field83: u16, pub field84: u16, pub field85: u16,
+        pub field86: u16, pub field87: u16, pub field88: u16,
+        pub field89: u16, pub field90: u16, pub field91: u16, pub field92: u16, pub field93: u16,
+        pub field94: u16, pub field95: u16, pub field96: u16,
+        pub field97: u16, pub field98: u16, pub field99: u16, pub field100: u16, pub field101: u16,
+        pub field102: u16, pub field103: u16, pub field104: u16,
+        pub field105: u16, pub field106: u16, pub field107: u16, pub field108: u16, pub field109: u16,
+        pub field110: u16, pub field111: u16, pub field112: u16,
+        pub field113: u16, pub field114: u16, pub field115: u16, pub field116: u16, pub field117: u16,
+        pub field118: u16, pub field119: u16, pub field120: u16,
+        pub field121: u16, pub field122: u16, pub field123: u16, pub field124: u16, pub field125: u16,
+        pub field126: u16, pub field127: u16, pub field128: u16,
+        pub field129: u16, pub field130: u16, pub field131: u16, pub field132: u16, pub field133: u16,
+        pub field134: u16, pub field135: u16, pub field136: u16,
+        pub field137: u16, pub field138: u16, pub field139: u16, pub field140: u16, pub field141: u16,
+        pub field142: u16, pub field143: u16, pub field144: u16,
+        pub field145: u16, pub field146: u16, pub field147: u16, pub field148: u16, pub field149: u16,
+        pub field150: u16, pub field151: u16, pub field152: u16,
+        pub field153: u16, pub field154: u16, pub field155: u16, pub field156: u16, pub field157: u16,
+        pub field158: u16, pub field159: u16, pub field160: u16,
+        pub field161: u16, pub field162: u16, pub field163: u16, pub field164: u16, pub field165: u16,
+        pub field166: u16, pub field167: u16, pub field168: u16,
+        pub field169: u16, pub field170: u16, pub field171: u16, pub field172: u16, pub field173: u16,
+        pub field174: u16, pub field175: u16, pub field176: u16,
+        pub field177: u16, pub field178: u16, pub field179: u16, pub field180: u16, pub field181: u16,
+        pub field182: u16, pub field183: u16, pub field184: u16,
+        pub field185: u16, pub field186: u16, pub field187: u16, pub field188: u16, pub field189: u16,
+        pub field190: u16, pub field191: u16, pub field192: u16,
+        pub field193: u16, pub field194: u16, pub field195: u16, pub field196: u16, pub field197: u16,
+        pub field198: u16, pub field199: u16, pub field200: u16,
+        pub field201: u16, pub field202: u16, pub field203: u16, pub field204: u16, pub field205: u16,
+        pub field206: u16, pub field207: u16, pub field208: u16,
+        pub field209: u16, pub field210: u16, pub field211: u16, pub field212: u16, pub field213: u16,
+        pub field214: u16, pub field215: u16, pub field216: u16,
+        pub field217: u16, pub field218: u16, pub field219: u16, pub field220: u16, pub field221: u16,
+        pub field222: u16, pub field223: u16, pub field224: u16,
+        pub field225: u16, pub field226: u16, pub field227: u16, pub field228: u16, pub field229: u16,
+        pub field230: u16, pub field231: u16, pub field232: u16,
+        pub field233: u16, pub field234: u16, pub field235: u16, pub field236: u16, pub field237: u16,
+        pub field238: u16, pub field239: u16, pub field240: u16,
+        pub field241: u16, pub field242: u16, pub field243: u16, pub field244: u16, pub field245: u16,
+        pub field246: u16, pub field247: u16, pub field248: u16,
+        pub field249: u16, pub field250: u16, pub field251: u16, pub field252: u16, pub field253: u16,
+        pub field254: u16, pub field255: u16, pub field256: u16,
+        pub field257: u16,
+    }
+}
+
+mod many_fields_300 {
+    use crate::marker_type::UnsafeIgnoredType;
+    use super::EnabledFields;
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(
+        kind(Prefix(prefix_struct="Prefix")),
+        prefix_bound="C:EnabledFields",
+        unsafe_unconstrained(C),
+        missing_field(option),
+    )]
+    pub struct PrefixVal<C> {
+        pub _marker:UnsafeIgnoredType<C>,
+        #[sabi(last_prefix_field)]
+        pub field0: u16,
+        pub field1: u16, pub field2: u16, pub field3: u16, pub field4: u16, pub field5: u16,
+        pub field6: u16, pub field7: u16, pub field8: u16,
+        pub field9: u16, pub field10: u16, pub field11: u16, pub field12: u16, pub field13: u16,
+        pub field14: u16, pub field15: u16, pub field16: u16,
+        pub field17: u16, pub field18: u16, pub field19: u16, pub field20: u16, pub field21: u16,
+        pub field22: u16, pub field23: u16, pub field24: u16,
+        pub field25: u16, pub field26: u16, pub field27: u16, pub field28: u16, pub field29: u16,
+        pub field30: u16, pub field31: u16, pub field32: u16,
+        pub field33: u16, pub field34: u16, pub field35: u16, pub field36: u16, pub field37: u16,
+        pub field38: u16, pub field39: u16, pub field40: u16,
+        pub field41: u16, pub field42: u16, pub field43: u16, pub field44: u16, pub field45: u16,
+        pub field46: u16, pub field47: u16, pub field48: u16,
+        pub field49: u16, pub field50: u16, pub field51: u16, pub field52: u16, pub field53: u16,
+        pub field54: u16, pub field55: u16, pub field56: u16,
+        pub field57: u16, pub field58: u16, pub field59: u16, pub field60: u16, pub field61: u16,
+        pub field62: u16, pub field63: u16, pub field64: u16,
+        pub field65: u16, pub field66: u16, pub field67: u16, pub field68: u16, pub field69: u16,
+        pub field70: u16, pub field71: u16, pub field72: u16,
+        pub field73: u16, pub field74: u16, pub field75: u16, pub field76: u16, pub field77: u16,
+        pub field78: u16, pub field79: u16, pub field80: u16,
+        pub field81: u16, pub field82: u16, pub field83: u16, pub field84: u16, pub field85: u16,
+        pub field86: u16, pub field87: u16, pub field88: u16,
+        pub field89: u16, pub field90: u16, pub field91: u16, pub field92: u16, pub field93: u16,
+        pub field94: u16, pub field95: u16, pub field96: u16,
+        pub field97: u16, pub field98: u16, pub field99: u16, pub field100: u16, pub field101: u16,
+        pub field102: u16, pub field103: u16, pub field104: u16,
+        pub field105: u16, pub field106: u16, pub field107: u16, pub field108: u16, pub field109: u16,
+        pub field110: u16, pub field111: u16, pub field112: u16,
+        pub field113: u16, pub field114: u16, pub field115: u16, pub field116: u16, pub field117: u16,
+        pub field118: u16, pub field119: u16, pub field120: u16,
+        pub field121: u16, pub field122: u16, pub field123: u16, pub field124: u16, pub field125: u16,
+        pub field126: u16, pub field127: u16, pub field128: u16,
+        pub field129: u16, pub field130: u16, pub field131: u16, pub field132: u16, pub field133: u16,
+        pub field134: u16, pub field135: u16, pub field136: u16,
+        pub field137: u16, pub field138: u16, pub field139: u16, pub field140: u16, pub field141: u16,
+        pub field142: u16, pub field143: u16, pub field144: u16,
+        pub field145: u16, pub field146: u16, pub field147: u16, pub field148: u16, pub field149: u16,
+        pub field150: u16, pub field151: u16, pub field152: u16,
+        pub field153: u16, pub field154: u16, pub field155: u16, pub field156: u16, pub field157: u16,
+        pub field158: u16, pub field159: u16, pub field160: u16,
+        pub field161: u16, pub field162: u16, pub field163: u16, pub field164: u16, pub field165: u16,
+        pub field166: u16, pub field167: u16, pub field168: u16,
+        pub field169: u16, pub field170: u16, pub field171: u16, pub field172: u16, pub field173: u16,
+        pub field174: u16, pub field175: u16, pub field176: u16,
+        pub field177: u16, pub field178: u16, pub field179: u16, pub field180: u16, pub field181: u16,
+        pub field182: u16, pub field183: u16, pub field184: u16,
+        pub field185: u16, pub field186: u16, pub field187: u16, pub field188: u16, pub field189: u16,
+        pub field190: u16, pub field191: u16, pub field192: u16,
+        pub field193: u16, pub field194: u16, pub field195: u16, pub field196: u16, pub field197: u16,
+        pub field198: u16, pub field199: u16, pub field200: u16,
+        pub field201: u16, pub field202: u16, pub field203: u16, pub field204: u16, pub field205: u16,
+        pub field206: u16, pub field207: u16, pub field208: u16,
+        pub field209: u16, pub field210: u16, pub field211: u16, pub field212: u16, pub field213: u16,
+        pub field214: u16, pub field215: u16, pub field216: u16,
+        pub field217: u16, pub field218: u16, pub field219: u16, pub field220: u16, pub field221: u16,
+        pub field222: u16, pub field223: u16, pub field224: u16,
+        pub field225: u16, pub field226: u16, pub field227: u16, pub field228: u16, pub field229: u16,
+        pub field230: u16, pub field231: u16, pub field232: u16,
+        pub field233: u16, pub field234: u16, pub field235: u16, pub field236: u16, pub field237: u16,
+        pub field238: u16, pub field239: u16, pub field240: u16,
+        pub field241: u16, pub field242: u16, pub field243: u16, pub field244: u16, pub field245: u16,
+        pub field246: u16, pub field247: u16, pub field248: u16,
+        pub field249: u16, pub field250: u16, pub field251: u16, pub field252: u16, pub field253: u16,
+        pub field254: u16, pub field255: u16, pub field256: u16,
+        pub field257: u16, pub field258: u16, pub field259: u16, pub field260: u16, pub field261: u16,
+        pub field262: u16, pub field263: u16, pub field264: u16,
+        pub field265: u16, pub field266: u16, pub field267: u16, pub field268: u16, pub field269: u16,
+        pub field270: u16, pub field271: u16, pub field272: u16,
+        pub field273: u16, pub field274: u16, pub field275: u16, pub field276: u16, pub field277: u16,
+        pub field278: u16, pub field279: u16,
+        #[sabi(accessible_if=" <C as EnabledFields>::ENABLE_FIELD_2 ")]
+        pub field280: u16,
+        pub field281: u16, pub field282: u16, pub field283: u16, pub field284: u16, pub field285: u16,
+        pub field286: u16, pub field287: u16, pub field288: u16,
+        pub field289: u16, pub field290: u16, pub field291: u16, pub field292: u16, pub field293: u16,
+        pub field294: u16, pub field295: u16, pub field296: u16,
+        pub field297: u16, pub field298: u16, pub field299: u16,
+    }
+}
+
+
+#[cfg_attr(not(miri),test)]
+fn prefix_with_more_than_256_fields() {
+    use crate::{
+        type_level::bools::{True as T,False as F},
+        marker_type::UnsafeIgnoredType,
+    };
+
+    type Prefix300<AF>=many_fields_300::Prefix<AF>;
+
+    let prefix258=many_fields_258::PrefixVal{
+        _marker:UnsafeIgnoredType::<(T,T,T,T)>::DEFAULT,
+        field0:0, field1:1, field2:2, field3:3, field4:4, field5:5, field6:6, field7:7,
+        field8:8, field9:9,
+        field10:10, field11:11, field12:12, field13:13, field14:14,
+        field15:15, field16:16, field17:17, field18:18, field19:19,
+        field20:20, field21:21, field22:22, field23:23, field24:24, field25:25, field26:26, field27:27,
+        field28:28, field29:29,
+        field30:30, field31:31, field32:32, field33:33, field34:34,
+        field35:35, field36:36, field37:37, field38:38, field39:39,
+        field40:40, field41:41, field42:42, field43:43, field44:44,
+        field45:45, field46:46, field47:47, field48:48, field49:49,
+        field50:50, field51:51, field52:52, field53:53, field54:54,
+        field55:55, field56:56, field57:57, field58:58, field59:59,
+        field60:60, field61:61, field62:62, field63:63, field64:64,
+        field65:65, field66:66, field67:67, field68:68, field69:69,
+        field70:70, field71:71, field72:72, field73:73, field74:74,
+        field75:75, field76:76, field77:77, field78:78, field79:79,
+        field80:80, field81:81, field82:82, field83:83, field84:84,
+        field85:85, field86:86, field87:87, field88:88, field89:89,
+        field90:90, field91:91, field92:92, field93:93, field94:94,
+        field95:95, field96:96, field97:97, field98:98, field99:99,
+        field100:100, field101:101, field102:102, field103:103, field104:104,
+        field105:105, field106:106, field107:107, field108:108, field109:109,
+        field110:110, field111:111, field112:112, field113:113, field114:114,
+        field115:115, field116:116, field117:117, field118:118, field119:119,
+        field120:120, field121:121, field122:122, field123:123, field124:124,
+        field125:125, field126:126, field127:127, field128:128, field129:129,
+        field130:130, field131:131, field132:132, field133:133, field134:134,
+        field135:135, field136:136, field137:137, field138:138, field139:139,
+        field140:140, field141:141, field142:142, field143:143, field144:144,
+        field145:145, field146:146, field147:147, field148:148, field149:149,
+        field150:150, field151:151, field152:152, field153:153, field154:154,
+        field155:155, field156:156, field157:157, field158:158, field159:159,
+        field160:160, field161:161, field162:162, field163:163, field164:164,
+        field165:165, field166:166, field167:167, field168:168, field169:169,
+        field170:170, field171:171, field172:172, field173:173, field174:174,
+        field175:175, field176:176, field177:177, field178:178, field179:179,
+        field180:180, field181:181, field182:182, field183:183, field184:184,
+        field185:185, field186:186, field187:187, field188:188, field189:189,
+        field190:190, field191:191, field192:192, field193:193, field194:194,
+        field195:195, field196:196, field197:197, field198:198, field199:199,
+        field200:200, field201:201, field202:202, field203:203, field204:204, field205:205, field206:206, field207:207,
+        field208:208, field209:209,
+        field210:210, field211:211, field212:212, field213:213, field214:214, field215:215, field216:216, field217:217,
+        field218:218, field219:219,
+        field220:220, field221:221, field222:222, field223:223, field224:224, field225:225, field226:226, field227:227,
+        field228:228, field229:229,
+        field230:230, field231:231, field232:232, field233:233, field234:234, field235:235, field236:236, field237:237,
+        field238:238, field239:239,
+        field240:240, field241:241, field242:242, field243:243, field244:244, field245:245, field246:246, field247:247,
+        field248:248, field249:249,
+        field250:250, field251:251, field252:252, field253:253, field254:254, field255:255, field256:256, field257:257,
+    }.leak_into_prefix();
+
+    let prefix300=many_fields_300::PrefixVal{
+        _marker:UnsafeIgnoredType::<(T,T,T,T)>::DEFAULT,
+        field0:0, field1:1, field2:2, field3:3, field4:4, field5:5, field6:6, field7:7,
+        field8:8, field9:9,
+        field10:10, field11:11, field12:12, field13:13, field14:14,
+        field15:15, field16:16, field17:17, field18:18, field19:19,
+        field20:20, field21:21, field22:22, field23:23, field24:24, field25:25, field26:26, field27:27,
+        field28:28, field29:29,
+        field30:30, field31:31, field32:32, field33:33, field34:34,
+        field35:35, field36:36, field37:37, field38:38, field39:39,
+        field40:40, field41:41, field42:42, field43:43, field44:44,
+        field45:45, field46:46, field47:47, field48:48, field49:49,
+        field50:50, field51:51, field52:52, field53:53, field54:54,
+        field55:55, field56:56, field57:57, field58:58, field59:59,
+        field60:60, field61:61, field62:62, field63:63, field64:64,
+        field65:65, field66:66, field67:67, field68:68, field69:69,
+        field70:70, field71:71, field72:72, field73:73, field74:74,
+        field75:75, field76:76, field77:77, field78:78, field79:79,
+        field80:80, field81:81, field82:82, field83:83, field84:84,
+        field85:85, field86:86, field87:87, field88:88, field89:89,
+        field90:90, field91:91, field92:92, field93:93, field94:94,
+        field95:95, field96:96, field97:97, field98:98, field99:99,
+        field100:100, field101:101, field102:102, field103:103, field104:104,
+        field105:105, field106:106, field107:107, field108:108, field109:109,
+        field110:110, field111:111, field112:112, field113:113, field114:114,
+        field115:115, field116:116, field117:117, field118:118, field119:119,
+        field120:120, field121:121, field122:122, field123:123, field124:124,
+        field125:125, field126:126, field127:127, field128:128, field129:129,
+        field130:130, field131:131, field132:132, field133:133, field134:134,
+        field135:135, field136:136, field137:137, field138:138, field139:139,
+        field140:140, field141:141, field142:142, field143:143, field144:144,
+        field145:145, field146:146, field147:147, field148:148, field149:149,
+        field150:150, field151:151, field152:152, field153:153, field154:154,
+        field155:155, field156:156, field157:157, field158:158, field159:159,
+        field160:160, field161:161, field162:162, field163:163, field164:164,
+        field165:165, field166:166, field167:167, field168:168, field169:169,
+        field170:170, field171:171, field172:172, field173:173, field174:174,
+        field175:175, field176:176, field177:177, field178:178, field179:179,
+        field180:180, field181:181, field182:182, field183:183, field184:184,
+        field185:185, field186:186, field187:187, field188:188, field189:189,
+        field190:190, field191:191, field192:192, field193:193, field194:194,
+        field195:195, field196:196, field197:197, field198:198, field199:199,
+        field200:200, field201:201, field202:202, field203:203, field204:204, field205:205, field206:206, field207:207,
+        field208:208, field209:209,
+        field210:210, field211:211, field212:212, field213:213, field214:214, field215:215, field216:216, field217:217,
+        field218:218, field219:219,
+        field220:220, field221:221, field222:222, field223:223, field224:224, field225:225, field226:226, field227:227,
+        field228:228, field229:229,
+        field230:230, field231:231, field232:232, field233:233, field234:234, field235:235, field236:236, field237:237,
+        field238:238, field239:239,
+        field240:240, field241:241, field242:242, field243:243, field244:244, field245:245, field246:246, field247:247,
+        field248:248, field249:249,
+        field250:250, field251:251, field252:252, field253:253, field254:254, field255:255, field256:256, field257:257,
+        field258:258, field259:259,
+        field260:260, field261:261, field262:262, field263:263, field264:264, field265:265, field266:266, field267:267,
+        field268:268, field269:269,
+        field270:270, field271:271, field272:272, field273:273, field274:274, field275:275, field276:276, field277:277,
+        field278:278, field279:279,
+        field280:280, field281:281, field282:282, field283:283, field284:284, field285:285, field286:286, field287:287,
+        field288:288, field289:289,
+        field290:290, field291:291, field292:292, field293:293, field294:294, field295:295, field296:296, field297:297,
+        field298:298, field299:299,
+    }.leak_into_prefix();
+
+    {// The fields after the 258th don't exist.
+        let value:&Prefix300<(T,T,T,T)>=unsafe{ transmute_reference(prefix258) };
+
+        assert_eq!(value.field0(),0);
+        assert_eq!(value.field255(),Some(255));
+        assert_eq!(value.field256(),Some(256));
+        assert_eq!(value.field257(),Some(257));
+        assert_eq!(value.field258(),None);
+        assert_eq!(value.field299(),None);
+    }
+    {
+        let value:&Prefix300<(T,T,T,T)>=prefix300;
+
+        assert_eq!(value.field257(),Some(257));
+        assert_eq!(value.field258(),Some(258));
+        assert_eq!(value.field280(),Some(280));
+        assert_eq!(value.field299(),Some(299));
+    }
+    {// The fields after the 256th can be conditionally disabled.
+        let value:&Prefix300<(T,T,F,T)>=unsafe{ transmute_reference(prefix300) };
+
+        assert_eq!(value.field279(),Some(279));
+        assert_eq!(value.field280(),None);
+        assert_eq!(value.field281(),Some(281));
+    }
+
+    let fields_258=<&many_fields_258::Prefix<(T,T,T,T)>>::ABI_INFO;
+    let fields_300=<&Prefix300<(T,T,T,T)>>::ABI_INFO;
+
+    let globals=CheckingGlobals::new();
+    check_layout_compatibility_with_globals(fields_258,fields_300,&globals).unwrap();
+
+    // The interface can't have more fields than the implementation.
+    let globals=CheckingGlobals::new();
+    check_layout_compatibility_with_globals(fields_300,fields_258,&globals).unwrap_err();
+}
@@ -284,7 +284,7 @@ fn same_different_abi_stability() {
         assert_sane_abi_info(this);
     }
 
-    let list = vec![
+    let mut list = vec![
         <&mut ()>::ABI_INFO,
         <&mut i32>::ABI_INFO,
         <&()>::ABI_INFO,
@@ -337,6 +337,16 @@ fn same_different_abi_stability() {
         <RBoxError>::ABI_INFO,
         <SendRBoxError>::ABI_INFO,
         <UnsyncRBoxError>::ABI_INFO,
+        <RBoxFn<'_,(),()>>::ABI_INFO,
+        <RBoxFn<'_,Tuple1<u32>,u32>>::ABI_INFO,
+        <SendRBoxFn<'_,(),()>>::ABI_INFO,
+        <SyncRBoxFn<'_,(),()>>::ABI_INFO,
+        <RBoxFnMut<'_,(),()>>::ABI_INFO,
+        <SendRBoxFnMut<'_,(),()>>::ABI_INFO,
+        <RBoxFnOnce<'_,(),()>>::ABI_INFO,
+        <SendRBoxFnOnce<'_,(),()>>::ABI_INFO,
+        <sabi_types::RFn<'_,(),()>>::ABI_INFO,
+        <sabi_types::RFnMut<'_,(),()>>::ABI_INFO,
         <RCmpOrdering>::ABI_INFO,
         <PhantomData<()>>::ABI_INFO,
         <PhantomData<RString>>::ABI_INFO,
@@ -375,6 +385,17 @@ fn same_different_abi_stability() {
         <enum_extra_fields_b::Enum>::ABI_INFO,
     ];
 
+    #[cfg(any(rust_1_36,feature="rust_1_36"))]
+    list.extend(vec![
+        <RFuture<'_,()>>::ABI_INFO,
+        <RFuture<'_,u32>>::ABI_INFO,
+        <UnsyncRFuture<'_,()>>::ABI_INFO,
+        <RPoll<()>>::ABI_INFO,
+        <RPoll<u32>>::ABI_INFO,
+        <RWaker>::ABI_INFO,
+        <RContext<'_>>::ABI_INFO,
+    ]);
+
     let (_dur, ()) = core_extensions::measure_time::measure(|| {
         for (i, this) in list.iter().cloned().enumerate() {
             for (j, other) in list.iter().cloned().enumerate() {
@@ -255,6 +255,11 @@ impl fmt::Debug for GetAbiInfo {
 }
 
 impl GetAbiInfo {
+    /// Constructs a `GetAbiInfo` from the function that returns the `AbiInfo`.
+    pub(crate) const fn from_fn(abi_info:extern "C" fn() -> &'static AbiInfo)->Self{
+        Self{abi_info}
+    }
+
     /// Gets the `&'static AbiInfo` of some type.
     pub fn get(self) -> &'static AbiInfo {
         (self.abi_info)()
@@ -0,0 +1,228 @@
+/*!
+An identity for types that is the same in every dynamic library/executable,
+used to unerase types that were erased in a different dynamic library/executable.
+*/
+
+use std::{
+    collections::HashMap,
+    fmt::{self,Display},
+    sync::Mutex,
+};
+
+use core_extensions::SelfOps;
+
+use super::{
+    AbiInfoWrapper,StableAbi,
+    fingerprint::{FingerprintHasher,LayoutFingerprint},
+};
+
+use crate::{
+    sabi_types::{LateStaticRef,MaybeCmp},
+    std_types::utypeid::UTypeId,
+    utils::leak_value,
+};
+
+
+/**
+An identity for a type which is the same in every dynamic library/executable
+that uses the same version of the crate that declared the type.
+
+Unlike `UTypeId`,which never compares equal for types coming from different
+dynamic libraries,this is computed from:
+
+- The package where the type was declared,and its version.
+
+- The module path of the type.
+
+- The `LayoutFingerprint` of the type,
+which is computed from everything that layout checking compares,
+including the types of all its fields.
+
+Two types with the same `StableTypeId` have layouts that layout checking
+considers identical,so the value of one type can be safely used as the other.
+
+This is only available for types that implement `StableAbi`,
+and computing it is expensive the first time it's done for each type.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    abi_stability::StableTypeId,
+    std_types::{RString,RVec},
+};
+
+assert_eq!(StableTypeId::new::<RString>(),StableTypeId::new::<RString>());
+assert_ne!(StableTypeId::new::<RString>(),StableTypeId::new::<RVec<u8>>());
+assert_ne!(StableTypeId::new::<RVec<u8>>(),StableTypeId::new::<RVec<u16>>());
+
+```
+
+*/
+#[repr(C)]
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Ord,PartialOrd,Hash,StableAbi)]
+pub struct StableTypeId{
+    hash:[u64;2],
+}
+
+
+/// Changing how the ids are computed requires changing this string,
+/// so that ids from different versions are never equal.
+const STABLE_TYPE_ID_SEED:&str="abi_stable 0.7 stable type id,format 1";
+
+
+impl StableTypeId{
+    /// Gets the StableTypeId of `T`.
+    pub fn new<T>()->Self
+    where
+        T:StableAbi,
+    {
+        Self::from_abi_info(T::ABI_INFO)
+    }
+
+    /// Gets the StableTypeId of the type that `abi_info` describes.
+    pub fn from_abi_info(abi_info:&'static AbiInfoWrapper)->Self{
+        let utypeid=abi_info.get().get_utypeid();
+        if let Some(id)=cached_ids().lock().unwrap().get(&utypeid) {
+            return *id;
+        }
+
+        // Computed outside of the lock because it can take a while.
+        let id=Self::compute(abi_info);
+        cached_ids().lock().unwrap().insert(utypeid,id);
+        id
+    }
+
+    fn compute(abi_info:&'static AbiInfoWrapper)->Self{
+        let layout=abi_info.get().layout;
+        let fingerprint=LayoutFingerprint::of(abi_info).hash();
+
+        let mut hasher=FingerprintHasher::with_seed(STABLE_TYPE_ID_SEED);
+        hasher.hash_u64(fingerprint as u64);
+        hasher.hash_u64((fingerprint>>64) as u64);
+        hasher.hash_str(&layout.mod_path().to_string());
+        hasher.hash_str(&layout.full_type.to_string());
+
+        let hash=hasher.state;
+        Self{
+            hash:[hash as u64,(hash>>64) as u64],
+        }
+    }
+}
+
+
+impl Display for StableTypeId{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        write!(f,"{:016x}{:016x}",self.hash[1],self.hash[0])
+    }
+}
+
+
+fn cached_ids()->&'static Mutex<HashMap<UTypeId,StableTypeId>>{
+    static CACHED_IDS:LateStaticRef<Mutex<HashMap<UTypeId,StableTypeId>>>=
+        LateStaticRef::new();
+
+    CACHED_IDS.init(|| HashMap::new().piped(Mutex::new).piped(leak_value) )
+}
+
+
+/// `extern "C" fn` version of StableTypeId::new.
+pub extern "C" fn new_stable_type_id<T>()->StableTypeId
+where
+    T:StableAbi,
+{
+    StableTypeId::new::<T>()
+}
+
+
+#[doc(hidden)]
+pub extern "C" fn some_stable_type_id<T>()->MaybeCmp<StableTypeId>
+where
+    T:StableAbi,
+{
+    MaybeCmp::Just(StableTypeId::new::<T>())
+}
+
+
+#[doc(hidden)]
+pub extern "C" fn no_stable_type_id()->MaybeCmp<StableTypeId>{
+    MaybeCmp::Nothing
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::std_types::{RString,RVec};
+
+    mod a{
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Point{
+            pub x:u32,
+            pub y:u32,
+        }
+    }
+
+    mod b{
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Point{
+            pub x:u32,
+            pub y:u32,
+        }
+    }
+
+    mod c{
+        #[repr(C)]
+        #[derive(StableAbi)]
+        pub struct Point{
+            pub x:u32,
+            pub y:u64,
+        }
+    }
+
+    #[test]
+    fn same_and_different_types(){
+        let list=vec![
+            StableTypeId::new::<a::Point>(),
+            // Same definition as `a::Point`,in a different module.
+            StableTypeId::new::<b::Point>(),
+            StableTypeId::new::<c::Point>(),
+            StableTypeId::new::<RString>(),
+            StableTypeId::new::<RVec<u8>>(),
+            StableTypeId::new::<RVec<u16>>(),
+            StableTypeId::new::<u32>(),
+            StableTypeId::new::<i32>(),
+        ];
+        for (i,l) in list.iter().enumerate() {
+            for (j,r) in list.iter().enumerate() {
+                assert_eq!(i==j,l==r,"{} {}",i,j);
+            }
+        }
+    }
+
+    #[test]
+    fn cached_is_same_as_computed(){
+        let abi_info=a::Point::ABI_INFO;
+        let first=StableTypeId::from_abi_info(abi_info);
+        assert_eq!(first,StableTypeId::from_abi_info(abi_info));
+        assert_eq!(first,StableTypeId::compute(abi_info));
+        assert_eq!(first,new_stable_type_id::<a::Point>());
+        assert_eq!(some_stable_type_id::<a::Point>(),MaybeCmp::Just(first));
+        assert_ne!(no_stable_type_id(),MaybeCmp::Just(first));
+    }
+
+    #[test]
+    fn display(){
+        let id=StableTypeId::new::<a::Point>();
+        let hex=id.to_string();
+        assert_eq!(hex.len(),32);
+        assert!(hex.chars().all(|c| c.is_digit(16) ));
+    }
+}
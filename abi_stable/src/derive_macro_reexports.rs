@@ -1,5 +1,6 @@
 pub use crate::{
     abi_stability::{
+        extra_checks::ExtraChecks,
         get_static_equivalent::{GetStaticEquivalent_,GetStaticEquivalent},
         stable_abi_trait::{
             MakeGetAbiInfo,  StableAbi,  SharedStableAbi, StableAbi_Bound, SharedStableAbi_Bound,
@@ -13,6 +13,11 @@ To convert `T` to `&PrefixEquivalent` you an use one of:
 - `PrefixTypeTrait::leak_into_prefix`:<br>
     Which does the conversion directly,but leaks the value.
 
+- `prefix_type::PrefixBox::new`/`prefix_type::PrefixArc::new`:<br>
+    Use this if the value is created and destroyed at runtime.
+    This moves the value to the heap,returning an owned pointer 
+    that dereferences to `PrefixEquivalent`.
+
 - `prefix_type::WithMetadata::new` and then `WithMetadata::as_prefix`:<br>
     Use this if you need a compiletime constant.
     First create a `&'a WithMetadata<Self>` constant,
@@ -19,6 +19,11 @@ By default these are the supertraits that `#[sabi_trait]` traits can have:
 
 - Sync
 
+- serde::Serialize:Written as `Serialize`.Look bellow for how to use serde.
+
+- serde::Deserialize:Written as `Deserialize<'a>` or `for<'a> Deserialize<'a>`.
+    Look bellow for how to use serde.
+
 To be able to have more supertraits you must use the `#[sabi(use_dyntrait)]` helper attribute,
 which changes the underlying implementation from `RObject<_>` to `DynTrait<_>`,
 allowing these supertraits:
@@ -45,6 +50,10 @@ allowing these supertraits:
 
 - std::error::Error:Written as `Error`.
 
+- serde::Serialize:Written as `Serialize`.
+
+- serde::Deserialize:Written as `Deserialize<'a>` or `for<'a> Deserialize<'a>`.
+
 - Eq 
 
 - PartialEq 
@@ -53,9 +62,94 @@ allowing these supertraits:
 
 - PartialOrd 
 
-- Hash 
+- Hash
+
+
+### Serialize and Deserialize
+
+Serialization of `#[sabi_trait]` trait objects uses the same proxy types as `DynTrait`,
+with either backend:
+
+- For `Serialize`:the `Trait_Interface` must implement `SerializeProxyType`,
+and the types the trait object is constructed from must implement
+`SerializeImplType<Interface=Trait_Interface>`,
+the trait object serializes the proxy type returned by `SerializeImplType::serialize_impl`.
+
+- For `Deserialize<'a>`:the `Trait_Interface` must implement
+`DeserializeDyn<'a,Trait_TO<..>>` for the trait object type that is deserialized,
+the trait object deserializes the proxy type and then calls `DeserializeDyn::deserialize_dyn`.
+
+Example of a `#[sabi_trait]` trait with serde supertraits:
+
+```ignore
+#[sabi_trait]
+pub trait State:Serialize+for<'de> Deserialize<'de>{
+    fn count(&self)->u32;
+}
+
+impl SerializeProxyType for State_Interface{
+    type Proxy=RString;
+}
+
+impl<'de> DeserializeDyn<'de,State_TO<'static,RBox<()>>> for State_Interface{
+    type Proxy=RString;
+
+    fn deserialize_dyn(s:RString)->Result<State_TO<'static,RBox<()>>,RBoxError>{
+        // Generally this delegates to a function exported by the implementation crate.
+        serde_json::from_str::<Counter>(&s)
+            .map(|x| State_TO::from_value(x,TU_Opaque) )
+            .map_err(RBoxError::new)
+    }
+}
+
+impl SerializeImplType for Counter{
+    type Interface=State_Interface;
+
+    fn serialize_impl(&self)->Result<RString,RBoxError>{
+        serde_json::to_string(self)
+            .map(RString::from)
+            .map_err(RBoxError::new)
+    }
+}
+```
+
+### `#[sabi_trait]` supertraits
+
+Traits that use `RObject<_>` can also have other `#[sabi_trait]` traits as supertraits,
+storing the vtable of each supertrait inside their own vtable.
+Those supertraits must be listed in the 
+[`#[sabi(sabi_supertraits(...))]`](#sabisabi_supertraits) attribute.
+
+Trait objects of a trait implement each of its `#[sabi_trait]` supertraits,
+and can be converted into the trait objects of those supertraits
+with `From`/`Into` or with the `UpcastTo` trait.
+
+These are the limitations of `#[sabi_trait]` supertraits:
+
+- The supertraits of a `#[sabi_trait]` supertrait must also be listed in the subtrait,
+this includes the marker traits (`Send`/`Sync`/`Debug`/`Clone`).
+
+- Neither the subtrait nor the supertrait can use the `#[sabi(use_dyntrait)]` attribute.
+
+- Supertraits cannot have associated types.
 
+- The `Trait_Interface` of the supertrait must be accessible at the same path as the supertrait,
+by importing both or by referring to the supertrait through its module.
 
+Example of a `#[sabi_trait]` supertrait:
+
+```ignore
+#[sabi_trait]
+pub trait Named:Send{
+    fn name(&self)->RString;
+}
+
+#[sabi_trait]
+#[sabi(sabi_supertraits(Named))]
+pub trait Plugin:Named+Send{
+    fn run(&mut self,input:u32)->u32;
+}
+```
 
 ### Supertrait Extensibility
 
@@ -163,6 +257,11 @@ Where `<Erasability>` can be either:
 -`TU_Unerasable`:
     Which allows the trait object to be unerased,requires that the value implements any.
 
+-`TU_StableUnerasable`:
+    Which allows the trait object to be unerased in any dynamic library/executable
+    that uses the same version of the crate that declared the type,
+    requires that the value implements `StableAbi+'static`.
+
 .`TU_Opaque`Which does not allow the trait object to be unerased.
 
 Where `TraitParam` are the type parameters of the trait.
@@ -179,6 +278,13 @@ Constructs the trait object from its underlying implementation,
 either `RObject` or `DynTrait` depending on whether the
 `#[sabi(use_dyntrait)]` helper attribute was used.
 
+###  Trait_Class
+
+Only generated if the trait has static methods or associated constants.
+
+Allows calling the static methods and reading the associated constants of a type,
+with inherent methods of the same name.
+
 ###  Trait 
 
 The trait is defined similarly to how it is before being transformed by the 
@@ -212,6 +318,13 @@ Note that this does not expand the output of the
 Changes how the trait object is implemented to use `DynTrait` instead of `RObject`,
 this allows using more traits,with the (potential) cost of having more overhead.
 
+###  #[sabi(sabi_supertraits(...))] 
+
+Lists the supertraits that are `#[sabi_trait]` traits,by the last identifier in their path,
+eg:`#[sabi(sabi_supertraits(Named,Counter))]` for `trait Plugin:Named+Counter<u32>`.
+
+Any other supertrait that is not in the list at the start of this page is an error.
+
 # Associated types
 
 The only valid way to refer to associated types in the trait declaration is with 
@@ -220,6 +333,171 @@ The only valid way to refer to associated types in the trait declaration is with
 Associated types in the trait object are transformed into type parameters 
 that come before those of the trait.
 
+# Static methods and associated constants
+
+Traits that use `RObject<_>` can have methods without a `self` receiver
+and associated constants,
+which are stored in a vtable shared by all trait objects constructed from the same type,
+accessible through the `Trait_Class` type.
+
+`Trait_Class` can be constructed from a type with `Trait_Class::from_type`,
+or from a trait object with the `sabi_class` method.
+
+These are the limitations of static methods and associated constants:
+
+- `Trait_TO` does not implement `Trait`,
+only having inherent method equivalents of the methods with a `self` receiver.
+
+- The types of associated constants must be `Copy`.
+
+- `Self` can only be used as the return type of static methods,
+in which case `Trait_Class` returns a `Trait_TO<'lt,RBox<()>>`.
+
+- Static methods cannot have type parameters or closure parameters.
+
+- The trait cannot use the `#[sabi(use_dyntrait)]` attribute or have associated types.
+
+Example of a trait with static items:
+
+```rust
+use abi_stable::{
+    sabi_trait,
+    sabi_trait::prelude::*,
+    std_types::StaticStr,
+};
+
+#[sabi_trait]
+pub trait Factory{
+    const NAME:StaticStr;
+
+    fn new(initial:u32)->Self;
+
+    fn value(&self)->u32;
+}
+
+struct Counter(u32);
+
+impl Factory for Counter{
+    const NAME:StaticStr=StaticStr::new("counter");
+
+    fn new(initial:u32)->Self{
+        Counter(initial)
+    }
+
+    fn value(&self)->u32{
+        self.0
+    }
+}
+
+# fn main() {
+
+let class=Factory_Class::from_type::<Counter,_>(TU_Opaque);
+assert_eq!(class.NAME().as_str(),"counter");
+assert_eq!(class.new(10).value(),10);
+
+let object=Factory_TO::from_value(Counter(3),TU_Opaque);
+assert_eq!(object.sabi_class().new(object.value()+1).value(),4);
+
+# }
+```
+
+# Closure parameters
+
+Methods with a `self` receiver can take closures as parameters,
+with any of these types:
+
+- `impl Fn(..)->R`/`impl FnMut(..)->R`/`impl FnOnce(..)->R`.
+
+- A type parameter of the method,bounded by `Fn(..)->R`/`FnMut(..)->R`/`FnOnce(..)->R`
+(the bound must be declared in the type parameter list,not in a where clause).
+
+- `&dyn Fn(..)->R`/`&mut dyn FnMut(..)->R`.
+
+These closures are passed through the vtable as 
+`abi_stable::sabi_types::{RFn,RFnMut,RFnOnce}`,
+which erase the type of the closure.
+
+These are the limitations of closure parameters:
+
+- They can take at most 4 parameters.
+
+- The closure trait must be their only bound,
+so `impl FnMut()+Send` is not supported.
+
+- Their return type can't have elided lifetimes.
+
+- The type parameters of the method must be the type of exactly one closure parameter.
+
+Example of a trait with closure parameters:
+
+```ignore
+#[sabi_trait]
+pub trait Container{
+    fn for_each(&self,f:impl FnMut(RStr<'_>));
+
+    fn visit_pairs<F:FnMut(&u32,&u32)->bool>(&self,f:F)->usize;
+
+    fn map_all(&mut self,f:&dyn Fn(u32)->u32);
+}
+
+let mut object=Container_TO::from_value(Numbers(vec![3,5,8]),TU_Opaque);
+
+let mut list=Vec::new();
+object.for_each(|s| list.push(s.to_string()) );
+assert_eq!(list,vec!["3","5","8"]);
+
+object.map_all(&|x| x*2 );
+assert_eq!(object.visit_pairs(|l,r| l < r ),2);
+```
+
+# Async methods
+
+Methods with a `&self`/`&mut self` receiver can return futures,
+by being declared as either:
+
+- `async fn method(&self)->T`,which returns an `RFuture<'_,T>`.
+
+- `fn method(&self)->impl Future<Output=T>+Send`,which returns an `RFuture<'_,T>`.
+
+- `fn method(&self)->impl Future<Output=T>`,which returns an `UnsyncRFuture<'_,T>`.
+
+An explicit lifetime bound (`impl Future<Output=T>+'a`) is used as 
+the lifetime of the future,otherwise it borrows `self`.
+
+Because those methods return an `abi_stable::std_types::RFuture`/`UnsyncRFuture`,
+implementors of the trait must return those types,
+constructing them with `RFuture::new(async move{ .. })`.
+
+The default implementation of these methods is written 
+the same way as in regular `async fn`/`impl Future` methods.
+
+These are the limitations of async methods:
+
+- They can't take `self` by value.
+
+- Static methods can't be async.
+
+Example of a trait with async methods:
+
+```ignore
+#[sabi_trait]
+pub trait Store: Sync{
+    fn get(&self,key:u32)->impl Future<Output=RString>+Send;
+
+    async fn describe(&self)->RString{
+        format!("value: {}",self.get(0).await).into()
+    }
+}
+
+impl Store for Names{
+    fn get(&self,key:u32)->RFuture<'_,RString>{
+        RFuture::new(async move{
+            self.names.get(key as usize).cloned().unwrap_or_default()
+        })
+    }
+}
+```
+
 # Object safety
 
 Trait objects generated using this attribute have similar restrictions to built-in trait objects:
@@ -69,6 +69,23 @@ Sibling means libraries loaded at runtime by the same library/binary
 
 For more information about tags,[look here](../../abi_stability/tagging/index.html)
 
+###  `#[sabi(extra_checks=" function_path ")]`
+
+Adds user-defined checks to the layout checking of the type,
+for invariants that can't be expressed with the layout of the type.
+
+The function must have the
+`extern "C" fn(ExtraChecksParams)->RResult<(),ExtraAbiErrors>` signature.
+
+Only the checks of the expected type (the interface) are run.
+Returning a fatal error stops layout checking immediately.
+
+The checks are also run against the previously loaded implementations of the type,
+so that sibling libraries are compatible with each other.
+
+For more information about extra checks,
+[look here](../../abi_stability/extra_checks/index.html)
+
 
 ###  `#[sabi(debug_print)]` 
 
@@ -18,16 +18,17 @@ use serde::{de, ser, Deserialize, Deserializer};
 use core_extensions::{prelude::*, ResultLike};
 
 use crate::{
-    abi_stability::SharedStableAbi,
+    abi_stability::{SharedStableAbi,StableAbi},
     pointer_trait::{
         TransmuteElement,OwnedPointer,
         GetPointerKind,PK_SmartPointer,PK_Reference,
     },
+    library::LibraryKeepAlive,
     marker_type::{ErasedObject,UnsafeIgnoredType}, 
     sabi_types::{StaticRef,MovePtr},
     std_types::{RBox, RStr,RVec,RIoError},
     type_level::{
-        unerasability::{TU_Unerasable,TU_Opaque},
+        unerasability::{TU_Unerasable,TU_StableUnerasable,TU_Opaque},
         impl_enum::{Implemented,Unimplemented},
         trait_marker,
     },
@@ -629,7 +630,7 @@ impl<'a> IteratorItem<'a> for IteratorInterface{
                 extra_vtable:(),
                 _marker:PhantomData,
                 _marker2:UnsafeIgnoredType::DEFAULT,
-            }
+            }.sabi_retain_library()
         }
 
         /// Constructs the `DynTrait<_>` from a type that doesn't borrow anything.
@@ -663,7 +664,47 @@ impl<'a> IteratorItem<'a> for IteratorInterface{
                 extra_vtable:(),
                 _marker:PhantomData,
                 _marker2:UnsafeIgnoredType::DEFAULT,
-            }
+            }.sabi_retain_library()
+        }
+
+        /// Constructs the `DynTrait<_>` from a type that doesn't borrow anything,
+        /// which can be unerased in any dynamic library/executable that uses 
+        /// the same version of the crate that declared `T`,
+        /// with the `sabi_*_stable_unerased` methods.
+        pub fn from_stable_value<T,I>(object: T,interface:I) -> DynTrait<'static,RBox<()>,I>
+        where
+            T:StableAbi+'static,
+            I:InterfaceBound,
+            InterfaceFor<T,I,TU_StableUnerasable> : GetVtable<'static,T,RBox<()>,RBox<T>,I>,
+        {
+            let object = RBox::new(object);
+            DynTrait::from_stable_ptr(object,interface)
+        }
+
+        /// Constructs the `DynTrait<_>` from a pointer to a 
+        /// type that doesn't borrow anything,
+        /// which can be unerased in any dynamic library/executable that uses 
+        /// the same version of the crate that declared `T`,
+        /// with the `sabi_*_stable_unerased` methods.
+        pub fn from_stable_ptr<P, T,I>(
+            object: P,
+            _interface:I
+        ) -> DynTrait<'static,P::TransmutedPtr,I>
+        where
+            I:InterfaceBound,
+            T:StableAbi+'static,
+            InterfaceFor<T,I,TU_StableUnerasable>: GetVtable<'static,T,P::TransmutedPtr,P,I>,
+            P: Deref<Target = T>+TransmuteElement<()>,
+        {
+            DynTrait {
+                object: unsafe{
+                    ManuallyDrop::new(object.transmute_element(<()>::T))
+                },
+                vtable: <InterfaceFor<T,I,TU_StableUnerasable>>::get_vtable(),
+                extra_vtable:(),
+                _marker:PhantomData,
+                _marker2:UnsafeIgnoredType::DEFAULT,
+            }.sabi_retain_library()
         }
         
         /// Constructs the `DynTrait<_>` from a value with a `'borr` borrow.
@@ -704,7 +745,7 @@ impl<'a> IteratorItem<'a> for IteratorInterface{
                 extra_vtable:(),
                 _marker:PhantomData,
                 _marker2:UnsafeIgnoredType::DEFAULT,
-            }
+            }.sabi_retain_library()
         }
     }
 
@@ -752,7 +793,7 @@ These are the requirements for the caller:
                 extra_vtable,
                 _marker:PhantomData,
                 _marker2:UnsafeIgnoredType::DEFAULT,
-            }
+            }.sabi_retain_library()
         }
     }
 
@@ -809,6 +850,24 @@ These are the requirements for the caller:
             (self.vtable as usize)&PTR_FLAGS
         }
 
+        /// Gets the handle that keeps the library the vtable comes from loaded,
+        /// returning None for reborrowed `DynTrait`s,which don't keep it loaded.
+        fn sabi_keep_alive(&self)->Option<&'static LibraryKeepAlive>{
+            if (self.sabi_vtable_ptr_flags()&PTR_FLAG_IS_BORROWED)==PTR_FLAG_IS_BORROWED {
+                None
+            }else{
+                self.sabi_vtable().keep_alive()()
+            }
+        }
+
+        /// Keeps the library that the vtable comes from loaded until this is dropped.
+        fn sabi_retain_library(self)->Self{
+            if let Some(keep_alive)=self.sabi_keep_alive() {
+                unsafe{ keep_alive.retain(); }
+            }
+            self
+        }
+
         /// Returns the address of the wrapped object.
         pub fn sabi_object_address(&self) -> usize
         where
@@ -860,7 +919,12 @@ These are the requirements for the caller:
             P: OwnedPointer<Target=()>,
             F:FnOnce(MovePtr<'_,()>)->R,
         {
-            OwnedPointer::with_move_ptr(self.sabi_into_erased_ptr(),f)
+            let keep_alive=self.sabi_keep_alive();
+            let ret=OwnedPointer::with_move_ptr(self.sabi_into_erased_ptr(),f);
+            if let Some(keep_alive)=keep_alive {
+                unsafe{ keep_alive.release(); }
+            }
+            ret
         }
 
 
@@ -887,6 +951,25 @@ These are the requirements for the caller:
             }
         }
 
+        /// The StableTypeId in the vtable has to be the same as the one for T,
+        /// otherwise it was not created from that T with `TU_StableUnerasable`.
+        fn sabi_check_same_stable_id<T>(&self) -> Result<(), UneraseError<()>>
+        where
+            T:StableAbi+'static,
+            I:InterfaceType,
+        {
+            let t_info = <InterfaceFor<T,I,TU_StableUnerasable>>::INFO;
+            if self.sabi_vtable().type_info().is_stable_compatible(t_info) {
+                Ok(())
+            } else {
+                Err(UneraseError {
+                    dyn_trait:(),
+                    expected_type_info:t_info,
+                    found_type_info:self.sabi_vtable().type_info(),
+                })
+            }
+        }
+
         /// Unwraps the `DynTrait<_>` into a pointer of 
         /// the concrete type that it was constructed with.
         ///
@@ -1062,6 +1145,86 @@ These are the requirements for the caller:
             unsafe { Ok(self.sabi_object_as_mut()) }
         }
 
+
+        /// Unwraps the `DynTrait<_>` into a pointer of 
+        /// the concrete type that it was constructed with.
+        ///
+        /// Unlike `sabi_into_any_unerased`,this can unerase a `DynTrait<_>`
+        /// constructed in a different dynamic library/executable.
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - The DynTrait was not constructed using a `from_stable_*` method.
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or it comes from a different version of the crate that declared it.
+        ///
+        pub fn sabi_into_stable_unerased<T>(self) -> Result<P::TransmutedPtr, UneraseError<Self>>
+        where
+            T:StableAbi+'static,
+            I:InterfaceType,
+            P: TransmuteElement<T>,
+            P::Target:Sized,
+        {
+            check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+            unsafe { 
+                let this=ManuallyDrop::new(self);
+                Ok(ptr::read(&*this.object).transmute_element(T::T)) 
+            }
+        }
+
+        /// Unwraps the `DynTrait<_>` into a reference of 
+        /// the concrete type that it was constructed with.
+        ///
+        /// Unlike `sabi_as_any_unerased`,this can unerase a `DynTrait<_>`
+        /// constructed in a different dynamic library/executable.
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - The DynTrait was not constructed using a `from_stable_*` method.
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or it comes from a different version of the crate that declared it.
+        ///
+        pub fn sabi_as_stable_unerased<T>(&self) -> Result<&T, UneraseError<&Self>>
+        where
+            T:StableAbi+'static,
+            I:InterfaceType,
+            P: Deref + TransmuteElement<T>,
+        {
+            check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+            unsafe { Ok(self.sabi_object_as()) }
+        }
+
+        /// Unwraps the `DynTrait<_>` into a mutable reference of 
+        /// the concrete type that it was constructed with.
+        ///
+        /// Unlike `sabi_as_any_unerased_mut`,this can unerase a `DynTrait<_>`
+        /// constructed in a different dynamic library/executable.
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - The DynTrait was not constructed using a `from_stable_*` method.
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or it comes from a different version of the crate that declared it.
+        ///
+        pub fn sabi_as_stable_unerased_mut<T>(&mut self) -> Result<&mut T, UneraseError<&mut Self>>
+        where
+            T:StableAbi+'static,
+            I:InterfaceType,
+            P: DerefMut + TransmuteElement<T>,
+        {
+            check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+            unsafe { Ok(self.sabi_object_as_mut()) }
+        }
+
     }
 
 
@@ -1150,7 +1313,7 @@ These are the requirements for the caller:
                 extra_vtable,
                 _marker:PhantomData,
                 _marker2:UnsafeIgnoredType::DEFAULT,
-            }
+            }.sabi_retain_library()
         }
     }
 
@@ -1233,7 +1396,13 @@ let _=borrow.default();
                 if (self.sabi_vtable_ptr_flags()&PTR_FLAG_IS_BORROWED)==PTR_FLAG_IS_BORROWED {
                     // Do nothing
                 }else{
+                    let keep_alive=vtable.keep_alive()();
                     vtable.drop_ptr()(&mut *self.object);
+                    // This must be done after the destructor returns,
+                    // since it can unload the library the destructor is in.
+                    if let Some(keep_alive)=keep_alive {
+                        keep_alive.release();
+                    }
                 }
             }
         }
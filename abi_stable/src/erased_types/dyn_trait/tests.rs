@@ -484,6 +484,57 @@ fn to_any_test(){
 }
 
 
+#[repr(C)]
+#[derive(StableAbi,Debug,Clone,PartialEq)]
+struct StablePoint{
+    x:u32,
+    y:u32,
+}
+
+
+#[test]
+fn to_stable_test(){
+    let point=StablePoint{x:3,y:5};
+
+    let mut wrapped=DynTrait::from_stable_value(point.clone(),DebugInterface);
+
+    macro_rules! to_unerased {
+        ( $wrapped:expr ; $method:ident ; $expected:expr ) => (
+            assert_eq!(
+                $wrapped.$method ::<RString>().map_err(drop),
+                Err(())
+            );
+
+            assert_eq!(
+                $wrapped.$method ::<StablePoint>().unwrap(),
+                $expected
+            );
+        )
+    }
+
+    to_unerased!( wrapped ; sabi_as_stable_unerased ; &point );
+    to_unerased!( wrapped ; sabi_as_stable_unerased_mut ; &mut point.clone() );
+    to_unerased!( wrapped.reborrow_mut() ; sabi_into_stable_unerased ; &mut point.clone() );
+    to_unerased!( wrapped.reborrow() ; sabi_into_stable_unerased ; &point );
+    assert_eq!(
+        wrapped.sabi_into_stable_unerased::<StablePoint>().unwrap(),
+        RBox::new(point.clone())
+    );
+
+    // Also unerasable with the sabi_*_any_unerased methods.
+    let wrapped=DynTrait::from_stable_ptr(RBox::new(point.clone()),DebugInterface);
+    assert_eq!(wrapped.sabi_as_any_unerased::<StablePoint>().unwrap(),&point);
+
+    // Not unerasable with the sabi_*_stable_unerased methods.
+    let wrapped=DynTrait::from_any_value(point.clone(),DebugInterface);
+    assert_eq!(
+        wrapped.sabi_as_stable_unerased::<StablePoint>().map_err(drop),
+        Err(())
+    );
+    assert_eq!(wrapped.sabi_as_any_unerased::<StablePoint>().unwrap(),&point);
+}
+
+
 
 
 
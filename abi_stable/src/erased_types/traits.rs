@@ -427,6 +427,7 @@ pub mod interface_for{
             size:mem::size_of::<T>(),
             alignment:mem::align_of::<T>(),
             _uid:<Unerasability as GetUTID<T>>::UID,
+            _stable_id:<Unerasability as GetUTID<T>>::STABLE_ID,
             name:StaticStr::new("<erased>"),
             module:StaticStr::new("<unavailable>"),
             package:StaticStr::new("<unavailable>"),
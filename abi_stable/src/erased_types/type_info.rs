@@ -5,6 +5,7 @@ Contains TypeInfo,metadata for a type.
 use std::fmt;
 
 use crate::{
+    abi_stability::StableTypeId,
     sabi_types::{MaybeCmp,ReturnValueEquality,VersionStrings},
     std_types::{StaticStr,utypeid::UTypeId},
 };
@@ -23,6 +24,10 @@ pub struct TypeInfo {
     pub module: StaticStr,
     pub package: StaticStr,
     pub package_version: VersionStrings,
+    /// This is after all the other fields so that it does not 
+    /// change the offset of the fields that existed before it.
+    #[doc(hidden)]
+    pub _stable_id: ReturnValueEquality<MaybeCmp<StableTypeId>>,
     #[doc(hidden)]
     pub _private_field: (),
 }
@@ -32,6 +37,15 @@ impl TypeInfo {
     pub fn is_compatible(&self, other: &Self) -> bool {
         self._uid==other._uid
     }
+
+    /// Whether the `self` is the TypeInfo for a type with the same `StableTypeId` as `other`,
+    /// which can be true for types coming from different dynamic libraries.
+    ///
+    /// This is only true if both TypeInfos were constructed for
+    /// types erased with `TU_StableUnerasable`.
+    pub fn is_stable_compatible(&self, other: &Self) -> bool {
+        self._stable_id==other._stable_id
+    }
 }
 
 impl fmt::Display for TypeInfo {
@@ -22,6 +22,7 @@ use super::{
 use crate::{
     StableAbi,
     const_utils::Transmuter,
+    library::{LibraryKeepAlive,current_keep_alive},
     marker_type::ErasedObject,
     prefix_type::{PrefixTypeTrait,WithMetadata,panic_on_missing_fieldname},
     pointer_trait::GetPointerKind,
@@ -118,6 +119,7 @@ macro_rules! declare_meta_vtable {
             pub type_info:&'static TypeInfo,
             _marker:PhantomData<extern fn()->Tuple3<$erased_ptr,$interf,&'borr()>>,
             pub drop_ptr:unsafe extern "C" fn(&mut $erased_ptr),
+            pub keep_alive:extern "C" fn()->Option<&'static LibraryKeepAlive>,
             $(
                 $( #[$field_attr] )*
                 $priv_field:$option_ty<($field_ty)>,
@@ -332,6 +334,7 @@ macro_rules! declare_meta_vtable {
             const TMP_VTABLE:VTableVal<'borr,$erased_ptr,$interf>=VTableVal{
                 type_info:This::INFO,
                 drop_ptr:drop_pointer_impl::<$orig_ptr,$erased_ptr>,
+                keep_alive:current_keep_alive,
                 $(
                     $priv_field:
                         <trait_selector::$selector as
@@ -256,26 +256,56 @@ pub use crate::{
 pub mod globals{
     use crate::{
         abi_stability::{
-            abi_checking::{check_layout_compatibility_for_ffi},
+            abi_checking::{
+                AbiInstabilityErrors,
+                check_layout_compatibility_for_ffi,
+                check_library_layout_for_ffi,
+            },
             stable_abi_trait::AbiInfoWrapper,
         },
+        prefix_type::PrefixTypeTrait,
         sabi_types::LateStaticRef,
-        std_types::{RResult,RBoxError},
-        utils::leak_value,
+        std_types::{RDuration,RResult,RStr,time::monotonic_clock},
     };
 
+    /// The state shared between the executable and the dynamic libraries it loads.
+    ///
+    /// This is a prefix type so that fields can be added to it in minor versions,
+    /// accessing fields missing in the `Globals` of an older executable
+    /// as described in their `missing_field` attribute.
     #[repr(C)]
     #[derive(StableAbi)]
-    pub struct Globals{
+    #[sabi(kind(Prefix(prefix_struct="Globals")))]
+    #[sabi(missing_field(panic))]
+    pub struct GlobalsVal{
+        #[sabi(last_prefix_field)]
         pub layout_checking:
-            extern fn(&'static AbiInfoWrapper,&'static AbiInfoWrapper) -> RResult<(), RBoxError> ,
+            extern fn(&'static AbiInfoWrapper,&'static AbiInfoWrapper) 
+                -> RResult<(), AbiInstabilityErrors> ,
+        /// The monotonic clock that `RInstant` is measured with.
+        ///
+        /// If the executable doesn't have this field,
+        /// the clock of the dynamic library is used,
+        /// so `RInstant`s created in it are not comparable with those of the executable.
+        #[sabi(missing_field(value="monotonic_clock"))]
+        pub monotonic_clock:extern fn()->RDuration,
+        /// Checks the layout of the root module of a dynamic library,
+        /// identified by the last parameter for the layout checking cache.
+        ///
+        /// If the executable doesn't have this field,`layout_checking` is used instead.
+        #[sabi(missing_field(option))]
+        pub library_layout_checking:
+            extern fn(&'static AbiInfoWrapper,&'static AbiInfoWrapper,RStr<'_>) 
+                -> RResult<(), AbiInstabilityErrors> ,
     }
 
     impl Globals{
         pub fn new()->&'static Self{
-            leak_value(Globals{
+            GlobalsVal{
                 layout_checking:check_layout_compatibility_for_ffi,
-            })
+                monotonic_clock,
+                library_layout_checking:check_library_layout_for_ffi,
+            }.leak_into_prefix()
         }
     }
 
@@ -27,23 +27,43 @@ pub use abi_stable_shared::mangled_root_module_loader_name;
 use crate::{
     abi_stability::{
         AbiInfoWrapper,
+        AbiInstabilityErrors,
         stable_abi_trait::SharedStableAbi,
     },
     globals::{self,Globals},
     marker_type::ErasedObject,
     utils::{transmute_reference},
-    sabi_types::{ LateStaticRef, ParseVersionError, VersionNumber, VersionStrings },
-    std_types::{RVec,RBoxError,StaticStr},
+    sabi_types::{
+        FullVersionNumber, LateStaticRef, ParseVersionError, ParseVersionReqError, VersionStrings,
+    },
+    std_types::{RVec,StaticStr},
 };
 
 
+mod keep_alive;
 mod lib_header;
+mod lib_metadata;
+mod loaded_library;
+mod plugin_registry;
 mod root_mod_trait;
 mod raw_library;
+mod version_policy;
 
 
 pub use self::{
+    keep_alive::{LibraryKeepAlive,current_keep_alive,no_keep_alive},
     lib_header::{AbiHeader,LibHeader},
+    lib_metadata::{
+        LibMetadata,
+        LibMetadataError,
+        LibMetadataSection,
+        RootModuleMetadata,
+        lib_metadata_from_path,
+        LIB_METADATA_SECTION,
+        LIB_METADATA_FILE,
+    },
+    loaded_library::{LoadedLibrary,LibraryPinned},
+    plugin_registry::{PluginRegistry,LoadedPlugin,ScanReport,PluginLoadError},
     root_mod_trait::{
         RootModule,
         lib_header_from_raw_library,
@@ -52,6 +72,7 @@ pub use self::{
         ErasedRootModuleConsts,
     },
     raw_library::RawLibrary,
+    version_policy::{VersionPolicy,FailedVersionRule},
 };
 
 
@@ -163,20 +184,32 @@ pub enum LibraryError {
     },
     /// The version string could not be parsed into a version number.
     ParseVersionError(ParseVersionError),
-    /// The version numbers of the library was incompatible.
+    /// The version requirement of `VersionPolicy::Requirement` could not be parsed.
+    ParseVersionReqError(ParseVersionReqError),
+    /// The version numbers of the library was incompatible,
+    /// according to the `RootModule::VERSION_POLICY` of the root module.
     IncompatibleVersionNumber {
         library_name: &'static str,
-        expected_version: VersionNumber,
-        actual_version: VersionNumber,
+        expected_version: FullVersionNumber,
+        actual_version: FullVersionNumber,
+        /// The rule of the version policy that the library did not satisfy.
+        failed_rule: FailedVersionRule,
     },
     /// The abi is incompatible.
-    /// The error is opaque,since the error always comes from the main binary
+    ///
+    /// The error always comes from the layout checker of the main binary
     /// (dynamic libraries can be loaded from other dynamic libraries),
-    /// and no approach for extensible enums is settled on yet.
-    AbiInstability(RBoxError),
+    /// the individual errors are stored in `NonExhaustive`s,
+    /// since more kinds of errors can be added in minor versions.
+    AbiInstability(AbiInstabilityErrors),
     /// The type used to check that this is a compatible abi_stable
     /// is not the same.
     InvalidAbiHeader(AbiHeader),
+    /// The `LibMetadata` of a library could not be read from its file.
+    LibMetadataError{
+        path:PathBuf,
+        error:LibMetadataError,
+    },
     /// There could have been 0 or more errors in the function.
     Many(RVec<Self>),
 }
@@ -187,6 +220,12 @@ impl From<ParseVersionError> for LibraryError {
     }
 }
 
+impl From<ParseVersionReqError> for LibraryError {
+    fn from(v: ParseVersionReqError) -> LibraryError {
+        LibraryError::ParseVersionReqError(v)
+    }
+}
+
 impl Display for LibraryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("\n")?;
@@ -204,14 +243,16 @@ impl Display for LibraryError {
                 io
             ),
             LibraryError::ParseVersionError(x) => fmt::Display::fmt(x, f),
+            LibraryError::ParseVersionReqError(x) => fmt::Display::fmt(x, f),
             LibraryError::IncompatibleVersionNumber {
                 library_name,
                 expected_version,
                 actual_version,
+                failed_rule,
             } => writeln!(
                 f,
-                "\n'{}' library version mismatch:\nuser:{}\nlibrary:{}",
-                library_name, expected_version, actual_version,
+                "\n'{}' library version mismatch:\nuser:{}\nlibrary:{}\nbecause {}",
+                library_name, expected_version, actual_version, failed_rule,
             ),
             LibraryError::AbiInstability(x) => fmt::Display::fmt(x, f),
             LibraryError::InvalidAbiHeader(found) => write!(
@@ -220,6 +261,11 @@ impl Display for LibraryError {
                  When this library expected:\n{:#?}",
                 found, AbiHeader::VALUE,
             ),
+            LibraryError::LibMetadataError{path,error} => writeln!(
+                f,
+                "Could not read the metadata of the library at:\n\t{}\nbecause:\n\t{}",
+                path.display(),error
+            ),
             LibraryError::Many(list)=>{
                 for e in list {
                     Display::fmt(e,f)?;
@@ -0,0 +1,96 @@
+/*!
+Handles used by the values that a dynamic library creates to keep the library loaded.
+*/
+
+use super::*;
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicPtr,Ordering},
+};
+
+
+/**
+A handle used by the values a dynamic library creates to keep the library loaded,
+if it was loaded with `RootModule::load_unloadable_from`.
+
+`DynTrait` and `RObject` get this handle from their vtable,
+calling `retain` when they're constructed,and `release` after they're dropped.
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct LibraryKeepAlive{
+    retain:unsafe extern "C" fn(&LibraryKeepAlive),
+    release:unsafe extern "C" fn(&LibraryKeepAlive),
+}
+
+
+impl LibraryKeepAlive{
+    pub(super) const fn new(
+        retain:unsafe extern "C" fn(&LibraryKeepAlive),
+        release:unsafe extern "C" fn(&LibraryKeepAlive),
+    )->Self{
+        Self{retain,release}
+    }
+
+    pub(super) fn retain_fn(&self)->unsafe extern "C" fn(&LibraryKeepAlive){
+        self.retain
+    }
+
+    /// Keeps the library loaded until `release` is called.
+    ///
+    /// # Safety
+    ///
+    /// The library must be loaded,
+    /// and `release` must be called once for every call to this method.
+    #[inline]
+    pub unsafe fn retain(&self){
+        (self.retain)(self)
+    }
+
+    /// Undoes a call to `retain`,unloading the library if nothing else keeps it loaded.
+    ///
+    /// # Safety
+    ///
+    /// This must be called once for every call to `retain`,
+    /// and not from code inside the library,since it can be unloaded by this call.
+    #[inline]
+    pub unsafe fn release(&self){
+        (self.release)(self)
+    }
+}
+
+impl fmt::Debug for LibraryKeepAlive{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("LibraryKeepAlive").finish()
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// The `LibraryKeepAlive` of the dynamic library/executable that this static is in.
+static CURRENT:AtomicPtr<LibraryKeepAlive>=AtomicPtr::new(ptr::null_mut());
+
+
+/// Gets the `LibraryKeepAlive` of the dynamic library this function is defined in,
+/// returning None if it was not loaded with `RootModule::load_unloadable_from`.
+pub extern "C" fn current_keep_alive()->Option<&'static LibraryKeepAlive>{
+    unsafe{ CURRENT.load(Ordering::Acquire).as_ref() }
+}
+
+
+/// Sets the `LibraryKeepAlive` of the dynamic library this function is defined in.
+pub(super) extern "C" fn set_current_keep_alive(keep_alive:Option<&'static LibraryKeepAlive>){
+    let ptr=keep_alive.map_or(ptr::null_mut(),|x| x as *const LibraryKeepAlive as *mut _ );
+    CURRENT.store(ptr,Ordering::Release);
+}
+
+
+/// Used as the `LibraryKeepAlive` getter of vtables
+/// created by libraries that don't have one.
+pub extern "C" fn no_keep_alive()->Option<&'static LibraryKeepAlive>{
+    None
+}
@@ -1,6 +1,10 @@
 use super::*;
 
 use crate::{
+    abi_stability::{
+        abi_checking::{CheckingGlobals,check_layout_compatibility_with_globals},
+        layout_cache::library_id,
+    },
     utils::Constructor,
 };
 
@@ -14,6 +18,7 @@ pub struct LibHeader {
     init_globals_with:InitGlobalsWith,
     module:LateStaticRef<ErasedObject>,
     constructor:Constructor<&'static ErasedObject>,
+    keep_alive:KeepAliveFns,
 }
 
 impl LibHeader {
@@ -29,6 +34,7 @@ impl LibHeader {
             init_globals_with: INIT_GLOBALS_WITH,
             module:LateStaticRef::new(),
             constructor:constructor,
+            keep_alive:KEEP_ALIVE_FNS,
         }
     }
 
@@ -44,6 +50,7 @@ impl LibHeader {
             init_globals_with: INIT_GLOBALS_WITH,
             module:LateStaticRef::initialized(value),
             constructor:GetAbortingConstructor::ABORTING_CONSTRUCTOR,
+            keep_alive:KEEP_ALIVE_FNS,
         }
     }
 
@@ -70,26 +77,27 @@ impl LibHeader {
         (self.init_globals_with.0)(globals);
     }
 
+    /// Gets the `LibraryKeepAlive` that values created by the library use.
+    pub(super) fn keep_alive(&self)->Option<&'static LibraryKeepAlive>{
+        (self.keep_alive.get)()
+    }
+
+    /// Sets the `LibraryKeepAlive` that values created by the library use.
+    pub(super) fn set_keep_alive(&self,keep_alive:Option<&'static LibraryKeepAlive>){
+        (self.keep_alive.set)(keep_alive);
+    }
+
 
     fn check_version<M>(&self)->Result<(),LibraryError>
     where
         M:RootModule
     {
         let expected_version = M::VERSION_STRINGS
-            .piped(VersionNumber::new)?;
-
-        let actual_version = self.version_strings().piped(VersionNumber::new)?;
-
-        if expected_version.major != actual_version.major || 
-            (expected_version.major==0) && expected_version.minor > actual_version.minor
-        {
-            return Err(LibraryError::IncompatibleVersionNumber {
-                library_name: M::NAME,
-                expected_version,
-                actual_version,
-            });
-        }
-        Ok(())
+            .piped(FullVersionNumber::new)?;
+
+        let actual_version = self.version_strings().piped(FullVersionNumber::new)?;
+
+        M::VERSION_POLICY.check(M::NAME, &expected_version, &actual_version)
     }
 
 
@@ -119,8 +127,12 @@ This will return these errors:
 If the version strings in the library can't be parsed as version numbers,
 this can only happen if the version strings are manually constructed.
 
+- LibraryError::ParseVersionReqError:
+If the requirement of `RootModule::VERSION_POLICY` can't be parsed.
+
 - LibraryError::IncompatibleVersionNumber:
-If the version number of the library is incompatible.
+If the version number of the library is incompatible,
+according to `RootModule::VERSION_POLICY`.
 
 - LibraryError::AbiInstability:
 If the layout of the root module is not the expected one.
@@ -137,6 +149,57 @@ If the layout of the root module is not the expected one.
     }
 
 
+    /// Does the same as `init_root_module`,
+    /// using the layout checking cache (if enabled) for the library at `library_path`.
+    pub(super) fn init_root_module_from_library<M>(
+        &self,
+        library_path:&Path,
+    )-> Result<&'static M, LibraryError>
+    where
+        M: RootModule
+    {
+        self.check_version::<M>()?;
+        self.check_layout_inner::<M>(Some(library_path))
+    }
+
+
+
+    /**
+Checks that the library is compatible,returning the root module on success.
+
+This is like `init_root_module`,
+except that the layout of the root module is checked in isolation,
+without registering the prefix types and nonexhaustive enums it uses
+in the global state shared by every library.
+
+This is used for libraries that can be unloaded,
+so that the global state does not keep references to their type layouts.
+
+# Errors
+
+This returns the same errors as `init_root_module`.
+
+    */
+    pub(super) fn init_unregistered_root_module<M>(&self)-> Result<&'static M, LibraryError>
+    where
+        M: RootModule
+    {
+        self.check_version::<M>()?;
+
+        if let IsAbiChecked::Yes(root_mod_abi_info)=self.root_mod_consts.abi_info(){
+            check_layout_compatibility_with_globals(
+                <&M>::S_ABI_INFO,
+                root_mod_abi_info,
+                &CheckingGlobals::new(),
+            ).map_err(LibraryError::AbiInstability)?;
+        }
+
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+
+        Ok(unsafe{ self.unchecked_layout() })
+    }
+
+
 
     /**
 Checks that the version number of the library is compatible,
@@ -166,8 +229,12 @@ This will return these errors:
 If the version strings in the library can't be parsed as version numbers,
 this can only happen if the version strings are manually constructed.
 
+- LibraryError::ParseVersionReqError:
+If the requirement of `RootModule::VERSION_POLICY` can't be parsed.
+
 - LibraryError::IncompatibleVersionNumber:
-If the version number of the library is incompatible.
+If the version number of the library is incompatible,
+according to `RootModule::VERSION_POLICY`.
 
     */
     pub unsafe fn init_root_module_with_unchecked_layout<M>(
@@ -185,6 +252,13 @@ If the version number of the library is incompatible.
     /// checking that the layout of the `M` from the dynamic library is 
     /// compatible with the expected layout.
     pub fn check_layout<M>(&self) -> Result<&'static M, LibraryError>
+    where
+        M: RootModule,
+    {
+        self.check_layout_inner::<M>(None)
+    }
+
+    fn check_layout_inner<M>(&self,library_path:Option<&Path>) -> Result<&'static M, LibraryError>
     where
         M: RootModule,
     {
@@ -198,8 +272,16 @@ If the version number of the library is incompatible.
             //
             // This might also reduce the code in the library,
             // because it doesn't have to compile the layout checker for every library.
-            (globals::initialized_globals().layout_checking)
-                (<&M>::S_ABI_INFO, root_mod_abi_info)
+            let globals=globals::initialized_globals();
+            let interface=<&M>::S_ABI_INFO;
+            let library_id=library_path.and_then(library_id);
+
+            match (library_id,globals.library_layout_checking()) {
+                (Some(library_id),Some(library_layout_checking))=>
+                    library_layout_checking(interface,root_mod_abi_info,library_id.as_str().into()),
+                _=>
+                    globals.layout_checking()(interface,root_mod_abi_info),
+            }
                 .into_result()
                 .map_err(LibraryError::AbiInstability)?;
         }
@@ -268,6 +350,22 @@ const INIT_GLOBALS_WITH:InitGlobalsWith=
     InitGlobalsWith(crate::globals::initialize_globals_with);
 
 
+//////////////////////////////////////////////////////////////////////
+
+/// Gets and sets the `LibraryKeepAlive` of the library.
+#[repr(C)]
+#[derive(StableAbi,Copy,Clone)]
+struct KeepAliveFns{
+    get:extern "C" fn()->Option<&'static LibraryKeepAlive>,
+    set:extern "C" fn(Option<&'static LibraryKeepAlive>),
+}
+
+const KEEP_ALIVE_FNS:KeepAliveFns=KeepAliveFns{
+    get:keep_alive::current_keep_alive,
+    set:keep_alive::set_current_keep_alive,
+};
+
+
 //////////////////////////////////////////////////////////////////////
 
 /**
@@ -291,10 +389,13 @@ pub struct AbiHeader{
 impl AbiHeader{
     /// The value of the AbiHeader stored in dynamic libraries that use this 
     /// version of abi_stable
+    ///
+    /// This must be bumped whenever a type shared with dynamic libraries 
+    /// without being layout checked (ie:`Globals`,`TypeLayout`) changes.
     pub const VALUE:AbiHeader=AbiHeader{
         magic_string:*b"abi stable library for Rust     ",
         abi_major:0,
-        abi_minor:6,
+        abi_minor:7,
         _priv:(),
     };
 }
@@ -309,3 +410,34 @@ impl AbiHeader{
         ( self.abi_major!=0 || self.abi_minor==other.abi_minor )
     }
 }
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    fn with_version(abi_major:u32,abi_minor:u32)->AbiHeader{
+        AbiHeader{abi_major,abi_minor,..AbiHeader::VALUE}
+    }
+
+    #[test]
+    fn abi_header_compatibility(){
+        let current=AbiHeader::VALUE;
+        assert!(current.is_compatible(&current));
+
+        // 0.6 libraries pass `Globals` and `TypeLayout` with a different layout.
+        assert!(!with_version(0,6).is_compatible(&current));
+        assert!(!current.is_compatible(&with_version(0,6)));
+        assert!(!with_version(0,current.abi_minor+1).is_compatible(&current));
+
+        assert!(with_version(1,2).is_compatible(&with_version(1,5)));
+        assert!(!with_version(1,2).is_compatible(&with_version(2,2)));
+
+        let mut not_abi_stable=current;
+        not_abi_stable.magic_string[0]=b'A';
+        assert!(!not_abi_stable.is_compatible(&current));
+    }
+}
@@ -0,0 +1,612 @@
+/*!
+Metadata about an abi_stable dynamic library that can be read without loading it.
+*/
+
+use super::*;
+
+use std::{
+    env,
+    fs,
+    str,
+};
+
+#[cfg(feature="serde_json")]
+use crate::reflection::export_module::MRItem;
+
+pub use abi_stable_shared::LIB_METADATA_FILE;
+
+
+mod elf;
+
+
+/// The name of the ELF section that `#[export_root_module]` stores the `LibMetadata` in.
+pub const LIB_METADATA_SECTION:&str=".abi_stable_meta";
+
+/// The magic bytes that the serialized `LibMetadata` starts with.
+const MAGIC:[u8;16]=*b"abi_stable meta\0";
+
+/// The version of the format that `LibMetadata` is serialized in.
+const FORMAT_VERSION:u32=1;
+
+
+/**
+Metadata about an abi_stable dynamic library,
+stored in the library so that it can be read without loading it.
+
+# Embedding the metadata
+
+`#[export_root_module]` stores the metadata in the `LIB_METADATA_SECTION` section
+of ELF dynamic libraries (Linux,the BSDs,etc),
+on other platforms it is not stored.
+
+The `abi_header` is always stored,
+since it is a constant of the abi_stable version that the library is compiled with.
+
+The `root_module` metadata is serialized by the build script of the implementation crate,
+which requires the interface crate to also be a build dependency.
+`#[export_root_module]` includes it if the build script wrote it.
+
+```text
+// In build.rs
+use abi_stable::library::RootModuleMetadata;
+use example_interface::ExampleMod;
+
+fn main(){
+    RootModuleMetadata::new::<ExampleMod>()
+        .write_to_out_dir()
+        .unwrap();
+}
+```
+
+# Reading the metadata
+
+Use `lib_metadata_from_path` to read the metadata,
+which only reads the file,without loading the library
+(and so without running its static initializers or loading its dependencies).
+
+*/
+#[derive(Debug,Clone)]
+pub struct LibMetadata{
+    /// The abi_stable version used by the library.
+    pub abi_header:AbiHeader,
+    /// Metadata about the root module,
+    /// which is None if the build script of the library did not write it.
+    pub root_module:Option<RootModuleMetadata>,
+}
+
+
+/**
+Metadata about the root module of an abi_stable dynamic library,
+serialized by the build script of the library with `write_to_out_dir`.
+
+Since build scripts are compiled for the host,
+the layout of the root module is only stored when the library is not cross-compiled,
+because the layout of types can differ between targets
+(eg:because of the size of pointers).
+*/
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct RootModuleMetadata{
+    /// The name of the dynamic library,which is the same on all platforms.
+    pub base_name:String,
+    /// The name of the library used in error messages.
+    pub name:String,
+    /// The version of the root module,
+    /// the same string as the `VersionStrings` that the `LibHeader` stores.
+    pub version:String,
+    /**
+The layout of the root module serialized as json,
+in the same format that `sabi_extract mods` outputs.
+
+This is None if abi_stable was compiled without the "serde_json" feature
+in the build script,or if the library was cross-compiled.
+    */
+    pub layout:Option<String>,
+}
+
+
+/// The reasons why the `LibMetadata` in a file could not be read.
+#[derive(Debug)]
+pub enum LibMetadataError{
+    /// When the file could not be read.
+    Io(io::Error),
+    /// When the file is not an ELF file.
+    NotElf,
+    /// When the ELF file is truncated or has invalid offsets.
+    MalformedElf(&'static str),
+    /// When the file does not have a `LIB_METADATA_SECTION` section,
+    /// because the library was compiled with a version of abi_stable that didn't store it.
+    NoMetadataSection,
+    /// When the metadata section does not start with the expected magic bytes.
+    InvalidMagic,
+    /// When the metadata was serialized by a newer version of abi_stable.
+    UnsupportedFormat{
+        format_version:u32,
+    },
+    /// When the metadata section ends before the entire `LibMetadata` is read.
+    Truncated,
+    /// When one of the strings is not valid utf-8.
+    InvalidUtf8(str::Utf8Error),
+}
+
+
+/**
+The contents of the `LIB_METADATA_SECTION` section,
+stored by `#[export_root_module]`.
+
+`B` is the `[u8;_]` array written by `RootModuleMetadata::write_to_out_dir`,
+or `[u8;0]` if it was not written.
+*/
+#[doc(hidden)]
+#[repr(C)]
+pub struct LibMetadataSection<B>{
+    magic:[u8;16],
+    format_version:u32,
+    abi_magic_string:[u8;32],
+    abi_major:u32,
+    abi_minor:u32,
+    root_module:B,
+}
+
+impl<B> LibMetadataSection<B>{
+    // The integers are stored as little endian so that 
+    // reading them does not depend on the endianness of the library.
+    pub const fn new(root_module:B)->Self{
+        Self{
+            magic:MAGIC,
+            format_version:FORMAT_VERSION.to_le(),
+            abi_magic_string:AbiHeader::VALUE.magic_string,
+            abi_major:AbiHeader::VALUE.abi_major.to_le(),
+            abi_minor:AbiHeader::VALUE.abi_minor.to_le(),
+            root_module,
+        }
+    }
+}
+
+
+impl LibMetadata{
+    /// Serializes this into the bytes stored in the metadata section.
+    pub fn to_bytes(&self)->Vec<u8>{
+        let mut bytes=Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        push_u32(&mut bytes,FORMAT_VERSION);
+        bytes.extend_from_slice(&self.abi_header.magic_string);
+        push_u32(&mut bytes,self.abi_header.abi_major);
+        push_u32(&mut bytes,self.abi_header.abi_minor);
+        if let Some(root_module)=&self.root_module {
+            bytes.extend_from_slice(&root_module.to_bytes());
+        }
+        bytes
+    }
+
+    /**
+Deserializes the bytes stored in the metadata section.
+
+# Errors
+
+This returns these errors:
+
+- LibMetadataError::InvalidMagic:
+If the bytes don't start with the magic bytes of the metadata.
+
+- LibMetadataError::UnsupportedFormat:
+If the metadata was serialized by a newer version of abi_stable.
+
+- LibMetadataError::Truncated:
+If the bytes end before the entire metadata is read.
+
+- LibMetadataError::InvalidUtf8:
+If one of the strings is not valid utf-8.
+
+    */
+    pub fn from_bytes(bytes:&[u8])->Result<Self,LibMetadataError>{
+        let mut reader=ByteReader{bytes};
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(LibMetadataError::InvalidMagic);
+        }
+        let format_version=reader.u32()?;
+        if format_version!=FORMAT_VERSION {
+            return Err(LibMetadataError::UnsupportedFormat{format_version});
+        }
+
+        let mut abi_header=AbiHeader::VALUE;
+        abi_header.magic_string.copy_from_slice(reader.take(32)?);
+        abi_header.abi_major=reader.u32()?;
+        abi_header.abi_minor=reader.u32()?;
+
+        // The section can have trailing padding after the fields above,
+        // so anything other than the tag of the root module metadata means that it's absent.
+        let root_module=match reader.bytes.first() {
+            Some(&ROOT_MODULE_TAG)=>{
+                reader.take(1)?;
+                let base_name=reader.str()?.to_string();
+                let name=reader.str()?.to_string();
+                let version=reader.str()?.to_string();
+                let layout=match reader.take(1)?[0] {
+                    0=>None,
+                    _=>Some(reader.str()?.to_string()),
+                };
+                Some(RootModuleMetadata{base_name,name,version,layout})
+            }
+            _=>None,
+        };
+
+        Ok(Self{
+            abi_header,
+            root_module,
+        })
+    }
+}
+
+
+/// The byte that the serialized `RootModuleMetadata` starts with.
+const ROOT_MODULE_TAG:u8=1;
+
+
+impl RootModuleMetadata{
+    /// Constructs the RootModuleMetadata of a dynamic library that 
+    /// exports `M` as its root module.
+    ///
+    /// The layout is not serialized if this is called in a build script 
+    /// that is compiling for a different target than the host.
+    pub fn new<M>()->Self
+    where
+        M:RootModule,
+    {
+        let layout=if is_cross_compiling() {
+            None
+        }else{
+            Self::serialize_layout::<M>()
+        };
+
+        Self{
+            base_name:M::BASE_NAME.to_string(),
+            name:M::NAME.to_string(),
+            version:M::VERSION_STRINGS.version.as_str().to_string(),
+            layout,
+        }
+    }
+
+    #[cfg(feature="serde_json")]
+    fn serialize_layout<M>()->Option<String>
+    where
+        M:RootModule,
+    {
+        let layout=<&M>::S_ABI_INFO.get().layout;
+        serde_json::to_string(&MRItem::from_abi_info(layout)).ok()
+    }
+
+    #[cfg(not(feature="serde_json"))]
+    fn serialize_layout<M>()->Option<String>
+    where
+        M:RootModule,
+    {
+        None
+    }
+
+    /// Serializes this into the bytes stored in the metadata section,
+    /// after the abi_stable version.
+    pub fn to_bytes(&self)->Vec<u8>{
+        let mut bytes=vec![ROOT_MODULE_TAG];
+        push_str(&mut bytes,&self.base_name);
+        push_str(&mut bytes,&self.name);
+        push_str(&mut bytes,&self.version);
+        match &self.layout {
+            Some(layout)=>{
+                bytes.push(1);
+                push_str(&mut bytes,layout);
+            }
+            None=>bytes.push(0),
+        }
+        bytes
+    }
+
+    /**
+Writes the file that `#[export_root_module]` includes in the metadata section,
+to the `OUT_DIR` directory.
+
+This must only be called in the build script of the crate that
+uses `#[export_root_module]` to export the root module.
+
+# Errors
+
+This returns an error if the `OUT_DIR` environment variable is not set,
+or the file could not be written.
+
+    */
+    pub fn write_to_out_dir(&self)->io::Result<()>{
+        let out_dir=env::var_os("OUT_DIR")
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::NotFound,
+                "The OUT_DIR environment variable is not set,\
+                 RootModuleMetadata::write_to_out_dir must be called in a build script.",
+            ))?;
+
+        fs::write(Path::new(&out_dir).join(LIB_METADATA_FILE),self.to_array_item())
+    }
+
+    /// The constant that `#[export_root_module]` includes,
+    /// containing the serialized metadata.
+    fn to_array_item(&self)->String{
+        use std::fmt::Write;
+
+        let bytes=self.to_bytes();
+
+        let mut item=String::with_capacity(bytes.len()*5+64);
+        let _=writeln!(item,"pub const LEN:usize={};",bytes.len());
+        item.push_str("pub const BYTES:[u8;LEN]=[\n");
+        for line in bytes.chunks(32) {
+            for byte in line {
+                let _=write!(item,"{},",byte);
+            }
+            item.push('\n');
+        }
+        item.push_str("];\n");
+        item
+    }
+}
+
+
+/// Whether this is a build script compiling for a different target than the host.
+fn is_cross_compiling()->bool{
+    match (env::var_os("HOST"),env::var_os("TARGET")) {
+        (Some(host),Some(target))=>host!=target,
+        _=>false,
+    }
+}
+
+
+/**
+Reads the `LibMetadata` of the library at the path,
+without loading the library.
+
+# Errors
+
+This returns these errors:
+
+- LibraryError::LibMetadataError:
+If the file could not be read,
+it is not an ELF file,
+or it does not contain a valid metadata section.
+
+- LibraryError::InvalidAbiHeader:
+If the abi_stable version used by the library is not compatible.
+
+*/
+pub fn lib_metadata_from_path(path:&Path)->Result<LibMetadata,LibraryError>{
+    let with_path=|error|LibraryError::LibMetadataError{
+        path:path.to_owned(),
+        error,
+    };
+
+    let file=fs::read(path).map_err(|e| with_path(LibMetadataError::Io(e)) )?;
+
+    let section=elf::find_section(&file,LIB_METADATA_SECTION)
+        .map_err(with_path)?
+        .ok_or_else(|| with_path(LibMetadataError::NoMetadataSection) )?;
+
+    let metadata=LibMetadata::from_bytes(section).map_err(with_path)?;
+
+    if !metadata.abi_header.is_compatible(&AbiHeader::VALUE) {
+        return Err(LibraryError::InvalidAbiHeader(metadata.abi_header));
+    }
+
+    Ok(metadata)
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+fn push_u32(bytes:&mut Vec<u8>,n:u32){
+    bytes.extend_from_slice(&[n as u8,(n>>8) as u8,(n>>16) as u8,(n>>24) as u8]);
+}
+
+fn push_str(bytes:&mut Vec<u8>,s:&str){
+    push_u32(bytes,s.len() as u32);
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+
+struct ByteReader<'a>{
+    bytes:&'a [u8],
+}
+
+impl<'a> ByteReader<'a>{
+    fn take(&mut self,len:usize)->Result<&'a [u8],LibMetadataError>{
+        if self.bytes.len() < len {
+            return Err(LibMetadataError::Truncated);
+        }
+        let (taken,rem)=self.bytes.split_at(len);
+        self.bytes=rem;
+        Ok(taken)
+    }
+
+    fn u32(&mut self)->Result<u32,LibMetadataError>{
+        let b=self.take(4)?;
+        Ok( b[0] as u32 | (b[1] as u32)<<8 | (b[2] as u32)<<16 | (b[3] as u32)<<24 )
+    }
+
+    fn str(&mut self)->Result<&'a str,LibMetadataError>{
+        let len=self.u32()? as usize;
+        str::from_utf8(self.take(len)?).map_err(LibMetadataError::InvalidUtf8)
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+impl Display for LibMetadataError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self {
+            LibMetadataError::Io(e)=>
+                write!(f,"Could not read the file:\n\t{}",e),
+            LibMetadataError::NotElf=>
+                f.write_str("The file is not an ELF file."),
+            LibMetadataError::MalformedElf(what)=>
+                write!(f,"The ELF file is malformed:{}",what),
+            LibMetadataError::NoMetadataSection=>write!(
+                f,
+                "The file has no '{}' section,\
+                 it was not exported with #[export_root_module] for an ELF target,\
+                 or it was compiled with a version of abi_stable that does not store metadata.",
+                LIB_METADATA_SECTION,
+            ),
+            LibMetadataError::InvalidMagic=>
+                f.write_str("The metadata section does not start with the magic bytes."),
+            LibMetadataError::UnsupportedFormat{format_version}=>write!(
+                f,
+                "The metadata was serialized in the format version {},\
+                 while this supports format version {}.",
+                format_version,
+                FORMAT_VERSION,
+            ),
+            LibMetadataError::Truncated=>
+                f.write_str("The metadata section is truncated."),
+            LibMetadataError::InvalidUtf8(e)=>
+                write!(f,"The metadata section contains an invalid string:\n\t{}",e),
+        }
+    }
+}
+
+impl ::std::error::Error for LibMetadataError{}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::{
+        StableAbi,
+        std_types::RString,
+    };
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="DummyMod")))]
+    #[sabi(missing_field(panic))]
+    pub struct DummyModVal{
+        #[sabi(last_prefix_field)]
+        pub hello:extern "C" fn(RString)->RString,
+    }
+
+    impl RootModule for DummyMod {
+        crate::declare_root_module_statics!{DummyMod}
+
+        const BASE_NAME: &'static str = "dummy";
+        const NAME: &'static str = "dummy";
+        const VERSION_STRINGS: VersionStrings = VersionStrings::new("0.3.4");
+    }
+
+    fn metadata()->LibMetadata{
+        LibMetadata{
+            abi_header:AbiHeader::VALUE,
+            root_module:Some(RootModuleMetadata{
+                base_name:"plugin".to_string(),
+                name:"the plugin".to_string(),
+                version:"1.2.3-rc.1".to_string(),
+                layout:Some("{\"item_name\":\"root\"}".to_string()),
+            }),
+        }
+    }
+
+    fn assert_same_header(found:&AbiHeader,expected:&AbiHeader){
+        assert_eq!(found.magic_string,expected.magic_string);
+        assert_eq!(found.abi_major,expected.abi_major);
+        assert_eq!(found.abi_minor,expected.abi_minor);
+    }
+
+    #[test]
+    fn roundtrip(){
+        let mut expected=metadata();
+        for _ in 0..3 {
+            let found=LibMetadata::from_bytes(&expected.to_bytes()).unwrap();
+            assert_same_header(&found.abi_header,&expected.abi_header);
+            assert_eq!(found.root_module,expected.root_module);
+
+            match &mut expected.root_module {
+                Some(x) if x.layout.is_some() => x.layout=None,
+                x => *x=None,
+            }
+        }
+    }
+
+    #[test]
+    fn section_layout(){
+        // The section stored by `#[export_root_module]` must be readable by `from_bytes`,
+        // including its trailing padding.
+        fn section_bytes<B>(section:&LibMetadataSection<B>)->&[u8]{
+            unsafe{
+                std::slice::from_raw_parts(
+                    section as *const LibMetadataSection<B> as *const u8,
+                    std::mem::size_of::<LibMetadataSection<B>>(),
+                )
+            }
+        }
+
+        let without_root=LibMetadataSection::new([0u8;0]);
+        let found=LibMetadata::from_bytes(section_bytes(&without_root)).unwrap();
+        assert_same_header(&found.abi_header,&AbiHeader::VALUE);
+        assert_eq!(found.root_module,None);
+
+        let root_module=metadata().root_module.unwrap();
+        let mut root_bytes=[0u8;256];
+        let serialized=root_module.to_bytes();
+        root_bytes[..serialized.len()].copy_from_slice(&serialized);
+
+        let with_root=LibMetadataSection::new(root_bytes);
+        let found=LibMetadata::from_bytes(section_bytes(&with_root)).unwrap();
+        assert_same_header(&found.abi_header,&AbiHeader::VALUE);
+        assert_eq!(found.root_module,Some(root_module));
+    }
+
+    #[test]
+    fn invalid_bytes(){
+        let bytes=metadata().to_bytes();
+
+        match LibMetadata::from_bytes(&bytes[..bytes.len()-1]) {
+            Err(LibMetadataError::Truncated)=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+
+        let mut wrong_magic=bytes.clone();
+        wrong_magic[0]=b'A';
+        match LibMetadata::from_bytes(&wrong_magic) {
+            Err(LibMetadataError::InvalidMagic)=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+
+        let mut newer_format=bytes.clone();
+        newer_format[MAGIC.len()]=2;
+        match LibMetadata::from_bytes(&newer_format) {
+            Err(LibMetadataError::UnsupportedFormat{format_version:2})=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+    }
+
+    #[test]
+    #[cfg(feature="serde_json")]
+    fn root_module_layout(){
+        let metadata=RootModuleMetadata::new::<DummyMod>();
+        assert_eq!(metadata.base_name,"dummy");
+        assert_eq!(metadata.version,"0.3.4");
+
+        let layout=metadata.layout.unwrap();
+        assert!(layout.contains("\"hello\""),"{}",layout);
+    }
+
+    #[test]
+    fn array_item(){
+        let root_module=metadata().root_module.unwrap();
+        let item=root_module.to_array_item();
+        assert!(
+            item.starts_with(&format!("pub const LEN:usize={};",root_module.to_bytes().len())),
+            "{}",
+            item,
+        );
+    }
+}
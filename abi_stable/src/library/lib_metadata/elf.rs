@@ -0,0 +1,332 @@
+/*!
+A minimal reader for the section headers of ELF files,
+supporting 32 and 64 bit files of either endianness.
+*/
+
+use super::LibMetadataError;
+
+
+const ELF_MAGIC:[u8;4]=*b"\x7FELF";
+
+const CLASS_32:u8=1;
+const CLASS_64:u8=2;
+
+const DATA_LITTLE_ENDIAN:u8=1;
+const DATA_BIG_ENDIAN:u8=2;
+
+/// The size of a section header in 32 bit files.
+const SECTION_HEADER_SIZE_32:u16=0x28;
+/// The size of a section header in 64 bit files.
+const SECTION_HEADER_SIZE_64:u16=0x40;
+
+/// The section type of sections that occupy no space in the file.
+const SECTION_TYPE_NOBITS:u32=8;
+
+/// The value of `e_shstrndx` when the index is stored in the `sh_link` of the first section.
+const SECTION_INDEX_EXTENDED:u16=0xFFFF;
+
+
+/// The endianness and word size of an ELF file.
+#[derive(Debug,Copy,Clone)]
+struct Format{
+    is_64_bit:bool,
+    is_little_endian:bool,
+}
+
+#[derive(Debug,Copy,Clone)]
+struct SectionHeader{
+    name:u32,
+    type_:u32,
+    offset:u64,
+    size:u64,
+    link:u32,
+}
+
+
+/// Finds the contents of the section named `name`,
+/// returning None if there is no such section.
+pub(super) fn find_section<'a>(
+    file:&'a [u8],
+    name:&str,
+)->Result<Option<&'a [u8]>,LibMetadataError>{
+    if file.len() < 16 || file[..4]!=ELF_MAGIC {
+        return Err(LibMetadataError::NotElf);
+    }
+
+    let format=Format{
+        is_64_bit:match file[4] {
+            CLASS_32=>false,
+            CLASS_64=>true,
+            _=>return Err(LibMetadataError::MalformedElf("invalid class")),
+        },
+        is_little_endian:match file[5] {
+            DATA_LITTLE_ENDIAN=>true,
+            DATA_BIG_ENDIAN=>false,
+            _=>return Err(LibMetadataError::MalformedElf("invalid endianness")),
+        },
+    };
+
+    let (shoff,shentsize,shnum,shstrndx)=if format.is_64_bit {
+        (
+            format.u64(file,0x28)?,
+            format.u16(file,0x3A)?,
+            format.u16(file,0x3C)?,
+            format.u16(file,0x3E)?,
+        )
+    }else{
+        (
+            format.u32(file,0x20)? as u64,
+            format.u16(file,0x2E)?,
+            format.u16(file,0x30)?,
+            format.u16(file,0x32)?,
+        )
+    };
+
+    if shoff==0 {
+        return Ok(None);
+    }
+
+    let min_shentsize=if format.is_64_bit {
+        SECTION_HEADER_SIZE_64
+    }else{
+        SECTION_HEADER_SIZE_32
+    };
+    if shentsize < min_shentsize {
+        return Err(LibMetadataError::MalformedElf("section header size too small"));
+    }
+
+    let section_at=|index:u64|->Result<SectionHeader,LibMetadataError>{
+        let offset=index.checked_mul(shentsize as u64)
+            .and_then(|x| x.checked_add(shoff) )
+            .ok_or(LibMetadataError::MalformedElf("section header offset overflowed"))?;
+        format.section_header(file,offset)
+    };
+
+    // If there are too many sections to fit in the ELF header,
+    // the count and the index of the section names are stored in the first section.
+    let first_section=section_at(0)?;
+    let shnum=match shnum {
+        0=>first_section.size,
+        n=>n as u64,
+    };
+    let shstrndx=match shstrndx {
+        SECTION_INDEX_EXTENDED=>first_section.link as u64,
+        n=>n as u64,
+    };
+
+    let headers_end=shnum.checked_mul(shentsize as u64)
+        .and_then(|x| x.checked_add(shoff) );
+    match headers_end {
+        Some(end) if end <= file.len() as u64 => {}
+        _=>return Err(LibMetadataError::MalformedElf("section headers out of bounds")),
+    }
+
+    let names=section_data(file,section_at(shstrndx)?)?;
+
+    for index in 0..shnum {
+        let section=section_at(index)?;
+        let section_name=names.get(section.name as usize..)
+            .and_then(|x| x.split(|&b| b==0 ).next() )
+            .ok_or(LibMetadataError::MalformedElf("invalid section name"))?;
+
+        if section_name==name.as_bytes() {
+            return section_data(file,section).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+
+fn section_data(file:&[u8],section:SectionHeader)->Result<&[u8],LibMetadataError>{
+    if section.type_==SECTION_TYPE_NOBITS {
+        return Ok(&[]);
+    }
+    get_range(file,section.offset,section.size)
+        .ok_or(LibMetadataError::MalformedElf("section out of bounds"))
+}
+
+
+fn get_range(file:&[u8],offset:u64,len:u64)->Option<&[u8]>{
+    let end=offset.checked_add(len)?;
+    if end > file.len() as u64 {
+        return None;
+    }
+    Some(&file[offset as usize..end as usize])
+}
+
+
+impl Format{
+    fn bytes(self,file:&[u8],offset:u64,len:u64)->Result<&[u8],LibMetadataError>{
+        get_range(file,offset,len)
+            .ok_or(LibMetadataError::MalformedElf("header out of bounds"))
+    }
+
+    fn uint(self,file:&[u8],offset:u64,len:u64)->Result<u64,LibMetadataError>{
+        let bytes=self.bytes(file,offset,len)?;
+        let fold=|acc:u64,&b:&u8| (acc<<8)|b as u64;
+        Ok(if self.is_little_endian {
+            bytes.iter().rev().fold(0,fold)
+        }else{
+            bytes.iter().fold(0,fold)
+        })
+    }
+
+    fn u16(self,file:&[u8],offset:u64)->Result<u16,LibMetadataError>{
+        self.uint(file,offset,2).map(|x| x as u16 )
+    }
+
+    fn u32(self,file:&[u8],offset:u64)->Result<u32,LibMetadataError>{
+        self.uint(file,offset,4).map(|x| x as u32 )
+    }
+
+    fn u64(self,file:&[u8],offset:u64)->Result<u64,LibMetadataError>{
+        self.uint(file,offset,8)
+    }
+
+    fn section_header(self,file:&[u8],offset:u64)->Result<SectionHeader,LibMetadataError>{
+        Ok(if self.is_64_bit {
+            SectionHeader{
+                name:self.u32(file,offset)?,
+                type_:self.u32(file,offset+0x04)?,
+                offset:self.u64(file,offset+0x18)?,
+                size:self.u64(file,offset+0x20)?,
+                link:self.u32(file,offset+0x28)?,
+            }
+        }else{
+            SectionHeader{
+                name:self.u32(file,offset)?,
+                type_:self.u32(file,offset+0x04)?,
+                offset:self.u32(file,offset+0x10)? as u64,
+                size:self.u32(file,offset+0x14)? as u64,
+                link:self.u32(file,offset+0x18)?,
+            }
+        })
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    /// Builds an ELF file with only a header and sections.
+    fn build_elf(format:Format,sections:&[(&str,&[u8])])->Vec<u8>{
+        let push=|file:&mut Vec<u8>,n:u64,len:usize|{
+            let mut bytes=(0..len).map(|i| (n>>(i*8)) as u8 ).collect::<Vec<u8>>();
+            if !format.is_little_endian {
+                bytes.reverse();
+            }
+            file.extend_from_slice(&bytes);
+        };
+        let word=if format.is_64_bit { 8 }else{ 4 };
+        let header_size=if format.is_64_bit { 0x40 }else{ 0x34 };
+        let shentsize=if format.is_64_bit { 0x40 }else{ 0x28 };
+
+        // The section names,the first section is the null section.
+        let mut names=vec![0u8];
+        let mut all_sections=vec![(0,&[][..])];
+        for &(name,data) in sections {
+            all_sections.push((names.len(),data));
+            names.extend_from_slice(name.as_bytes());
+            names.push(0);
+        }
+        all_sections.push((names.len(),&[][..]));
+        names.extend_from_slice(b".shstrtab\0");
+        let last_index=all_sections.len()-1;
+        all_sections[last_index].1=&names;
+
+        let mut data=Vec::new();
+        let mut offsets=Vec::new();
+        for &(_,section_data) in &all_sections {
+            offsets.push(header_size+data.len());
+            data.extend_from_slice(section_data);
+        }
+        let shoff=header_size+data.len();
+
+        let mut file=Vec::new();
+        file.extend_from_slice(&ELF_MAGIC);
+        file.push(if format.is_64_bit { CLASS_64 }else{ CLASS_32 });
+        file.push(if format.is_little_endian { DATA_LITTLE_ENDIAN }else{ DATA_BIG_ENDIAN });
+        file.resize(if format.is_64_bit { 0x28 }else{ 0x20 },0);
+        push(&mut file,shoff as u64,word);
+        file.resize(header_size-6,0);
+        push(&mut file,shentsize as u64,2);
+        push(&mut file,all_sections.len() as u64,2);
+        push(&mut file,last_index as u64,2);
+        file.extend_from_slice(&data);
+
+        for (&(name,section_data),&offset) in all_sections.iter().zip(&offsets) {
+            let start=file.len();
+            push(&mut file,name as u64,4);
+            push(&mut file,1,4);
+            file.resize(start+if format.is_64_bit { 0x18 }else{ 0x10 },0);
+            push(&mut file,offset as u64,word);
+            push(&mut file,section_data.len() as u64,word);
+            file.resize(start+shentsize,0);
+        }
+        file
+    }
+
+    #[test]
+    fn find_sections(){
+        let sections:&[(&str,&[u8])]=&[
+            (".text",&[0xC3]),
+            (".abi_stable_meta",b"hello"),
+            (".data",&[1,2,3]),
+        ];
+        for &is_64_bit in &[false,true] {
+            for &is_little_endian in &[false,true] {
+                let format=Format{is_64_bit,is_little_endian};
+                let file=build_elf(format,sections);
+                for &(name,data) in sections {
+                    assert_eq!(find_section(&file,name).unwrap(),Some(data),"{:?}",format);
+                }
+                assert_eq!(find_section(&file,".abi_stable").unwrap(),None);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_files(){
+        match find_section(b"MZ\x90\x00 this is not an ELF file",".text") {
+            Err(LibMetadataError::NotElf)=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+
+        let format=Format{is_64_bit:true,is_little_endian:true};
+        let file=build_elf(format,&[(".text",&[0xC3])]);
+        match find_section(&file[..file.len()-0x20],".text") {
+            Err(LibMetadataError::MalformedElf(_))=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+    }
+
+    /// A section count that doesn't fit in the ELF header is read from the first section,
+    /// which must not make the reader iterate over the same section header forever.
+    #[test]
+    fn huge_section_count(){
+        let format=Format{is_64_bit:true,is_little_endian:true};
+        let mut file=build_elf(format,&[(".text",&[0xC3])]);
+        let shoff=format.u64(&file,0x28).unwrap() as usize;
+        // The size of the first section,which is the count of sections when e_shnum is 0.
+        file[shoff+0x20..shoff+0x28].copy_from_slice(&u64::max_value().to_le_bytes());
+        // e_shnum
+        file[0x3C..0x3E].copy_from_slice(&[0,0]);
+
+        match find_section(&file,".abi_stable") {
+            Err(LibMetadataError::MalformedElf("section headers out of bounds"))=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+
+        // e_shentsize
+        file[0x3A..0x3C].copy_from_slice(&[0,0]);
+        match find_section(&file,".abi_stable") {
+            Err(LibMetadataError::MalformedElf("section header size too small"))=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+    }
+}
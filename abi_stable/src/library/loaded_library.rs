@@ -0,0 +1,392 @@
+/*!
+Reference-counted handles to dynamic libraries that can be unloaded.
+*/
+
+use super::{
+    *,
+    root_mod_trait::load_raw_library,
+};
+
+use std::{
+    fmt::{self,Debug},
+    mem::{self,ManuallyDrop},
+    ops::{Deref,DerefMut},
+    ptr,
+    sync::Arc,
+};
+
+use crate::external_types::RMutex;
+
+
+/**
+A reference-counted handle to a dynamic library and its root module,
+which unloads the library once the last handle
+(including the ones inside `LibraryPinned`) is dropped.
+
+This is constructed with `RootModule::load_unloadable_from` and related functions.
+
+As opposed to the modules returned by `RootModule::load_from`,
+the root module is not stored in `RootModule::root_module_statics`,
+so `RootModule::get_module` won't return it.
+
+# Unloading
+
+The dynamic library is unloaded once all of these are dropped:
+
+- Every clone of the `LoadedLibrary`.
+
+- Every `LibraryPinned` created with `LoadedLibrary::pin`.
+
+- Every `DynTrait` and `RObject` whose vtable comes from the library.
+
+`DynTrait`s and `RObject`s keep the library loaded by themselves,
+using the `LibraryKeepAlive` handle they get from their vtable.
+They can be unerased back into the original pointer,
+after which the library is never unloaded,since the pointer could point into it.
+
+Other values created by the library which point into it
+(eg:function pointers,or `RBox`es/`RString`s allocated by the library)
+must either be dropped before the library is unloaded,
+or be wrapped with `LoadedLibrary::pin` so that they keep the library loaded.
+
+# Layout checking
+
+To not have dangling references to the type layouts of the library after it's unloaded,
+the layout of the root module is checked in isolation,
+without registering the prefix types and nonexhaustive enums it uses
+in the global state used to check libraries loaded with `RootModule::load_from`.
+
+If loading the library fails after it was opened
+(eg:because the layout of the root module is incompatible),
+the library is never unloaded,
+since the returned `LibraryError` can reference statics in the library.
+
+*/
+pub struct LoadedLibrary<M:'static>{
+    // This reference is only valid while `library` is alive.
+    module:&'static M,
+    header:&'static LibHeader,
+    library:LibraryHandle,
+}
+
+
+impl<M> LoadedLibrary<M>
+where
+    M:RootModule,
+{
+    /// Loads the dynamic library at `where_` and checks that its root module is compatible.
+    pub(super) unsafe fn load(where_:LibraryPath<'_>)->Result<Self,LibraryError>{
+        let raw_library=load_raw_library::<M>(where_)?;
+
+        let loaded=lib_header_from_raw_library(&raw_library)
+            .and_then(|header|{
+                let module=header.init_unregistered_root_module::<M>()?
+                    .initialization()?;
+                Ok((header,module))
+            });
+
+        let (header,module)=match loaded {
+            Ok(x)=>x,
+            Err(e)=>{
+                // The error can reference statics of the library
+                // (eg:type layouts,version strings),
+                // so the library must not be unloaded.
+                mem::forget(raw_library);
+                return Err(e);
+            }
+        };
+
+        let _guard=HANDLES_LOCK.lock();
+
+        let library=match header.keep_alive() {
+            Some(keep_alive)if keep_alive.retain_fn()==retain_library as _ =>{
+                // The library was already loaded with `LoadedLibrary`,
+                // so this reuses its handle,
+                // since values created by the library only keep that handle alive.
+                let existing=ManuallyDrop::new(LibraryHandle::from_keep_alive(keep_alive));
+                LibraryHandle::clone(&existing)
+            }
+            current=>{
+                let library=LibraryHandle::new(SharedLibrary{
+                    keep_alive:LibraryKeepAlive::new(retain_library,release_library),
+                    header,
+                    raw:raw_library,
+                });
+                // If the library was loaded by a different copy of abi_stable
+                // (eg:by a dynamic library loading other dynamic libraries),
+                // the values it creates keep the library loaded with that copy's handle.
+                if current.is_none() {
+                    let keep_alive=&*(&library.keep_alive as *const LibraryKeepAlive);
+                    header.set_keep_alive(Some(keep_alive));
+                }
+                library
+            }
+        };
+
+        Ok(Self{
+            module,
+            header,
+            library,
+        })
+    }
+}
+
+
+impl<M:'static> LoadedLibrary<M>{
+    /// Gets the root module.
+    #[inline]
+    pub fn root_module(&self)->&M{
+        self.module
+    }
+
+    /// Gets the header of the library,
+    /// containing its version and the layout of its root module.
+    #[inline]
+    pub fn lib_header(&self)->&LibHeader{
+        self.header
+    }
+
+    /// Gets the RawLibrary of this library.
+    #[inline]
+    pub fn raw_library(&self)->&RawLibrary{
+        &self.library.raw
+    }
+
+    /// Gets the path the library was loaded from.
+    #[inline]
+    pub fn path(&self)->&Path{
+        self.library.raw.path()
+    }
+
+    /// Gets the amount of handles keeping the library loaded,
+    /// including the ones in `LibraryPinned`s,`DynTrait`s,and `RObject`s.
+    pub fn handle_count(this:&Self)->usize{
+        Arc::strong_count(&this.library.shared)
+    }
+
+/**
+Ties `value` to this library,so that the library stays loaded until it's dropped.
+
+This is intended for values that point into the library,
+like function pointers returned by the root module.
+`DynTrait`s and `RObject`s don't need to be pinned,
+since they keep the library loaded by themselves.
+
+# Example
+
+```ignore
+let library=unsafe{ TextOpsMod::load_unloadable_from_directory(directory)? };
+
+let function=library.pin( library.get_function() );
+
+// The library stays loaded,even though this is the last LoadedLibrary.
+drop(library);
+
+(*function)();
+
+// The library is unloaded here.
+drop(function);
+
+```
+
+*/
+    pub fn pin<T>(&self,value:T)->LibraryPinned<T>{
+        LibraryPinned{
+            value:ManuallyDrop::new(value),
+            library:self.library.clone(),
+        }
+    }
+}
+
+
+impl<M:'static> Clone for LoadedLibrary<M>{
+    fn clone(&self)->Self{
+        Self{
+            module:self.module,
+            header:self.header,
+            library:self.library.clone(),
+        }
+    }
+}
+
+impl<M:'static> Deref for LoadedLibrary<M>{
+    type Target=M;
+
+    #[inline]
+    fn deref(&self)->&M{
+        self.module
+    }
+}
+
+impl<M:'static> Debug for LoadedLibrary<M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("LoadedLibrary")
+         .field("path",&self.library.raw.path())
+         .field("version",&self.header.version_strings())
+         .field("handle_count",&Arc::strong_count(&self.library.shared))
+         .finish()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+
+/**
+A value which keeps the dynamic library it came from loaded until it's dropped.
+
+This is constructed with `LoadedLibrary::pin`.
+
+The value is always dropped before the library is unloaded.
+*/
+pub struct LibraryPinned<T>{
+    value:ManuallyDrop<T>,
+    library:LibraryHandle,
+}
+
+
+impl<T> LibraryPinned<T>{
+    /// Gets the path of the library this value keeps loaded.
+    #[inline]
+    pub fn library_path(&self)->&Path{
+        self.library.raw.path()
+    }
+
+/**
+Unwraps the value,without keeping the library loaded.
+
+# Safety
+
+The caller must ensure that the library outlives the returned value,
+either by keeping a `LoadedLibrary` (or another `LibraryPinned`) of the same library alive,
+or by only using the parts of the value that don't point into the library.
+*/
+    pub unsafe fn into_inner(self)->T{
+        let mut this=ManuallyDrop::new(self);
+        let value=ptr::read(&this.value);
+        ptr::drop_in_place(&mut this.library);
+        ManuallyDrop::into_inner(value)
+    }
+}
+
+
+impl<T> Deref for LibraryPinned<T>{
+    type Target=T;
+
+    #[inline]
+    fn deref(&self)->&T{
+        &self.value
+    }
+}
+
+impl<T> DerefMut for LibraryPinned<T>{
+    #[inline]
+    fn deref_mut(&mut self)->&mut T{
+        &mut self.value
+    }
+}
+
+impl<T> Drop for LibraryPinned<T>{
+    fn drop(&mut self){
+        // The value must be dropped while the library is still loaded,
+        // since its destructor likely lives in the library.
+        unsafe{ ManuallyDrop::drop(&mut self.value) }
+    }
+}
+
+impl<T:Debug> Debug for LibraryPinned<T>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        Debug::fmt(&*self.value,f)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+
+/// The state shared by every handle to a library loaded with `LoadedLibrary`.
+#[repr(C)]
+struct SharedLibrary{
+    // This must be the first field,
+    // since `LibraryHandle::from_keep_alive` casts it to a `SharedLibrary`.
+    keep_alive:LibraryKeepAlive,
+    header:&'static LibHeader,
+    raw:RawLibrary,
+}
+
+impl Drop for SharedLibrary{
+    fn drop(&mut self){
+        // The library is unloaded after this,
+        // and the values it creates from now on can't keep it loaded.
+        let is_current=self.header.keep_alive()
+            .map_or(false,|x| ptr::eq(x,&self.keep_alive) );
+        if is_current {
+            self.header.set_keep_alive(None);
+        }
+    }
+}
+
+
+/// Held while a `LibraryHandle` is dropped,
+/// and while `LoadedLibrary::load` reuses the handle of an already loaded library,
+/// so that it never reuses the handle of a library that is being unloaded.
+static HANDLES_LOCK:RMutex<()>=RMutex::new(());
+
+
+/// A reference-counted handle to a `SharedLibrary`.
+struct LibraryHandle{
+    shared:ManuallyDrop<Arc<SharedLibrary>>,
+}
+
+impl LibraryHandle{
+    fn new(shared:SharedLibrary)->Self{
+        Self{
+            shared:ManuallyDrop::new(Arc::new(shared)),
+        }
+    }
+
+    /// Takes ownership of a handle that was leaked by `retain_library`.
+    ///
+    /// # Safety
+    ///
+    /// `keep_alive` must be the `keep_alive` field of a `SharedLibrary`,
+    /// for which this handle was leaked.
+    unsafe fn from_keep_alive(keep_alive:&LibraryKeepAlive)->Self{
+        let shared=keep_alive as *const LibraryKeepAlive as *const SharedLibrary;
+        Self{
+            shared:ManuallyDrop::new(Arc::from_raw(shared)),
+        }
+    }
+}
+
+impl Clone for LibraryHandle{
+    fn clone(&self)->Self{
+        Self{
+            shared:ManuallyDrop::new(Arc::clone(&self.shared)),
+        }
+    }
+}
+
+impl Deref for LibraryHandle{
+    type Target=SharedLibrary;
+
+    #[inline]
+    fn deref(&self)->&SharedLibrary{
+        &self.shared
+    }
+}
+
+impl Drop for LibraryHandle{
+    fn drop(&mut self){
+        let _guard=HANDLES_LOCK.lock();
+        unsafe{ ManuallyDrop::drop(&mut self.shared) }
+    }
+}
+
+
+unsafe extern "C" fn retain_library(keep_alive:&LibraryKeepAlive){
+    let handle=ManuallyDrop::new(LibraryHandle::from_keep_alive(keep_alive));
+    mem::forget(LibraryHandle::clone(&handle));
+}
+
+unsafe extern "C" fn release_library(keep_alive:&LibraryKeepAlive){
+    drop(LibraryHandle::from_keep_alive(keep_alive));
+}
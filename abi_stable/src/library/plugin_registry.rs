@@ -0,0 +1,404 @@
+/*!
+Scanning directories for dynamic libraries that export the same kind of root module.
+*/
+
+use super::*;
+
+use std::{
+    fmt::{self,Debug},
+    fs,
+};
+
+
+/**
+A registry of plugins,dynamic libraries whose root module is `M`,
+found by scanning directories.
+
+Each dynamic library can have any base name,
+as long as its file name follows the naming convention of the platform
+(eg:`lib<base_name>.so` on Linux,`<base_name>.dll` on Windows),
+and the `LibrarySuffix` passed to the registry.
+
+The libraries are loaded with `lib_header_from_path`,
+leaking them like `RootModule::load_instance_from` does,
+and are not stored in `RootModule::root_module_statics`.
+
+# Example
+
+```ignore
+use abi_stable::library::{LibrarySuffix,PluginRegistry};
+
+let mut registry=PluginRegistry::<PluginMod>::new(LibrarySuffix::NoSuffix);
+registry.add_directory("./plugins/");
+
+let report=registry.scan();
+for error in &report.errors {
+    eprintln!("could not load plugin at '{}':{}",error.path.display(),error.error);
+}
+
+for plugin in registry.plugins() {
+    println!("loaded '{}'",plugin.path().display());
+}
+
+```
+
+*/
+pub struct PluginRegistry<M:'static>{
+    directories:Vec<PathBuf>,
+    suffix:LibrarySuffix,
+    plugins:Vec<LoadedPlugin<M>>,
+}
+
+
+/// A plugin loaded by a `PluginRegistry`.
+pub struct LoadedPlugin<M:'static>{
+    path:PathBuf,
+    base_name:String,
+    module:&'static M,
+    header:&'static LibHeader,
+}
+
+
+/// The plugins that were loaded by `PluginRegistry::scan`,
+/// and the errors for the files that could not be loaded.
+pub struct ScanReport<M:'static>{
+    /// The plugins that were loaded by this scan.
+    pub loaded:Vec<LoadedPlugin<M>>,
+    /// The errors that happened while loading each plugin,
+    /// or reading a directory.
+    pub errors:Vec<PluginLoadError>,
+}
+
+
+/// An error produced when loading a plugin,or reading a directory to scan.
+#[derive(Debug)]
+pub struct PluginLoadError{
+    /// The path of the library,or directory,that caused the error.
+    pub path:PathBuf,
+    /// The error that happened while loading the library,or reading the directory.
+    pub error:LibraryError,
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+impl<M> PluginRegistry<M>
+where
+    M:RootModule,
+{
+    /// Constructs an empty registry,
+    /// which loads dynamic libraries following the `suffix` naming convention.
+    pub fn new(suffix:LibrarySuffix)->Self{
+        Self{
+            directories:Vec::new(),
+            suffix,
+            plugins:Vec::new(),
+        }
+    }
+
+    /// Adds a directory to scan for plugins in `scan`.
+    pub fn add_directory<P>(&mut self,directory:P)->&mut Self
+    where
+        P:Into<PathBuf>
+    {
+        self.directories.push(directory.into());
+        self
+    }
+
+    /// The directories this scans for plugins.
+    pub fn directories(&self)->&[PathBuf]{
+        &self.directories
+    }
+
+    /// The naming convention used for the dynamic libraries.
+    pub fn suffix(&self)->LibrarySuffix{
+        self.suffix
+    }
+
+    /// All the plugins loaded so far,in the order that they were loaded.
+    pub fn plugins(&self)->&[LoadedPlugin<M>]{
+        &self.plugins
+    }
+
+    /// Gets the root module of the plugin with the `base_name` base name.
+    pub fn get(&self,base_name:&str)->Option<&'static M>{
+        self.plugins.iter()
+            .find(|p| p.base_name==base_name )
+            .map(|p| p.module )
+    }
+
+/**
+Scans all the directories for dynamic libraries,
+loading the ones that weren't already loaded by this registry.
+
+The files of each directory are loaded in alphabetical order.
+
+Files that do not follow the naming convention of dynamic libraries are ignored,
+as are files with the same base name as an already loaded plugin.
+
+# Errors
+
+This does not stop on the first error,
+every error is stored in the `errors` field of the returned `ScanReport`,
+which can be:
+
+- LibraryError::OpenError:
+If a directory could not be read,
+or a dynamic library could not be loaded.
+
+- The same errors as `RootModule::load_from`,for each dynamic library.
+
+# Warning
+
+The same warnings as `RootModule::load_from` apply to this function.
+
+*/
+    pub fn scan(&mut self)->ScanReport<M>{
+        let mut report=ScanReport{
+            loaded:Vec::new(),
+            errors:Vec::new(),
+        };
+
+        for directory in &self.directories {
+            let mut paths=match read_directory(directory) {
+                Ok(x)=>x,
+                Err(io)=>{
+                    report.errors.push(PluginLoadError{
+                        path:directory.clone(),
+                        error:LibraryError::OpenError{ path:directory.clone(), io },
+                    });
+                    continue;
+                }
+            };
+            paths.sort();
+
+            for path in paths {
+                let base_name=match RawLibrary::base_name_of(&path,self.suffix) {
+                    Some(x)=>x.to_string(),
+                    None=>continue,
+                };
+
+                let already_loaded=
+                    self.plugins.iter().chain(&report.loaded)
+                        .any(|p| p.base_name==base_name );
+                if already_loaded {
+                    continue;
+                }
+
+                match load_plugin::<M>(&path) {
+                    Ok((module,header))=>{
+                        report.loaded.push(LoadedPlugin{ path, base_name, module, header });
+                    }
+                    Err(error)=>{
+                        report.errors.push(PluginLoadError{ path, error });
+                    }
+                }
+            }
+        }
+
+        self.plugins.extend(report.loaded.iter().cloned());
+
+        report
+    }
+}
+
+
+impl<M> Debug for PluginRegistry<M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("PluginRegistry")
+         .field("directories",&self.directories)
+         .field("suffix",&self.suffix)
+         .field("plugins",&self.plugins)
+         .finish()
+    }
+}
+
+
+fn read_directory(directory:&Path)->io::Result<Vec<PathBuf>>{
+    let mut paths=Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry=entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    Ok(paths)
+}
+
+
+/// Loads the library at `path`,checking that its root module is compatible with `M`.
+fn load_plugin<M>(path:&Path)->Result<(&'static M,&'static LibHeader),LibraryError>
+where
+    M:RootModule,
+{
+    let header=lib_header_from_path(path)?;
+    let module=header.init_root_module_from_library::<M>(path)?.initialization()?;
+    Ok((module,header))
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+impl<M> LoadedPlugin<M>{
+    /// The path the plugin was loaded from.
+    pub fn path(&self)->&Path{
+        &self.path
+    }
+
+    /// The base name of the plugin,
+    /// the file name without the platform specific prefix/suffix/extension.
+    pub fn base_name(&self)->&str{
+        &self.base_name
+    }
+
+    /// The root module of the plugin.
+    pub fn module(&self)->&'static M{
+        self.module
+    }
+
+    /// The header of the plugin,
+    /// containing its version and the layout of its root module.
+    pub fn lib_header(&self)->&'static LibHeader{
+        self.header
+    }
+}
+
+
+impl<M> Clone for LoadedPlugin<M>{
+    fn clone(&self)->Self{
+        Self{
+            path:self.path.clone(),
+            base_name:self.base_name.clone(),
+            module:self.module,
+            header:self.header,
+        }
+    }
+}
+
+
+impl<M> Debug for LoadedPlugin<M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("LoadedPlugin")
+         .field("path",&self.path)
+         .field("base_name",&self.base_name)
+         .field("version",&self.header.version_strings())
+         .finish()
+    }
+}
+
+
+impl<M> Debug for ScanReport<M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("ScanReport")
+         .field("loaded",&self.loaded)
+         .field("errors",&self.errors)
+         .finish()
+    }
+}
+
+
+impl Display for PluginLoadError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        writeln!(f,"Error loading plugin at:\n\t{}",self.path.display())?;
+        Display::fmt(&self.error,f)
+    }
+}
+
+impl ::std::error::Error for PluginLoadError {}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::{
+        StableAbi,
+        package_version_strings,
+    };
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    struct DummyMod{
+        value:u32,
+    }
+
+    impl RootModule for DummyMod {
+        crate::declare_root_module_statics!{DummyMod}
+
+        const BASE_NAME: &'static str = "dummy";
+        const NAME: &'static str = "dummy";
+        const VERSION_STRINGS: VersionStrings = package_version_strings!();
+    }
+
+    fn library_name(base_name:&str,suffix:LibrarySuffix)->String{
+        RawLibrary::path_in_directory("".as_ref(),base_name,suffix)
+            .file_name().unwrap()
+            .to_str().unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn base_name_of(){
+        for &suffix in &[LibrarySuffix::NoSuffix,LibrarySuffix::Suffix] {
+            for base_name in vec!["a","plugin_b","hello-world"] {
+                let path=RawLibrary::path_in_directory("foo".as_ref(),base_name,suffix);
+                assert_eq!(RawLibrary::base_name_of(&path,suffix),Some(base_name));
+            }
+        }
+
+        let no_suffix=library_name("plugin",LibrarySuffix::NoSuffix);
+        assert_eq!(
+            RawLibrary::base_name_of(no_suffix.as_ref(),LibrarySuffix::Suffix),
+            None
+        );
+
+        for name in vec!["plugin.txt","plugin",".so",""] {
+            for &suffix in &[LibrarySuffix::NoSuffix,LibrarySuffix::Suffix] {
+                assert_eq!(RawLibrary::base_name_of(name.as_ref(),suffix),None);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_reports_errors(){
+        let directory=std::env::temp_dir()
+            .join(format!("abi_stable_plugin_registry_{}",std::process::id()));
+        let _=fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        let broken_0=directory.join(library_name("broken_0",LibrarySuffix::NoSuffix));
+        let broken_1=directory.join(library_name("broken_1",LibrarySuffix::NoSuffix));
+        fs::write(&broken_0,b"not a dynamic library").unwrap();
+        fs::write(&broken_1,b"not a dynamic library").unwrap();
+        fs::write(directory.join("readme.txt"),b"hello").unwrap();
+
+        let missing=directory.join("missing");
+
+        let mut registry=PluginRegistry::<DummyMod>::new(LibrarySuffix::NoSuffix);
+        registry
+            .add_directory(directory.clone())
+            .add_directory(missing.clone());
+
+        let report=registry.scan();
+
+        assert!(report.loaded.is_empty());
+        assert!(registry.plugins().is_empty());
+
+        let error_paths=report.errors.iter().map(|e| e.path.clone() ).collect::<Vec<_>>();
+        assert_eq!(error_paths,vec![broken_0,broken_1,missing]);
+
+        for error in &report.errors {
+            match error.error {
+                LibraryError::OpenError{..}=>{}
+                ref e=>panic!("unexpected error:{}",e),
+            }
+        }
+
+        let _=fs::remove_dir_all(&directory);
+    }
+}
@@ -18,20 +18,11 @@ impl RawLibrary {
     )->PathBuf{
         let formatted:String;
 
-        let (prefix,extension) = match (cfg!(windows), cfg!(target_os="macos")) {
-            (false, false) => ("lib","so"),
-            (false, true) => ("lib","dylib"),
-            (true, false) => ("","dll"),
-            _ => unreachable!("system is both windows and mac"),
-        };
-        
-        let is_64_bits =
-            cfg!(any(x86_64, powerpc64, aarch64)) || ::std::mem::size_of::<usize>() == 8;
-        let bits = if is_64_bits { "64" } else { "32" };
+        let (prefix,extension) = Self::prefix_and_extension();
 
         let maybe_suffixed_name=match suffix {
             LibrarySuffix::Suffix=>{
-                formatted=format!("{}-{}", base_name, bits);
+                formatted=format!("{}-{}", base_name, Self::pointer_size_suffix());
                 &*formatted
             }
             LibrarySuffix::NoSuffix=>{
@@ -43,6 +34,56 @@ impl RawLibrary {
         directory.join(name)
     }
 
+    /// Gets the prefix and extension of the file name of dynamic libraries 
+    /// in the current platform.
+    ///
+    /// eg:`("lib","so")` on Linux,`("","dll")` on Windows.
+    pub(super) fn prefix_and_extension()->(&'static str,&'static str){
+        match (cfg!(windows), cfg!(target_os="macos")) {
+            (false, false) => ("lib","so"),
+            (false, true) => ("lib","dylib"),
+            (true, false) => ("","dll"),
+            _ => unreachable!("system is both windows and mac"),
+        }
+    }
+
+    /// Gets the pointer size suffix used by `LibrarySuffix::Suffix`.
+    pub(super) fn pointer_size_suffix()->&'static str{
+        let is_64_bits =
+            cfg!(any(x86_64, powerpc64, aarch64)) || ::std::mem::size_of::<usize>() == 8;
+        if is_64_bits { "64" } else { "32" }
+    }
+
+    /// Gets the base name of the library at `path`,
+    /// returning None if its file name does not follow the naming convention 
+    /// of dynamic libraries in the current platform (with `suffix`).
+    ///
+    /// This is the inverse of `path_in_directory`.
+    pub fn base_name_of(path:&Path,suffix:LibrarySuffix)->Option<&str>{
+        let (prefix,extension) = Self::prefix_and_extension();
+
+        let file_name=path.file_name()?.to_str()?;
+        if !file_name.starts_with(prefix) { return None }
+        let name=&file_name[prefix.len()..];
+
+        let dot=name.rfind('.')?;
+        if &name[dot+1..]!=extension { return None }
+        let name=&name[..dot];
+
+        let name=match suffix {
+            LibrarySuffix::Suffix=>{
+                let bits=Self::pointer_size_suffix();
+                if !name.ends_with(bits) { return None }
+                let name=&name[..name.len()-bits.len()];
+                if !name.ends_with('-') { return None }
+                &name[..name.len()-1]
+            }
+            LibrarySuffix::NoSuffix=>name,
+        };
+
+        if name.is_empty() { None }else{ Some(name) }
+    }
+
     /// Loads the dynamic library at the `full_path` path.
     pub fn load_at(full_path:&Path) -> Result<Self,LibraryError> {
         match LibLoadingLibrary::new(&full_path) {
@@ -51,6 +92,11 @@ impl RawLibrary {
         }
     }
 
+    /// Gets the path this library was loaded from.
+    pub fn path(&self)->&Path{
+        &self.path
+    }
+
     /// Gets access to a static/function declared by the library.
     ///
     /// # Safety
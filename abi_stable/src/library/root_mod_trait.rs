@@ -1,6 +1,14 @@
 use super::*;
 
-use crate::utils::leak_value;
+use std::{
+    any::TypeId,
+    collections::HashMap,
+};
+
+use crate::{
+    external_types::RMutex,
+    utils::leak_value,
+};
 
 
 /**
@@ -26,6 +34,12 @@ pub trait RootModule: Sized+SharedStableAbi+'static  {
     /// Initialize this with ` package_version_strings!() `
     const VERSION_STRINGS: VersionStrings;
 
+    /// The policy used to decide whether the version of a dynamic library
+    /// is compatible with `VERSION_STRINGS`.
+    ///
+    /// This defaults to `VersionPolicy::SameMajor`.
+    const VERSION_POLICY: VersionPolicy = VersionPolicy::SameMajor;
+
     /// All the constants of this trait and supertraits.
     ///
     /// It can safely be used as a proxy for the associated constants of this trait.
@@ -134,8 +148,12 @@ If the abi_stable version used by the library is not compatible.
 If the version strings in the library can't be parsed as version numbers,
 this can only happen if the version strings are manually constructed.
 
+- LibraryError::ParseVersionReqError:
+If the requirement of `RootModule::VERSION_POLICY` can't be parsed.
+
 - LibraryError::IncompatibleVersionNumber:
-If the version number of the library is incompatible.
+If the version number of the library is incompatible,
+according to `RootModule::VERSION_POLICY`.
 
 - LibraryError::AbiInstability:
 If the layout of the root module is not the expected one.
@@ -144,14 +162,7 @@ If the layout of the root module is not the expected one.
     fn load_from(where_:LibraryPath<'_>) -> Result<&'static Self, LibraryError>{
         let statics=Self::root_module_statics();
         statics.root_mod.try_init(||{
-            let raw_library=load_raw_library::<Self>(where_)?;
-            let items = unsafe{ lib_header_from_raw_library(&raw_library)? };
-
-            let root_mod=items.init_root_module::<Self>()?.initialization()?;
-
-            // Important,If I don't leak the library after sucessfully loading the root module
-            // it would cause any use of the module to be a use after free.
-            let raw_lib=leak_value(raw_library);
+            let (root_mod,raw_lib)=load_leaked_library::<Self>(where_)?;
             statics.raw_lib.init(|| raw_lib );
 
             Ok(root_mod)
@@ -188,6 +199,127 @@ Warnings and Errors are detailed in `load_from`,
         Self::load_from(LibraryPath::FullPath(path_))
     }
 
+/**
+Loads an instance of this module from the path specified by `where_`,
+without storing it in `root_module_statics`.
+
+This allows loading multiple libraries that implement the same interface
+(eg:`plugin_a` and `plugin_b`,with the same `RootModule` type),
+returning each one's root module independently.
+
+The library is never unloaded,
+and `Self::get_module`/`Self::get_raw_library` are not affected by this function.
+
+Loading the same file multiple times returns the same root module,
+since the dynamic library is only loaded once by the operating system.
+The library is only leaked,and `RootModule::initialization` only called,
+the first time that `Self` is loaded from it
+(including loading it with `load_from`).
+
+# Warning
+
+The same warnings as `load_from` apply to this function.
+
+# Errors
+
+This returns the same errors as `load_from`.
+
+*/
+    fn load_instance_from(where_:LibraryPath<'_>) -> Result<&'static Self, LibraryError>{
+        load_leaked_library::<Self>(where_)
+            .map(|(root_mod,_)| root_mod )
+    }
+
+/**
+Loads an instance of this module from the directory specified by `where_`,
+without storing it in `root_module_statics`.
+
+Warnings and Errors are detailed in `load_instance_from`,
+
+*/
+    fn load_instance_from_directory(where_:&Path) -> Result<&'static Self, LibraryError>{
+        Self::load_instance_from(LibraryPath::Directory(where_))
+    }
+
+/**
+Loads an instance of this module from the file at `path_`,
+without storing it in `root_module_statics`.
+
+Warnings and Errors are detailed in `load_instance_from`,
+
+*/
+    fn load_instance_from_file(path_:&Path) -> Result<&'static Self, LibraryError>{
+        Self::load_instance_from(LibraryPath::FullPath(path_))
+    }
+
+/**
+Loads this module from the path specified by `where_`,
+returning a reference-counted handle which unloads the dynamic library 
+once it (and all its clones) are dropped.
+
+As opposed to `load_from`,this does not store the root module in 
+`root_module_statics`,
+and each call returns a handle that keeps the library loaded independently.
+
+Loading the same file multiple times returns handles to the same library,
+it is only unloaded once every handle to it is dropped
+(and it was not also loaded with `load_from`).
+
+`DynTrait`s and `RObject`s created by the library keep it loaded until they're dropped,
+using the `LibraryKeepAlive` handle stored in their vtable.
+
+# Safety
+
+Other values that point into the library 
+(eg:function pointers returned by the root module,
+or `RBox`es/`RString`s allocated by the library)
+must not outlive every handle to the library,
+since using them after the library is unloaded is undefined behavior.
+`LoadedLibrary::pin` can be used to make such values keep the library loaded.
+
+The library must not register thread-local destructors,
+or spawn threads that outlive the handles,
+since those would run code from the unloaded library.
+
+# Errors
+
+This returns the same errors as `load_from`.
+
+*/
+    unsafe fn load_unloadable_from(
+        where_:LibraryPath<'_>
+    ) -> Result<LoadedLibrary<Self>, LibraryError>{
+        LoadedLibrary::load(where_)
+    }
+
+/**
+Loads this module from the directory specified by `where_`,
+returning a reference-counted handle which unloads the dynamic library 
+once it (and all its clones) are dropped.
+
+Safety requirements and errors are detailed in `load_unloadable_from`.
+
+*/
+    unsafe fn load_unloadable_from_directory(
+        where_:&Path
+    ) -> Result<LoadedLibrary<Self>, LibraryError>{
+        Self::load_unloadable_from(LibraryPath::Directory(where_))
+    }
+
+/**
+Loads this module from the file at `path_`,
+returning a reference-counted handle which unloads the dynamic library 
+once it (and all its clones) are dropped.
+
+Safety requirements and errors are detailed in `load_unloadable_from`.
+
+*/
+    unsafe fn load_unloadable_from_file(
+        path_:&Path
+    ) -> Result<LoadedLibrary<Self>, LibraryError>{
+        Self::load_unloadable_from(LibraryPath::FullPath(path_))
+    }
+
     /// Defines behavior that happens once the module is loaded.
     ///
     /// The default implementation does nothing.
@@ -197,8 +329,90 @@ Warnings and Errors are detailed in `load_from`,
 }
 
 
+/// Loads the library at `where_`,checks that it is compatible,
+/// and leaks it,returning the root module and the library.
+///
+/// The library is only leaked,and `RootModule::initialization` only called,
+/// the first time that `M` is loaded from it.
+fn load_leaked_library<M>(
+    where_:LibraryPath<'_>
+) -> Result<(&'static M,&'static RawLibrary), LibraryError>
+where
+    M:RootModule
+{
+    let raw_library=load_raw_library::<M>(where_)?;
+
+    let header_and_module=unsafe{ lib_header_from_raw_library(&raw_library) }
+        .and_then(|header|{
+            let root_mod=header.init_root_module_from_library::<M>(raw_library.path())?;
+            Ok((header,root_mod))
+        });
+
+    let (header,root_mod)=match header_and_module {
+        Ok(x)=>x,
+        Err(e)=>{
+            // The error can reference statics of the library,so it must not be unloaded.
+            mem::forget(raw_library);
+            return Err(e);
+        }
+    };
+
+    let leaked=leaked_library_slot::<M>(header);
+
+    let mut raw_library=Some(raw_library);
+
+    let leaked=leaked.try_init(||{
+        let module=root_mod.initialization()?;
+        // Important,If I don't leak the library after sucessfully loading the root module
+        // it would cause any use of the module to be a use after free.
+        Ok(leak_value(LeakedLibrary{
+            module:unsafe{ transmute_reference::<M,ErasedObject>(module) },
+            raw_library:raw_library.take().unwrap(),
+        }))
+    });
+
+    match leaked {
+        Ok(leaked)=>{
+            // If the library was already leaked,
+            // `raw_library` is dropped here,which doesn't unload it.
+            let module=unsafe{ transmute_reference::<ErasedObject,M>(leaked.module) };
+            Ok((module,&leaked.raw_library))
+        }
+        Err(e)=>{
+            mem::forget(raw_library);
+            Err(e)
+        }
+    }
+}
+
+
+/// A library leaked by `load_leaked_library`,along with its initialized root module.
+struct LeakedLibrary{
+    module:&'static ErasedObject,
+    raw_library:RawLibrary,
+}
+
+/// Gets the slot that the library of `header` is leaked into the first time 
+/// `load_leaked_library` loads `M` from it.
+fn leaked_library_slot<M>(header:&'static LibHeader)->&'static LateStaticRef<LeakedLibrary>
+where
+    M:RootModule
+{
+    type LeakedMap=HashMap<(usize,TypeId),&'static LateStaticRef<LeakedLibrary>>;
+
+    static LEAKED:LateStaticRef<RMutex<LeakedMap>>=LateStaticRef::new();
+
+    let key=(header as *const LibHeader as usize,TypeId::of::<M>());
+
+    *LEAKED.init(|| HashMap::new().piped(RMutex::new).piped(leak_value) )
+        .lock()
+        .entry(key)
+        .or_insert_with(|| leak_value(LateStaticRef::new()) )
+}
+
+
 /// Loads the raw library at `where_`
-fn load_raw_library<M>(where_:LibraryPath<'_>) -> Result<RawLibrary, LibraryError>
+pub(super) fn load_raw_library<M>(where_:LibraryPath<'_>) -> Result<RawLibrary, LibraryError>
 where
     M:RootModule
 {
@@ -0,0 +1,258 @@
+/*!
+The policies used to decide whether the version of a dynamic library is compatible.
+*/
+
+use super::*;
+
+use crate::sabi_types::{FullVersionNumber,VersionReq,UnsatisfiedVersionReq};
+
+
+/**
+The policy used to decide whether the version number of a dynamic library
+is compatible with the version number of the `RootModule` the user was compiled with.
+
+This is declared with the `RootModule::VERSION_POLICY` associated constant.
+
+# Example
+
+```
+use abi_stable::library::VersionPolicy;
+
+// Requires the library to have a version between 1.2.0 (inclusive) and 3.0.0 (exclusive),
+// which can be a 1.4.0-rc.1 pre-release.
+const POLICY_REQ:VersionPolicy=VersionPolicy::Requirement(">=1.2, <3, >=1.4.0-rc.1");
+
+const POLICY_LIST:VersionPolicy=VersionPolicy::AllowList(&["1.0.0","1.0.1","1.1.0-beta.2"]);
+
+// The user and library must have the exact same version.
+const POLICY_CUSTOM:VersionPolicy=VersionPolicy::Custom(|user,library| user==library );
+
+```
+
+*/
+#[derive(Debug, Copy, Clone)]
+pub enum VersionPolicy{
+    /**
+The default policy,
+which requires that the user and library have the same major version,
+and for `0.y.z` versions that the minor version of the user is not greater than the library's.
+
+Pre-release identifiers are ignored.
+    */
+    SameMajor,
+    /**
+Requires that the version of the library satisfies a semver requirement,
+with the same syntax and semantics as the ones in Cargo.toml,
+eg:`"^1.2"`,`">=1.0.0, <3"`,`"~0.4.1"`.
+
+Read the docs for `VersionReq` for how pre-release versions are matched.
+    */
+    Requirement(&'static str),
+    /// Requires that the version of the library is one of the listed versions,
+    /// which are compared including their pre-release identifiers.
+    AllowList(&'static [&'static str]),
+    /// Requires that the function returns true
+    /// when passed the version of the user and the version of the library,
+    /// in that order.
+    Custom(fn(&FullVersionNumber,&FullVersionNumber)->bool),
+}
+
+
+/// The rule of a `VersionPolicy` that the version of a dynamic library did not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailedVersionRule{
+    /// `VersionPolicy::SameMajor`:the major versions are different.
+    DifferentMajor,
+    /// `VersionPolicy::SameMajor`:
+    /// the versions are `0.y.z`,and the minor version of the user
+    /// is greater than the one of the library.
+    GreaterMinor,
+    /// `VersionPolicy::Requirement`:the library does not satisfy the requirement.
+    Requirement{
+        requirement:&'static str,
+        unsatisfied:UnsatisfiedVersionReq,
+    },
+    /// `VersionPolicy::AllowList`:the library is not one of the allowed versions.
+    NotAllowed{
+        allowed:&'static [&'static str],
+    },
+    /// `VersionPolicy::Custom`:the function returned false.
+    Custom,
+}
+
+
+impl Default for VersionPolicy{
+    fn default()->Self{
+        VersionPolicy::SameMajor
+    }
+}
+
+
+impl VersionPolicy{
+/**
+Checks that the `library` version is compatible with the `user` version.
+
+# Errors
+
+- LibraryError::IncompatibleVersionNumber:
+If the library version doesn't follow this policy.
+
+- LibraryError::ParseVersionReqError:
+If the requirement of `VersionPolicy::Requirement` could not be parsed.
+
+- LibraryError::ParseVersionError:
+If a version of `VersionPolicy::AllowList` could not be parsed.
+
+*/
+    pub fn check(
+        &self,
+        library_name:&'static str,
+        user:&FullVersionNumber,
+        library:&FullVersionNumber,
+    )->Result<(),LibraryError>{
+        let failed_rule=match *self {
+            VersionPolicy::SameMajor=>{
+                let (user,library)=(user.number,library.number);
+                if user.major != library.major {
+                    Some(FailedVersionRule::DifferentMajor)
+                }else if user.major==0 && user.minor > library.minor {
+                    Some(FailedVersionRule::GreaterMinor)
+                }else{
+                    None
+                }
+            }
+            VersionPolicy::Requirement(requirement)=>{
+                VersionReq::parse(requirement)?
+                    .check(library)
+                    .err()
+                    .map(|unsatisfied| FailedVersionRule::Requirement{requirement,unsatisfied} )
+            }
+            VersionPolicy::AllowList(allowed)=>{
+                let mut is_allowed=false;
+                for &version in allowed {
+                    if VersionStrings::new(version).parsed_full()? == *library {
+                        is_allowed=true;
+                    }
+                }
+                if is_allowed {
+                    None
+                }else{
+                    Some(FailedVersionRule::NotAllowed{allowed})
+                }
+            }
+            VersionPolicy::Custom(function)=>{
+                if function(user,library) {
+                    None
+                }else{
+                    Some(FailedVersionRule::Custom)
+                }
+            }
+        };
+
+        match failed_rule {
+            Some(failed_rule)=>Err(LibraryError::IncompatibleVersionNumber {
+                library_name,
+                expected_version: user.clone(),
+                actual_version: library.clone(),
+                failed_rule,
+            }),
+            None=>Ok(()),
+        }
+    }
+}
+
+
+impl Display for FailedVersionRule{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self {
+            FailedVersionRule::DifferentMajor=>
+                f.write_str("the major versions must be the same"),
+            FailedVersionRule::GreaterMinor=>
+                f.write_str(
+                    "for 0.y.z versions,\
+                     the minor version of the user must not be greater than the library's"
+                ),
+            FailedVersionRule::Requirement{requirement,unsatisfied}=>
+                write!(
+                    f,
+                    "the library must satisfy the '{}' requirement,\n{}",
+                    requirement,
+                    unsatisfied,
+                ),
+            FailedVersionRule::NotAllowed{allowed}=>
+                write!(f,"the library must be one of these versions:{:?}",allowed),
+            FailedVersionRule::Custom=>
+                f.write_str("the custom version check of the root module failed"),
+        }
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    fn version(s:&'static str)->FullVersionNumber{
+        VersionStrings::new(s).parsed_full().unwrap()
+    }
+
+    fn failed_rule(policy:VersionPolicy,user:&'static str,library:&'static str)
+    ->Option<FailedVersionRule>
+    {
+        match policy.check("lib",&version(user),&version(library)) {
+            Ok(())=>None,
+            Err(LibraryError::IncompatibleVersionNumber{failed_rule,..})=>Some(failed_rule),
+            Err(e)=>panic!("unexpected error:{}",e),
+        }
+    }
+
+    #[test]
+    fn same_major(){
+        let policy=VersionPolicy::SameMajor;
+        assert_eq!(failed_rule(policy,"1.2.0","1.0.0"),None);
+        assert_eq!(failed_rule(policy,"1.2.0","1.5.0-rc.1"),None);
+        assert_eq!(failed_rule(policy,"0.2.0","0.3.0"),None);
+        assert_eq!(failed_rule(policy,"1.0.0","2.0.0"),Some(FailedVersionRule::DifferentMajor));
+        assert_eq!(failed_rule(policy,"0.3.0","0.2.0"),Some(FailedVersionRule::GreaterMinor));
+    }
+
+    #[test]
+    fn requirement(){
+        let policy=VersionPolicy::Requirement(">=1.2, <3");
+        assert_eq!(failed_rule(policy,"1.0.0","2.9.0"),None);
+        match failed_rule(policy,"1.0.0","3.0.0") {
+            Some(FailedVersionRule::Requirement{
+                requirement:">=1.2, <3",
+                unsatisfied:UnsatisfiedVersionReq::Comparator(cmp),
+            })=>assert_eq!(cmp.to_string(),"<3"),
+            x=>panic!("unexpected result:{:?}",x),
+        }
+
+        let policy=VersionPolicy::Requirement(">=1.2,,");
+        match policy.check("lib",&version("1.0.0"),&version("1.0.0")) {
+            Err(LibraryError::ParseVersionReqError(_))=>{}
+            x=>panic!("unexpected result:{:?}",x),
+        }
+    }
+
+    #[test]
+    fn allow_list(){
+        let policy=VersionPolicy::AllowList(&["1.0.0","1.1.0-beta.2"]);
+        assert_eq!(failed_rule(policy,"1.0.0","1.0.0"),None);
+        assert_eq!(failed_rule(policy,"1.0.0","1.1.0-beta.2"),None);
+        assert_eq!(
+            failed_rule(policy,"1.0.0","1.1.0"),
+            Some(FailedVersionRule::NotAllowed{allowed:&["1.0.0","1.1.0-beta.2"]}),
+        );
+    }
+
+    #[test]
+    fn custom(){
+        let policy=VersionPolicy::Custom(|user,library| user.number.patch==library.number.patch );
+        assert_eq!(failed_rule(policy,"1.0.3","2.0.3"),None);
+        assert_eq!(failed_rule(policy,"1.0.3","1.0.4"),Some(FailedVersionRule::Custom));
+    }
+}
@@ -289,6 +289,7 @@ macro_rules! impl_get_type_info {
             use std::mem;
             use $crate::{
                 erased_types::type_info::TypeInfo,
+                abi_stability::stable_type_id::no_stable_type_id,
                 sabi_types::{ReturnValueEquality},
                 std_types::{StaticStr,utypeid::some_utypeid},
             };
@@ -299,6 +300,9 @@ macro_rules! impl_get_type_info {
                 _uid:ReturnValueEquality{
                     function:some_utypeid::<Self>
                 },
+                _stable_id:ReturnValueEquality{
+                    function:no_stable_type_id
+                },
                 name:StaticStr::new(stringify!($type)),
                 module:StaticStr::new(module_path!()),
                 package:StaticStr::new(env!("CARGO_PKG_NAME")),
@@ -20,12 +20,14 @@ use core_extensions::SelfOps;
 mod accessible_fields;
 mod empty_prefix;
 mod layout;
+mod pointers;
 mod pt_metadata;
 
 pub use self::{
     accessible_fields::{FieldAccessibility,IsAccessible},
     empty_prefix::EmptyPrefixType,
     layout::{PTStructLayout,PTStructLayoutParams},
+    pointers::{PrefixArc,PrefixBox},
 };
 
 pub(crate) use self::pt_metadata::PrefixTypeMetadata;
@@ -51,8 +53,16 @@ pub unsafe trait PrefixTypeTrait:Sized{
 
     /// A bit array,where the bit at the field index represents whether that 
     /// field is accessible.
+    ///
+    /// This only describes the first 64 fields.
     const PT_FIELD_ACCESSIBILITY:FieldAccessibility;
 
+    /// The accessibility of the fields after the first 64,
+    /// each element describes the next 64 fields.
+    ///
+    /// This must also be stored in the `more_accessible_fields` field of `PT_LAYOUT`.
+    const PT_MORE_FIELD_ACCESSIBILITY:&'static [FieldAccessibility]=&[];
+
     #[doc(hidden)]
     // Whether each individual field in the prefix is conditional.
     //
@@ -105,9 +115,9 @@ pub type WithMetadata<T>=
 #[repr(C,align(4))]
 pub struct WithMetadata_<T,P>{
     /// A bit array,where the bit at field index represents whether a field is accessible.
-    #[inline(doc)]
+    #[doc(inline)]
     pub _prefix_type_field_acc:FieldAccessibility,
-    #[inline(doc)]
+    #[doc(inline)]
     pub _prefix_type_layout:&'static PTStructLayout,
     pub original:T,
     _marker:PhantomData<P>,
@@ -247,7 +257,7 @@ where T:PrefixTypeTrait
 #[cold]
 #[inline(never)]
 pub fn panic_on_missing_fieldname<T>(
-    field_index:u8,
+    field_index:usize,
     actual_layout:&'static PTStructLayout,
 )->!
 where T:PrefixTypeTrait
@@ -265,7 +275,7 @@ where T:PrefixTypeTrait
     }
 
     inner(
-        field_index as usize,
+        field_index,
         T::PT_LAYOUT,
         actual_layout,
     )
@@ -8,6 +8,11 @@ use std::iter::ExactSizeIterator;
 /// Describes which prefix-type fields are accessible.
 ///
 /// Each field is represented as a bit,where 0 is IsAccessible::No,and 1 s IsAccessible::Yes.
+///
+/// This only describes 64 fields,prefix-types with more fields describe 
+/// the accessibility of the remaining fields with a `&'static [FieldAccessibility]`,
+/// where each element describes the next 64 fields
+/// (this is what the `*_with_more` methods take as the `more` parameter).
 #[must_use="FieldAccessibility is returned by value by every mutating method."]
 #[derive(StableAbi)]
 #[derive(Debug,Copy,Clone,PartialEq,Eq)]
@@ -40,9 +45,11 @@ impl IsAccessible{
 
 impl FieldAccessibility{
     /// Creates a FieldAccessibility where the first `field_count` fields are accessible.
+    ///
+    /// All fields are accessible if `field_count` is larger than 64.
     #[inline]
     pub const fn with_field_count(field_count:usize)->Self{
-        let (n,overflowed)=1u64.overflowing_shl(field_count as u32);
+        let (n,overflowed)=1u64.overflowing_shl(min_usize(field_count,64) as u32);
         Self{
             bits:n.wrapping_sub([1,2][overflowed as usize])
         }
@@ -86,6 +93,41 @@ impl FieldAccessibility{
         (self.bits&bits)!=0
     }
 
+    /// Queries whether the field at the `index` position is accessible,
+    /// where `self` describes the first 64 fields,
+    /// and each element of `more` describes the next 64 fields.
+    pub fn is_accessible_with_more(self,more:&[FieldAccessibility],index:usize)->bool{
+        match index.checked_sub(64) {
+            None=>self.is_accessible(index),
+            Some(index)=>more.get(index/64).map_or(false,|x| x.is_accessible(index%64) ),
+        }
+    }
+
+    /// Sets the accessibility of a field,
+    /// where `self` describes the first 64 fields,
+    /// and each element of `more` describes the next 64 fields.
+    ///
+    /// `more` is extended with inaccessible fields if it is too short to contain the field.
+    pub fn set_accessibility_with_more(
+        &mut self,
+        more:&mut Vec<FieldAccessibility>,
+        index:usize,
+        cond:IsAccessible,
+    ){
+        match index.checked_sub(64) {
+            None=>{
+                *self=self.set_accessibility(index,cond);
+            }
+            Some(index)=>{
+                let (word,bit)=(index/64,index%64);
+                if more.len() <= word {
+                    more.resize(word+1,FieldAccessibility::empty());
+                }
+                more[word]=more[word].set_accessibility(bit,cond);
+            }
+        }
+    }
+
     #[inline]
     pub const fn bits(self)->u64{
         self.bits
@@ -98,6 +140,18 @@ impl FieldAccessibility{
         }
     }
 
+    /// Iterates over the accessibility of the first `field_count` fields,
+    /// where `self` describes the first 64 fields,
+    /// and each element of `more` describes the next 64 fields.
+    pub fn iter_field_count_with_more<'a>(
+        self,
+        more:&'a [FieldAccessibility],
+        field_count:usize,
+    )->impl ExactSizeIterator<Item=IsAccessible>+Clone+'a{
+        (0..field_count)
+            .map(move|i| IsAccessible::new(self.is_accessible_with_more(more,i)) )
+    }
+
     pub fn is_compatible(self,other:Self,field_count:usize)->bool{
         let all_accessible=Self::with_field_count(field_count);
         let implication=(!self.bits|other.bits)&all_accessible.bits;
@@ -251,6 +305,56 @@ mod tests{
         }
     }
     
+    #[test]
+    fn with_field_count_over_64(){
+        for count in 64..=200 {
+            assert_eq!(FieldAccessibility::with_field_count(count).bits(),!0);
+        }
+    }
+
+    #[test]
+    fn with_more(){
+        let more=[
+            FieldAccessibility::with_field_count(64)
+                .set_accessibility(3,IsAccessible::No),
+            FieldAccessibility::with_field_count(10),
+        ];
+        let accessibility=FieldAccessibility::with_field_count(64)
+            .set_accessibility(5,IsAccessible::No);
+
+        let expected=(0..150)
+            .map(|i| i!=5 && i!=64+3 && i<64*2+10 )
+            .collect::<Vec<bool>>();
+
+        assert_eq!(
+            (0..150)
+                .map(|i| accessibility.is_accessible_with_more(&more,i) )
+                .collect::<Vec<bool>>(),
+            expected
+        );
+        assert_eq!(
+            accessibility.iter_field_count_with_more(&more,150)
+                .map(IsAccessible::is_accessible)
+                .collect::<Vec<bool>>(),
+            expected
+        );
+
+        let mut first=FieldAccessibility::empty();
+        let mut more=Vec::new();
+        for &i in &[1,63,64,130,200] {
+            first.set_accessibility_with_more(&mut more,i,IsAccessible::Yes);
+        }
+        assert_eq!(more.len(),3);
+        assert_eq!(
+            (0..250)
+                .filter(|&i| first.is_accessible_with_more(&more,i) )
+                .collect::<Vec<usize>>(),
+            vec![1,63,64,130,200]
+        );
+        first.set_accessibility_with_more(&mut more,130,IsAccessible::No);
+        assert!(!first.is_accessible_with_more(&more,130));
+    }
+
     #[test]
     fn iter_test(){
         let iter=FieldAccessibility::with_field_count(8)
@@ -1,11 +1,16 @@
 use crate::{
     sabi_types::{CmpIgnored,VersionStrings},
-    std_types::StaticStr,
+    std_types::{StaticSlice,StaticStr},
 };
 
+use super::FieldAccessibility;
+
 
 
 /// Represents the layout of a prefix-type,for use in error messages.
+///
+/// This also stores the accessibility of the fields after the first 64,
+/// since the `WithMetadata` header only has room for the first 64 fields.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, StableAbi)]
 pub struct PTStructLayout {
@@ -16,6 +21,9 @@ pub struct PTStructLayout {
     pub file:CmpIgnored<StaticStr>, // This is for the Debug string
     pub line:CmpIgnored<u32>, // This is for the Debug string
     pub field_names:StaticStr,
+    /// The accessibility of the fields after the first 64,
+    /// each element describes the next 64 fields.
+    pub more_accessible_fields:StaticSlice<FieldAccessibility>,
 }
 
 
@@ -44,9 +52,20 @@ impl PTStructLayout{
             file:CmpIgnored::new(StaticStr::new(params.file)),
             line:CmpIgnored::new(params.line),
             field_names:StaticStr::new(params.field_names),
+            more_accessible_fields:StaticSlice::new(&[]),
         }
     }
 
+    /// Sets the accessibility of the fields after the first 64,
+    /// each element of `more` describes the next 64 fields.
+    pub const fn with_more_accessible_fields(
+        mut self,
+        more:&'static [FieldAccessibility],
+    )->Self{
+        self.more_accessible_fields=StaticSlice::new(more);
+        self
+    }
+
     pub fn get_field_names(&self)->impl Iterator<Item=&'static str>{
         self.field_names.as_str().split(';').filter(|x| !x.is_empty() )
     }
@@ -0,0 +1,204 @@
+/*!
+Owned,heap allocated,pointers to prefix-types.
+*/
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+};
+
+use crate::{
+    marker_type::ErasedObject,
+    std_types::{RArc,RBox},
+};
+
+use super::{PrefixTypeTrait,WithMetadata};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+An owned pointer to a heap allocated prefix-type,
+dereferencing to the `<prefix_struct>` type `P`.
+
+The value is dropped and deallocated by the dynamic library/executable that created it,
+which means that this can be dropped even if the dynamic library/executable that
+drops it only knows about some of the fields of the type.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    prefix_type::PrefixBox,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_struct="Config")))]
+pub struct ConfigVal{
+    #[sabi(last_prefix_field)]
+    pub threads:u32,
+    pub verbose:bool,
+}
+
+# fn main(){
+    let config:PrefixBox<Config>=PrefixBox::new(ConfigVal{ threads:4, verbose:true });
+
+    assert_eq!(config.threads(),4);
+    assert_eq!(config.verbose(),Some(true));
+# }
+
+```
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(unsafe_unconstrained(P),bound="P:__SharedStableAbi")]
+pub struct PrefixBox<P>{
+    // A type erased `RBox<WithMetadata<T>>`,
+    // whose vtable drops the `T` it was constructed with.
+    inner:RBox<ErasedObject>,
+    _marker:PhantomData<*const P>,
+}
+
+impl<P> PrefixBox<P>{
+    /// Constructs a `PrefixBox<P>`,moving `value` to the heap.
+    pub fn new<T>(value:T)->Self
+    where
+        T:PrefixTypeTrait<Prefix=P>,
+    {
+        let boxed=RBox::new(value.into_with_metadata());
+        unsafe{
+            Self{
+                inner:mem::transmute::<RBox<WithMetadata<T>>,RBox<ErasedObject>>(boxed),
+                _marker:PhantomData,
+            }
+        }
+    }
+}
+
+impl<P> Deref for PrefixBox<P>{
+    type Target=P;
+
+    fn deref(&self)->&P{
+        unsafe{
+            &*(&*self.inner as *const ErasedObject as *const P)
+        }
+    }
+}
+
+impl<P> fmt::Debug for PrefixBox<P>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("PrefixBox{..}")
+    }
+}
+
+unsafe impl<P:Send> Send for PrefixBox<P> {}
+unsafe impl<P:Sync> Sync for PrefixBox<P> {}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+A reference counted pointer to a heap allocated prefix-type,
+dereferencing to the `<prefix_struct>` type `P`.
+
+The value is dropped and deallocated by the dynamic library/executable that created it,
+which means that this can be dropped even if the dynamic library/executable that
+drops it only knows about some of the fields of the type.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    prefix_type::PrefixArc,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_struct="Config")))]
+pub struct ConfigVal{
+    #[sabi(last_prefix_field)]
+    pub threads:u32,
+    pub verbose:bool,
+}
+
+# fn main(){
+    let config:PrefixArc<Config>=PrefixArc::new(ConfigVal{ threads:4, verbose:true });
+    let clone=config.clone();
+
+    assert_eq!(clone.threads(),4);
+    assert_eq!(clone.verbose(),Some(true));
+# }
+
+```
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(unsafe_unconstrained(P),bound="P:__SharedStableAbi")]
+pub struct PrefixArc<P>{
+    // A type erased `RArc<WithMetadata<T>>`,
+    // whose vtable drops the `T` it was constructed with.
+    inner:RArc<ErasedObject>,
+    _marker:PhantomData<*const P>,
+}
+
+impl<P> PrefixArc<P>{
+    /// Constructs a `PrefixArc<P>`,moving `value` to the heap.
+    pub fn new<T>(value:T)->Self
+    where
+        T:PrefixTypeTrait<Prefix=P>,
+    {
+        let arc=RArc::new(value.into_with_metadata());
+        unsafe{
+            Self{
+                inner:mem::transmute::<RArc<WithMetadata<T>>,RArc<ErasedObject>>(arc),
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Whether both `PrefixArc`s point to the same allocation.
+    pub fn ptr_eq(this:&Self,other:&Self)->bool{
+        std::ptr::eq(&*this.inner,&*other.inner)
+    }
+}
+
+impl<P> Clone for PrefixArc<P>{
+    fn clone(&self)->Self{
+        Self{
+            inner:self.inner.clone(),
+            _marker:PhantomData,
+        }
+    }
+}
+
+impl<P> Deref for PrefixArc<P>{
+    type Target=P;
+
+    fn deref(&self)->&P{
+        unsafe{
+            &*(&*self.inner as *const ErasedObject as *const P)
+        }
+    }
+}
+
+impl<P> fmt::Debug for PrefixArc<P>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("PrefixArc{..}")
+    }
+}
+
+unsafe impl<P:Send+Sync> Send for PrefixArc<P> {}
+unsafe impl<P:Send+Sync> Sync for PrefixArc<P> {}
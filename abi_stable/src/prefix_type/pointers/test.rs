@@ -0,0 +1,108 @@
+use super::*;
+
+use crate::{
+    abi_stability::abi_checking::{CheckingGlobals,check_layout_compatibility_with_globals},
+    std_types::RArc,
+    StableAbi,
+};
+
+
+mod prefix0 {
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+    }
+}
+
+#[allow(dead_code)]
+mod prefix2 {
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal<T> {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        pub field1: u16,
+        #[sabi(field_bound="Copy")]
+        pub field2: T,
+    }
+}
+
+
+fn new_value(counter:&RArc<u32>)->prefix2::PrefixVal<RArc<u32>>{
+    prefix2::PrefixVal{
+        field0:3,
+        field1:5,
+        field2:counter.clone(),
+    }
+}
+
+
+#[test]
+fn prefix_box_drops_unknown_fields(){
+    let mut counter=RArc::new(0);
+
+    {
+        let boxed:PrefixBox<prefix2::Prefix<RArc<u32>>>=PrefixBox::new(new_value(&counter));
+        assert_eq!(boxed.field0(),3);
+        assert_eq!(boxed.field1(),Some(5));
+        assert_eq!(RArc::get_mut(&mut counter),None);
+    }
+    assert_eq!(RArc::get_mut(&mut counter),Some(&mut 0));
+
+    {
+        let boxed:PrefixBox<prefix0::Prefix>=unsafe{
+            mem::transmute(PrefixBox::new(new_value(&counter)))
+        };
+        assert_eq!(boxed.field0(),3);
+        assert_eq!(RArc::get_mut(&mut counter),None);
+    }
+    assert_eq!(RArc::get_mut(&mut counter),Some(&mut 0));
+}
+
+
+#[test]
+fn prefix_arc_drops_unknown_fields(){
+    let mut counter=RArc::new(0);
+
+    {
+        let arc:PrefixArc<prefix0::Prefix>=unsafe{
+            mem::transmute(PrefixArc::new(new_value(&counter)))
+        };
+        let clone=arc.clone();
+        assert!(PrefixArc::ptr_eq(&arc,&clone));
+        assert!(!PrefixArc::ptr_eq(&arc,&PrefixArc::new(prefix0::PrefixVal{ field0:3 })));
+        
+        drop(arc);
+        assert_eq!(clone.field0(),3);
+        assert_eq!(RArc::get_mut(&mut counter),None);
+    }
+    assert_eq!(RArc::get_mut(&mut counter),Some(&mut 0));
+}
+
+
+#[test]
+fn prefix_pointer_layouts(){
+    let globals=CheckingGlobals::new();
+
+    check_layout_compatibility_with_globals(
+        <PrefixBox<prefix0::Prefix>>::ABI_INFO,
+        <PrefixBox<prefix2::Prefix<RArc<u32>>>>::ABI_INFO,
+        &globals,
+    ).unwrap();
+
+    check_layout_compatibility_with_globals(
+        <PrefixArc<prefix0::Prefix>>::ABI_INFO,
+        <PrefixArc<prefix2::Prefix<RArc<u32>>>>::ABI_INFO,
+        &globals,
+    ).unwrap();
+
+    check_layout_compatibility_with_globals(
+        <PrefixBox<prefix2::Prefix<RArc<u32>>>>::ABI_INFO,
+        <PrefixBox<prefix0::Prefix>>::ABI_INFO,
+        &CheckingGlobals::new(),
+    ).unwrap_err();
+}
@@ -30,6 +30,9 @@ pub(crate) struct PrefixTypeMetadata{
 
     pub accessible_fields:FieldAccessibility,
 
+    /// The accessibility of the fields after the first 64.
+    pub more_accessible_fields:Cow<'static,[FieldAccessibility]>,
+
     pub conditional_prefix_fields:&'static [IsConditional],
 
     pub fields:InitialFieldsOrMut,
@@ -61,12 +64,26 @@ impl PrefixTypeMetadata{
         Self{
             fields:InitialFieldsOrMut::from(prefix.fields),
             accessible_fields:prefix.accessible_fields,
+            more_accessible_fields:Cow::Borrowed(prefix.more_accessible_fields.as_slice()),
             conditional_prefix_fields:prefix.conditional_prefix_fields.as_slice(),
             prefix_field_count:prefix.first_suffix_field,
             layout,
         }
     }
 
+    /// Whether the field at the `index` position is accessible.
+    pub(crate) fn is_accessible(&self,index:usize)->bool{
+        self.accessible_fields.is_accessible_with_more(&self.more_accessible_fields,index)
+    }
+
+    fn set_accessibility(&mut self,index:usize,cond:IsAccessible){
+        self.accessible_fields.set_accessibility_with_more(
+            self.more_accessible_fields.to_mut(),
+            index,
+            cond,
+        );
+    }
+
     
     // #[cfg(test)]
     // pub(crate) fn assert_valid(&self){
@@ -114,13 +131,9 @@ impl PrefixTypeMetadata{
 
         let min_field_count=o_fields.len().min(self.fields.len());
         
-        for (field_i,(t_acc,o_acc)) in 
-            self.accessible_fields.iter_field_count(min_field_count)
-                .zip(other.accessible_fields.iter_field_count(min_field_count))
-                .enumerate() 
-        {
+        for field_i in 0..min_field_count {
             let o_field=o_fields.next().unwrap();
-            if !t_acc.is_accessible() && o_acc.is_accessible() {
+            if !self.is_accessible(field_i) && other.is_accessible(field_i) {
                 let t_fields=self.fields.to_mut();
 
                 t_fields[field_i]=o_field.into_owned();
@@ -128,14 +141,11 @@ impl PrefixTypeMetadata{
         }
 
         if min_field_count==self.fields.len() {
-            let t_fields=self.fields.to_mut();
-            
             for (i,o_field) in o_fields.enumerate() {
                 let field_i=i+min_field_count;
 
-                t_fields.push(o_field.into_owned());
-                self.accessible_fields=
-                    self.accessible_fields.set_accessibility(field_i,IsAccessible::Yes);
+                self.fields.to_mut().push(o_field.into_owned());
+                self.set_accessibility(field_i,IsAccessible::Yes);
             }
         }
     }
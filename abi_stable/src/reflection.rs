@@ -5,8 +5,13 @@ Types and submodules for doing runtime reflection.
 #[cfg(all(test,not(feature="only_new_tests")))]
 pub mod tests{
     pub mod derive_reflection;
+    pub mod prefix_fields;
 }
 
+mod prefix_fields;
+
+pub use self::prefix_fields::{PrefixField,PrefixFields,PrefixFieldsIter};
+
 /**
 Implementation details of the sabi_extract tool.
 
@@ -19,7 +24,7 @@ pub mod export_module;
 ///
 /// Module reflection only allows accessing public fields.
 #[repr(u8)]
-#[derive(Debug,Copy,Clone,PartialEq,Eq,StableAbi)]
+#[derive(Debug,Copy,Clone,PartialEq,Eq,StableAbi,Serialize,Deserialize)]
 pub enum ModReflMode{
     /// For modules that are reflected on at runtime.
     ///
@@ -0,0 +1,338 @@
+/*!
+Runtime reflection on the fields of a prefix-type
+(types deriving StableAbi with `#[sabi(kind(Prefix(..)))]`).
+*/
+
+use std::{
+    fmt,
+    mem,
+};
+
+use crate::{
+    abi_stability::{
+        stable_abi_trait::PrefixKind,
+        AbiInfo,SharedStableAbi,StableAbi,
+    },
+    marker_type::ErasedObject,
+    prefix_type::{PTStructLayout,WithMetadata_},
+    type_layout::{TLData,TLFOSIter,TLField,TLPrefixType,TypeLayout},
+};
+
+
+/**
+Reflects on the fields of a prefix-type,
+as they are known by the `<prefix_struct>` type `P` it was constructed from.
+
+The fields in this are those of the type that created the layout of `P`,
+while the accessibility of the fields also depends on the
+dynamic library/executable that constructed the prefix-type,
+since it might have been compiled with an older version of the type.
+
+# Example
+
+```
+use abi_stable::{
+    StableAbi,
+    prefix_type::{PrefixTypeTrait,WithMetadata},
+    reflection::PrefixFields,
+    std_types::RStr,
+};
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_struct="Module")))]
+pub struct ModuleVal{
+    pub name:RStr<'static>,
+    #[sabi(last_prefix_field)]
+    pub version:u32,
+    pub flags:u64,
+}
+
+# fn main(){
+    let module:&WithMetadata<ModuleVal>=&WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        ModuleVal{ name:"hello".into(), version:3, flags:0 },
+    );
+
+    let fields=PrefixFields::new(module.as_prefix());
+
+    let names=fields.iter().map(|f| f.name() ).collect::<Vec<&str>>();
+    assert_eq!(names,vec!["name","version","flags"]);
+
+    let version=fields.field_by_name("version").unwrap();
+    assert!(version.is_prefix_field());
+    assert!(version.is_accessible());
+    assert_eq!(version.layout().name.as_str(),"u32");
+    assert_eq!(version.get_as::<u32>(),Some(&3));
+    assert_eq!(version.get_as::<u64>(),None);
+# }
+
+```
+
+*/
+#[derive(Copy,Clone)]
+pub struct PrefixFields<'a>{
+    header:&'a WithMetadata_<(),()>,
+    layout:&'static TypeLayout,
+    prefix:&'static TLPrefixType,
+    original_offset:usize,
+}
+
+
+impl<'a> PrefixFields<'a>{
+    /// Constructs a `PrefixFields` from a reference to a `<prefix_struct>` type.
+    ///
+    /// # Panic
+    ///
+    /// This panics if the layout of `P` does not describe a prefix-type,
+    /// which can only happen if `P` implements `SharedStableAbi` manually.
+    pub fn new<P>(prefix:&'a P)->Self
+    where
+        P:SharedStableAbi<Kind=PrefixKind>+'static,
+    {
+        let layout=P::S_LAYOUT;
+        let prefix_layout=match &layout.data {
+            TLData::PrefixType(x)=>x,
+            _=>panic!(
+                "expected the layout of a prefix-type,found:\n{}",
+                layout.full_type,
+            ),
+        };
+        let header=unsafe{
+            &*(prefix as *const P as *const WithMetadata_<(),()>)
+        };
+
+        // The layout of `P` has the alignment of `WithMetadata_<DerivingType,P>`,
+        // which is the alignment of the deriving type if that is larger than
+        // the alignment of the header,
+        // otherwise the deriving type is aligned to its most aligned field.
+        let alignment=if layout.alignment > mem::align_of::<WithMetadata_<(),()>>() {
+            layout.alignment
+        }else{
+            prefix_layout.fields.get_fields()
+                .map(|f| f.abi_info.get().layout.alignment )
+                .max()
+                .unwrap_or(1)
+        };
+        let header_size=
+            (&header.original as *const () as usize) - (header as *const _ as usize);
+
+        Self{
+            header,
+            layout,
+            prefix:prefix_layout,
+            original_offset:round_up(header_size,alignment),
+        }
+    }
+
+    /// The layout of the `<prefix_struct>` type this was constructed from.
+    pub fn layout(&self)->&'static TypeLayout{
+        self.layout
+    }
+
+    /// The prefix-type part of the layout of the `<prefix_struct>` type
+    /// this was constructed from.
+    pub fn prefix_layout(&self)->&'static TLPrefixType{
+        self.prefix
+    }
+
+    /// The layout of the prefix-type,
+    /// as described by the dynamic library/executable that constructed it.
+    pub fn pt_layout(&self)->&'static PTStructLayout{
+        self.header._prefix_type_layout
+    }
+
+    /// The amount of fields in the prefix-type.
+    pub fn field_count(&self)->usize{
+        self.prefix.fields.len()
+    }
+
+    /// Iterates over all the fields,including inaccessible ones.
+    pub fn iter(&self)->PrefixFieldsIter<'a>{
+        PrefixFieldsIter{
+            fields:*self,
+            iter:self.prefix.fields.get_fields(),
+            index:0,
+            offset:0,
+        }
+    }
+
+    /// Gets the field at the `index` position.
+    pub fn field(&self,index:usize)->Option<PrefixField<'a>>{
+        self.iter().nth(index)
+    }
+
+    /// Gets the field named `name`.
+    pub fn field_by_name(&self,name:&str)->Option<PrefixField<'a>>{
+        self.iter().find(|f| f.name()==name )
+    }
+
+    fn is_accessible(&self,index:usize)->bool{
+        let header=self.header;
+        self.prefix.is_accessible(index)&&
+        header._prefix_type_field_acc.is_accessible_with_more(
+            header._prefix_type_layout.more_accessible_fields.as_slice(),
+            index,
+        )
+    }
+}
+
+
+impl<'a> fmt::Debug for PrefixFields<'a>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_list()
+         .entries(self.iter())
+         .finish()
+    }
+}
+
+
+/// An iterator over the fields of a prefix-type,returned by `PrefixFields::iter`.
+#[derive(Clone)]
+pub struct PrefixFieldsIter<'a>{
+    fields:PrefixFields<'a>,
+    iter:TLFOSIter,
+    index:usize,
+    // The offset of the end of the previous field.
+    offset:usize,
+}
+
+
+impl<'a> Iterator for PrefixFieldsIter<'a>{
+    type Item=PrefixField<'a>;
+
+    fn next(&mut self)->Option<PrefixField<'a>>{
+        let field=self.iter.next()?;
+        let index=self.index;
+        let field_layout=field.abi_info.get().layout;
+        let offset=round_up(self.offset,field_layout.alignment);
+
+        self.index+=1;
+        self.offset=offset+field_layout.size;
+
+        let fields=&self.fields;
+        Some(PrefixField{
+            header:fields.header,
+            field,
+            index,
+            offset:fields.original_offset+offset,
+            is_prefix_field:index < fields.prefix.first_suffix_field,
+            is_conditional:fields.prefix.conditional_prefix_fields.as_slice()
+                .get(index)
+                .map_or(false,|x| x.is_conditional() ),
+            is_accessible:fields.is_accessible(index),
+        })
+    }
+
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+
+/// A field of a prefix-type,returned by the methods of `PrefixFields`.
+#[derive(Copy,Clone)]
+pub struct PrefixField<'a>{
+    header:&'a WithMetadata_<(),()>,
+    field:TLField,
+    index:usize,
+    // The offset of the field from the start of the `WithMetadata_`.
+    offset:usize,
+    is_prefix_field:bool,
+    is_conditional:bool,
+    is_accessible:bool,
+}
+
+
+impl<'a> PrefixField<'a>{
+    /// The name of the field.
+    pub fn name(&self)->&'static str{
+        self.field.name.as_str()
+    }
+
+    /// The position of the field in the prefix-type.
+    pub fn index(&self)->usize{
+        self.index
+    }
+
+    /// The type layout of the field.
+    pub fn field(&self)->TLField{
+        self.field
+    }
+
+    /// The `AbiInfo` of the type of the field.
+    pub fn abi_info(&self)->&'static AbiInfo{
+        self.field.abi_info.get()
+    }
+
+    /// The layout of the type of the field.
+    pub fn layout(&self)->&'static TypeLayout{
+        self.abi_info().layout
+    }
+
+    /// Whether the field is at or before the one with the
+    /// `#[sabi(last_prefix_field)]` attribute.
+    pub fn is_prefix_field(&self)->bool{
+        self.is_prefix_field
+    }
+
+    /// Whether the field is a prefix field that is only accessible conditionally,
+    /// with the `#[sabi(accessible_if=" expression ")]` attribute.
+    pub fn is_conditional(&self)->bool{
+        self.is_conditional
+    }
+
+    /// Whether the field can be read,
+    /// which requires that it be accessible both in the `<prefix_struct>` type
+    /// this was constructed from,and in the dynamic library/executable
+    /// that constructed the prefix-type.
+    pub fn is_accessible(&self)->bool{
+        self.is_accessible
+    }
+
+    /// Gets a type-erased reference to the field,
+    /// returning None if the field is not accessible.
+    pub fn get(&self)->Option<&'a ErasedObject>{
+        if !self.is_accessible {
+            return None;
+        }
+        unsafe{
+            let ptr=(self.header as *const WithMetadata_<(),()> as *const u8)
+                .add(self.offset);
+            Some(&*(ptr as *const ErasedObject))
+        }
+    }
+
+    /// Gets a reference to the field,
+    /// returning None if the field is not accessible or its type is not `T`.
+    pub fn get_as<T>(&self)->Option<&'a T>
+    where
+        T:StableAbi+'static,
+    {
+        if self.abi_info().get_utypeid() != T::ABI_INFO.get().get_utypeid() {
+            return None;
+        }
+        debug_assert_eq!(mem::size_of::<T>(),self.layout().size);
+
+        self.get().map(|x| unsafe{ &*(x as *const ErasedObject as *const T) } )
+    }
+}
+
+
+impl<'a> fmt::Debug for PrefixField<'a>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_struct("PrefixField")
+         .field("name",&self.name())
+         .field("index",&self.index)
+         .field("type",&self.layout().full_type)
+         .field("is_prefix_field",&self.is_prefix_field)
+         .field("is_conditional",&self.is_conditional)
+         .field("is_accessible",&self.is_accessible)
+         .finish()
+    }
+}
+
+
+fn round_up(offset:usize,alignment:usize)->usize{
+    (offset+alignment-1)/alignment*alignment
+}
@@ -0,0 +1,228 @@
+/*!
+Tests for runtime reflection on the fields of prefix-types.
+*/
+
+
+use crate::{
+    marker_type::UnsafeIgnoredType,
+    prefix_type::{PrefixTypeTrait,WithMetadata},
+    reflection::PrefixFields,
+    std_types::{RString,StaticStr},
+    utils::transmute_reference,
+};
+
+
+mod prefix0 {
+    use crate::std_types::StaticStr;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        pub field0: u8,
+        #[sabi(last_prefix_field)]
+        pub field1: StaticStr,
+    }
+}
+
+#[allow(dead_code)]
+mod prefix1 {
+    use crate::std_types::StaticStr;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        pub field0: u8,
+        #[sabi(last_prefix_field)]
+        pub field1: StaticStr,
+        pub field2: u16,
+        pub field3: u64,
+    }
+}
+
+#[allow(dead_code)]
+mod prefix_aligned {
+    #[repr(C,align(16))]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Prefix")))]
+    pub struct PrefixVal {
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        pub field1: u32,
+    }
+}
+
+
+pub trait EnabledFields{
+    const ENABLE_FIELD_0:bool;
+    const ENABLE_FIELD_1:bool;
+}
+
+pub struct Field0Disabled;
+
+impl EnabledFields for Field0Disabled{
+    const ENABLE_FIELD_0:bool=false;
+    const ENABLE_FIELD_1:bool=true;
+}
+
+#[allow(dead_code)]
+mod cond_fields {
+    use crate::marker_type::UnsafeIgnoredType;
+    use super::EnabledFields;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(
+        kind(Prefix(prefix_struct="Prefix")),
+        prefix_bound="C:EnabledFields",
+        unsafe_unconstrained(C),
+    )]
+    pub struct PrefixVal<C> {
+        pub _marker:UnsafeIgnoredType<C>,
+        #[sabi(accessible_if=" <C as EnabledFields>::ENABLE_FIELD_0 ")]
+        #[sabi(last_prefix_field)]
+        pub field0: u8,
+        #[sabi(accessible_if=" <C as EnabledFields>::ENABLE_FIELD_1 ")]
+        pub field1: u32,
+    }
+}
+
+
+const PREFIX0:&WithMetadata<prefix0::PrefixVal>=&WithMetadata::new(
+    PrefixTypeTrait::METADATA,
+    prefix0::PrefixVal{
+        field0:3,
+        field1:StaticStr::new("hello"),
+    },
+);
+
+const PREFIX1:&WithMetadata<prefix1::PrefixVal>=&WithMetadata::new(
+    PrefixTypeTrait::METADATA,
+    prefix1::PrefixVal{
+        field0:5,
+        field1:StaticStr::new("world"),
+        field2:8,
+        field3:13,
+    },
+);
+
+
+#[test]
+fn iterate_fields(){
+    let fields=PrefixFields::new(PREFIX1.as_prefix());
+
+    assert_eq!(fields.field_count(),4);
+    assert_eq!(fields.layout().name.as_str(),"PrefixVal");
+    assert_eq!(fields.prefix_layout().first_suffix_field,2);
+    assert_eq!(
+        fields.pt_layout().get_field_names_vec(),
+        vec!["field0","field1","field2","field3"],
+    );
+
+    let list=fields.iter().collect::<Vec<_>>();
+    assert_eq!(list.len(),4);
+
+    let names=list.iter().map(|f| f.name() ).collect::<Vec<_>>();
+    assert_eq!(names,vec!["field0","field1","field2","field3"]);
+
+    let type_names=list.iter().map(|f| f.layout().name.as_str() ).collect::<Vec<_>>();
+    assert_eq!(type_names,vec!["u8","StaticStr","u16","u64"]);
+
+    for (i,field) in list.iter().enumerate() {
+        assert_eq!(field.index(),i);
+        assert_eq!(field.is_prefix_field(),i < 2);
+        assert!(!field.is_conditional());
+        assert!(field.is_accessible());
+        assert!(field.get().is_some());
+    }
+
+    assert_eq!(list[0].get_as::<u8>(),Some(&5));
+    assert_eq!(list[1].get_as::<StaticStr>().map(|x| x.as_str() ),Some("world"));
+    assert_eq!(list[2].get_as::<u16>(),Some(&8));
+    assert_eq!(list[3].get_as::<u64>(),Some(&13));
+}
+
+
+#[test]
+fn get_field_by_name_and_index(){
+    let fields=PrefixFields::new(PREFIX1.as_prefix());
+
+    let field2=fields.field_by_name("field2").unwrap();
+    assert_eq!(field2.index(),2);
+    assert_eq!(field2.get_as::<u16>(),Some(&8));
+
+    let field3=fields.field(3).unwrap();
+    assert_eq!(field3.name(),"field3");
+    assert_eq!(field3.get_as::<u64>(),Some(&13));
+
+    assert!(fields.field_by_name("field4").is_none());
+    assert!(fields.field(4).is_none());
+}
+
+
+#[test]
+fn get_as_wrong_type(){
+    let fields=PrefixFields::new(PREFIX1.as_prefix());
+
+    let field3=fields.field(3).unwrap();
+    assert_eq!(field3.get_as::<u32>(),None);
+    assert_eq!(field3.get_as::<i64>(),None);
+    assert_eq!(field3.get_as::<RString>(),None);
+}
+
+
+#[test]
+fn fields_missing_in_older_version(){
+    let prefix:&prefix1::Prefix=unsafe{ transmute_reference(PREFIX0.as_prefix()) };
+    let fields=PrefixFields::new(prefix);
+
+    assert_eq!(fields.field_count(),4);
+    assert_eq!(fields.pt_layout().get_field_names_vec(),vec!["field0","field1"]);
+
+    let accessible=fields.iter().map(|f| f.is_accessible() ).collect::<Vec<_>>();
+    assert_eq!(accessible,vec![true,true,false,false]);
+
+    assert_eq!(fields.field(0).unwrap().get_as::<u8>(),Some(&3));
+    assert_eq!(
+        fields.field(1).unwrap().get_as::<StaticStr>().map(|x| x.as_str() ),
+        Some("hello"),
+    );
+    assert!(fields.field(2).unwrap().get().is_none());
+    assert_eq!(fields.field(3).unwrap().get_as::<u64>(),None);
+}
+
+
+#[test]
+fn overaligned_prefix_type(){
+    let value:&WithMetadata<prefix_aligned::PrefixVal>=&WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        prefix_aligned::PrefixVal{ field0:21, field1:34 },
+    );
+    let fields=PrefixFields::new(value.as_prefix());
+
+    assert_eq!(fields.field(0).unwrap().get_as::<u8>(),Some(&21));
+    assert_eq!(fields.field(1).unwrap().get_as::<u32>(),Some(&34));
+}
+
+
+#[test]
+fn conditional_fields(){
+    let value:&WithMetadata<cond_fields::PrefixVal<Field0Disabled>>=&WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        cond_fields::PrefixVal{ _marker:UnsafeIgnoredType::DEFAULT, field0:55, field1:89 },
+    );
+    let fields=PrefixFields::new(value.as_prefix());
+
+    let field0=fields.field_by_name("field0").unwrap();
+    assert!(field0.is_prefix_field());
+    assert!(field0.is_conditional());
+    assert!(!field0.is_accessible());
+    assert_eq!(field0.get_as::<u8>(),None);
+
+    let field1=fields.field_by_name("field1").unwrap();
+    assert!(!field1.is_prefix_field());
+    assert!(!field1.is_conditional());
+    assert!(field1.is_accessible());
+    assert_eq!(field1.get_as::<u32>(),Some(&89));
+}
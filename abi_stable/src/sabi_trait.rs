@@ -17,24 +17,32 @@ pub mod reexports{
             erased_types::{
                 DynTrait,
                 GetVtable,
-                traits::InterfaceFor,
+                traits::{InterfaceFor,DeserializeDyn},
             },
             marker_type::{UnsafeIgnoredType,SyncSend,UnsyncUnsend,UnsyncSend,SyncUnsend},
             pointer_trait::{TransmuteElement,OwnedPointer},
             prefix_type::{PrefixTypeTrait,WithMetadata},
             traits::IntoInner,
-            sabi_types::{StaticRef,MovePtr},
+            sabi_types::{StaticRef,MovePtr,RFn,RFnMut,RFnOnce},
             sabi_trait::{
                 robject::{
                     RObject,
                 },
                 vtable::{GetVTable,RObjectVtable,GetRObjectVTable},
-                for_generated_code::{sabi_from_ref,sabi_from_mut},
+                upcasting::{
+                    SabiTraitInterface,SabiTraitObject,GetSabiTraitVTable,UpcastTo,
+                },
+                for_generated_code::{
+                    sabi_from_ref,sabi_from_mut,sabi_erased_box,sabi_deserialize_from_proxy,
+                },
             },
-            std_types::RBox,
+            std_types::{RBox,Tuple1,Tuple2,Tuple3,Tuple4},
             utils::{transmute_reference,transmute_mut_reference,take_manuallydrop},
         };
 
+        #[cfg(any(rust_1_36,feature="rust_1_36"))]
+        pub use abi_stable::std_types::{RFuture,UnsyncRFuture};
+
         pub use core_extensions::{
             utils::transmute_ignore_size,
             TypeIdentity,
@@ -50,7 +58,7 @@ pub mod reexports{
 
 /// A prelude for modules using `#[sabi_trait]` generated traits/trait objects.
 pub mod prelude{
-    pub use crate::type_level::unerasability::{TU_Unerasable,TU_Opaque};
+    pub use crate::type_level::unerasability::{TU_Unerasable,TU_StableUnerasable,TU_Opaque};
 }
 
 #[doc(hidden)]
@@ -71,6 +79,8 @@ pub mod robject;
 #[doc(hidden)]
 pub mod vtable;
 
+pub mod upcasting;
+
 #[cfg(all(test,not(feature="only_new_tests")))]
 pub mod tests;
 
@@ -1,5 +1,11 @@
 use super::*;
 
+use core_extensions::SelfOps;
+
+use serde::{de,Deserialize,Deserializer};
+
+use crate::erased_types::DeserializeDyn;
+
 /**
 Converts a `&PhantomData<_Self>` to `&_Self`,and calls a function with that reference.
 
@@ -40,3 +46,35 @@ where
 }
 
 
+
+
+/**
+Boxes `value`,erasing its type.
+
+This is used to return `Self` from the static methods stored in the 
+class vtable of `#[sabi_trait]` traits.
+*/
+#[inline]
+pub fn sabi_erased_box<_Self>(value:_Self)->RBox<()>{
+    unsafe{
+        RBox::new(value).transmute_element(<()>::T)
+    }
+}
+
+
+
+/**
+Deserializes `T` by first deserializing the proxy type of the `I` interface,
+then converting it into `T` with `DeserializeDyn::deserialize_dyn`.
+
+This is used by the `Deserialize` impl of `#[sabi_trait]` trait objects.
+*/
+pub fn sabi_deserialize_from_proxy<'de,I,T,D>(deserializer:D)->Result<T,D::Error>
+where
+    I:DeserializeDyn<'de,T>,
+    I::Proxy:Deserialize<'de>,
+    D:Deserializer<'de>,
+{
+    let proxy=<I::Proxy as Deserialize<'de>>::deserialize(deserializer)?;
+    I::deserialize_dyn(proxy).map_err(de::Error::custom)
+}
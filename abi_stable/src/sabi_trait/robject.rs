@@ -10,9 +10,15 @@ use std::{
 
 use core_extensions::SelfOps;
 
+use serde::{ser,Serialize,Serializer};
+
 use crate::{
-    abi_stability::SharedStableAbi,
-    erased_types::c_functions::adapt_std_fmt,
+    abi_stability::{SharedStableAbi,StableAbi,StableTypeId},
+    erased_types::{
+        c_functions::adapt_std_fmt,
+        traits::GetSerializeProxyType,
+    },
+    library::LibraryKeepAlive,
     sabi_types::MaybeCmp,
     std_types::{RBox,UTypeId},
     pointer_trait::{
@@ -20,7 +26,7 @@ use crate::{
         GetPointerKind,PK_SmartPointer,PK_Reference,
     },
     type_level::{
-        unerasability::{TU_Unerasable,TU_Opaque},
+        unerasability::{TU_Unerasable,TU_StableUnerasable,TU_Opaque},
         impl_enum::{Implemented,Unimplemented},
         trait_marker,
     },
@@ -51,6 +57,14 @@ you can call one of these methods:
 - from_value_unerasable
     Can be constructed from the value directly.Requires a `'static` value.
 
+- from_ptr_stable_unerasable:
+    Can be constructed from a pointer of a value.
+    Requires a `'static` value that implements `StableAbi`.
+
+- from_value_stable_unerasable
+    Can be constructed from the value directly.
+    Requires a `'static` value that implements `StableAbi`.
+
 # Trait object
 
 `RObject<'borrow,Pointer<()>,Interface,VTable>` 
@@ -67,6 +81,10 @@ These are the traits:
 
 - Clone
 
+- Serialize:
+    first calls `SerializeImplType::serialize_impl` to convert the value into
+    the proxy type of the interface,then serializes the proxy.
+
 # Deconstruction
 
 `RObject<_>` can then be unwrapped into a concrete type,
@@ -79,6 +97,17 @@ using these (fallible) conversion methods:
 
 - sabi_as_any_unerased_mut:Unwraps into a `&mut T`.Requires `T:'static`.
 
+If it was constructed with one of the `*_stable_unerasable` methods,
+it can also be unwrapped in other dynamic libraries/executables 
+that use the same version of the crate that declared the concrete type,
+using these (fallible) conversion methods:
+
+- sabi_into_stable_unerased:Unwraps into a pointer to `T`.Requires `T:StableAbi+'static`.
+
+- sabi_as_stable_unerased:Unwraps into a `&T`.Requires `T:StableAbi+'static`.
+
+- sabi_as_stable_unerased_mut:Unwraps into a `&mut T`.Requires `T:StableAbi+'static`.
+
 `RObject` can only be converted back if it was created 
 using a `RObject::*_unerased` function.
 
@@ -132,7 +161,7 @@ macro_rules! impl_from_ptr_method {
                     ManuallyDrop::new(ptr)
                 },
                 _marker:PhantomData,
-            }
+            }.sabi_retain_library()
         }
 
     )
@@ -162,6 +191,20 @@ the `sabi_*_unerased` methods (RObject reserves `sabi` as a prefix for its own m
         method_name=from_ptr_unerasable,
         requires_any=TU_Unerasable 
     }
+    impl_from_ptr_method!{
+        /**
+Creates a trait object from a pointer to a type that must implement the 
+trait that `I` requires.
+
+The constructed trait object can be converted back to the original type with 
+the `sabi_*_stable_unerased` methods,
+in any dynamic library/executable that uses the same version of 
+the crate that declared the type.
+
+        */
+        method_name=from_ptr_stable_unerasable,
+        requires_any=TU_StableUnerasable 
+    }
 }
 
 impl<I,V> RObject<'_,RBox<()>,I,V>{
@@ -197,6 +240,25 @@ the `sabi_*_unerased` methods (RObject reserves `sabi` as a prefix for its own m
     {
         Self::from_ptr_unerasable::<_,Params>(RBox::new(value))
     }
+
+/**
+Creates a trait object from a type that must implement the trait that `I` requires.
+
+The constructed trait object can be converted back to the original type with 
+the `sabi_*_stable_unerased` methods,
+in any dynamic library/executable that uses the same version of 
+the crate that declared the type.
+*/
+    pub fn from_value_stable_unerasable<'lt,T,Params>(
+        value:T,
+    )-> RObject<'lt,RBox<()>,I,V>
+    where 
+        T:'lt,
+        I:GetVTable<TU_StableUnerasable,T,RBox<()>,RBox<T>,Params,VTable=V>,
+        I:GetRObjectVTable<TU_StableUnerasable,T,RBox<()>,RBox<T>>
+    {
+        Self::from_ptr_stable_unerasable::<_,Params>(RBox::new(value))
+    }
 }
 
 
@@ -224,7 +286,7 @@ where
             is_reborrowed:self.is_reborrowed,
             ptr:ManuallyDrop::new(ptr),
             _marker:PhantomData,
-        }
+        }.sabi_retain_library()
     }
 }
 
@@ -293,6 +355,32 @@ where
 }
 
 
+/**
+First it converts an `RObject<_>` into a proxy type by using
+`<ConcreteType as SerializeImplType>::serialize_impl`,
+then it serializes the proxy type.
+*/
+impl<'lt,P,I,V> Serialize for RObject<'lt,P,I,V>
+where
+    P: Deref<Target=()>,
+    I: InterfaceType<Serialize = Implemented<trait_marker::Serialize>>,
+    I: GetSerializeProxyType,
+    I::ProxyType: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        unsafe{
+            self.sabi_robject_vtable().sabi_serialize()(self.sabi_erased_ref())
+                .into_result()
+                .map_err(ser::Error::custom)?
+                .serialize(serializer)
+        }
+    }
+}
+
+
 unsafe impl<'lt,P,I,V> Send for RObject<'lt,P,I,V> 
 where 
     I:InterfaceType<Send = Implemented<trait_marker::Send>>,
@@ -342,7 +430,7 @@ These are the requirements for the caller:
             is_reborrowed:false,
             ptr:ManuallyDrop::new( ptr.transmute_element(<()>::T) ),
             _marker:PhantomData,
-        }
+        }.sabi_retain_library()
     }
 }
 
@@ -363,6 +451,30 @@ impl<'lt,P,I,V> RObject<'lt,P,I,V>{
                 robject:(),
                 expected_typeid,
                 actual_typeid,
+                expected_stable_id:MaybeCmp::Nothing,
+                actual_stable_id:MaybeCmp::Nothing,
+            })
+        }
+    }
+
+    /// The StableTypeId in the vtable has to be the same as the one for T,
+    /// otherwise it was not created from that T with a `*_stable_unerasable` method.
+    fn sabi_check_same_stable_id<T>(&self) -> Result<(), UneraseError<()>>
+    where
+        T:StableAbi+'static,
+    {
+        let vtable=self.sabi_robject_vtable();
+        let expected_stable_id=vtable._sabi_stable_type_id().get();
+        let actual_stable_id=StableTypeId::new::<T>();
+        if expected_stable_id == MaybeCmp::Just(actual_stable_id) {
+            Ok(())
+        } else {
+            Err(UneraseError {
+                robject:(),
+                expected_typeid:vtable._sabi_type_id().get(),
+                actual_typeid:UTypeId::new::<T>(),
+                expected_stable_id,
+                actual_stable_id:MaybeCmp::Just(actual_stable_id),
             })
         }
     }
@@ -442,6 +554,81 @@ impl<'lt,P,I,V> RObject<'lt,P,I,V>{
         }
     }
 
+    /// Attempts to unerase this trait object into the pointer it was constructed with,
+    /// which can be done in any dynamic library/executable that uses the same version 
+    /// of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - The RObject was not constructed using a `*_stable_unerasable` method.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or it comes from a different version of the crate that declared it.
+    ///
+    pub fn sabi_into_stable_unerased<T>(self) -> Result<P::TransmutedPtr, UneraseError<Self>>
+    where
+        T:StableAbi+'static,
+        P: Deref<Target=()>+TransmuteElement<T>,
+    {
+        check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+        unsafe {
+            let this=ManuallyDrop::new(self);
+            Ok(ptr::read(&*this.ptr).transmute_element(T::T)) 
+        }
+    }
+
+    /// Attempts to unerase this trait object into a reference of 
+    /// the value was constructed with,
+    /// which can be done in any dynamic library/executable that uses the same version 
+    /// of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - The RObject was not constructed using a `*_stable_unerasable` method.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or it comes from a different version of the crate that declared it.
+    ///
+    pub fn sabi_as_stable_unerased<T>(&self) -> Result<&T, UneraseError<&Self>>
+    where
+        T:StableAbi+'static,
+        P:Deref<Target=()>+TransmuteElement<T>,
+    {
+        check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+        unsafe { 
+            Ok(transmute_reference::<(),T>(&**self.ptr))
+        }
+    }
+
+    /// Attempts to unerase this trait object into a mutable reference of 
+    /// the value was constructed with,
+    /// which can be done in any dynamic library/executable that uses the same version 
+    /// of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - The RObject was not constructed using a `*_stable_unerasable` method.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or it comes from a different version of the crate that declared it.
+    ///
+    pub fn sabi_as_stable_unerased_mut<T>(&mut self) -> Result<&mut T, UneraseError<&mut Self>>
+    where
+        T:StableAbi+'static,
+        P:DerefMut<Target=()>+TransmuteElement<T>,
+    {
+        check_unerased!(self,self.sabi_check_same_stable_id::<T>());
+        unsafe { 
+            Ok(transmute_mut_reference::<(),T>(&mut **self.ptr))
+        }
+    }
+
 }
 
 
@@ -525,12 +712,49 @@ impl<'lt,P,I,V> RObject<'lt,P,I,V>{
         }
     }
 
+    /// Gets the handle that keeps the library the vtable comes from loaded,
+    /// returning None for reborrowed `RObject`s,which don't keep it loaded.
+    fn sabi_keep_alive(&self)->Option<&'static LibraryKeepAlive>{
+        if self.is_reborrowed {
+            None
+        }else{
+            self.sabi_robject_vtable()._sabi_keep_alive()()
+        }
+    }
+
+    /// Keeps the library that the vtable comes from loaded until this is dropped.
+    fn sabi_retain_library(self)->Self{
+        if let Some(keep_alive)=self.sabi_keep_alive() {
+            unsafe{ keep_alive.retain(); }
+        }
+        self
+    }
+
     #[inline]
     fn sabi_into_erased_ptr(self)->ManuallyDrop<P>{
         let mut __this= ManuallyDrop::new(self);
         unsafe{ ptr::read(&mut __this.ptr) }
     }
 
+    /// Converts this into an RObject of a supertrait,which uses `vtable` as its vtable.
+    ///
+    /// This is mostly intended to be called by `#[sabi_trait]` derived trait objects.
+    ///
+    /// # Safety
+    ///
+    /// `vtable` must be the vtable of the supertrait,
+    /// constructed for the same type and pointer as the vtable of this RObject.
+    #[inline]
+    pub unsafe fn sabi_into_supertrait<I2,V2>(self,vtable:StaticRef<V2>)->RObject<'lt,P,I2,V2>{
+        let is_reborrowed=self.is_reborrowed;
+        RObject{
+            vtable,
+            is_reborrowed,
+            ptr:self.sabi_into_erased_ptr(),
+            _marker:PhantomData,
+        }
+    }
+
     #[inline]
     pub fn sabi_erased_ref(&self)->&ErasedObject<()>
     where
@@ -553,7 +777,12 @@ impl<'lt,P,I,V> RObject<'lt,P,I,V>{
         P: OwnedPointer<Target=()>,
         F:FnOnce(MovePtr<'_,()>)->R,
     {
-        OwnedPointer::with_move_ptr(self.sabi_into_erased_ptr(),f)
+        let keep_alive=self.sabi_keep_alive();
+        let ret=OwnedPointer::with_move_ptr(self.sabi_into_erased_ptr(),f);
+        if let Some(keep_alive)=keep_alive {
+            unsafe{ keep_alive.release(); }
+        }
+        ret
     }
 }
 
@@ -562,9 +791,15 @@ impl<P,I,V> Drop for RObject<'_,P,I,V>{
         // This condition is necessary because if the RObject was reborrowed,
         // the destructor function would take a different pointer type.
         if !self.is_reborrowed{
+            let keep_alive=self.sabi_keep_alive();
             let destructor=self.sabi_robject_vtable()._sabi_drop();
             unsafe{
                 destructor(&mut self.ptr);
+                // This must be done after the destructor returns,
+                // since it can unload the library the destructor is in.
+                if let Some(keep_alive)=keep_alive {
+                    keep_alive.release();
+                }
             }
         }
     }
@@ -583,6 +818,8 @@ pub struct UneraseError<T> {
     robject:T,
     expected_typeid:MaybeCmp<UTypeId>,
     actual_typeid:UTypeId,
+    expected_stable_id:MaybeCmp<StableTypeId>,
+    actual_stable_id:MaybeCmp<StableTypeId>,
 }
 
 
@@ -594,6 +831,8 @@ impl<T> UneraseError<T>{
             robject        :f(self.robject),
             expected_typeid:self.expected_typeid,
             actual_typeid  :self.actual_typeid,
+            expected_stable_id:self.expected_stable_id,
+            actual_stable_id  :self.actual_stable_id,
         }
     }
 
@@ -611,6 +850,8 @@ impl<D> fmt::Debug for UneraseError<D>{
             .field("dyn_trait",&"<not shown>")
             .field("expected_typeid",&self.expected_typeid)
             .field("actual_typeid",&self.actual_typeid)
+            .field("expected_stable_id",&self.expected_stable_id)
+            .field("actual_stable_id",&self.actual_stable_id)
             .finish()
     }
 }
@@ -16,8 +16,12 @@ use std::{
 
 
 
+use serde::Serialize;
+
 use crate::{
     sabi_trait,
+    erased_types::{DeserializeDyn,SerializeProxyType,SerializeImplType},
+    std_types::{RBox,RBoxError,RStr,RString},
     type_level::unerasability::TU_Unerasable,
 };
 
@@ -106,73 +110,111 @@ pub mod only_debug{
     }
 }
 
-// pub mod only_serialize{
-//     use super::*;
-
-//     #[sabi_trait]
-//     #[sabi(use_dyntrait)]
-//     pub trait Trait:Serialize{
-//         fn method(&self){}
-//     }
-
-//     #[derive(::serde::Serialize)]
-//     pub struct Struct;
-
-//     impl Trait for Struct{}
-
-//     impl SerializeImplType for Struct {
-//         fn serialize_impl<'a>(&'a self) -> Result<RCow<'a, str>, RBoxError>{
-//             Ok(RCow::from("Struct"))
-//         }
-//     }
-
-//     fn assert_bound<T>(_:&T)
-//     where
-//         T:serde::Serialize
-//     {}
-
-//     fn test_constructible(){
-//         let object=Trait_TO::from_value(Struct,TU_Unerasable);
-//         object.method();
-//         assert_bound(&object);
-//     }
-// }
-
-// pub mod only_deserialize_a{
-//     use super::*;
-//     use serde::Deserialize;
-
-//     #[sabi_trait]
-//     #[sabi(use_dyntrait)]
-//     //#[sabi(debug_print_trait)]
-//     pub trait Trait: for<'a> Deserialize<'a> {
-//         fn method(&self){}
-//     }
-
-//     impl<'a> DeserializeOwnedInterface<'a> for Trait_Interface{
-//         type Deserialized=Trait_Backend<'a,RBox<()>>;
-
-//         fn deserialize_impl(s: RStr<'_>) -> Result<Self::Deserialized, RBoxError>{
-//             Ok(DynTrait::from_any_value(Struct,Trait_Interface::NEW))
-//         }
-//     }
-
-//     #[derive(Deserialize)]
-//     pub struct Struct;
-
-//     impl Trait for Struct{}
-
-//     fn assert_bound<T>(_:&T)
-//     where
-//         T:for<'a>Deserialize<'a>
-//     {}
-
-//     fn test_constructible(){
-//         let object=Trait_TO::from_value(Struct,TU_Unerasable);
-//         object.method();
-//         assert_bound(&object);
-//     }
-// }
+pub mod only_serialize{
+    use super::*;
+
+    #[sabi_trait]
+    pub trait Trait:Serialize{
+        fn method(&self){}
+    }
+
+    #[derive(::serde::Serialize)]
+    pub struct Struct;
+
+    impl Trait for Struct{}
+
+    impl SerializeImplType for Struct {
+        type Interface=Trait_Interface;
+
+        fn serialize_impl(&self) -> Result<RString, RBoxError>{
+            Ok(RString::from("Struct"))
+        }
+    }
+
+    impl SerializeProxyType for Trait_Interface{
+        type Proxy=RString;
+    }
+
+    fn assert_bound<T>(_:&T)
+    where
+        T:serde::Serialize
+    {}
+
+    fn test_constructible(){
+        let object=Trait_TO::from_value(Struct,TU_Unerasable);
+        object.method();
+        assert_bound(&object);
+    }
+}
+
+pub mod only_deserialize_a{
+    use super::*;
+    use serde::Deserialize;
+
+    #[sabi_trait]
+    pub trait Trait: for<'a> Deserialize<'a> {
+        fn method(&self){}
+    }
+
+    impl<'a> DeserializeDyn<'a,Trait_TO<'static,RBox<()>>> for Trait_Interface{
+        type Proxy=RString;
+
+        fn deserialize_dyn(_:RString) -> Result<Trait_TO<'static,RBox<()>>, RBoxError>{
+            Ok(Trait_TO::from_value(Struct,TU_Unerasable))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Struct;
+
+    impl Trait for Struct{}
+
+    fn assert_bound<T>(_:&T)
+    where
+        T:for<'a>Deserialize<'a>
+    {}
+
+    fn test_constructible(){
+        let object=Trait_TO::from_value(Struct,TU_Unerasable);
+        object.method();
+        assert_bound(&object);
+    }
+}
+
+pub mod only_deserialize_b{
+    use super::*;
+    use serde::Deserialize;
+
+    #[sabi_trait]
+    #[sabi(use_dyntrait)]
+    pub trait Trait<'de>: Deserialize<'de> {
+        fn method(&self){}
+    }
+
+    impl<'de> DeserializeDyn<'de,Trait_TO<'de,'static,RBox<()>>> for Trait_Interface<'de>{
+        type Proxy=RStr<'de>;
+
+        fn deserialize_dyn(_:RStr<'de>) -> Result<Trait_TO<'de,'static,RBox<()>>, RBoxError>{
+            Ok(Trait_TO::from_value(Struct,TU_Unerasable))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Struct;
+
+    impl<'de> Trait<'de> for Struct{}
+
+    fn assert_bound<'de,T>(_:&T)
+    where
+        T:Deserialize<'de>
+    {}
+
+    fn test_constructible<'de>(){
+        let object=Trait_TO::<'de,'static,_>::from_value(Struct,TU_Unerasable);
+        object.method();
+        assert_bound(&object);
+    }
+}
 
 pub mod only_partial_eq{
     use super::*;
@@ -100,6 +100,49 @@ fn downcasting_tests(){
 
 
 
+#[repr(C)]
+#[derive(StableAbi,Debug,Clone,PartialEq)]
+struct StablePoint{
+    x:u32,
+    y:u32,
+}
+
+impl method_no_default::Trait for StablePoint{
+    fn apply(&self,l:u32,r:u32)->u32{
+        l*self.x+r*self.y
+    }
+}
+
+#[test]
+fn stable_unerasing_tests(){
+    use self::method_no_default::*;
+
+    let point=StablePoint{x:3,y:5};
+
+    let mut object=Trait_TO::from_value(point.clone(),TU_StableUnerasable);
+    assert_eq!(object.apply(2,7),41);
+
+    assert_eq!(object.obj.sabi_as_stable_unerased::<StablePoint>().unwrap(),&point);
+    assert_eq!(object.obj.sabi_as_stable_unerased_mut::<StablePoint>().unwrap(),&mut point.clone());
+    assert!(object.obj.sabi_as_stable_unerased::<u32>().is_err());
+    assert_eq!(object.obj.sabi_as_any_unerased::<StablePoint>().unwrap(),&point);
+    assert_eq!(
+        object.obj.sabi_into_stable_unerased::<StablePoint>().unwrap(),
+        RBox::new(point.clone())
+    );
+
+    let object=Trait_TO::from_ptr(RBox::new(point.clone()),TU_StableUnerasable);
+    assert_eq!(object.obj.sabi_as_stable_unerased::<StablePoint>().unwrap(),&point);
+
+    for object in vec![
+        Trait_TO::from_value(point.clone(),TU_Unerasable),
+        Trait_TO::from_value(point.clone(),TU_Opaque),
+    ] {
+        assert!(object.obj.sabi_as_stable_unerased::<StablePoint>().is_err());
+    }
+}
+
+
 #[sabi_trait]
 trait DefaultMethodPair{
     fn foo(&self,x:u32)->u32{
@@ -146,4 +189,698 @@ fn default_methods(){
     assert_eq!(a.foo(1), 101);
     assert_eq!(b.foo(1), 211);
     assert_eq!(c.foo(1), 331);
-}
\ No newline at end of file
+}
+
+
+mod user_supertraits{
+    use super::*;
+
+    use crate::std_types::RString;
+
+    #[sabi_trait]
+    pub trait Named{
+        fn name(&self)->&RString;
+
+        fn greeting(&self)->RString{
+            format!("hello {}",self.name()).into()
+        }
+    }
+
+    #[sabi_trait]
+    pub trait IntoName{
+        fn into_name(self)->RString;
+    }
+
+    #[sabi_trait]
+    pub trait Counter<T>{
+        fn add(&mut self,n:T)->T;
+    }
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Named,Counter,IntoName))]
+    pub trait Plugin:Named+Counter<u32>+IntoName{
+        fn describe(&self)->RString{
+            format!("{}:{}",self.name(),self.greeting()).into()
+        }
+    }
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Plugin,Named,Counter,IntoName))]
+    pub trait SubPlugin:Plugin+Named+Counter<u32>+IntoName{}
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Named))]
+    pub trait Described:Named{
+        fn describe(&self)->RString{
+            format!("described {}",self.name()).into()
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct Thing{
+        pub name:RString,
+        pub count:u32,
+    }
+
+    impl Named for Thing{
+        fn name(&self)->&RString{
+            &self.name
+        }
+    }
+
+    impl IntoName for Thing{
+        fn into_name(self)->RString{
+            self.name
+        }
+    }
+
+    impl Counter<u32> for Thing{
+        fn add(&mut self,n:u32)->u32{
+            self.count+=n;
+            self.count
+        }
+    }
+
+    impl Plugin for Thing{}
+
+    impl SubPlugin for Thing{}
+
+    impl Described for Thing{}
+
+    pub fn new_thing()->Thing{
+        Thing{name:"thing".into(),count:0}
+    }
+}
+
+
+#[test]
+fn user_supertrait_methods(){
+    use self::user_supertraits::*;
+
+    let mut object=Plugin_TO::from_value(new_thing(),TU_Opaque);
+
+    assert_eq!(Named::name(&object).as_str(),"thing");
+    assert_eq!(Named::greeting(&object).as_str(),"hello thing");
+    assert_eq!(object.describe().as_str(),"thing:hello thing");
+    assert_eq!(Counter::add(&mut object,3),3);
+    assert_eq!(Counter::add(&mut object,5),8);
+    assert_eq!(IntoName::into_name(object).as_str(),"thing");
+}
+
+
+#[test]
+fn user_supertrait_upcasting(){
+    use self::user_supertraits::*;
+
+    {
+        let mut object=Plugin_TO::from_value(new_thing(),TU_Opaque);
+        assert_eq!(Counter::add(&mut object,3),3);
+
+        let mut counter:Counter_TO<'_,RBox<()>,u32>=object.into();
+        assert_eq!(counter.add(4),7);
+    }
+    {
+        let object=Plugin_TO::from_value(new_thing(),TU_Opaque);
+        let into_name=IntoName_TO::from(object);
+        assert_eq!(into_name.into_name().as_str(),"thing");
+    }
+    {
+        let mut object=SubPlugin_TO::from_value(new_thing(),TU_Opaque);
+        assert_eq!(Counter::add(&mut object,2),2);
+        assert_eq!(Plugin::describe(&object).as_str(),"thing:hello thing");
+
+        let mut plugin:Plugin_TO<'_,RBox<()>>=object.into();
+        assert_eq!(Counter::add(&mut plugin,2),4);
+
+        let named:Named_TO<'_,RBox<()>>=plugin.into();
+        assert_eq!(named.name().as_str(),"thing");
+        assert_eq!(named.greeting().as_str(),"hello thing");
+    }
+    {
+        let thing=new_thing();
+        let object=Described_TO::from_ptr(&thing,TU_Opaque);
+        assert_eq!(Named::name(&object).as_str(),"thing");
+        assert_eq!(object.describe().as_str(),"described thing");
+
+        let named:Named_TO<'_,&()>=object.into();
+        assert_eq!(named.greeting().as_str(),"hello thing");
+    }
+}
+
+
+
+mod static_items{
+    use super::*;
+
+    use crate::std_types::{RStr,RString,StaticStr};
+
+    #[sabi_trait]
+    pub trait Factory{
+        const NAME:StaticStr;
+        const MAX:u32=100;
+
+        fn new(initial:u32)->Self;
+
+        fn describe(prefix:RStr<'_>)->RString;
+
+        fn value(&self)->u32;
+
+        fn add(&mut self,n:u32);
+    }
+
+    #[sabi_trait]
+    pub trait Scaled<T>{
+        const FACTOR:u32;
+
+        fn with_value(value:T)->Self;
+
+        fn get(&self)->T;
+    }
+
+    #[sabi_trait]
+    pub trait Named{
+        fn name(&self)->RString;
+    }
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Named))]
+    pub trait NamedFactory:Named{
+        fn with_name(name:RString)->Self;
+    }
+
+    #[derive(Debug,Clone,PartialEq)]
+    pub struct Counter(pub u32);
+
+    pub struct Person(pub RString);
+
+    impl Named for Person{
+        fn name(&self)->RString{
+            self.0.clone()
+        }
+    }
+
+    impl NamedFactory for Person{
+        fn with_name(name:RString)->Self{
+            Person(name)
+        }
+    }
+
+    impl Factory for Counter{
+        const NAME:StaticStr=StaticStr::new("counter");
+
+        fn new(initial:u32)->Self{
+            Counter(initial)
+        }
+
+        fn describe(prefix:RStr<'_>)->RString{
+            format!("{}:counter",prefix).into()
+        }
+
+        fn value(&self)->u32{
+            self.0
+        }
+
+        fn add(&mut self,n:u32){
+            self.0+=n;
+        }
+    }
+
+    impl Scaled<u64> for Counter{
+        const FACTOR:u32=3;
+
+        fn with_value(value:u64)->Self{
+            Counter(value as u32 * Self::FACTOR)
+        }
+
+        fn get(&self)->u64{
+            self.0 as u64
+        }
+    }
+}
+
+
+#[test]
+fn static_items_class(){
+    use self::static_items::*;
+
+    let class=Factory_Class::from_type::<Counter,_>(TU_Opaque);
+    assert_eq!(class.NAME().as_str(),"counter");
+    assert_eq!(class.MAX(),100);
+    assert_eq!(class.describe("a".into()).as_str(),"a:counter");
+
+    let mut object=class.new(5);
+    assert_eq!(object.value(),5);
+    object.add(3);
+    assert_eq!(object.value(),8);
+
+    let named_class=NamedFactory_Class::from_type::<Person,_>(TU_Opaque);
+    let person=named_class.with_name("joe".into());
+    assert_eq!(Named::name(&person).as_str(),"joe");
+    let named:Named_TO<'_,RBox<()>>=person.into();
+    assert_eq!(named.name().as_str(),"joe");
+
+    let generic_class=Scaled_Class::<'_,u64>::from_type::<Counter,_>(TU_Opaque);
+    assert_eq!(generic_class.FACTOR(),3);
+    assert_eq!(generic_class.with_value(7).get(),21);
+}
+
+
+#[test]
+fn static_items_class_from_object(){
+    use self::static_items::*;
+
+    let object=Factory_TO::from_value(Counter(1),TU_Unerasable);
+    let class=object.sabi_class();
+    assert_eq!(class.NAME().as_str(),"counter");
+
+    let other=class.new(10);
+    assert_eq!(other.value(),10);
+    assert_eq!(other.obj.sabi_as_any_unerased::<Counter>().ok(),Some(&Counter(10)));
+
+    let counter=Counter(2);
+    let object=Factory_TO::from_ptr(&counter,TU_Opaque);
+    assert_eq!(object.value(),2);
+    assert_eq!(object.sabi_class().describe("b".into()).as_str(),"b:counter");
+    assert_eq!(object.sabi_class().new(4).value(),4);
+}
+
+
+mod serde_supertraits{
+    use super::*;
+
+    use serde::{Deserialize,Serialize};
+
+    use crate::{
+        erased_types::{DeserializeDyn,SerializeImplType,SerializeProxyType},
+        std_types::{RBoxError,RString},
+    };
+
+    #[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+    pub struct Counter{
+        pub count:u32,
+    }
+
+    fn to_json<T>(value:&T)->Result<RString,RBoxError>
+    where T:Serialize
+    {
+        match serde_json::to_string(value) {
+            Ok(v)=>Ok(v.into()),
+            Err(e)=>Err(RBoxError::new(e)),
+        }
+    }
+
+    fn from_json(s:&str)->Result<Counter,RBoxError>{
+        serde_json::from_str::<Counter>(s).map_err(RBoxError::new)
+    }
+
+
+    #[sabi_trait]
+    pub trait State:Serialize+for<'de> Deserialize<'de>+Debug{
+        fn count(&self)->u32;
+
+        fn increment(&mut self);
+    }
+
+    impl State for Counter{
+        fn count(&self)->u32{
+            self.count
+        }
+        fn increment(&mut self){
+            self.count+=1;
+        }
+    }
+
+    impl SerializeImplType for Counter{
+        type Interface=State_Interface;
+
+        fn serialize_impl(&self)->Result<RString,RBoxError>{
+            to_json(self)
+        }
+    }
+
+    impl SerializeProxyType for State_Interface{
+        type Proxy=RString;
+    }
+
+    impl<'de> DeserializeDyn<'de,State_TO<'static,RBox<()>>> for State_Interface{
+        type Proxy=RString;
+
+        fn deserialize_dyn(s:RString)->Result<State_TO<'static,RBox<()>>,RBoxError>{
+            from_json(&s).map(|x| State_TO::from_value(x,TU_Opaque) )
+        }
+    }
+
+
+    #[sabi_trait]
+    #[sabi(use_dyntrait)]
+    pub trait DynState:Serialize+for<'de> Deserialize<'de>+Debug{
+        fn count(&self)->u32;
+
+        fn increment(&mut self);
+    }
+
+    /// A wrapper,since `Counter` already implements `SerializeImplType` for `State`.
+    #[derive(Debug,Clone,PartialEq,Serialize,Deserialize)]
+    pub struct Wrapper(pub Counter);
+
+    impl DynState for Wrapper{
+        fn count(&self)->u32{
+            self.0.count
+        }
+        fn increment(&mut self){
+            self.0.count+=1;
+        }
+    }
+
+    impl SerializeImplType for Wrapper{
+        type Interface=DynState_Interface;
+
+        fn serialize_impl(&self)->Result<RString,RBoxError>{
+            to_json(&self.0)
+        }
+    }
+
+    impl SerializeProxyType for DynState_Interface{
+        type Proxy=RString;
+    }
+
+    impl<'de> DeserializeDyn<'de,DynState_TO<'static,RBox<()>>> for DynState_Interface{
+        type Proxy=RString;
+
+        fn deserialize_dyn(s:RString)->Result<DynState_TO<'static,RBox<()>>,RBoxError>{
+            from_json(&s).map(|x| DynState_TO::from_value(Wrapper(x),TU_Opaque) )
+        }
+    }
+}
+
+
+#[test]
+fn serde_supertraits_robject(){
+    use self::serde_supertraits::*;
+
+    let mut object=State_TO::from_value(Counter{count:3},TU_Opaque);
+    object.increment();
+
+    let json=serde_json::to_string(&object).unwrap();
+    assert_eq!(json,r#""{\"count\":4}""#);
+
+    let mut deserialized:State_TO<'static,RBox<()>>=serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.count(),4);
+    deserialized.increment();
+    assert_eq!(State::count(&deserialized),5);
+
+    assert!(serde_json::from_str::<State_TO<'static,RBox<()>>>(r#""{}""#).is_err());
+}
+
+
+#[test]
+fn serde_supertraits_dyntrait(){
+    use self::serde_supertraits::*;
+
+    let mut object=DynState_TO::from_value(Wrapper(Counter{count:10}),TU_Opaque);
+    object.increment();
+
+    let json=serde_json::to_string(&object).unwrap();
+    assert_eq!(json,r#""{\"count\":11}""#);
+
+    let mut deserialized:DynState_TO<'static,RBox<()>>=serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.count(),11);
+    deserialized.increment();
+    assert_eq!(DynState::count(&deserialized),12);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+mod closure_params{
+    use super::*;
+
+    use crate::std_types::{RStr,RString,RVec};
+
+    #[sabi_trait]
+    pub trait Container{
+        fn for_each(&self,f:impl FnMut(RStr<'_>));
+
+        /// Visits pairs of elements until `f` returns false,
+        /// returning how many pairs were visited.
+        fn visit_pairs<F:FnMut(&u32,&u32)->bool>(&self,f:F)->usize;
+
+        fn map_all(&mut self,f:&dyn Fn(u32)->u32);
+
+        fn count_matching(&self,f:&mut dyn FnMut(u32)->bool)->usize;
+
+        fn join_with(&self,f:impl Fn(RStr<'_>,RStr<'_>)->RString)->RString;
+
+        fn into_joined(self,f:impl FnOnce(RVec<RString>)->RString)->RString;
+
+        fn first_or_else(&self,f:impl FnOnce()->u32)->u32{
+            f()
+        }
+    }
+
+    #[derive(Debug,Clone,PartialEq)]
+    pub struct Numbers(pub Vec<u32>);
+
+    impl Container for Numbers{
+        fn for_each(&self,mut f:impl FnMut(RStr<'_>)){
+            for x in &self.0 {
+                f(x.to_string().as_str().into());
+            }
+        }
+
+        fn visit_pairs<F:FnMut(&u32,&u32)->bool>(&self,mut f:F)->usize{
+            self.0.windows(2)
+                .take_while(|pair| f(&pair[0],&pair[1]) )
+                .count()
+        }
+
+        fn map_all(&mut self,f:&dyn Fn(u32)->u32){
+            for x in &mut self.0 {
+                *x=f(*x);
+            }
+        }
+
+        fn count_matching(&self,f:&mut dyn FnMut(u32)->bool)->usize{
+            self.0.iter().filter(|&&x| f(x) ).count()
+        }
+
+        fn join_with(&self,f:impl Fn(RStr<'_>,RStr<'_>)->RString)->RString{
+            let mut iter=self.0.iter().map(|x| x.to_string() );
+            let first=iter.next().unwrap_or_default().into();
+            iter.fold(first,|acc:RString,x| f(acc.as_rstr(),x.as_str().into()) )
+        }
+
+        fn into_joined(self,f:impl FnOnce(RVec<RString>)->RString)->RString{
+            f(self.0.iter().map(|x| RString::from(x.to_string()) ).collect())
+        }
+
+        fn first_or_else(&self,f:impl FnOnce()->u32)->u32{
+            self.0.first().cloned().unwrap_or_else(f)
+        }
+    }
+
+    pub struct Empty;
+
+    impl Container for Empty{
+        fn for_each(&self,_:impl FnMut(RStr<'_>)){}
+
+        fn visit_pairs<F:FnMut(&u32,&u32)->bool>(&self,_:F)->usize{
+            0
+        }
+
+        fn map_all(&mut self,_:&dyn Fn(u32)->u32){}
+
+        fn count_matching(&self,_:&mut dyn FnMut(u32)->bool)->usize{
+            0
+        }
+
+        fn join_with(&self,_:impl Fn(RStr<'_>,RStr<'_>)->RString)->RString{
+            RString::new()
+        }
+
+        fn into_joined(self,f:impl FnOnce(RVec<RString>)->RString)->RString{
+            f(RVec::new())
+        }
+    }
+}
+
+
+#[test]
+fn closure_params(){
+    use self::closure_params::*;
+
+    use crate::std_types::RString;
+
+    let mut object=Container_TO::from_value(Numbers(vec![3,5,8,4]),TU_Opaque);
+
+    let mut list=Vec::new();
+    object.for_each(|s| list.push(s.to_string()) );
+    assert_eq!(list,vec!["3","5","8","4"]);
+
+    let mut pairs=Vec::new();
+    let visited=object.visit_pairs(|&l,&r|{
+        pairs.push((l,r));
+        l < r
+    });
+    assert_eq!(visited,2);
+    assert_eq!(pairs,vec![(3,5),(5,8),(8,4)]);
+
+    let offset=10;
+    object.map_all(&|x| x+offset );
+    
+    let mut seen=0;
+    let count=object.count_matching(&mut|x|{
+        seen+=1;
+        x%2==0
+    });
+    assert_eq!(count,2);
+    assert_eq!(seen,4);
+
+    let sep=String::from("-");
+    let joined=object.join_with(|l,r| format!("{}{}{}",l,sep,r).into() );
+    assert_eq!(joined.as_str(),"13-15-18-14");
+
+    // The closure is not called by the default method,
+    // so it must be dropped by the caller.
+    let first=object.first_or_else(||unreachable!());
+    assert_eq!(first,13);
+
+    let suffix=RString::from("!");
+    let joined=object.into_joined(move|list|{
+        let mut joined=list.iter().map(|x| x.as_str() ).collect::<Vec<_>>().join(",");
+        joined.push_str(&suffix);
+        joined.into()
+    });
+    assert_eq!(joined.as_str(),"13,15,18,14!");
+
+    let empty=Container_TO::from_value(Empty,TU_Opaque);
+    assert_eq!(empty.first_or_else(||100),100);
+    let counter=std::rc::Rc::new(());
+    {
+        let cloned=counter.clone();
+        let object=Container_TO::from_value(Empty,TU_Opaque);
+        assert_eq!(object.first_or_else(move||{ drop(cloned); 7 }),7);
+        assert_eq!(std::rc::Rc::strong_count(&counter),1);
+    }
+    {
+        let cloned=counter.clone();
+        let object=Container_TO::from_value(Numbers(vec![1]),TU_Opaque);
+        assert_eq!(object.first_or_else(move||{ drop(cloned); 7 }),1);
+        assert_eq!(std::rc::Rc::strong_count(&counter),1);
+    }
+}
+
+
+#[cfg(any(rust_1_39,feature="rust_1_39"))]
+mod async_methods{
+    use super::*;
+
+    use std::sync::{
+        atomic::{AtomicBool,Ordering},
+        Arc,
+    };
+
+    use crate::std_types::{RFuture,RString,UnsyncRFuture};
+
+    #[sabi_trait]
+    pub trait Store: Sync{
+        fn get(&self,key:u32)->impl Future<Output=RString>+Send;
+
+        fn len(&self)->impl Future<Output=usize>+Send+'_;
+
+        async fn describe(&self)->RString{
+            format!("{} entries",self.len().await).into()
+        }
+
+        async fn clear(&self){}
+
+        fn bump(&mut self)->impl Future<Output=u32>{
+            async{ 0 }
+        }
+    }
+
+    pub struct Names{
+        pub names:Vec<RString>,
+        pub cleared:Arc<AtomicBool>,
+        pub bumps:u32,
+    }
+
+    impl Store for Names{
+        fn get(&self,key:u32)->RFuture<'_,RString>{
+            RFuture::new(async move{
+                self.names.get(key as usize).cloned().unwrap_or_default()
+            })
+        }
+
+        fn len(&self)->RFuture<'_,usize>{
+            RFuture::new(async move{ self.names.len() })
+        }
+
+        fn clear(&self)->RFuture<'_,()>{
+            RFuture::new(async move{ self.cleared.store(true,Ordering::SeqCst) })
+        }
+
+        fn bump(&mut self)->UnsyncRFuture<'_,u32>{
+            UnsyncRFuture::new(async move{
+                self.bumps+=1;
+                self.bumps
+            })
+        }
+    }
+
+    impl Store for (){
+        fn get(&self,_:u32)->RFuture<'_,RString>{
+            RFuture::new(async{ RString::new() })
+        }
+
+        fn len(&self)->RFuture<'_,usize>{
+            RFuture::new(async{ 0 })
+        }
+    }
+}
+
+
+#[cfg(any(rust_1_39,feature="rust_1_39"))]
+#[test]
+fn async_methods(){
+    use self::async_methods::*;
+
+    use crate::test_utils::block_on;
+
+    use std::sync::{
+        atomic::{AtomicBool,Ordering},
+        Arc,
+    };
+
+    let cleared=Arc::new(AtomicBool::new(false));
+    let names=Names{
+        names:vec!["foo".into(),"bar".into()],
+        cleared:cleared.clone(),
+        bumps:0,
+    };
+    let mut object=Store_TO::from_value(names,TU_Opaque);
+
+    block_on(async{
+        assert_eq!(object.get(0).await.as_str(),"foo");
+        assert_eq!(object.get(1).await.as_str(),"bar");
+        assert_eq!(object.get(2).await.as_str(),"");
+        assert_eq!(object.len().await,2);
+        assert_eq!(object.describe().await.as_str(),"2 entries");
+        object.clear().await;
+        assert_eq!(object.bump().await,1);
+        assert_eq!(object.bump().await,2);
+    });
+    assert!(cleared.load(Ordering::SeqCst));
+
+    fn assert_send<T:Send>(_:&T){}
+    assert_send(&object.get(0));
+    assert_send(&object.describe());
+
+    let mut object=Store_TO::from_value((),TU_Opaque);
+    block_on(async{
+        assert_eq!(object.describe().await.as_str(),"0 entries");
+        object.clear().await;
+        assert_eq!(object.bump().await,0);
+    });
+}
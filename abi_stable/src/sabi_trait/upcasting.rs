@@ -0,0 +1,156 @@
+/*!
+Traits used to have `#[sabi_trait]` traits as supertraits of other `#[sabi_trait]` traits,
+and to convert the trait object of a trait into the trait object of its supertraits.
+*/
+
+use super::*;
+
+use crate::sabi_trait::robject::RObject;
+
+
+/**
+Describes the vtable of a `#[sabi_trait]` trait object,
+implemented by the `Trait_Interface` type generated for the trait.
+
+# Safety
+
+`VTable` must be the vtable type that the trait object uses,for an erased `_Self`.
+*/
+pub unsafe trait SabiTraitInterface<ErasedPtr>:Sized{
+    /// The vtable of the trait object.
+    type VTable;
+}
+
+
+/**
+Gets the trait object of a `#[sabi_trait]` trait from its underlying `RObject`,
+implemented by the `Trait_Interface` type generated for the trait.
+
+# Safety
+
+`Object` must be the `Trait_TO` type generated for the trait.
+*/
+pub unsafe trait SabiTraitObject<'lt,ErasedPtr>:SabiTraitInterface<ErasedPtr>{
+    /// The trait object of the trait.
+    type Object;
+
+    /// Wraps the underlying implementation of the trait object.
+    fn sabi_from_robject(obj:RObject<'lt,ErasedPtr,Self,Self::VTable>)->Self::Object;
+}
+
+
+/**
+Gets the vtable of a `#[sabi_trait]` trait for `_Self`,
+implemented by the `Trait_Interface` type generated for the trait.
+
+This is used to store the vtable of a supertrait in the vtable of its subtraits.
+
+# Safety
+
+`SABI_TRAIT_VTABLE` must be the vtable constructed for `_Self` by the trait object.
+*/
+pub unsafe trait GetSabiTraitVTable<IA,_Self,ErasedPtr,OrigPtr>:SabiTraitInterface<ErasedPtr>{
+    /// The vtable of the trait for `_Self`.
+    const SABI_TRAIT_VTABLE:StaticRef<Self::VTable>;
+}
+
+
+/**
+Converts a `#[sabi_trait]` trait object into the trait object of a `#[sabi_trait]` supertrait.
+
+`I` is the `Trait_Interface` type generated for the supertrait.
+
+This is implemented by the `Trait_TO` generated for every trait
+for each of its `#[sabi_trait]` supertraits,
+and is also what the supertrait is implemented through,
+calling the functions in the vtable of the supertrait.
+
+Trait objects of supertraits also implement `From<Trait_TO<..>>`,
+using this trait to do the conversion.
+
+# Example
+
+```
+use abi_stable::{
+    sabi_trait,
+    sabi_trait::prelude::*,
+    std_types::{RBox,RString},
+};
+
+#[sabi_trait]
+pub trait Named:Send+Sync{
+    fn name(&self)->RString;
+}
+
+#[sabi_trait]
+#[sabi(sabi_supertraits(Named))]
+pub trait Plugin:Named+Send+Sync{
+    fn run(&mut self,input:u32)->u32;
+}
+
+struct Doubler;
+
+impl Named for Doubler{
+    fn name(&self)->RString{
+        "doubler".into()
+    }
+}
+
+impl Plugin for Doubler{
+    fn run(&mut self,input:u32)->u32{
+        input*2
+    }
+}
+
+# fn main(){
+
+let mut plugin=Plugin_TO::from_value(Doubler,TU_Opaque);
+assert_eq!(plugin.run(3),6);
+
+// The trait object of a trait implements the `#[sabi_trait]` supertraits.
+assert_eq!(Named::name(&plugin).as_str(),"doubler");
+
+let named:Named_TO<'_,RBox<()>>=plugin.into();
+assert_eq!(named.name().as_str(),"doubler");
+
+# }
+
+```
+
+# Safety
+
+`Target` must be the `Trait_TO` type generated for the supertrait,
+pointing to the same value as `Self`.
+
+`sabi_supertrait_vtable` must return the vtable of the supertrait,
+for the same type and pointer as the one this trait object was constructed with.
+
+*/
+pub unsafe trait UpcastTo<I>:Sized{
+    /// The trait object of the supertrait.
+    type Target;
+
+    /// The pointer that the trait object wraps,with the referent erased.
+    type ErasedPtr;
+
+    /// Converts this trait object into the trait object of the supertrait.
+    fn sabi_upcast(self)->Self::Target;
+
+    /// Gets the vtable of the supertrait,stored inside the vtable of this trait object.
+    #[doc(hidden)]
+    fn sabi_supertrait_vtable(&self)->StaticRef<<I as SabiTraitInterface<Self::ErasedPtr>>::VTable>
+    where
+        I:SabiTraitInterface<Self::ErasedPtr>;
+
+    /// Gets a reference to the erased value that this trait object points to.
+    #[doc(hidden)]
+    fn sabi_erased_ref(&self)->&ErasedObject<()>
+    where
+        Self::ErasedPtr:__DerefTrait<Target=()>;
+
+    /// Gets a mutable reference to the erased value that this trait object points to.
+    #[doc(hidden)]
+    fn sabi_erased_mut(&mut self)->&mut ErasedObject<()>
+    where
+        Self::ErasedPtr:__DerefMutTrait<Target=()>;
+}
@@ -1,14 +1,20 @@
 use super::*;
 
 use crate::{
-    erased_types::FormattingMode,
+    abi_stability::stable_type_id::{StableTypeId,no_stable_type_id},
+    const_utils::Transmuter,
+    library::{LibraryKeepAlive,current_keep_alive,no_keep_alive},
+    erased_types::{
+        FormattingMode,
+        traits::{SerializeImplType,SerializeProxyType,GetSerializeProxyType},
+    },
     type_level::{
         unerasability::{GetUTID},
         impl_enum::{Implemented,Unimplemented,IsImplemented},
         trait_marker,
     },
     sabi_types::ReturnValueEquality,
-    std_types::{UTypeId,RResult,RString},
+    std_types::{UTypeId,RBoxError,RResult,RString},
 };
 
 /// Gets the vtable of a trait object.
@@ -55,6 +61,7 @@ where
     I::Send:RequiresSend<_Self,ErasedPtr,OrigPtr>,
     I::Clone:InitCloneField<_Self,ErasedPtr,OrigPtr>,
     I::Debug:InitDebugField<_Self,ErasedPtr,OrigPtr>,
+    I::Serialize:InitSerializeField<_Self,I>,
     IA:GetUTID<_Self>,
 {
     const VTABLE_VAL:RObjectVtableVal<_Self,ErasedPtr,I>=
@@ -64,6 +71,9 @@ where
             _sabi_drop:c_functions::drop_pointer_impl::<OrigPtr,ErasedPtr>,
             _sabi_clone:<I::Clone as InitCloneField<_Self,ErasedPtr,OrigPtr>>::VALUE,
             _sabi_debug:<I::Debug as InitDebugField<_Self,ErasedPtr,OrigPtr>>::VALUE,
+            _sabi_serialize:<I::Serialize as InitSerializeField<_Self,I>>::VALUE,
+            _sabi_stable_type_id:<IA as GetUTID<_Self>>::STABLE_ID,
+            _sabi_keep_alive:current_keep_alive,
         };
 }
 
@@ -95,6 +105,10 @@ where
 #[derive(StableAbi)]
 #[sabi(kind(Prefix(prefix_struct="RObjectVtable")))]
 #[sabi(missing_field(default))]
+#[sabi(
+    bound="I:GetSerializeProxyType",
+    bound="<I as GetSerializeProxyType>::ProxyType:crate::StableAbi",
+)]
 pub struct RObjectVtableVal<_Self,ErasedPtr,I>{
     pub _sabi_tys:PhantomData<extern "C" fn(_Self,ErasedPtr,I)>,
     
@@ -106,6 +120,59 @@ pub struct RObjectVtableVal<_Self,ErasedPtr,I>{
     pub _sabi_debug:Option<
         unsafe extern "C" fn(&ErasedObject,FormattingMode,&mut RString)->RResult<(),()>
     >,
+    #[sabi(unsafe_change_type=r#"
+        Option<
+            unsafe extern "C" fn(
+                &ErasedObject
+            )->RResult<<I as GetSerializeProxyType>::ProxyType,RBoxError>
+        >
+    "#)]
+    pub _sabi_serialize:Option<ErasedSerializeFn>,
+    #[sabi(missing_field(value="NO_STABLE_TYPE_ID"))]
+    pub _sabi_stable_type_id:ReturnValueEquality<MaybeCmp<StableTypeId>>,
+    #[sabi(missing_field(value="no_keep_alive"))]
+    pub _sabi_keep_alive:extern "C" fn()->Option<&'static LibraryKeepAlive>,
+}
+
+
+/// The StableTypeId of trait objects constructed in libraries compiled with 
+/// a version of abi_stable where `RObject` didn't store it.
+const NO_STABLE_TYPE_ID:ReturnValueEquality<MaybeCmp<StableTypeId>>=
+    ReturnValueEquality{
+        function:no_stable_type_id
+    };
+
+
+type ErasedSerializeFn=
+    unsafe extern "C" fn(&ErasedObject)->RResult<ErasedObject,RBoxError>;
+
+/// The serialization function of an `I:InterfaceType`,
+/// taking a reference to the erased value.
+pub type UnerasedSerializeFn<I>=
+    unsafe extern "C" fn(
+        &ErasedObject
+    )->RResult<<I as GetSerializeProxyType>::ProxyType,RBoxError>;
+
+
+impl<_Self,ErasedPtr,I> RObjectVtable<_Self,ErasedPtr,I>{
+    /// Gets the function that serializes the erased value into a proxy type.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the vtable was constructed without the function,
+    /// which only happens if the trait object comes from a library compiled
+    /// with a version of abi_stable where `RObject` didn't support serialization.
+    pub fn sabi_serialize(&self)->UnerasedSerializeFn<I>
+    where
+        I:InterfaceType<Serialize=Implemented<trait_marker::Serialize>>,
+        I:GetSerializeProxyType,
+    {
+        let func=self._sabi_serialize()
+            .expect("This RObject's vtable does not contain the serialization function.");
+        unsafe{
+            std::mem::transmute::<ErasedSerializeFn,UnerasedSerializeFn<I>>(func)
+        }
+    }
 }
 
 
@@ -116,6 +183,8 @@ pub struct RObjectVtableVal<_Self,ErasedPtr,I>{
 #[derive(StableAbi)]
 #[sabi(
     bound="I:InterfaceBound",
+    bound="I:GetSerializeProxyType",
+    bound="<I as GetSerializeProxyType>::ProxyType:crate::StableAbi",
     tag="<I as InterfaceBound>::TAG",
     kind(Prefix(prefix_struct="BaseVtable")),
 )]
@@ -207,6 +276,34 @@ pub mod trait_bounds{
     }
 
 
+    /// Initializes the serialization function of the vtable,
+    /// requires `_Self` to be serializable through `I`'s proxy type.
+    pub trait InitSerializeField<_Self,I>{
+        const VALUE:Option<ErasedSerializeFn>;
+    }
+
+    impl<_Self,I> InitSerializeField<_Self,I> for Unimplemented<trait_marker::Serialize>{
+        const VALUE:Option<ErasedSerializeFn>=None;
+    }
+
+    impl<_Self,I> InitSerializeField<_Self,I> for Implemented<trait_marker::Serialize>
+    where
+        _Self:SerializeImplType<Interface=I>,
+        I:SerializeProxyType,
+    {
+        const VALUE:Option<ErasedSerializeFn>=Some(unsafe{
+            Transmuter::<
+                unsafe extern "C" fn(
+                    &ErasedObject
+                )->RResult<<I as SerializeProxyType>::Proxy,RBoxError>,
+                ErasedSerializeFn,
+            >{
+                from:c_functions::serialize_impl::<_Self,I>
+            }.to
+        });
+    }
+
+
 
 
 }
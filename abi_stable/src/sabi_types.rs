@@ -8,6 +8,7 @@ mod late_static_ref;
 mod maybe_cmp;
 pub mod move_ptr;
 mod return_value_equality;
+pub mod rfn;
 mod static_ref;
 pub mod version;
 pub mod rsmallbox;
@@ -20,6 +21,10 @@ pub use self::{
     move_ptr::MovePtr,
     return_value_equality::ReturnValueEquality,
     rsmallbox::RSmallBox,
+    rfn::{RFn,RFnMut,RFnOnce},
     late_static_ref::LateStaticRef,
-    version::{VersionNumber,VersionStrings,ParseVersionError},
+    version::{
+        VersionNumber,FullVersionNumber,VersionStrings,ParseVersionError,
+        VersionReq,VersionComparator,ComparatorOp,UnsatisfiedVersionReq,ParseVersionReqError,
+    },
 };
\ No newline at end of file
@@ -0,0 +1,412 @@
+/*!
+Contains the `RFn`,`RFnMut`,and `RFnOnce` ffi-safe closure references.
+
+The arguments of these closures are passed as a single tuple `A`,
+which is one of `()`,`Tuple1<_>`,`Tuple2<_,_>`,`Tuple3<_,_,_>`,or `Tuple4<_,_,_,_>`.
+
+For owned closures look at
+[the `RBoxFn`/`RBoxFnMut`/`RBoxFnOnce` types](../../std_types/boxed_fn/index.html).
+
+# Example
+
+```
+use abi_stable::{
+    sabi_types::{RFn,RFnMut},
+    std_types::{Tuple1,Tuple2},
+};
+
+let offset=10;
+let add=move|l:u32,r:u32| l+r+offset;
+let add=RFn::new(&add);
+assert_eq!(add.call(Tuple2(3,5)),18);
+
+let mut list=Vec::new();
+{
+    let mut push=|x:u32| list.push(x);
+    let mut push=RFnMut::new(&mut push);
+    push.call_mut(Tuple1(3));
+    push.call_mut(Tuple1(5));
+}
+assert_eq!(list,vec![3,5]);
+
+```
+
+*/
+
+use std::{
+    fmt,
+    marker::PhantomData,
+};
+
+use crate::{
+    marker_type::{ErasedObject,UnsyncUnsend},
+    std_types::{Tuple1,Tuple2,Tuple3,Tuple4},
+    utils::{transmute_reference,transmute_mut_reference},
+};
+
+
+/// A closure that is called by reference,with all its arguments passed in the `A` tuple.
+pub trait TupledFn<A>:TupledFnMut<A>{
+    /// Calls the closure.
+    fn call_tupled(&self,args:A)->Self::Output;
+}
+
+/// A closure that is called by mutable reference,
+/// with all its arguments passed in the `A` tuple.
+pub trait TupledFnMut<A>:TupledFnOnce<A>{
+    /// Calls the closure.
+    fn call_mut_tupled(&mut self,args:A)->Self::Output;
+}
+
+/// A closure that is called by value,with all its arguments passed in the `A` tuple.
+pub trait TupledFnOnce<A>{
+    /// The return type of the closure.
+    type Output;
+
+    /// Calls the closure.
+    fn call_once_tupled(self,args:A)->Self::Output;
+}
+
+
+macro_rules! impl_tupled_fn {
+    ( $( ($tuple:ty,$tuple_pat:pat)[ $($param:ident),* ] )* ) => (
+        $(
+            impl<F,$($param,)* R> TupledFnOnce<$tuple> for F
+            where
+                F:FnOnce($($param,)*)->R,
+            {
+                type Output=R;
+
+                #[inline]
+                fn call_once_tupled(self,$tuple_pat:$tuple)->R{
+                    self($($param,)*)
+                }
+            }
+
+            impl<F,$($param,)* R> TupledFnMut<$tuple> for F
+            where
+                F:FnMut($($param,)*)->R,
+            {
+                #[inline]
+                fn call_mut_tupled(&mut self,$tuple_pat:$tuple)->R{
+                    self($($param,)*)
+                }
+            }
+
+            impl<F,$($param,)* R> TupledFn<$tuple> for F
+            where
+                F:Fn($($param,)*)->R,
+            {
+                #[inline]
+                fn call_tupled(&self,$tuple_pat:$tuple)->R{
+                    self($($param,)*)
+                }
+            }
+        )*
+    )
+}
+
+#[allow(non_snake_case)]
+mod tupled_fn_impls{
+    use super::*;
+
+    impl_tupled_fn!{
+        ((),())[]
+        (Tuple1<A0>,Tuple1(A0))[A0]
+        (Tuple2<A0,A1>,Tuple2(A0,A1))[A0,A1]
+        (Tuple3<A0,A1,A2>,Tuple3(A0,A1,A2))[A0,A1,A2]
+        (Tuple4<A0,A1,A2,A3>,Tuple4(A0,A1,A2,A3))[A0,A1,A2,A3]
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+/**
+An ffi-safe reference to a closure that can be called by reference,
+equivalent to `&'a dyn Fn(..)->R`.
+
+`A` is the tuple of arguments that the closure takes.
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RFn<'a,A,R>{
+    env:&'a ErasedObject,
+    call:unsafe extern "C" fn(env:&ErasedObject,args:A)->R,
+    _marker:PhantomData<UnsyncUnsend>,
+}
+
+impl<'a,A,R> RFn<'a,A,R>{
+    /// Constructs an `RFn` from a reference to a closure.
+    pub fn new<F>(f:&'a F)->Self
+    where
+        F:TupledFn<A,Output=R>,
+    {
+        unsafe{
+            RFn{
+                env:transmute_reference::<F,ErasedObject>(f),
+                call:call_fn::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Constructs an `RFn` from a type-erased closure and the function that calls it.
+    pub(crate) unsafe fn from_raw_parts(
+        env:&'a ErasedObject,
+        call:unsafe extern "C" fn(env:&ErasedObject,args:A)->R,
+    )->Self{
+        RFn{
+            env,
+            call,
+            _marker:PhantomData,
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call(&self,args:A)->R{
+        unsafe{ (self.call)(self.env,args) }
+    }
+}
+
+impl<'a,A,R> Clone for RFn<'a,A,R>{
+    fn clone(&self)->Self{
+        RFn{
+            env:self.env,
+            call:self.call,
+            _marker:PhantomData,
+        }
+    }
+}
+
+impl<'a,A,R> fmt::Debug for RFn<'a,A,R>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RFn{..}")
+    }
+}
+
+
+/**
+An ffi-safe reference to a closure that can be called by mutable reference,
+equivalent to `&'a mut dyn FnMut(..)->R`.
+
+`A` is the tuple of arguments that the closure takes.
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RFnMut<'a,A,R>{
+    env:&'a mut ErasedObject,
+    call:unsafe extern "C" fn(env:&mut ErasedObject,args:A)->R,
+    _marker:PhantomData<UnsyncUnsend>,
+}
+
+impl<'a,A,R> RFnMut<'a,A,R>{
+    /// Constructs an `RFnMut` from a mutable reference to a closure.
+    pub fn new<F>(f:&'a mut F)->Self
+    where
+        F:TupledFnMut<A,Output=R>,
+    {
+        unsafe{
+            RFnMut{
+                env:transmute_mut_reference::<F,ErasedObject>(f),
+                call:call_fn_mut::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Constructs an `RFnMut` from a type-erased closure and the function that calls it.
+    pub(crate) unsafe fn from_raw_parts(
+        env:&'a mut ErasedObject,
+        call:unsafe extern "C" fn(env:&mut ErasedObject,args:A)->R,
+    )->Self{
+        RFnMut{
+            env,
+            call,
+            _marker:PhantomData,
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call_mut(&mut self,args:A)->R{
+        unsafe{ (self.call)(self.env,args) }
+    }
+
+    /// Reborrows this `RFnMut`,
+    /// so that it can be passed by value without giving up access to it.
+    pub fn reborrow<'b>(&'b mut self)->RFnMut<'b,A,R>{
+        RFnMut{
+            env:&mut *self.env,
+            call:self.call,
+            _marker:PhantomData,
+        }
+    }
+}
+
+impl<'a,A,R> fmt::Debug for RFnMut<'a,A,R>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RFnMut{..}")
+    }
+}
+
+
+/**
+An ffi-safe reference to a closure that can be called at most once,
+equivalent to passing a `FnOnce(..)->R` by value.
+
+`A` is the tuple of arguments that the closure takes.
+
+This borrows an `Option<F>`,taking the closure out of it when called,
+so that the closure gets dropped by its owner if it was never called.
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RFnOnce<'a,A,R>{
+    env:&'a mut ErasedObject,
+    call:unsafe extern "C" fn(env:&mut ErasedObject,args:A)->R,
+    _marker:PhantomData<UnsyncUnsend>,
+}
+
+impl<'a,A,R> RFnOnce<'a,A,R>{
+    /// Constructs an `RFnOnce` from a mutable reference to an optional closure,
+    /// the closure is taken out of the `Option` when called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` is `None`.
+    pub fn new<F>(f:&'a mut Option<F>)->Self
+    where
+        F:TupledFnOnce<A,Output=R>,
+    {
+        assert!(
+            f.is_some(),
+            "Cannot construct an RFnOnce from an `Option` that doesn't contain a closure."
+        );
+        unsafe{
+            RFnOnce{
+                env:transmute_mut_reference::<Option<F>,ErasedObject>(f),
+                call:call_fn_once::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call_once(self,args:A)->R{
+        unsafe{ (self.call)(self.env,args) }
+    }
+}
+
+impl<'a,A,R> fmt::Debug for RFnOnce<'a,A,R>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RFnOnce{..}")
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+pub(crate) unsafe extern "C" fn call_fn<F,A,R>(env:&ErasedObject,args:A)->R
+where
+    F:TupledFn<A,Output=R>,
+{
+    extern_fn_panic_handling!{
+        let f=transmute_reference::<ErasedObject,F>(env);
+        f.call_tupled(args)
+    }
+}
+
+pub(crate) unsafe extern "C" fn call_fn_mut<F,A,R>(env:&mut ErasedObject,args:A)->R
+where
+    F:TupledFnMut<A,Output=R>,
+{
+    extern_fn_panic_handling!{
+        let f=transmute_mut_reference::<ErasedObject,F>(env);
+        f.call_mut_tupled(args)
+    }
+}
+
+unsafe extern "C" fn call_fn_once<F,A,R>(env:&mut ErasedObject,args:A)->R
+where
+    F:TupledFnOnce<A,Output=R>,
+{
+    extern_fn_panic_handling!{
+        let f=transmute_mut_reference::<ErasedObject,Option<F>>(env);
+        // RFnOnce::call_once consumes the RFnOnce,
+        // and RFnOnce::new requires the Option to contain the closure.
+        f.take().unwrap().call_once_tupled(args)
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::std_types::{RStr,RString};
+
+    fn call_for_each<'s>(mut f:RFnMut<'_,Tuple1<RStr<'s>>,()>,second:&'s str){
+        f.call_mut(Tuple1("hello".into()));
+        f.call_mut(Tuple1(second.into()));
+    }
+
+    #[test]
+    fn rfn(){
+        let sep=", ";
+        let join=|l:RStr<'_>,r:RStr<'_>|->RString{
+            format!("{}{}{}",l,sep,r).into()
+        };
+        let join=RFn::new(&join);
+        assert_eq!(join.call(Tuple2("hello".into(),"world".into())).as_str(),"hello, world");
+        assert_eq!(join.clone().call(Tuple2("a".into(),"b".into())).as_str(),"a, b");
+
+        let answer=||42;
+        assert_eq!(RFn::new(&answer).call(()),42);
+    }
+
+    #[test]
+    fn rfn_mut(){
+        let mut list=Vec::<String>::new();
+        {
+            let mut push=|s:RStr<'_>| list.push(s.into());
+            let mut push=RFnMut::new(&mut push);
+            let owned=String::from("world");
+            call_for_each(push.reborrow(),&owned);
+            call_for_each(push,&owned);
+        }
+        assert_eq!(list,vec!["hello","world","hello","world"]);
+
+        let mut total=0;
+        {
+            let mut add=|a:u32,b:u32,c:u32,d:u32| total+=a+b+c+d;
+            RFnMut::new(&mut add).call_mut(Tuple4(1,2,3,4));
+        }
+        assert_eq!(total,10);
+    }
+
+    #[test]
+    fn rfn_once(){
+        let text=String::from("hello");
+        let mut into_string=Some(move|suffix:RStr<'_>| text+suffix.as_str() );
+        let ret=RFnOnce::new(&mut into_string).call_once(Tuple1(" world".into()));
+        assert_eq!(ret,"hello world");
+        assert!(into_string.is_none());
+
+        // The closure is dropped by the owner of the `Option` if it's never called.
+        let counter=std::rc::Rc::new(());
+        let cloned=counter.clone();
+        let mut uncalled=Some(move||drop(cloned));
+        let _=RFnOnce::<(),()>::new(&mut uncalled);
+        assert_eq!(std::rc::Rc::strong_count(&counter),2);
+        drop(uncalled);
+        assert_eq!(std::rc::Rc::strong_count(&counter),1);
+    }
+}
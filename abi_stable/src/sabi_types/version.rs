@@ -5,12 +5,20 @@ Types representing the version number of a library.
 use core_extensions::prelude::*;
 
 use std::{
+    cmp::Ordering,
     error,
     fmt::{self, Display},
-    num::ParseIntError,
 };
 
-use crate::std_types::StaticStr;
+use crate::std_types::{RString,StaticStr};
+
+
+mod requirement;
+
+pub use self::requirement::{
+    VersionReq,VersionComparator,ComparatorOp,UnsatisfiedVersionReq,ParseVersionReqError,
+};
+
 
 /// The `<major>.<minor>.<patch>` version of a library,
 ///
@@ -46,6 +54,11 @@ pub struct VersionStrings {
 /// any previous minor release (with the same major number).
 ///
 /// Patch cannot change the api/abi of the library at all,fixes only.
+///
+/// # Pre-release versions
+///
+/// The pre-release identifiers and build metadata are not stored in this type,
+/// use `FullVersionNumber` to get the pre-release identifiers.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
 #[repr(C)]
 pub struct VersionNumber {
@@ -54,6 +67,26 @@ pub struct VersionNumber {
     pub patch: u32,
 }
 
+/// The parsed (`<major>.<minor>.<patch>[-<pre_release>][+<build_metadata>]`)
+/// version number of a library,including its pre-release identifiers.
+///
+/// The pre-release identifiers are used when ordering version numbers
+/// and when matching a `VersionReq`,
+/// where pre-release versions come before the release with the same `major.minor.patch`.
+///
+/// The build metadata is ignored.
+///
+/// This owns the pre-release identifiers,
+/// so that it can outlive the dynamic library that the version string came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullVersionNumber {
+    /// The `major.minor.patch` version.
+    pub number: VersionNumber,
+    /// The pre-release identifiers,the `alpha.1` in `1.0.0-alpha.1`,
+    /// which is empty for release versions.
+    pub pre: RString,
+}
+
 impl VersionStrings {
     pub const fn new(version:&'static str)->Self{
         Self{version:StaticStr::new(version)}
@@ -61,11 +94,20 @@ impl VersionStrings {
     pub fn parsed(self) -> Result<VersionNumber, ParseVersionError> {
         VersionNumber::new(self)
     }
+    /// Parses this into a version number,including its pre-release identifiers.
+    pub fn parsed_full(self) -> Result<FullVersionNumber, ParseVersionError> {
+        FullVersionNumber::new(self)
+    }
 }
 
 impl VersionNumber {
+    /// Parses the version number,ignoring anything after the patch number.
     pub fn new(vn: VersionStrings) -> Result<Self, ParseVersionError> {
-        let mut iter=vn.version.splitn(3,'.');
+        Self::parse_numbers(vn,vn.version.as_str())
+    }
+
+    fn parse_numbers(vn: VersionStrings,version:&str) -> Result<Self, ParseVersionError> {
+        let mut iter=version.splitn(3,'.');
 
         VersionNumber {
             major: iter.next()
@@ -83,8 +125,7 @@ impl VersionNumber {
                 .map_or("0", |x| x.str)
                 .parse()
                 .map_err(|x| ParseVersionError::new(vn, "patch", x))?,
-        }
-        .piped(Ok)
+        }.piped(Ok)
     }
 
     /// Whether the `self` version number is compatible with the
@@ -108,12 +149,135 @@ impl VersionNumber {
     }
 }
 
+impl FullVersionNumber {
+    pub fn new(vn: VersionStrings) -> Result<Self, ParseVersionError> {
+        let (version,pre)=split_pre_release(vn.version.as_str())
+            .map_err(|e| ParseVersionError::new(vn, "pre-release", e))?;
+
+        let number=VersionNumber::parse_numbers(vn,version)?;
+
+        Ok(FullVersionNumber{
+            number,
+            pre: pre.into(),
+        })
+    }
+
+    /// Whether this is a pre-release version,eg:`1.0.0-alpha.1`.
+    pub fn is_pre_release(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl From<VersionNumber> for FullVersionNumber {
+    fn from(number: VersionNumber) -> Self {
+        FullVersionNumber{
+            number,
+            pre: RString::new(),
+        }
+    }
+}
+
+/// Orders version numbers by their `major.minor.patch` version.
+impl Ord for VersionNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major,self.minor,self.patch).cmp(&(other.major,other.minor,other.patch))
+    }
+}
+
+impl PartialOrd for VersionNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders version numbers by semver precedence,
+/// where pre-release versions come before the release with the same `major.minor.patch`.
+impl Ord for FullVersionNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number.cmp(&other.number)
+            .then_with(|| cmp_pre_release(&self.pre,&other.pre) )
+    }
+}
+
+impl PartialOrd for FullVersionNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for VersionNumber {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
     }
 }
 
+impl fmt::Display for FullVersionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.number, f)?;
+        if self.is_pre_release() {
+            write!(f, "-{}", self.pre)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Splits `version` into the `major.minor.patch` part and the pre-release identifiers,
+/// ignoring the build metadata.
+///
+/// The pre-release identifiers must be non-empty,
+/// and only contain ascii alphanumerics and hyphens.
+fn split_pre_release(version: &str) -> Result<(&str, &str), String> {
+    let version=version.split('+').next().unwrap_or("");
+    let pos=match version.find('-') {
+        Some(pos)=>pos,
+        None=>return Ok((version,"")),
+    };
+    let pre=&version[pos+1..];
+    for ident in pre.split('.') {
+        if ident.is_empty() {
+            return Err(format!("empty identifier in '{}'",pre));
+        }
+        if let Some(c)=ident.chars().find(|&c| !c.is_ascii_alphanumeric() && c!='-' ) {
+            return Err(format!("invalid character '{}' in '{}'",c,pre));
+        }
+    }
+    Ok((&version[..pos],pre))
+}
+
+
+/// Compares pre-release identifiers by semver precedence,
+/// where the empty string (a release) is greater than any pre-release.
+fn cmp_pre_release(l: &str, r: &str) -> Ordering {
+    match (l.is_empty(),r.is_empty()) {
+        (true,true)=>return Ordering::Equal,
+        (true,false)=>return Ordering::Greater,
+        (false,true)=>return Ordering::Less,
+        (false,false)=>{}
+    }
+
+    let mut l_idents=l.split('.');
+    let mut r_idents=r.split('.');
+    loop {
+        let ord=match (l_idents.next(),r_idents.next()) {
+            (None,None)=>return Ordering::Equal,
+            (None,Some(_))=>return Ordering::Less,
+            (Some(_),None)=>return Ordering::Greater,
+            (Some(l_ident),Some(r_ident))=>{
+                match (l_ident.parse::<u64>(),r_ident.parse::<u64>()) {
+                    (Ok(l_num),Ok(r_num))=>l_num.cmp(&r_num),
+                    (Ok(_),Err(_))=>Ordering::Less,
+                    (Err(_),Ok(_))=>Ordering::Greater,
+                    (Err(_),Err(_))=>l_ident.cmp(r_ident),
+                }
+            }
+        };
+        if ord!=Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
 impl fmt::Display for VersionStrings {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.version,f)
@@ -137,29 +301,41 @@ macro_rules! package_version_strings {
 ////////////////////////////////////////////////////////////////////////////////
 
 /// When the `VersionStrings` could not be converted into a `VersionNumber`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, StableAbi)]
+#[repr(C)]
 pub struct ParseVersionError {
     version_strings: VersionStrings,
-    which_field: &'static str,
-    parse_error: ParseIntError,
+    which_field: StaticStr,
+    /// The error message of the `ParseIntError`,
+    /// stored as a string since `ParseIntError` is not ffi-safe.
+    parse_error: RString,
 }
 
 impl ParseVersionError {
-    fn new(
+    fn new<E>(
         version_strings: VersionStrings,
         which_field: &'static str,
-        parse_error: ParseIntError,
-    ) -> Self {
+        parse_error: E,
+    ) -> Self
+    where
+        E: Display,
+    {
         Self {
             version_strings,
-            which_field,
-            parse_error,
+            which_field: StaticStr::new(which_field),
+            parse_error: parse_error.to_string().into(),
         }
     }
 
     pub fn version_strings(&self) -> VersionStrings {
         self.version_strings
     }
+
+    /// The field of the version number that could not be parsed,
+    /// one of `"major"`/`"minor"`/`"patch"`/`"pre-release"`.
+    pub fn which_field(&self) -> &'static str {
+        self.which_field.as_str()
+    }
 }
 
 impl Display for ParseVersionError {
@@ -173,3 +349,62 @@ impl Display for ParseVersionError {
 }
 
 impl error::Error for ParseVersionError {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    fn parse(s:&'static str)->Result<FullVersionNumber,ParseVersionError>{
+        VersionStrings::new(s).parsed_full()
+    }
+
+    #[test]
+    fn parse_pre_release(){
+        let v=parse("1.2.3-rc.1+build.5").unwrap();
+        assert_eq!(v.number,VersionNumber{major:1,minor:2,patch:3});
+        assert_eq!(v.pre.as_str(),"rc.1");
+        assert_eq!(v.to_string(),"1.2.3-rc.1");
+        assert_eq!(v.number.to_string(),"1.2.3");
+        assert_eq!(VersionStrings::new("1.2.3-rc.1+build.5").parsed(),Ok(v.number));
+
+        let v=parse("0.4.0+build").unwrap();
+        assert_eq!(v.number,VersionNumber{major:0,minor:4,patch:0});
+        assert!(!v.is_pre_release());
+        assert_eq!(v,FullVersionNumber::from(v.number));
+
+        for s in vec!["1.0.0-","1.0.0-rc..1","1.0.0-rc_1"] {
+            assert_eq!(parse(s).unwrap_err().which_field(),"pre-release");
+        }
+    }
+
+    #[test]
+    fn lenient_version_number(){
+        let one=VersionNumber{major:1,minor:0,patch:0};
+        for s in vec!["1.0.0-rc_1","1.0.0-","1.0.0-rc..1","1.0.0-rc.1+build","1.0.0+build_5"] {
+            assert_eq!(VersionStrings::new(s).parsed(),Ok(one),"{}",s);
+        }
+        assert!(parse("1.0.0-rc_1").is_err());
+    }
+
+    #[test]
+    fn pre_release_ordering(){
+        let list=[
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1-alpha",
+        ];
+        for pair in list.windows(2) {
+            assert!(parse(pair[0]).unwrap() < parse(pair[1]).unwrap(),"{:?}",pair);
+        }
+    }
+}
@@ -0,0 +1,504 @@
+/*!
+Semver version requirements,with the same syntax and semantics as the ones in Cargo.toml.
+*/
+
+use std::{
+    cmp::Ordering,
+    error,
+    fmt::{self, Display},
+};
+
+use super::{FullVersionNumber,cmp_pre_release,split_pre_release};
+
+
+/**
+A semver version requirement,
+a comma separated list of comparators that a version must all satisfy.
+
+This uses the same syntax and semantics as the version requirements of Cargo,
+eg:`"^1.2"`,`">=1.0.0, <3"`,`"~0.4.1"`,`"=1.2.3-rc.1"`,`"1.*"`,`"*"`.
+
+A comparator without an operator is interpreted as a caret (`^`) requirement.
+
+# Pre-release versions
+
+Pre-release versions only satisfy a requirement if one of its comparators
+has a pre-release with the same `major.minor.patch` version,
+eg:`1.0.0-rc.2` satisfies `>=1.0.0-rc.1` but not `>=0.9`.
+
+# Example
+
+```
+use abi_stable::sabi_types::{VersionReq,VersionStrings};
+
+let req=VersionReq::parse(">=1.2, <3").unwrap();
+
+let version=VersionStrings::new("2.5.0").parsed_full().unwrap();
+assert!( req.matches(&version) );
+
+let version=VersionStrings::new("3.0.0").parsed_full().unwrap();
+assert!( !req.matches(&version) );
+
+let version=VersionStrings::new("2.0.0-alpha.1").parsed_full().unwrap();
+assert!( !req.matches(&version) );
+
+```
+
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<VersionComparator>,
+}
+
+
+/// A single comparator in a `VersionReq`,eg:the `>=1.2` in `>=1.2, <3`.
+///
+/// The minor and patch versions are optional,
+/// in which case they match any minor/patch version allowed by the operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionComparator {
+    pub op: ComparatorOp,
+    pub major: u32,
+    pub minor: Option<u32>,
+    pub patch: Option<u32>,
+    /// The pre-release identifiers,which can only be non-empty if `patch` is `Some`.
+    pub pre: String,
+}
+
+
+/// The operator of a `VersionComparator`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ComparatorOp {
+    /// `=1.2.3`
+    Exact,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`
+    Tilde,
+    /// `^1.2.3`,or `1.2.3`
+    Caret,
+    /// `1.*` or `1.2.*`
+    Wildcard,
+}
+
+
+/// Why a version does not satisfy a `VersionReq`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsatisfiedVersionReq {
+    /// The version does not satisfy this comparator.
+    Comparator(VersionComparator),
+    /// The version is a pre-release,
+    /// and none of the comparators has a pre-release with the same `major.minor.patch`.
+    PreRelease,
+}
+
+
+/// When a string could not be parsed into a `VersionReq`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionReqError {
+    requirement: String,
+    message: String,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+impl VersionReq {
+    /// Parses a version requirement,
+    /// a comma separated list of comparators,or `*` to match any version.
+    pub fn parse(requirement: &str) -> Result<Self, ParseVersionReqError> {
+        let trimmed=requirement.trim();
+        if trimmed=="*" {
+            return Ok(Self{ comparators:Vec::new() });
+        }
+
+        let comparators=trimmed.split(',')
+            .map(|comparator| VersionComparator::parse(comparator.trim()) )
+            .collect::<Result<Vec<_>,String>>()
+            .map_err(|message| ParseVersionReqError{
+                requirement:requirement.to_string(),
+                message,
+            })?;
+
+        Ok(Self{comparators})
+    }
+
+    /// The comparators of this requirement,which is empty for `*`.
+    pub fn comparators(&self) -> &[VersionComparator] {
+        &self.comparators
+    }
+
+    /// Whether `version` satisfies this requirement.
+    pub fn matches(&self, version: &FullVersionNumber) -> bool {
+        self.check(version).is_ok()
+    }
+
+    /// Checks that `version` satisfies this requirement,
+    /// returning the reason it doesn't if it doesn't.
+    pub fn check(&self, version: &FullVersionNumber) -> Result<(), UnsatisfiedVersionReq> {
+        if let Some(cmp)=self.comparators.iter().find(|cmp| !cmp.matches(version) ) {
+            return Err(UnsatisfiedVersionReq::Comparator(cmp.clone()));
+        }
+
+        let pre_release_allowed=
+            !version.is_pre_release()||
+            self.comparators.iter().any(|cmp|{
+                cmp.major==version.number.major &&
+                cmp.minor==Some(version.number.minor) &&
+                cmp.patch==Some(version.number.patch) &&
+                !cmp.pre.is_empty()
+            });
+
+        if pre_release_allowed {
+            Ok(())
+        }else{
+            Err(UnsatisfiedVersionReq::PreRelease)
+        }
+    }
+}
+
+
+impl VersionComparator {
+    fn parse(comparator: &str) -> Result<Self, String> {
+        let operators=[
+            (">=",ComparatorOp::GreaterEq),
+            ("<=",ComparatorOp::LessEq),
+            (">" ,ComparatorOp::Greater),
+            ("<" ,ComparatorOp::Less),
+            ("=" ,ComparatorOp::Exact),
+            ("~" ,ComparatorOp::Tilde),
+            ("^" ,ComparatorOp::Caret),
+        ];
+
+        let (mut op,version)=operators.iter()
+            .find(|(prefix,_)| comparator.starts_with(prefix) )
+            .map_or((ComparatorOp::Caret,comparator),|&(prefix,op)|{
+                (op,comparator[prefix.len()..].trim_start())
+            });
+
+        if version.is_empty() {
+            return Err(format!("expected a version in the '{}' comparator",comparator));
+        }
+
+        let (version,pre)=split_pre_release(version)?;
+
+        let mut parts=version.split('.');
+        let mut numbers=[None;3];
+        let mut is_wildcard=false;
+
+        for (i,number) in numbers.iter_mut().enumerate() {
+            let part=match parts.next() {
+                Some(x)=>x,
+                None if i==0 =>
+                    return Err(format!("expected a version in the '{}' comparator",comparator)),
+                None=>break,
+            };
+            if part=="*" || part=="x" || part=="X" {
+                if i==0 {
+                    return Err(format!(
+                        "the '{}' comparator can't have a wildcard major version",
+                        comparator,
+                    ));
+                }
+                is_wildcard=true;
+                break;
+            }
+            *number=match part.parse::<u32>() {
+                Ok(x)=>Some(x),
+                Err(e)=>return Err(format!("invalid number '{}' in '{}':{}",part,comparator,e)),
+            };
+        }
+
+        if parts.next().is_some() {
+            return Err(format!("too many version numbers in the '{}' comparator",comparator));
+        }
+
+        if is_wildcard {
+            if op!=ComparatorOp::Caret || comparator.starts_with('^') {
+                return Err(format!(
+                    "wildcards can't be used with operators,in the '{}' comparator",
+                    comparator,
+                ));
+            }
+            op=ComparatorOp::Wildcard;
+        }
+
+        if !pre.is_empty() && numbers[2].is_none() {
+            return Err(format!(
+                "pre-release identifiers require a patch version,in the '{}' comparator",
+                comparator,
+            ));
+        }
+
+        Ok(Self{
+            op,
+            major:numbers[0].unwrap_or(0),
+            minor:numbers[1],
+            patch:numbers[2],
+            pre:pre.to_string(),
+        })
+    }
+
+    /// Whether `version` satisfies this comparator,
+    /// ignoring whether pre-release versions are allowed by the requirement.
+    pub fn matches(&self, version: &FullVersionNumber) -> bool {
+        match self.op {
+            ComparatorOp::Exact=>self.matches_exact(version),
+            ComparatorOp::Greater=>self.cmp_partial(version)==Ordering::Greater,
+            ComparatorOp::GreaterEq=>self.cmp_partial(version)!=Ordering::Less,
+            ComparatorOp::Less=>self.cmp_partial(version)==Ordering::Less,
+            ComparatorOp::LessEq=>self.cmp_partial(version)!=Ordering::Greater,
+            ComparatorOp::Tilde=>self.matches_tilde(version),
+            ComparatorOp::Caret=>self.matches_caret(version),
+            ComparatorOp::Wildcard=>self.matches_exact(version),
+        }
+    }
+
+    /// Compares `version` to this comparator's version,
+    /// treating missing minor/patch versions as matching any version.
+    fn cmp_partial(&self, version: &FullVersionNumber) -> Ordering {
+        let ord=version.number.major.cmp(&self.major);
+        let minor=match (ord,self.minor) {
+            (Ordering::Equal,Some(minor))=>minor,
+            _=>return ord,
+        };
+        let ord=version.number.minor.cmp(&minor);
+        let patch=match (ord,self.patch) {
+            (Ordering::Equal,Some(patch))=>patch,
+            _=>return ord,
+        };
+        version.number.patch.cmp(&patch)
+            .then_with(|| cmp_pre_release(&version.pre,&self.pre) )
+    }
+
+    fn matches_exact(&self, version: &FullVersionNumber) -> bool {
+        version.number.major==self.major &&
+        self.minor.map_or(true,|minor| version.number.minor==minor ) &&
+        self.patch.map_or(true,|patch| version.number.patch==patch && *version.pre==*self.pre )
+    }
+
+    fn matches_tilde(&self, version: &FullVersionNumber) -> bool {
+        if version.number.major!=self.major {
+            return false;
+        }
+        match self.minor {
+            Some(minor) if version.number.minor!=minor=>return false,
+            Some(_)=>{}
+            None=>return true,
+        }
+        match self.patch {
+            Some(patch) if version.number.patch!=patch=>version.number.patch > patch,
+            Some(_)=>cmp_pre_release(&version.pre,&self.pre)!=Ordering::Less,
+            None=>true,
+        }
+    }
+
+    fn matches_caret(&self, version: &FullVersionNumber) -> bool {
+        if version.number.major!=self.major {
+            return false;
+        }
+        let minor=match self.minor {
+            Some(minor)=>minor,
+            None=>return true,
+        };
+        let patch=match self.patch {
+            Some(patch)=>patch,
+            None if self.major > 0=>return version.number.minor >= minor,
+            None=>return version.number.minor==minor,
+        };
+
+        if self.major > 0 {
+            if version.number.minor!=minor {
+                return version.number.minor > minor;
+            }
+        } else if minor > 0 {
+            if version.number.minor!=minor {
+                return false;
+            }
+        } else if version.number.minor!=minor || version.number.patch!=patch {
+            return false;
+        }
+
+        if version.number.patch!=patch {
+            return version.number.patch > patch;
+        }
+        cmp_pre_release(&version.pre,&self.pre)!=Ordering::Less
+    }
+}
+
+
+impl ParseVersionReqError {
+    /// The requirement that could not be parsed.
+    pub fn requirement(&self) -> &str {
+        &self.requirement
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+impl Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return f.write_str("*");
+        }
+        for (i,cmp) in self.comparators.iter().enumerate() {
+            if i!=0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(cmp,f)?;
+        }
+        Ok(())
+    }
+}
+
+
+impl Display for VersionComparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op=match self.op {
+            ComparatorOp::Exact=>"=",
+            ComparatorOp::Greater=>">",
+            ComparatorOp::GreaterEq=>">=",
+            ComparatorOp::Less=>"<",
+            ComparatorOp::LessEq=>"<=",
+            ComparatorOp::Tilde=>"~",
+            ComparatorOp::Caret=>"^",
+            ComparatorOp::Wildcard=>"",
+        };
+        write!(f,"{}{}",op,self.major)?;
+        match (self.minor,self.patch) {
+            (Some(minor),Some(patch))=>write!(f,".{}.{}",minor,patch)?,
+            (Some(minor),None)=>write!(f,".{}",minor)?,
+            (None,_)=>{}
+        }
+        if !self.pre.is_empty() {
+            write!(f,"-{}",self.pre)?;
+        }
+        if self.op==ComparatorOp::Wildcard {
+            f.write_str(".*")?;
+        }
+        Ok(())
+    }
+}
+
+
+impl Display for UnsatisfiedVersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsatisfiedVersionReq::Comparator(cmp)=>
+                write!(f,"the version does not satisfy the '{}' comparator",cmp),
+            UnsatisfiedVersionReq::PreRelease=>
+                f.write_str(
+                    "pre-release versions must be allowed by a comparator \
+                     with the same major.minor.patch version"
+                ),
+        }
+    }
+}
+
+
+impl Display for ParseVersionReqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "\nInvalid version requirement:'{}'\n{}",
+            self.requirement,
+            self.message,
+        )
+    }
+}
+
+impl error::Error for ParseVersionReqError {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests{
+    use super::*;
+
+    use crate::sabi_types::VersionStrings;
+
+    fn version(s:&'static str)->FullVersionNumber{
+        VersionStrings::new(s).parsed_full().unwrap()
+    }
+
+    fn assert_matches(req:&str,matching:&[&'static str],not_matching:&[&'static str]){
+        let parsed=VersionReq::parse(req).unwrap();
+        for v in matching {
+            assert!(parsed.matches(&version(v)),"'{}' should match '{}'",req,v);
+        }
+        for v in not_matching {
+            assert!(!parsed.matches(&version(v)),"'{}' should not match '{}'",req,v);
+        }
+    }
+
+    #[test]
+    fn caret(){
+        assert_matches("1.2.3",&["1.2.3","1.2.4","1.9.0"],&["1.2.2","2.0.0","0.9.0"]);
+        assert_matches("^1.2",&["1.2.0","1.5.1"],&["1.1.9","2.0.0"]);
+        assert_matches("^1",&["1.0.0","1.9.9"],&["0.9.0","2.0.0"]);
+        assert_matches("^0.2.3",&["0.2.3","0.2.9"],&["0.2.2","0.3.0"]);
+        assert_matches("^0.0.3",&["0.0.3"],&["0.0.4","0.0.2"]);
+        assert_matches("^0.0",&["0.0.0","0.0.9"],&["0.1.0"]);
+    }
+
+    #[test]
+    fn tilde_and_wildcard(){
+        assert_matches("~1.2.3",&["1.2.3","1.2.9"],&["1.3.0","1.2.2"]);
+        assert_matches("~1",&["1.0.0","1.9.0"],&["2.0.0"]);
+        assert_matches("1.*",&["1.0.0","1.9.0"],&["2.0.0","0.1.0"]);
+        assert_matches("1.2.x",&["1.2.0","1.2.9"],&["1.3.0"]);
+        assert_matches("*",&["0.0.1","99.0.0"],&["1.0.0-alpha"]);
+    }
+
+    #[test]
+    fn comparisons(){
+        assert_matches(">=1.2, <3",&["1.2.0","2.9.9"],&["1.1.9","3.0.0"]);
+        assert_matches(">1.2",&["1.3.0","2.0.0"],&["1.2.0","1.2.9"]);
+        assert_matches("<=1.2",&["1.2.9","1.0.0"],&["1.3.0"]);
+        assert_matches("=1.2.3",&["1.2.3"],&["1.2.4","1.2.3-rc.1"]);
+        assert_matches("=1.2",&["1.2.0","1.2.5"],&["1.3.0"]);
+    }
+
+    #[test]
+    fn pre_release(){
+        assert_matches(
+            ">=1.0.0-rc.1",
+            &["1.0.0-rc.1","1.0.0-rc.2","1.0.0-rc.10","1.0.0","1.5.0"],
+            &["1.0.0-beta.3","1.1.0-rc.1"],
+        );
+        assert_matches("^1.0.0-alpha",&["1.0.0-alpha","1.0.0-beta","1.2.0"],&["2.0.0-alpha"]);
+        assert_matches(">=0.9",&["1.0.0"],&["1.0.0-rc.1"]);
+
+        let req=VersionReq::parse(">=0.9").unwrap();
+        assert_eq!(req.check(&version("1.0.0-rc.1")),Err(UnsatisfiedVersionReq::PreRelease));
+
+        let req=VersionReq::parse(">=0.9, <1").unwrap();
+        match req.check(&version("1.0.0")) {
+            Err(UnsatisfiedVersionReq::Comparator(cmp))=>{
+                assert_eq!(cmp.to_string(),"<1");
+            }
+            x=>panic!("unexpected result:{:?}",x),
+        }
+    }
+
+    #[test]
+    fn parse_errors(){
+        for req in vec!["","^",">= ","1.2.3.4","a.b","*.1","1.*.3",">1.*","1.2-rc","1.2.3-",">=1,"] {
+            assert!(VersionReq::parse(req).is_err(),"'{}' should not parse",req);
+        }
+        for req in vec!["*","1","1.2","1.2.3","1.2.3-rc.1+build",">= 1.2"," ~1.2 ,<2 "] {
+            assert!(VersionReq::parse(req).is_ok(),"'{}' should parse",req);
+        }
+    }
+}
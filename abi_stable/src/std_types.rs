@@ -8,14 +8,23 @@ the [external_types module](../external_types/index.html)
 */
 
 pub mod arc;
+pub mod binary_heap;
 pub mod boxed;
+pub mod boxed_fn;
+pub mod btree_map;
+pub mod btree_set;
 pub mod cmp_ordering;
 pub mod cow;
+#[cfg(any(rust_1_36,feature="rust_1_36"))]
+pub mod future;
 //pub mod old_cow;
 pub mod option;
+pub mod os_str;
+pub mod path;
 pub mod map;
 pub mod range;
 pub mod result;
+pub mod set;
 pub mod slice_mut;
 pub mod slices;
 pub mod static_slice;
@@ -24,10 +33,13 @@ pub mod std_error;
 pub mod std_io;
 pub mod str;
 pub mod string;
+#[cfg(any(rust_1_36,feature="rust_1_36"))]
+pub mod task;
 pub mod time;
 pub mod tuple;
 pub mod utypeid;
 pub mod vec;
+pub mod vec_deque;
 
 
 /**
@@ -47,22 +59,42 @@ pub mod sync{}
 #[doc(inline)]
 pub use self::{
     arc::RArc,
+    binary_heap::RBinaryHeap,
     boxed::RBox,
+    boxed_fn::{
+        RBoxFn_,RBoxFn,SendRBoxFn,SyncRBoxFn,
+        RBoxFnMut_,RBoxFnMut,SendRBoxFnMut,SyncRBoxFnMut,
+        RBoxFnOnce_,RBoxFnOnce,SendRBoxFnOnce,SyncRBoxFnOnce,
+    },
+    btree_map::RBTreeMap,
+    btree_set::RBTreeSet,
     cmp_ordering::RCmpOrdering,
     cow::RCow,
     map::RHashMap,
     option::{RNone, ROption, RSome},
+    os_str::{ROsStr,ROsString},
+    path::{RPath,RPathBuf},
     result::{RErr, ROk, RResult},
+    set::RHashSet,
     slice_mut::RSliceMut,
     slices::RSlice,
     std_error::{RBoxError,SendRBoxError, UnsyncRBoxError},
     std_io::{RIoError,RSeekFrom, RIoErrorKind},
     str::RStr,
     string::RString,
-    time::RDuration,
+    time::{RDuration,RInstant,RSystemTime},
     tuple::{Tuple1,Tuple2, Tuple3, Tuple4},
     vec::RVec,
+    vec_deque::RVecDeque,
     utypeid::UTypeId,
     static_str::StaticStr,
     static_slice::StaticSlice,
-};
\ No newline at end of file
+};
+
+
+#[cfg(any(rust_1_36,feature="rust_1_36"))]
+#[doc(inline)]
+pub use self::{
+    future::{RFuture_,RFuture,UnsyncRFuture},
+    task::{RContext,RPending,RPoll,RReady,RWaker},
+};
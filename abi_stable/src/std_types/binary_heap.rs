@@ -0,0 +1,480 @@
+/*!
+Contains the ffi-safe equivalent of `std::collections::BinaryHeap`,and related items.
+*/
+
+use std::{
+    collections::BinaryHeap,
+    fmt::{self,Debug},
+    iter::FromIterator,
+    mem,
+    ops::{Deref,DerefMut},
+    slice,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::std_types::{
+    RVec,
+    vec,
+};
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests;
+
+
+/**
+Ffi-safe equivalent of `std::collections::BinaryHeap`,a priority queue implemented
+as a max-heap,in which `pop` returns the greatest element.
+
+The elements are stored in an `RVec<T>`,
+so growing and deallocating the heap is done through the vtable of `RVec<T>`,
+allowing an `RBinaryHeap<T>` to be mutated from any dynamic library/executable,
+regardless of which one created it.
+
+Methods that need to compare elements require `T:Ord`.
+
+# Example
+
+```
+use abi_stable::std_types::RBinaryHeap;
+
+let mut heap=RBinaryHeap::new();
+
+heap.push(3);
+heap.push(8);
+heap.push(5);
+
+assert_eq!(heap.peek(),Some(&8));
+
+assert_eq!(heap.pop(),Some(8));
+assert_eq!(heap.pop(),Some(5));
+assert_eq!(heap.pop(),Some(3));
+assert_eq!(heap.pop(),None);
+
+```
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBinaryHeap<T>{
+    data:RVec<T>,
+}
+
+
+impl<T> RBinaryHeap<T>{
+    /// Creates a new,empty `RBinaryHeap<T>`.
+    ///
+    /// This function does not allocate.
+    pub fn new()->Self{
+        RBinaryHeap{
+            data:RVec::new(),
+        }
+    }
+
+    /// Creates a new,empty `RBinaryHeap<T>`,with a capacity of `cap`.
+    ///
+    /// This function does not allocate if `cap`==0.
+    pub fn with_capacity(cap:usize)->Self{
+        RBinaryHeap{
+            data:RVec::with_capacity(cap),
+        }
+    }
+
+    /// Returns a reference to the greatest element,
+    /// returns None if the `RBinaryHeap<T>` is empty.
+    pub fn peek(&self)->Option<&T>{
+        self.data.first()
+    }
+
+    /// Returns the ammount of elements in the `RBinaryHeap<T>`.
+    pub fn len(&self)->usize{
+        self.data.len()
+    }
+
+    /// Whether the length of the `RBinaryHeap<T>` is 0.
+    pub fn is_empty(&self)->bool{
+        self.data.is_empty()
+    }
+
+    /// Returns the ammount of elements the `RBinaryHeap<T>` can hold without reallocating.
+    pub fn capacity(&self)->usize{
+        self.data.capacity()
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    /// This may reserve more than necessary for the additional capacity.
+    pub fn reserve(&mut self,additional:usize){
+        self.data.reserve(additional);
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    ///
+    /// Prefer using `reserve` for most situations.
+    pub fn reserve_exact(&mut self,additional:usize){
+        self.data.reserve_exact(additional);
+    }
+
+    /// Shrinks the capacity of the `RBinaryHeap<T>` to match its length.
+    pub fn shrink_to_fit(&mut self){
+        self.data.shrink_to_fit();
+    }
+
+    /// Removes all the elements from collection.
+    ///
+    /// Note:this has no effect on the capacity of the `RBinaryHeap<T>`.
+    pub fn clear(&mut self){
+        self.data.clear();
+    }
+
+    /// Iterates over references to all the elements,in an unspecified order.
+    pub fn iter(&self)->slice::Iter<'_,T>{
+        self.data.iter()
+    }
+
+    /// Creates a draining iterator that removes and yields all the elements,
+    /// in an unspecified order.
+    pub fn drain(&mut self)->vec::Drain<'_,T>{
+        self.data.drain(..)
+    }
+
+    /// Returns the underlying `RVec<T>`,with the elements in an unspecified order.
+    pub fn into_rvec(self)->RVec<T>{
+        self.data
+    }
+
+    /// Returns the elements in an unspecified order.
+    ///
+    /// # Allocation
+    ///
+    /// If this is invoked outside of the dynamic library/binary that created it,
+    /// it will allocate a new `Vec<T>` and move the data into it.
+    pub fn into_vec(self)->Vec<T>{
+        self.data.into_vec()
+    }
+}
+
+
+impl<T> RBinaryHeap<T>
+where
+    T:Ord,
+{
+    /// Adds `value` to the `RBinaryHeap<T>`.
+    pub fn push(&mut self,value:T){
+        self.data.push(value);
+        let last=self.len()-1;
+        self.sift_up(last);
+    }
+
+    /// Attempts to remove the greatest element,
+    /// returns None if the `RBinaryHeap<T>` is empty.
+    pub fn pop(&mut self)->Option<T>{
+        let mut item=self.data.pop()?;
+        if !self.is_empty() {
+            mem::swap(&mut item,&mut self.data[0]);
+            let len=self.len();
+            self.sift_down_range(0,len);
+        }
+        Some(item)
+    }
+
+    /// Returns a mutable reference to the greatest element,
+    /// returns None if the `RBinaryHeap<T>` is empty.
+    ///
+    /// The heap is reordered when the returned `PeekMut<'_,T>` is dropped.
+    pub fn peek_mut(&mut self)->Option<PeekMut<'_,T>>{
+        if self.is_empty() {
+            None
+        }else{
+            Some(PeekMut{ heap:self, sift:true })
+        }
+    }
+
+    /// Moves all the elements of `other` into this `RBinaryHeap<T>`,
+    /// leaving `other` empty.
+    pub fn append(&mut self,other:&mut Self){
+        self.extend(other.drain());
+    }
+
+    /// Returns the elements sorted in ascending order.
+    ///
+    /// # Allocation
+    ///
+    /// If this is invoked outside of the dynamic library/binary that created it,
+    /// it will allocate a new `Vec<T>` and move the data into it.
+    pub fn into_sorted_vec(mut self)->Vec<T>{
+        let mut end=self.len();
+        while end > 1 {
+            end-=1;
+            self.data.swap(0,end);
+            self.sift_down_range(0,end);
+        }
+        self.into_vec()
+    }
+
+    /// Moves the element at `pos` up the heap until its parent is not smaller than it.
+    fn sift_up(&mut self,mut pos:usize){
+        let data=&mut self.data[..];
+        while pos > 0 {
+            let parent=(pos-1)/2;
+            if data[pos] <= data[parent] {
+                break;
+            }
+            data.swap(pos,parent);
+            pos=parent;
+        }
+    }
+
+    /// Moves the element at `pos` down the heap until its children are not greater than it,
+    /// only considering the elements before `end`.
+    fn sift_down_range(&mut self,mut pos:usize,end:usize){
+        let data=&mut self.data[..end];
+        loop {
+            let mut child=2*pos+1;
+            if child >= end {
+                break;
+            }
+            if child+1 < end && data[child] < data[child+1] {
+                child+=1;
+            }
+            if data[pos] >= data[child] {
+                break;
+            }
+            data.swap(pos,child);
+            pos=child;
+        }
+    }
+
+    /// Reorders all the elements so that they form a heap.
+    fn rebuild(&mut self){
+        let len=self.len();
+        for pos in (0..len/2).rev() {
+            self.sift_down_range(pos,len);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+/// A mutable reference to the greatest element of an `RBinaryHeap<T>`,
+/// returned by `RBinaryHeap::peek_mut`.
+///
+/// The heap is reordered when this is dropped.
+pub struct PeekMut<'a,T>
+where
+    T:Ord,
+{
+    heap:&'a mut RBinaryHeap<T>,
+    sift:bool,
+}
+
+impl<'a,T> PeekMut<'a,T>
+where
+    T:Ord,
+{
+    /// Removes the peeked element from the heap,returning it.
+    pub fn pop(mut this:Self)->T{
+        this.sift=false;
+        this.heap.pop().expect("PeekMut is only constructed for non-empty heaps")
+    }
+}
+
+impl<'a,T> Deref for PeekMut<'a,T>
+where
+    T:Ord,
+{
+    type Target=T;
+
+    fn deref(&self)->&T{
+        &self.heap.data[0]
+    }
+}
+
+impl<'a,T> DerefMut for PeekMut<'a,T>
+where
+    T:Ord,
+{
+    fn deref_mut(&mut self)->&mut T{
+        &mut self.heap.data[0]
+    }
+}
+
+impl<'a,T> Drop for PeekMut<'a,T>
+where
+    T:Ord,
+{
+    fn drop(&mut self){
+        if self.sift {
+            let len=self.heap.len();
+            self.heap.sift_down_range(0,len);
+        }
+    }
+}
+
+impl<'a,T> Debug for PeekMut<'a,T>
+where
+    T:Ord+Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_tuple("PeekMut")
+         .field(&**self)
+         .finish()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+impl<T> Clone for RBinaryHeap<T>
+where
+    T:Clone,
+{
+    fn clone(&self)->Self{
+        RBinaryHeap{
+            data:self.data.clone(),
+        }
+    }
+}
+
+impl<T> Default for RBinaryHeap<T>{
+    fn default()->Self{
+        Self::new()
+    }
+}
+
+impl<T> Debug for RBinaryHeap<T>
+where
+    T:Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_list()
+         .entries(self.iter())
+         .finish()
+    }
+}
+
+
+impl<T> From<RVec<T>> for RBinaryHeap<T>
+where
+    T:Ord,
+{
+    fn from(data:RVec<T>)->Self{
+        let mut heap=RBinaryHeap{data};
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T> From<Vec<T>> for RBinaryHeap<T>
+where
+    T:Ord,
+{
+    fn from(data:Vec<T>)->Self{
+        RVec::from(data).into()
+    }
+}
+
+impl_from_rust_repr! {
+    impl[T] From<BinaryHeap<T>> for RBinaryHeap<T>{
+        fn(this){
+            // The elements of a BinaryHeap are already ordered as a heap.
+            RBinaryHeap{
+                data:this.into_vec().into(),
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<BinaryHeap<T>> for RBinaryHeap<T>
+    where[
+        T:Ord,
+    ]{
+        fn(this){
+            this.into_vec().into()
+        }
+    }
+}
+
+
+impl<T> FromIterator<T> for RBinaryHeap<T>
+where
+    T:Ord,
+{
+    fn from_iter<I>(iter:I)->Self
+    where
+        I:IntoIterator<Item=T>,
+    {
+        iter.piped(RVec::from_iter).piped(Self::from)
+    }
+}
+
+impl<T> Extend<T> for RBinaryHeap<T>
+where
+    T:Ord,
+{
+    fn extend<I>(&mut self,iter:I)
+    where
+        I:IntoIterator<Item=T>,
+    {
+        let iter=iter.into_iter();
+        let (lower,_)=iter.size_hint();
+        self.reserve(lower);
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+
+/// This returns an iterator that yields all the elements in an unspecified order.
+impl<T> IntoIterator for RBinaryHeap<T>{
+    type Item=T;
+
+    type IntoIter=vec::IntoIter<T>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.data.into_iter()
+    }
+}
+
+/// This returns an iterator over references to all the elements in an unspecified order.
+impl<'a,T> IntoIterator for &'a RBinaryHeap<T>{
+    type Item=&'a T;
+
+    type IntoIter=slice::Iter<'a,T>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.iter()
+    }
+}
+
+
+impl<'de,T> Deserialize<'de> for RBinaryHeap<T>
+where
+    T:Deserialize<'de>+Ord,
+{
+    fn deserialize<D>(deserializer:D)->Result<Self,D::Error>
+    where
+        D:Deserializer<'de>,
+    {
+        <RVec<T>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T> Serialize for RBinaryHeap<T>
+where
+    T:Serialize,
+{
+    fn serialize<S>(&self,serializer:S)->Result<S::Ok,S::Error>
+    where
+        S:Serializer,
+    {
+        self.data.serialize(serializer)
+    }
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+use crate::std_types::RString;
+
+
+fn assert_is_heap<T:Ord>(heap:&RBinaryHeap<T>){
+    let data=&heap.data[..];
+    for i in 1..data.len() {
+        assert!(data[(i-1)/2] >= data[i]);
+    }
+}
+
+const UNSORTED:[u32;10]=[5,13,1,8,21,3,2,1,34,0];
+
+fn sorted()->Vec<u32>{
+    let mut list=UNSORTED.to_vec();
+    list.sort();
+    list
+}
+
+
+#[test]
+fn push_pop(){
+    let mut heap=RBinaryHeap::new();
+    for &elem in &UNSORTED {
+        heap.push(elem);
+        assert_is_heap(&heap);
+    }
+    assert_eq!(heap.len(),UNSORTED.len());
+    assert_eq!(heap.peek(),Some(&34));
+
+    let mut popped=Vec::new();
+    while let Some(elem)=heap.pop() {
+        assert_is_heap(&heap);
+        popped.push(elem);
+    }
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek(),None);
+
+    popped.reverse();
+    assert_eq!(popped,sorted());
+}
+
+
+#[test]
+fn peek_mut(){
+    let mut heap=UNSORTED.iter().cloned().collect::<RBinaryHeap<u32>>();
+
+    *heap.peek_mut().unwrap()=4;
+    assert_is_heap(&heap);
+    assert_eq!(heap.peek(),Some(&21));
+
+    assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()),21);
+    assert_is_heap(&heap);
+    assert_eq!(heap.peek(),Some(&13));
+    assert_eq!(heap.len(),UNSORTED.len()-1);
+
+    assert!(RBinaryHeap::<u32>::new().peek_mut().is_none());
+}
+
+
+#[test]
+fn into_sorted_vec(){
+    let heap=RBinaryHeap::from(UNSORTED.to_vec());
+    assert_is_heap(&heap);
+    assert_eq!(heap.into_sorted_vec(),sorted());
+}
+
+
+#[test]
+fn append_drain_clear(){
+    let mut heap=UNSORTED[..5].iter().cloned().collect::<RBinaryHeap<u32>>();
+    let mut other=UNSORTED[5..].iter().cloned().collect::<RBinaryHeap<u32>>();
+
+    heap.append(&mut other);
+    assert!(other.is_empty());
+    assert_is_heap(&heap);
+    assert_eq!(heap.clone().into_sorted_vec(),sorted());
+
+    let mut drained=heap.drain().collect::<Vec<_>>();
+    drained.sort();
+    assert_eq!(drained,sorted());
+    assert!(heap.is_empty());
+
+    heap.extend(UNSORTED.iter().cloned());
+    heap.clear();
+    assert!(heap.is_empty());
+}
+
+
+#[test]
+fn conversions(){
+    let std_heap=UNSORTED.iter().cloned().collect::<BinaryHeap<u32>>();
+
+    let heap=RBinaryHeap::from(std_heap.clone());
+    assert_is_heap(&heap);
+    assert_eq!(heap.peek(),std_heap.peek());
+
+    let back:BinaryHeap<u32>=heap.clone().into();
+    assert_eq!(back.into_sorted_vec(),sorted());
+
+    let mut list=heap.clone().into_iter().collect::<Vec<_>>();
+    list.sort();
+    assert_eq!(list,sorted());
+
+    let mut list=heap.into_rvec().into_vec();
+    list.sort();
+    assert_eq!(list,sorted());
+}
+
+
+#[test]
+fn strings(){
+    let mut heap=RBinaryHeap::<RString>::with_capacity(4);
+    for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+        heap.push(word.into());
+    }
+    assert_eq!(heap.pop().map(|x| x.into_string() ),Some("the".to_string()));
+    assert_eq!(heap.pop().map(|x| x.into_string() ),Some("the".to_string()));
+    assert_eq!(heap.pop().map(|x| x.into_string() ),Some("quick".to_string()));
+}
+
+
+#[test]
+fn test_serde(){
+    let heap=UNSORTED.iter().cloned().collect::<RBinaryHeap<u32>>();
+
+    let serialized=serde_json::to_string(&heap).unwrap();
+    let deserialized=serde_json::from_str::<RBinaryHeap<u32>>(&serialized).unwrap();
+    assert_eq!(deserialized.into_sorted_vec(),sorted());
+
+    let deserialized=serde_json::from_str::<RBinaryHeap<u32>>("[3,1,4,1,5]").unwrap();
+    assert_is_heap(&deserialized);
+    assert_eq!(deserialized.into_sorted_vec(),vec![1,1,3,4,5]);
+}
@@ -0,0 +1,407 @@
+/*!
+Ffi-safe equivalents of `Box<dyn Fn(..)->R>`,`Box<dyn FnMut(..)->R>`,
+and `Box<dyn FnOnce(..)->R>`,
+whose `Send+Sync`ness is determined by the `M` type parameter.
+
+The arguments of these closures are passed as a single tuple `A`,
+which is one of `()`,`Tuple1<_>`,`Tuple2<_,_>`,`Tuple3<_,_,_>`,or `Tuple4<_,_,_,_>`.
+
+The std `Fn*` traits can't be implemented outside the standard library in stable Rust,
+so these types are called with the `call`/`call_mut`/`call_once` methods,
+or converted to a closure with the `into_fn`/`into_fn_mut`/`into_fn_once` methods.
+
+For borrowed closures look at
+[the `RFn`/`RFnMut`/`RFnOnce` types](../../sabi_types/rfn/index.html).
+
+# Example
+
+```
+use abi_stable::std_types::{RBoxFnMut,SendRBoxFnOnce,RString,Tuple1,Tuple2};
+
+let mut total=0;
+{
+    let mut add=RBoxFnMut::new(|x:u32| total+=x );
+    add.call_mut(Tuple1(3));
+    add.call_mut(Tuple1(5));
+}
+assert_eq!(total,8);
+
+let greeting=String::from("hello");
+let task:SendRBoxFnOnce<'_,Tuple2<u32,u32>,RString>=
+    SendRBoxFnOnce::new(move|l:u32,r:u32| format!("{} {}",greeting,l+r).into() );
+
+let ret=std::thread::spawn(move|| task.into_fn_once()(3,5) ).join().unwrap();
+assert_eq!(ret.as_str(),"hello 8");
+
+```
+
+*/
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+};
+
+use crate::{
+    marker_type::{ErasedObject,SyncSend,UnsyncSend,UnsyncUnsend},
+    sabi_types::rfn::{
+        RFn,RFnMut,TupledFn,TupledFnMut,TupledFnOnce,
+        call_fn,call_fn_mut,
+    },
+    std_types::{RBox,Tuple1,Tuple2,Tuple3,Tuple4},
+    utils::transmute_reference,
+};
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..)->R+'a>`,
+/// whose `Send+Sync`ness is determined by the `M` type parameter.
+///
+/// `A` is the tuple of arguments that the closure takes.
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBoxFn_<'a,A,R,M=UnsyncUnsend>{
+    env:RBox<ErasedObject>,
+    call:unsafe extern "C" fn(env:&ErasedObject,args:A)->R,
+    _marker:PhantomData<Tuple2<&'a (),M>>,
+}
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..)->R+'a>`.
+pub type RBoxFn<'a,A,R>=RBoxFn_<'a,A,R,UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..)->R+Send+'a>`.
+pub type SendRBoxFn<'a,A,R>=RBoxFn_<'a,A,R,UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..)->R+Send+Sync+'a>`.
+pub type SyncRBoxFn<'a,A,R>=RBoxFn_<'a,A,R,SyncSend>;
+
+
+impl<'a,A,R,M> RBoxFn_<'a,A,R,M>{
+    fn new_inner<F>(f:F)->Self
+    where
+        F:TupledFn<A,Output=R>+'a,
+    {
+        unsafe{
+            RBoxFn_{
+                env:mem::transmute::<RBox<F>,RBox<ErasedObject>>(RBox::new(f)),
+                call:call_fn::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call(&self,args:A)->R{
+        unsafe{ (self.call)(&self.env,args) }
+    }
+
+    /// Borrows this as an `RFn`.
+    pub fn as_rfn(&self)->RFn<'_,A,R>{
+        unsafe{ RFn::from_raw_parts(&self.env,self.call) }
+    }
+
+    /// Converts this `RBoxFn_<_>` to `RBoxFn`.
+    pub fn into_unsync(self)->RBoxFn<'a,A,R>{
+        unsafe{
+            mem::transmute::<RBoxFn_<'a,A,R,M>,RBoxFn<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R> RBoxFn_<'a,A,R,SyncSend>{
+    /// Casts this `&SyncRBoxFn` to `&SendRBoxFn`.
+    pub fn as_send(&self)->&SendRBoxFn<'a,A,R>{
+        unsafe{
+            transmute_reference::<SyncRBoxFn<'a,A,R>,SendRBoxFn<'a,A,R>>(self)
+        }
+    }
+
+    /// Converts this `SyncRBoxFn` to `SendRBoxFn`.
+    pub fn into_send(self)->SendRBoxFn<'a,A,R>{
+        unsafe{
+            mem::transmute::<SyncRBoxFn<'a,A,R>,SendRBoxFn<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R,M> fmt::Debug for RBoxFn_<'a,A,R,M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RBoxFn{..}")
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..)->R+'a>`,
+/// whose `Send+Sync`ness is determined by the `M` type parameter.
+///
+/// `A` is the tuple of arguments that the closure takes.
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBoxFnMut_<'a,A,R,M=UnsyncUnsend>{
+    env:RBox<ErasedObject>,
+    call:unsafe extern "C" fn(env:&mut ErasedObject,args:A)->R,
+    _marker:PhantomData<Tuple2<&'a (),M>>,
+}
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..)->R+'a>`.
+pub type RBoxFnMut<'a,A,R>=RBoxFnMut_<'a,A,R,UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..)->R+Send+'a>`.
+pub type SendRBoxFnMut<'a,A,R>=RBoxFnMut_<'a,A,R,UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..)->R+Send+Sync+'a>`.
+pub type SyncRBoxFnMut<'a,A,R>=RBoxFnMut_<'a,A,R,SyncSend>;
+
+
+impl<'a,A,R,M> RBoxFnMut_<'a,A,R,M>{
+    fn new_inner<F>(f:F)->Self
+    where
+        F:TupledFnMut<A,Output=R>+'a,
+    {
+        unsafe{
+            RBoxFnMut_{
+                env:mem::transmute::<RBox<F>,RBox<ErasedObject>>(RBox::new(f)),
+                call:call_fn_mut::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call_mut(&mut self,args:A)->R{
+        unsafe{ (self.call)(&mut self.env,args) }
+    }
+
+    /// Borrows this as an `RFnMut`.
+    pub fn as_rfn_mut(&mut self)->RFnMut<'_,A,R>{
+        unsafe{ RFnMut::from_raw_parts(&mut self.env,self.call) }
+    }
+
+    /// Converts this `RBoxFnMut_<_>` to `RBoxFnMut`.
+    pub fn into_unsync(self)->RBoxFnMut<'a,A,R>{
+        unsafe{
+            mem::transmute::<RBoxFnMut_<'a,A,R,M>,RBoxFnMut<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R> RBoxFnMut_<'a,A,R,SyncSend>{
+    /// Converts this `SyncRBoxFnMut` to `SendRBoxFnMut`.
+    pub fn into_send(self)->SendRBoxFnMut<'a,A,R>{
+        unsafe{
+            mem::transmute::<SyncRBoxFnMut<'a,A,R>,SendRBoxFnMut<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R,M> fmt::Debug for RBoxFnMut_<'a,A,R,M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RBoxFnMut{..}")
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..)->R+'a>`,
+/// whose `Send+Sync`ness is determined by the `M` type parameter.
+///
+/// `A` is the tuple of arguments that the closure takes.
+///
+/// The closure is dropped without being called if this is dropped.
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RBoxFnOnce_<'a,A,R,M=UnsyncUnsend>{
+    env:RBox<ErasedObject>,
+    call:unsafe extern "C" fn(env:RBox<ErasedObject>,args:A)->R,
+    _marker:PhantomData<Tuple2<&'a (),M>>,
+}
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..)->R+'a>`.
+pub type RBoxFnOnce<'a,A,R>=RBoxFnOnce_<'a,A,R,UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..)->R+Send+'a>`.
+pub type SendRBoxFnOnce<'a,A,R>=RBoxFnOnce_<'a,A,R,UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..)->R+Send+Sync+'a>`.
+pub type SyncRBoxFnOnce<'a,A,R>=RBoxFnOnce_<'a,A,R,SyncSend>;
+
+
+impl<'a,A,R,M> RBoxFnOnce_<'a,A,R,M>{
+    fn new_inner<F>(f:F)->Self
+    where
+        F:TupledFnOnce<A,Output=R>+'a,
+    {
+        unsafe{
+            RBoxFnOnce_{
+                env:mem::transmute::<RBox<F>,RBox<ErasedObject>>(RBox::new(f)),
+                call:call_box_fn_once::<F,A,R>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Calls the closure.
+    pub fn call_once(self,args:A)->R{
+        unsafe{ (self.call)(self.env,args) }
+    }
+
+    /// Converts this `RBoxFnOnce_<_>` to `RBoxFnOnce`.
+    pub fn into_unsync(self)->RBoxFnOnce<'a,A,R>{
+        unsafe{
+            mem::transmute::<RBoxFnOnce_<'a,A,R,M>,RBoxFnOnce<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R> RBoxFnOnce_<'a,A,R,SyncSend>{
+    /// Converts this `SyncRBoxFnOnce` to `SendRBoxFnOnce`.
+    pub fn into_send(self)->SendRBoxFnOnce<'a,A,R>{
+        unsafe{
+            mem::transmute::<SyncRBoxFnOnce<'a,A,R>,SendRBoxFnOnce<'a,A,R>>(self)
+        }
+    }
+}
+
+impl<'a,A,R,M> fmt::Debug for RBoxFnOnce_<'a,A,R,M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RBoxFnOnce{..}")
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+macro_rules! declare_constructors {
+    (
+        bounds=( $($bounds:tt)* ) ,
+        marker=$marker:ident,
+    ) => (
+        impl<'a,A,R> RBoxFn_<'a,A,R,$marker>{
+            /// Constructs this from a closure.
+            pub fn new<F>(f:F)->Self
+            where
+                F:TupledFn<A,Output=R> $($bounds)* +'a,
+            {
+                Self::new_inner(f)
+            }
+        }
+
+        impl<'a,A,R> RBoxFnMut_<'a,A,R,$marker>{
+            /// Constructs this from a closure.
+            pub fn new<F>(f:F)->Self
+            where
+                F:TupledFnMut<A,Output=R> $($bounds)* +'a,
+            {
+                Self::new_inner(f)
+            }
+        }
+
+        impl<'a,A,R> RBoxFnOnce_<'a,A,R,$marker>{
+            /// Constructs this from a closure.
+            pub fn new<F>(f:F)->Self
+            where
+                F:TupledFnOnce<A,Output=R> $($bounds)* +'a,
+            {
+                Self::new_inner(f)
+            }
+        }
+    )
+}
+
+declare_constructors!{
+    bounds=( + Send + Sync ) ,
+    marker=SyncSend,
+}
+declare_constructors!{
+    bounds=( + Send ) ,
+    marker=UnsyncSend,
+}
+declare_constructors!{
+    bounds=( ) ,
+    marker=UnsyncUnsend,
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+macro_rules! impl_into_fn {
+    ( $( ($tuple:ty,$tuple_expr:expr)[ $($param:ident),* ] )* ) => (
+        $(
+            impl<'a,$($param,)* R,M> RBoxFn_<'a,$tuple,R,M>
+            where
+                $($param:'a,)*
+                R:'a,
+                M:'a,
+            {
+                /// Converts this into a closure that takes the arguments separately.
+                pub fn into_fn(self)->impl Fn($($param,)*)->R+'a {
+                    move|$($param,)*| self.call($tuple_expr)
+                }
+            }
+
+            impl<'a,$($param,)* R,M> RBoxFnMut_<'a,$tuple,R,M>
+            where
+                $($param:'a,)*
+                R:'a,
+                M:'a,
+            {
+                /// Converts this into a closure that takes the arguments separately.
+                pub fn into_fn_mut(mut self)->impl FnMut($($param,)*)->R+'a {
+                    move|$($param,)*| self.call_mut($tuple_expr)
+                }
+            }
+
+            impl<'a,$($param,)* R,M> RBoxFnOnce_<'a,$tuple,R,M>
+            where
+                $($param:'a,)*
+                R:'a,
+                M:'a,
+            {
+                /// Converts this into a closure that takes the arguments separately.
+                pub fn into_fn_once(self)->impl FnOnce($($param,)*)->R+'a {
+                    move|$($param,)*| self.call_once($tuple_expr)
+                }
+            }
+        )*
+    )
+}
+
+#[allow(non_snake_case)]
+mod into_fn_impls{
+    use super::*;
+
+    impl_into_fn!{
+        ((),())[]
+        (Tuple1<A0>,Tuple1(A0))[A0]
+        (Tuple2<A0,A1>,Tuple2(A0,A1))[A0,A1]
+        (Tuple3<A0,A1,A2>,Tuple3(A0,A1,A2))[A0,A1,A2]
+        (Tuple4<A0,A1,A2,A3>,Tuple4(A0,A1,A2,A3))[A0,A1,A2,A3]
+    }
+}
+
+
+//////////////////////////////////////////////////////////////////////////////
+
+
+unsafe extern "C" fn call_box_fn_once<F,A,R>(env:RBox<ErasedObject>,args:A)->R
+where
+    F:TupledFnOnce<A,Output=R>,
+{
+    extern_fn_panic_handling!{
+        let f=RBox::into_inner(mem::transmute::<RBox<ErasedObject>,RBox<F>>(env));
+        f.call_once_tupled(args)
+    }
+}
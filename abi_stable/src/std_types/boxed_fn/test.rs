@@ -0,0 +1,115 @@
+use super::*;
+
+use std::{
+    rc::Rc,
+    sync::Arc,
+    thread,
+};
+
+use crate::std_types::{RStr,RString};
+
+
+fn assert_send_sync<T:Send+Sync>(_:&T){}
+
+fn assert_send<T:Send>(_:&T){}
+
+
+#[test]
+fn rboxfn(){
+    let sep=String::from(", ");
+    let join=RBoxFn::new(move|l:RStr<'_>,r:RStr<'_>|->RString{
+        format!("{}{}{}",l,sep,r).into()
+    });
+    assert_eq!(join.call(Tuple2("hello".into(),"world".into())).as_str(),"hello, world");
+    assert_eq!(join.as_rfn().call(Tuple2("a".into(),"b".into())).as_str(),"a, b");
+
+    let join=join.into_fn();
+    assert_eq!(join("c".into(),"d".into()).as_str(),"c, d");
+
+    let answer=RBoxFn::new(||42);
+    assert_eq!(answer.call(()),42);
+    assert_eq!(answer.into_fn()(),42);
+}
+
+
+#[test]
+fn rboxfn_mut(){
+    let mut list=Vec::<String>::new();
+    {
+        let mut push=RBoxFnMut::new(|s:RStr<'_>| list.push(s.into()) );
+        push.call_mut(Tuple1("hello".into()));
+        push.as_rfn_mut().call_mut(Tuple1("world".into()));
+
+        let mut push=push.into_fn_mut();
+        push("foo".into());
+    }
+    assert_eq!(list,vec!["hello","world","foo"]);
+
+    let mut total=0;
+    {
+        let mut add=RBoxFnMut::new(|a:u32,b:u32,c:u32,d:u32| total+=a+b+c+d );
+        add.call_mut(Tuple4(1,2,3,4));
+        add.call_mut(Tuple4(5,6,7,8));
+    }
+    assert_eq!(total,36);
+}
+
+
+#[test]
+fn rboxfn_once(){
+    let text=String::from("hello");
+    let into_string=RBoxFnOnce::new(move|suffix:RStr<'_>| text+suffix.as_str() );
+    assert_eq!(into_string.call_once(Tuple1(" world".into())),"hello world");
+
+    let text=String::from("foo");
+    let into_string=RBoxFnOnce::new(move|| text );
+    assert_eq!(into_string.into_fn_once()(),"foo");
+
+    // The closure is dropped if it's never called.
+    let counter=Rc::new(());
+    let cloned=counter.clone();
+    let uncalled=RBoxFnOnce::<(),()>::new(move||drop(cloned));
+    assert_eq!(Rc::strong_count(&counter),2);
+    drop(uncalled);
+    assert_eq!(Rc::strong_count(&counter),1);
+}
+
+
+#[test]
+fn send_sync(){
+    let counter=Arc::new(());
+
+    let cloned=counter.clone();
+    let func=SyncRBoxFn::new(move|x:u32| x+Arc::strong_count(&cloned) as u32 );
+    assert_send_sync(&func);
+    let func=Arc::new(func);
+    let handles=(0..4)
+        .map(|i|{
+            let func=func.clone();
+            thread::spawn(move|| func.call(Tuple1(i)) )
+        })
+        .collect::<Vec<_>>();
+    for (i,handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(),i as u32+2);
+    }
+    drop(func);
+    assert_eq!(Arc::strong_count(&counter),1);
+
+    let mut calls=0;
+    let mut func=SendRBoxFnMut::new(move||{ calls+=1; calls });
+    assert_send(&func);
+    let func=thread::spawn(move||{
+        func.call_mut(());
+        func
+    }).join().unwrap();
+    assert_eq!(func.into_fn_mut()(),2);
+
+    let cloned=counter.clone();
+    let task=SyncRBoxFnOnce::new(move||Arc::strong_count(&cloned)).into_send();
+    assert_send(&task);
+    assert_eq!(thread::spawn(move|| task.call_once(()) ).join().unwrap(),2);
+    assert_eq!(Arc::strong_count(&counter),1);
+
+    let unsync=SyncRBoxFnMut::new(||()).into_send().into_unsync();
+    drop(unsync);
+}
@@ -0,0 +1,757 @@
+/*!
+Contains the ffi-safe equivalent of `std::collections::BTreeMap`,and related items.
+*/
+
+use std::{
+    borrow::Borrow,
+    collections::BTreeMap,
+    cmp::{Ord,Ordering,PartialOrd},
+    fmt::{self,Debug},
+    hash::{Hash,Hasher},
+    ops::{Bound,Index,IndexMut,RangeBounds},
+    iter::FromIterator,
+    ptr::NonNull,
+    marker::PhantomData,
+    mem,
+};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::{
+    DynTrait,
+    StableAbi,
+    marker_type::{ErasedObject,NotCopyNotClone,UnsafeIgnoredType},
+    prefix_type::{PrefixTypeTrait,WithMetadata},
+    std_types::*,
+    traits::{IntoReprRust,ErasedType},
+    utils::{transmute_reference,transmute_mut_reference},
+};
+
+
+mod entry;
+mod extern_fns;
+mod iterator_stuff;
+mod map_query;
+mod map_key;
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+use self::{
+    map_query::{MapQuery,QueryBound,check_range},
+    map_key::MapKey,
+    entry::{BoxedREntry},
+};
+
+pub use self::{
+    iterator_stuff::{
+        RefIterInterface,MutIterInterface,ValIterInterface,
+        IntoIter,
+    },
+    entry::{REntry,ROccupiedEntry,RVacantEntry},
+};
+
+
+/**
+
+An ffi-safe ordered map,which wraps `std::collections::BTreeMap<K,V>`,
+only requiring the `K:Ord` bound when constructing it.
+
+Most of the API in `BTreeMap` is implemented here,
+including the Entry API and range queries.
+
+The entries are iterated over in ascending order of the keys.
+
+# Example
+
+```
+use abi_stable::std_types::{RBTreeMap,RString,Tuple2};
+
+let mut map=RBTreeMap::<RString,u32>::new();
+
+map.insert("c".into(),3);
+map.insert("a".into(),1);
+map.insert("b".into(),2);
+
+assert_eq!(map.get("b"),Some(&2));
+
+let keys=map.iter().map(|Tuple2(k,_)| k.as_str() ).collect::<Vec<_>>();
+assert_eq!(keys,vec!["a","b","c"]);
+
+let range=map.range(RString::from("b")..).map(|Tuple2(_,v)| *v ).collect::<Vec<_>>();
+assert_eq!(range,vec![2,3]);
+
+*map.entry("d".into()).or_insert(0)+=4;
+assert_eq!(map["d"],4);
+
+```
+
+*/
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct RBTreeMap<K,V>{
+    map:RBox<ErasedMap<K,V>>,
+    vtable:*const VTable<K,V>,
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+struct BoxedBTreeMap<'a,K,V>{
+    map:BTreeMap<MapKey<K>,V>,
+    entry:Option<BoxedREntry<'a,K,V>>,
+}
+
+/// An RBTreeMap iterator,
+/// implementing `Iterator<Item= Tuple2< &K, &V > >+DoubleEndedIterator+!Send+!Sync+Clone`
+pub type Iter<'a,K,V>=
+    DynTrait<'a,RBox<()>,RefIterInterface<K,V>>;
+
+/// An RBTreeMap iterator,
+/// implementing `Iterator<Item= Tuple2< &K, &mut V > >+DoubleEndedIterator+!Send+!Sync`
+pub type IterMut<'a,K,V>=
+    DynTrait<'a,RBox<()>,MutIterInterface<K,V>>;
+
+
+/// Used as the erased type of the RBTreeMap type.
+#[repr(C)]
+#[derive(StableAbi)]
+struct ErasedMap<K,V>(
+    PhantomData<Tuple2<K,V>>,
+);
+
+unsafe impl<'a,K:'a,V:'a> ErasedType<'a> for ErasedMap<K,V> {
+    type Unerased=BoxedBTreeMap<'a,K,V>;
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+impl<K,V> RBTreeMap<K,V>{
+    /// Constructs an empty RBTreeMap.
+    pub fn new()->RBTreeMap<K,V>
+    where
+        K:Ord,
+    {
+        RBTreeMap{
+            map:VTable::<K,V>::erased_map(),
+            vtable:unsafe{
+                (*VTable::VTABLE_REF).as_prefix_raw()
+            },
+        }
+    }
+
+    fn vtable<'a>(&self)->&'a VTable<K,V>{
+        unsafe{
+            &*self.vtable
+        }
+    }
+}
+
+
+impl<K,V> RBTreeMap<K,V>{
+    /// Returns whether the map associates a value with the key.
+    pub fn contains_key<Q>(&self,query:&Q)->bool
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        self.get(query).is_some()
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get<Q>(&self,query:&Q)->Option<&V>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        let vtable=self.vtable();
+        vtable.get_elem()(&*self.map,MapQuery::new(&query))
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut<Q>(&mut self,query:&Q)->Option<&mut V>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        let vtable=self.vtable();
+        vtable.get_mut_elem()(&mut *self.map,MapQuery::new(&query))
+    }
+
+    /// Removes the value associated with the key.
+    pub fn remove<Q>(&mut self,query:&Q)->ROption<V>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        self.remove_entry(query).map(|x| x.1 )
+    }
+
+    /// Removes the entry for the key.
+    pub fn remove_entry<Q>(&mut self,query:&Q)->ROption<Tuple2<K,V>>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        let vtable=self.vtable();
+        vtable.remove_entry()(&mut *self.map,MapQuery::new(&query))
+    }
+
+    /// Iterates over the entries in the map whose keys are in the range,
+    /// with references to the values in the map.
+    ///
+    /// This returns an
+    /// `Iterator<Item= Tuple2< &K, &V > >+DoubleEndedIterator+!Send+!Sync+Clone`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end,
+    /// or if the start is equal to the end and both bounds are `Excluded`.
+    pub fn range<Q,R>(&self,range:R)->Iter<'_,K,V>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized,
+        R:RangeBounds<Q>,
+    {
+        let start=range.start_bound();
+        let end=range.end_bound();
+        check_range(start,end);
+
+        let vtable=self.vtable();
+        vtable.range()(&*self.map,QueryBound::new(&start),QueryBound::new(&end))
+    }
+
+    /// Iterates over the entries in the map whose keys are in the range,
+    /// with mutable references to the values in the map.
+    ///
+    /// This returns an
+    /// `Iterator<Item= Tuple2< &K, &mut V > >+DoubleEndedIterator+!Send+!Sync`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end,
+    /// or if the start is equal to the end and both bounds are `Excluded`.
+    pub fn range_mut<Q,R>(&mut self,range:R)->IterMut<'_,K,V>
+    where
+        K:Borrow<Q>,
+        Q:Ord+?Sized,
+        R:RangeBounds<Q>,
+    {
+        let start=range.start_bound();
+        let end=range.end_bound();
+        check_range(start,end);
+
+        let vtable=self.vtable();
+        vtable.range_mut()(&mut *self.map,QueryBound::new(&start),QueryBound::new(&end))
+    }
+}
+
+
+impl<K,V> RBTreeMap<K,V>{
+    /// Returns whether the map associates a value with the key.
+    pub fn contains_key_p(&self,key:&K)->bool{
+        self.get_p(key).is_some()
+    }
+
+    /// Returns a reference to the value associated with the key.
+    pub fn get_p(&self,key:&K)->Option<&V>{
+        let vtable=self.vtable();
+        vtable.get_elem_p()(&*self.map,&key)
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    pub fn get_mut_p(&mut self,key:&K)->Option<&mut V>{
+        let vtable=self.vtable();
+        vtable.get_mut_elem_p()(&mut *self.map,&key)
+    }
+
+    /// Removes the entry for the key.
+    pub fn remove_entry_p(&mut self,key:&K)->ROption<Tuple2<K,V>>{
+        let vtable=self.vtable();
+        vtable.remove_entry_p()(&mut *self.map,&key)
+    }
+
+    /// Removes the value associated with the key.
+    pub fn remove_p(&mut self,key:&K)->ROption<V>{
+        self.remove_entry_p(key).map(|x| x.1 )
+    }
+
+    /// Returns a reference to the value associated with the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not associated with a value.
+    pub fn index_p(&self,key:&K)->&V{
+        self.get_p(key).expect("no entry in RBTreeMap<_,_> found for key")
+    }
+
+    /// Returns a mutable reference to the value associated with the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not associated with a value.
+    pub fn index_mut_p(&mut self,key:&K)->&mut V{
+        self.get_mut_p(key).expect("no entry in RBTreeMap<_,_> found for key")
+    }
+
+    //////////////////////////////////
+
+    /// Inserts a value into the map,associating it with a key,returning the previous value.
+    pub fn insert(&mut self,key:K,value:V)->ROption<V>{
+        let vtable=self.vtable();
+        vtable.insert_elem()(&mut *self.map,key,value)
+    }
+
+    /// Removes all the entries in the map.
+    pub fn clear(&mut self){
+        let vtable=self.vtable();
+        vtable.clear_map()(&mut *self.map);
+    }
+
+    /// Returns the ammount of entries in the map.
+    pub fn len(&self)->usize{
+        let vtable=self.vtable();
+        vtable.len()(&*self.map)
+    }
+
+    /// Returns whether the map contains any entries.
+    pub fn is_empty(&self)->bool{
+        self.len()==0
+    }
+
+    /// Iterates over the entries in the map,with references to the values in the map.
+    ///
+    /// This returns an
+    /// `Iterator<Item= Tuple2< &K, &V > >+DoubleEndedIterator+!Send+!Sync+Clone`
+    pub fn iter    (&self)->Iter<'_,K,V>{
+        let vtable=self.vtable();
+
+        vtable.iter()(&*self.map)
+    }
+
+    /// Iterates over the entries in the map,with mutable references to the values in the map.
+    ///
+    /// This returns an
+    /// `Iterator<Item= Tuple2< &K, &mut V > >+DoubleEndedIterator+!Send+!Sync`
+    pub fn iter_mut(&mut self)->IterMut<'_,K,V>{
+        let vtable=self.vtable();
+
+        vtable.iter_mut()(&mut *self.map)
+    }
+
+    /// Gets a handle into the entry in the map for the key,
+    /// that allows operating directly on the entry.
+    pub fn entry(&mut self,key:K)->REntry<'_,K,V>{
+        let vtable=self.vtable();
+
+        vtable.entry()(&mut *self.map,key)
+    }
+}
+
+
+/// This returns an `Iterator<Item= Tuple2< K, V > >+DoubleEndedIterator+!Send+!Sync`
+impl<K,V> IntoIterator for RBTreeMap<K,V>{
+    type Item=Tuple2<K,V>;
+    type IntoIter=IntoIter<K,V>;
+
+    fn into_iter(self)->IntoIter<K,V>{
+        let vtable=self.vtable();
+
+        vtable.iter_val()(self.map)
+    }
+}
+
+
+/// This returns an
+/// `Iterator<Item= Tuple2< &K, &V > >+DoubleEndedIterator+!Send+!Sync+Clone`
+impl<'a,K,V> IntoIterator for &'a RBTreeMap<K,V>{
+    type Item=Tuple2<&'a K,&'a V>;
+    type IntoIter=Iter<'a,K,V>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.iter()
+    }
+}
+
+
+/// This returns an
+/// `Iterator<Item= Tuple2< &K, &mut V > >+DoubleEndedIterator+!Send+!Sync`
+impl<'a,K,V> IntoIterator for &'a mut RBTreeMap<K,V>{
+    type Item=Tuple2<&'a K,&'a mut V>;
+    type IntoIter=IterMut<'a,K,V>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.iter_mut()
+    }
+}
+
+
+impl<K,V> From<BTreeMap<K,V>> for RBTreeMap<K,V>
+where
+    K:Ord,
+{
+    fn from(map:BTreeMap<K,V>)->Self{
+        map.into_iter().collect()
+    }
+}
+
+impl<K,V> Into<BTreeMap<K,V>> for RBTreeMap<K,V>
+where
+    K:Ord,
+{
+    fn into(self)->BTreeMap<K,V>{
+        self.into_iter().map(IntoReprRust::into_rust).collect()
+    }
+}
+
+
+impl<K,V> FromIterator<(K,V)> for RBTreeMap<K,V>
+where
+    K:Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K,V)>
+    {
+        let mut map=Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+
+impl<K,V> FromIterator<Tuple2<K,V>> for RBTreeMap<K,V>
+where
+    K:Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Tuple2<K,V>>
+    {
+        let mut map=Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+
+impl<K,V> Extend<(K,V)> for RBTreeMap<K,V>{
+    fn extend<I>(&mut self,iter: I)
+    where
+        I: IntoIterator<Item = (K,V)>
+    {
+        for (k,v) in iter {
+            self.insert(k,v);
+        }
+    }
+}
+
+
+impl<K,V> Extend<Tuple2<K,V>> for RBTreeMap<K,V>{
+    #[inline]
+    fn extend<I>(&mut self,iter: I)
+    where
+        I: IntoIterator<Item = Tuple2<K,V>>
+    {
+        self.extend( iter.into_iter().map(Tuple2::into_rust) );
+    }
+}
+
+impl<K,V> Default for RBTreeMap<K,V>
+where
+    K:Ord,
+{
+    fn default()->Self{
+        Self::new()
+    }
+}
+
+
+impl<K,V> Clone for RBTreeMap<K,V>
+where
+    K:Clone+Ord,
+    V:Clone,
+{
+    fn clone(&self)->Self{
+        self.iter().map(|Tuple2(k,v)| (k.clone(),v.clone()) ).collect()
+    }
+}
+
+
+impl<K,V> Debug for RBTreeMap<K,V>
+where
+    K:Debug,
+    V:Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_map()
+         .entries(self.iter().map(Tuple2::into_rust))
+         .finish()
+    }
+}
+
+
+impl<K,V> Eq for RBTreeMap<K,V>
+where
+    K:Eq,
+    V:Eq,
+{}
+
+
+impl<K,V> PartialEq for RBTreeMap<K,V>
+where
+    K:PartialEq,
+    V:PartialEq,
+{
+    fn eq(&self,other:&Self)->bool{
+        self.len()==other.len() &&
+        self.iter().eq(other.iter())
+    }
+}
+
+
+impl<K,V> PartialOrd for RBTreeMap<K,V>
+where
+    K:PartialOrd,
+    V:PartialOrd,
+{
+    fn partial_cmp(&self,other:&Self)->Option<Ordering>{
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+
+impl<K,V> Ord for RBTreeMap<K,V>
+where
+    K:Ord,
+    V:Ord,
+{
+    fn cmp(&self,other:&Self)->Ordering{
+        self.iter().cmp(other.iter())
+    }
+}
+
+
+impl<K,V> Hash for RBTreeMap<K,V>
+where
+    K:Hash,
+    V:Hash,
+{
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.len().hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+
+unsafe impl<K, V> Send for RBTreeMap<K, V>
+where
+    BTreeMap<K, V>: Send,
+{}
+
+unsafe impl<K, V> Sync for RBTreeMap<K, V>
+where
+    BTreeMap<K, V>: Sync,
+{}
+
+
+impl<K,Q,V> Index<&Q> for RBTreeMap<K,V>
+where
+    K:Borrow<Q>,
+    Q:Ord+?Sized,
+{
+    type Output=V;
+
+    fn index(&self,query:&Q)->&V{
+        self.get(query).expect("no entry in RBTreeMap<_,_> found for key")
+    }
+}
+
+impl<K,Q,V> IndexMut<&Q> for RBTreeMap<K,V>
+where
+    K:Borrow<Q>,
+    Q:Ord+?Sized,
+{
+    fn index_mut(&mut self,query:&Q)->&mut V{
+        self.get_mut(query).expect("no entry in RBTreeMap<_,_> found for key")
+    }
+}
+
+
+mod serde{
+    use super::*;
+
+    use ::serde::{
+        de::{Visitor, MapAccess},
+        ser::SerializeMap,
+        Deserialize,Serialize,Deserializer,Serializer,
+    };
+
+
+    struct RBTreeMapVisitor<K,V> {
+        marker: PhantomData<fn() -> RBTreeMap<K,V>>
+    }
+
+    impl<K,V> RBTreeMapVisitor<K,V> {
+        fn new() -> Self {
+            RBTreeMapVisitor {
+                marker: PhantomData
+            }
+        }
+    }
+
+    impl<'de,K,V> Visitor<'de> for RBTreeMapVisitor<K,V>
+    where
+        K: Deserialize<'de>+Ord,
+        V: Deserialize<'de>,
+    {
+        type Value = RBTreeMap<K,V>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RBTreeMap")
+        }
+
+        fn visit_map<M>(self, mut map_access: M) -> Result<Self::Value, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            let mut map = RBTreeMap::new();
+
+            while let Some((k, v)) = map_access.next_entry()? {
+                map.insert(k, v);
+            }
+
+            Ok(map)
+        }
+    }
+
+    impl<'de,K,V> Deserialize<'de> for RBTreeMap<K,V>
+    where
+        K: Deserialize<'de>+Ord,
+        V: Deserialize<'de>,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(RBTreeMapVisitor::new())
+        }
+    }
+
+
+
+    impl<K,V> Serialize for RBTreeMap<K,V>
+    where
+        K:Serialize,
+        V:Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer
+        {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for Tuple2(k, v) in self.iter() {
+                map.serialize_entry(k, v)?;
+            }
+            map.end()
+        }
+    }
+
+
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+#[derive(StableAbi)]
+#[repr(C)]
+#[sabi(
+    kind(Prefix(prefix_struct="VTable")),
+    missing_field(panic),
+)]
+struct VTableVal<K,V>{
+    ///
+    insert_elem:extern fn(&mut ErasedMap<K,V>,K,V)->ROption<V>,
+
+    get_elem:for<'a> extern fn(&'a ErasedMap<K,V>,MapQuery<'_,K>)->Option<&'a V>,
+    get_mut_elem:for<'a> extern fn(&'a mut ErasedMap<K,V>,MapQuery<'_,K>)->Option<&'a mut V>,
+    remove_entry:extern fn(&mut ErasedMap<K,V>,MapQuery<'_,K>)->ROption<Tuple2<K,V>>,
+
+    get_elem_p:for<'a> extern fn(&'a ErasedMap<K,V>,&K)->Option<&'a V>,
+    get_mut_elem_p:for<'a> extern fn(&'a mut ErasedMap<K,V>,&K)->Option<&'a mut V>,
+    remove_entry_p:extern fn(&mut ErasedMap<K,V>,&K)->ROption<Tuple2<K,V>>,
+
+    clear_map:extern fn(&mut ErasedMap<K,V>),
+    len:extern fn(&ErasedMap<K,V>)->usize,
+    iter    :extern fn(&ErasedMap<K,V>     )->Iter<'_,K,V>,
+    iter_mut:extern fn(&mut ErasedMap<K,V> )->IterMut<'_,K,V>,
+    range   :for<'a> extern fn(&'a ErasedMap<K,V>,QueryBound<'_,K>,QueryBound<'_,K>)->Iter<'a,K,V>,
+    range_mut:for<'a> extern fn(
+        &'a mut ErasedMap<K,V>,
+        QueryBound<'_,K>,
+        QueryBound<'_,K>,
+    )->IterMut<'a,K,V>,
+    iter_val:extern fn(RBox<ErasedMap<K,V>>)->IntoIter<K,V>,
+    #[sabi(last_prefix_field)]
+    entry:extern fn(&mut ErasedMap<K,V>,K)->REntry<'_,K,V>,
+}
+
+
+
+impl<K,V> VTable<K,V>
+where
+    K:Ord,
+{
+    const VTABLE_REF: *const WithMetadata<VTableVal<K,V>> = {
+        &WithMetadata::new(PrefixTypeTrait::METADATA,Self::VTABLE)
+    };
+
+    fn erased_map()->RBox<ErasedMap<K,V>>{
+        unsafe{
+            let boxed=BoxedBTreeMap{
+                map:BTreeMap::<MapKey<K>,V>::new(),
+                entry:None,
+            };
+            let boxed=RBox::new(boxed);
+            let boxed=mem::transmute::<RBox<_>,RBox<ErasedMap<K,V>>>(boxed);
+            boxed
+        }
+    }
+
+
+    const VTABLE:VTableVal<K,V>=VTableVal{
+        insert_elem :ErasedMap::insert_elem,
+
+        get_elem    :ErasedMap::get_elem,
+        get_mut_elem:ErasedMap::get_mut_elem,
+        remove_entry:ErasedMap::remove_entry,
+
+        get_elem_p    :ErasedMap::get_elem_p,
+        get_mut_elem_p:ErasedMap::get_mut_elem_p,
+        remove_entry_p:ErasedMap::remove_entry_p,
+
+        clear_map   :ErasedMap::clear_map,
+        len         :ErasedMap::len,
+        iter        :ErasedMap::iter,
+        iter_mut    :ErasedMap::iter_mut,
+        range       :ErasedMap::range,
+        range_mut   :ErasedMap::range_mut,
+        iter_val    :ErasedMap::iter_val,
+        entry       :ErasedMap::entry,
+    };
+
+}
+
+
+
+///////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,179 @@
+use super::*;
+
+use std::collections::btree_map::Entry as BTreeEntry;
+
+use crate::{
+    pointer_trait::TransmuteElement,
+    traits::IntoReprC,
+};
+
+
+impl<K,V> ErasedMap<K,V>
+where
+    K:Ord,
+{
+    pub(super) unsafe fn as_btreemap(&self)->&BoxedBTreeMap<'_,K,V>{
+        transmute_reference(self)
+    }
+    pub(super) unsafe fn as_mut_btreemap(&mut self)->&mut BoxedBTreeMap<'_,K,V>{
+        transmute_mut_reference(self)
+    }
+
+    fn run<'a,F,R>(&'a self,f:F)->R
+    where F:FnOnce(&'a BoxedBTreeMap<'a,K,V>)->R
+    {
+        extern_fn_panic_handling!{
+            let map=unsafe{ self.as_btreemap() };
+            f( map )
+        }
+    }
+
+    fn run_mut<'a,F,R>(&'a mut self,f:F)->R
+    where F:FnOnce(&'a mut BoxedBTreeMap<'a,K,V>)->R
+    {
+        extern_fn_panic_handling!{
+            let map=unsafe{ self.as_mut_btreemap() };
+            f( map )
+        }
+    }
+
+    fn run_val<'a,F,R>(this:RBox<Self>,f:F)->R
+    where
+        F:FnOnce(RBox<BoxedBTreeMap<'a,K,V>>)->R,
+        K:'a,
+        V:'a,
+    {
+        extern_fn_panic_handling!{
+            let map=unsafe{ this.transmute_element(<BoxedBTreeMap<'a,K,V>>::T) };
+            f( map )
+        }
+    }
+
+    pub(super)extern fn insert_elem(&mut self,key:K,value:V)->ROption<V>{
+        self.run_mut(|this|{
+            this.map.insert(MapKey::Value(key),value)
+                .into_c()
+        })
+    }
+
+    pub(super)extern fn get_elem(&self,key:MapQuery<'_,K>)->Option<&V>{
+        self.run(|this|unsafe{
+            this.map.get(&key.as_mapkey())
+        })
+    }
+
+    pub(super)extern fn get_mut_elem(&mut self,key:MapQuery<'_,K>)->Option<&mut V>{
+        self.run_mut(|this|unsafe{
+            this.map.get_mut(&key.as_mapkey())
+        })
+    }
+
+    pub(super)extern fn remove_entry(&mut self,key:MapQuery<'_,K>)->ROption<Tuple2<K,V>>{
+        self.run_mut(|this|{
+            // `BTreeMap::remove_entry` requires Rust 1.45,
+            // so this goes through the entry API,
+            // which returns the key stored in the map.
+            match this.map.entry(unsafe{ key.as_mapkey() }) {
+                BTreeEntry::Occupied(entry)=>{
+                    let (k,v)=entry.remove_entry();
+                    RSome(Tuple2(k.into_inner(),v))
+                }
+                BTreeEntry::Vacant(_)=>RNone,
+            }
+        })
+    }
+
+    pub(super)extern fn get_elem_p(&self,key:&K)->Option<&V>{
+        self.run(|this| this.map.get(key) )
+    }
+
+    pub(super)extern fn get_mut_elem_p(&mut self,key:&K)->Option<&mut V>{
+        self.run_mut(|this| this.map.get_mut(key) )
+    }
+
+    pub(super)extern fn remove_entry_p(&mut self,key:&K)->ROption<Tuple2<K,V>>{
+        Self::remove_entry(self,MapQuery::new(&key))
+    }
+
+    pub(super)extern fn clear_map(&mut self){
+        self.run_mut(|this| this.map.clear() )
+    }
+
+    pub(super)extern fn len(&self)->usize{
+        self.run(|this| this.map.len() )
+    }
+
+    pub(super)extern fn iter     (&self)->Iter<'_,K,V>{
+        self.run(|this|{
+            let iter=this.map.iter().map(map_iter_ref);
+            DynTrait::from_borrowing_value(iter,RefIterInterface::NEW)
+        })
+    }
+
+    pub(super)extern fn iter_mut (&mut self)->IterMut<'_,K,V>{
+        self.run_mut(|this|{
+            let iter=this.map.iter_mut().map(map_iter_ref);
+            DynTrait::from_borrowing_value(iter,MutIterInterface::NEW)
+        })
+    }
+
+    pub(super)extern fn range(
+        &self,
+        start:QueryBound<'_,K>,
+        end:QueryBound<'_,K>,
+    )->Iter<'_,K,V>{
+        self.run(|this|{
+            let bounds=unsafe{ (start.as_mapkey(),end.as_mapkey()) };
+            let iter=this.map.range(bounds).map(map_iter_ref);
+            DynTrait::from_borrowing_value(iter,RefIterInterface::NEW)
+        })
+    }
+
+    pub(super)extern fn range_mut(
+        &mut self,
+        start:QueryBound<'_,K>,
+        end:QueryBound<'_,K>,
+    )->IterMut<'_,K,V>{
+        self.run_mut(|this|{
+            let bounds=unsafe{ (start.as_mapkey(),end.as_mapkey()) };
+            let iter=this.map.range_mut(bounds).map(map_iter_ref);
+            DynTrait::from_borrowing_value(iter,MutIterInterface::NEW)
+        })
+    }
+
+    pub(super)extern fn iter_val<'a>(this:RBox<ErasedMap<K,V>>)->IntoIter<K,V>{
+        Self::run_val(this,|this|{
+            let iter=this.piped(RBox::into_inner).map.into_iter().map(map_iter_val);
+            let iter=DynTrait::from_borrowing_value(iter,ValIterInterface::NEW);
+            unsafe{ IntoIter::new(iter) }
+        })
+    }
+
+    pub(super)extern fn entry(&mut self,key:K)->REntry<'_,K,V>{
+        self.run_mut(|this|{
+            this.entry=None;
+            let map=&mut this.map;
+            let entry_mut=this.entry
+                .get_or_insert_with(||{
+                    {map}.entry(MapKey::Value(key))
+                       .piped(BoxedREntry::from)
+                });
+
+            unsafe{
+                REntry::new(entry_mut)
+            }
+        })
+    }
+}
+
+
+fn map_iter_ref<'a,K,V:'a>((key,val):(&'a MapKey<K>,V))->Tuple2<&'a K,V>{
+    Tuple2( key.as_ref(),val )
+}
+
+fn map_iter_val<K,V>((key,val):(MapKey<K>,V))->Tuple2<K,V>{
+    Tuple2( key.into_inner(),val )
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,105 @@
+use super::*;
+
+pub enum MapKey<K>{
+    Value(K),
+    /// This is a horrible hack.
+    Query(NonNull<MapQuery<'static,K>>),
+}
+
+
+impl<K> MapKey<K>{
+    #[inline]
+    pub fn into_inner(self)->K{
+        match self {
+            MapKey::Value(v)=>v,
+            _=>unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!")
+        }
+    }
+
+    #[inline]
+    pub fn as_ref(&self)->&K{
+        match self {
+            MapKey::Value(v)=>v,
+            _=>unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!")
+        }
+    }
+}
+
+
+impl<K> From<K> for MapKey<K>{
+    #[inline]
+    fn from(value:K)->Self{
+        MapKey::Value(value)
+    }
+}
+
+impl<K> Debug for MapKey<K>
+where
+    K:Debug
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result {
+        match self {
+            MapKey::Value(v)=>Debug::fmt(v,f),
+            _=>unreachable!("This is a BUG!!!!!!!!!!!!!!!!!!!!")
+        }
+    }
+}
+
+impl<K> Eq for MapKey<K>
+where
+    K:Ord
+{}
+
+impl<K> PartialEq for MapKey<K>
+where
+    K:Ord
+{
+    fn eq(&self, other: &Self) -> bool{
+        self.cmp(other)==Ordering::Equal
+    }
+}
+
+impl<K> PartialOrd for MapKey<K>
+where
+    K:Ord
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for MapKey<K>
+where
+    K:Ord
+{
+    fn cmp(&self, other: &Self) -> Ordering{
+        match (self,other) {
+            (MapKey::Value(lhs),MapKey::Value(rhs))=>{
+                lhs.cmp(rhs)
+            }
+            (MapKey::Value(lhs),MapKey::Query(rhs))=>{
+                unsafe{
+                    rhs.as_ref().cmp_key(lhs)
+                }
+            }
+            (MapKey::Query(lhs),MapKey::Value(rhs))=>{
+                unsafe{
+                    lhs.as_ref().cmp_key(rhs).reverse()
+                }
+            }
+            // Only happens when BTreeMap compares the bounds of a range.
+            (MapKey::Query(lhs),MapKey::Query(rhs))=>{
+                unsafe{
+                    lhs.as_ref().cmp_query(rhs.as_ref())
+                }
+            }
+        }
+    }
+}
+
+
+impl<K> Borrow<K> for MapKey<K>{
+    fn borrow(&self)->&K{
+        self.as_ref()
+    }
+}
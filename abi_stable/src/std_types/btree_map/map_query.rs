@@ -0,0 +1,134 @@
+use super::*;
+
+/// A trait object used in method that access map entries without replacing them.
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct MapQuery<'a,K>{
+    _marker:NotCopyNotClone,
+    cmp_key:extern fn(&K,&ErasedObject)->RCmpOrdering,
+    cmp_query:extern fn(&ErasedObject,&ErasedObject)->RCmpOrdering,
+    query:&'a ErasedObject,
+}
+
+impl<'a,K> MapQuery<'a,K>{
+    #[inline]
+    pub(super) fn new<Q>(query:&'a &'a Q)->Self
+    where
+        K:Borrow<Q>,
+        Q:Ord + 'a+?Sized,
+    {
+        MapQuery{
+            _marker:NotCopyNotClone,
+            cmp_key:cmp_key::<K,Q>,
+            cmp_query:cmp_query::<Q>,
+            query:unsafe{ transmute_reference(query) },
+        }
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_static(&self)->&MapQuery<'static,K>{
+        transmute_reference(self)
+    }
+}
+
+impl<'a,K> MapQuery<'a,K>{
+    /// Compares the key with the query.
+    #[inline]
+    pub(super) fn cmp_key(&self,key:&K)->Ordering{
+        (self.cmp_key)(key,self.query).into()
+    }
+
+    /// Compares this query with another one with the same type.
+    #[inline]
+    pub(super) fn cmp_query(&self,other:&Self)->Ordering{
+        (self.cmp_query)(self.query,other.query).into()
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_mapkey(&self)->MapKey<K>{
+        MapKey::Query(NonNull::from(self.as_static()))
+    }
+}
+
+
+extern fn cmp_key<K,Q>(key:&K,query:&ErasedObject)->RCmpOrdering
+where
+    K:Borrow<Q>,
+    Q:Ord+?Sized,
+{
+    extern_fn_panic_handling!{
+        let query =unsafe{ transmute_reference::<ErasedObject,&Q>(query) };
+        key.borrow().cmp(*query).into()
+    }
+}
+
+
+extern fn cmp_query<Q>(lhs:&ErasedObject,rhs:&ErasedObject)->RCmpOrdering
+where
+    Q:Ord+?Sized,
+{
+    extern_fn_panic_handling!{
+        let lhs =unsafe{ transmute_reference::<ErasedObject,&Q>(lhs) };
+        let rhs =unsafe{ transmute_reference::<ErasedObject,&Q>(rhs) };
+        (*lhs).cmp(*rhs).into()
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// One of the bounds of a range query.
+#[derive(StableAbi)]
+#[repr(C)]
+pub enum QueryBound<'a,K>{
+    Included(MapQuery<'a,K>),
+    Excluded(MapQuery<'a,K>),
+    Unbounded,
+}
+
+
+impl<'a,K> QueryBound<'a,K>{
+    #[inline]
+    pub(super) fn new<Q>(bound:&'a Bound<&'a Q>)->Self
+    where
+        K:Borrow<Q>,
+        Q:Ord + 'a+?Sized,
+    {
+        match bound {
+            Bound::Included(query)=>QueryBound::Included(MapQuery::new(query)),
+            Bound::Excluded(query)=>QueryBound::Excluded(MapQuery::new(query)),
+            Bound::Unbounded=>QueryBound::Unbounded,
+        }
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_mapkey(&self)->Bound<MapKey<K>>{
+        match self {
+            QueryBound::Included(query)=>Bound::Included(query.as_mapkey()),
+            QueryBound::Excluded(query)=>Bound::Excluded(query.as_mapkey()),
+            QueryBound::Unbounded=>Bound::Unbounded,
+        }
+    }
+}
+
+
+/// Panics with the same conditions as `BTreeMap::range`,
+/// so that it doesn't happen inside of an `extern fn`,which would abort the process.
+pub(super) fn check_range<Q>(start:Bound<&Q>,end:Bound<&Q>)
+where
+    Q:Ord+?Sized,
+{
+    match (start,end) {
+        (Bound::Excluded(s),Bound::Excluded(e)) if s==e => {
+            panic!("range start and end are equal and excluded in RBTreeMap")
+        }
+        (Bound::Included(s),Bound::Included(e))
+        |(Bound::Included(s),Bound::Excluded(e))
+        |(Bound::Excluded(s),Bound::Included(e))
+        |(Bound::Excluded(s),Bound::Excluded(e)) if s>e => {
+            panic!("range start is greater than range end in RBTreeMap")
+        }
+        _=>{}
+    }
+}
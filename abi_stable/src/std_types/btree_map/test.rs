@@ -0,0 +1,441 @@
+use super::*;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    ops::Bound,
+    str::FromStr,
+};
+
+use crate::std_types::RString;
+
+
+fn new_stdmap()->BTreeMap<u32,u32>{
+    vec![
+        (90,40),
+        (10,20),
+        (88,30),
+        (77,22),
+    ].into_iter()
+     .collect()
+}
+
+fn new_map<K,V>()->RBTreeMap<K,V>
+where
+    K:FromStr+Ord,
+    V:FromStr,
+    K::Err:Debug,
+    V::Err:Debug,
+{
+    vec![
+        ("90","40"),
+        ("10","20"),
+        ("88","30"),
+        ("77","22"),
+    ].into_iter()
+     .map(|(k,v)| (k.parse::<K>().unwrap(),v.parse::<V>().unwrap()) )
+     .collect()
+}
+
+fn hash_value<T:Hash>(value:&T)->u64{
+    let mut hasher=DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+#[test]
+fn test_new_map(){
+    let mut map=RBTreeMap::new();
+    map.insert(10,100);
+    assert_eq!(map.get(&10), Some(&100));
+}
+
+
+#[test]
+fn test_default(){
+    let default_=RBTreeMap::<u32,u32>::default();
+    let new_=RBTreeMap::<u32,u32>::new();
+
+    assert_eq!(default_.len(),0);
+    assert_eq!(default_,new_);
+}
+
+
+#[test]
+fn test_eq_ord_hash(){
+    let map0=new_map::<String,String>();
+    let map1=new_map::<String,String>();
+    let mut map2=new_map::<String,String>();
+    map2.insert("99".into(),"0".into());
+
+    assert_eq!(map0, map1);
+    assert_ne!(map0, map2);
+    assert_eq!(map0.cmp(&map1), Ordering::Equal);
+    assert_eq!(map0.cmp(&map2), Ordering::Less);
+    assert_eq!(map2.partial_cmp(&map0), Some(Ordering::Greater));
+
+    let stdmap0=map0.clone().piped(Into::<BTreeMap<_,_>>::into);
+    let stdmap2=map2.clone().piped(Into::<BTreeMap<_,_>>::into);
+    assert_eq!(map0.cmp(&map2), stdmap0.cmp(&stdmap2));
+
+    assert_eq!(hash_value(&map0), hash_value(&map1));
+    assert_ne!(hash_value(&map0), hash_value(&map2));
+}
+
+
+#[test]
+fn clone(){
+    let map=new_map::<String,String>();
+    let clone_=map.clone();
+
+    // Cloned String should never point to the same buffer
+    assert_ne!(
+        map.get("90").unwrap().as_ptr(),
+        clone_.get("90").unwrap().as_ptr(),
+    );
+
+    assert_eq!(map, clone_);
+}
+
+
+#[test]
+fn insert(){
+    let mut map=RBTreeMap::<String,_>::new();
+    map.insert("what".into(),10);
+    map.insert("the".into(),5);
+
+    assert_eq!(
+        map.insert("what".into(),33),
+        RSome(10),
+    );
+    assert_eq!(
+        map.insert("the".into(),77),
+        RSome(5),
+    );
+    assert_eq!(
+        map.insert("is".into(),1),
+        RNone,
+    );
+}
+
+
+#[test]
+fn remove(){
+    let mut map=RBTreeMap::<String,_>::new();
+    map.insert("what".into(),10);
+    map.insert("the".into(),5);
+    map.insert("is".into(),14);
+    map.insert("that".into(),54);
+
+    assert_eq!(map.remove_entry("the"),RSome(Tuple2("the".to_string(),5)));
+    assert_eq!(map.remove_entry("the"),RNone);
+
+    assert_eq!(map.remove_entry_p(&"what".into()),RSome(Tuple2("what".to_string(),10)));
+    assert_eq!(map.remove_entry_p(&"what".into()),RNone);
+
+    assert_eq!(map.remove_p(&"is".into()),RSome(14));
+    assert_eq!(map.remove_p(&"is".into()),RNone);
+
+    assert_eq!(map.remove("that"),RSome(54));
+    assert_eq!(map.remove("that"),RNone);
+
+    assert!(map.is_empty());
+}
+
+
+fn check_get<K,V>(map:&mut RBTreeMap<K,V>,key:K,value:Option<V>)
+where
+    K:Ord+Clone+Debug,
+    V:PartialEq+Clone+Debug,
+{
+    assert_eq!(map.get(&key).cloned(),value.clone());
+    assert_eq!(map.get_p(&key).cloned(),value.clone());
+    assert_eq!(map.get_mut(&key).cloned(),value.clone());
+    assert_eq!(map.get_mut_p(&key).cloned(),value.clone());
+
+    assert_eq!(map.contains_key(&key),value.is_some(),"\nkey:{:?} value:{:?}\n",key,value );
+    assert_eq!(map.contains_key_p(&key),value.is_some(),"\nkey:{:?} value:{:?}\n",key,value );
+
+    if let Some(mut value)=value.clone() {
+        assert_eq!(&map[&key],&value);
+        assert_eq!(map.index_p(&key),&value);
+
+        assert_eq!((&mut map[&key]),&mut value);
+        assert_eq!(map.index_mut_p(&key),&mut value);
+    }
+}
+
+
+#[test]
+fn get(){
+    let mut map=RBTreeMap::<String,_>::new();
+    map.insert("what".into(),10);
+    map.insert("the".into(),5);
+    map.insert("oof".into(),33);
+    map.insert("you".into(),55);
+
+    check_get(&mut map,"what".into(),Some(10));
+    check_get(&mut map,"the".into(),Some(5));
+    check_get(&mut map,"oof".into(),Some(33));
+    check_get(&mut map,"you".into(),Some(55));
+
+    check_get(&mut map,"wasdat".into(),None);
+    check_get(&mut map,"thasdae".into(),None);
+    check_get(&mut map,"ofwwf".into(),None);
+    check_get(&mut map,"youeeeee".into(),None);
+
+    for key in vec!["what","the","oof","you"] {
+        if let Some(x)=map.get_mut(key) {
+            *x=*x*2;
+        }
+    }
+
+    assert_eq!(map.get("what"),Some(&20));
+    assert_eq!(map.get("the"),Some(&10));
+    assert_eq!(map.get("oof"),Some(&66));
+    assert_eq!(map.get("you"),Some(&110));
+}
+
+
+#[test]
+fn clear_len_is_empty(){
+    let mut map=RBTreeMap::<String,_>::new();
+
+    assert!(map.is_empty());
+    assert_eq!(map.len(),0);
+
+    map.insert("what".into(),10);
+    map.insert("the".into(),5);
+    map.insert("oof".into(),33);
+    assert!(!map.is_empty());
+    assert_eq!(map.len(),3);
+
+    map.clear();
+
+    assert!(map.is_empty());
+    assert_eq!(map.len(),0);
+    assert_eq!(map.get("what"),None);
+}
+
+
+#[test]
+fn iteration_order(){
+    let stdmap=new_stdmap();
+    let mut map:RBTreeMap<u32,u32>=stdmap.clone().into();
+
+    let expected=stdmap.iter().map(|(k,v)| (*k,*v) ).collect::<Vec<_>>();
+
+    let list=map.iter().map(|Tuple2(k,v)| (*k,*v) ).collect::<Vec<_>>();
+    assert_eq!(list,expected);
+
+    let list=map.iter().rev().map(|Tuple2(k,v)| (*k,*v) ).collect::<Vec<_>>();
+    assert_eq!(list,expected.iter().cloned().rev().collect::<Vec<_>>());
+
+    for Tuple2(_,v) in map.iter_mut() {
+        *v+=1;
+    }
+    let list=(&map).into_iter().map(|Tuple2(k,v)| (*k,*v) ).collect::<Vec<_>>();
+    assert_eq!(list,expected.iter().map(|&(k,v)| (k,v+1) ).collect::<Vec<_>>());
+
+    let mut into_iter=map.into_iter();
+    assert_eq!(into_iter.next(),Some(Tuple2(10,21)));
+    assert_eq!(into_iter.next_back(),Some(Tuple2(90,41)));
+    assert_eq!(into_iter.collect::<Vec<_>>(),vec![Tuple2(77,23),Tuple2(88,31)]);
+}
+
+
+#[test]
+fn ranges(){
+    let map=new_map::<RString,u32>();
+
+    fn values<'a,I>(iter:I)->Vec<u32>
+    where I:Iterator<Item=Tuple2<&'a RString,&'a u32>>
+    {
+        iter.map(|Tuple2(_,v)| *v ).collect()
+    }
+
+    assert_eq!(values(map.range::<RString,_>(..)),vec![20,22,30,40]);
+    assert_eq!(values(map.range(RString::from("77")..)),vec![22,30,40]);
+    assert_eq!(values(map.range(RString::from("77")..RString::from("90"))),vec![22,30]);
+    assert_eq!(values(map.range(RString::from("11")..=RString::from("88"))),vec![22,30]);
+    assert_eq!(values(map.range(..RString::from("88"))),vec![20,22]);
+    assert_eq!(values(map.range(RString::from("77")..RString::from("77"))),Vec::<u32>::new());
+
+    assert_eq!(
+        values(map.range::<str,_>((Bound::Excluded("10"),Bound::Included("88")))),
+        vec![22,30]
+    );
+    assert_eq!(
+        values(map.range::<str,_>((Bound::Unbounded,Bound::Excluded("77"))).rev()),
+        vec![20]
+    );
+}
+
+
+#[test]
+fn range_mut(){
+    let mut map=new_map::<RString,u32>();
+
+    for Tuple2(_,v) in map.range_mut::<str,_>((Bound::Included("77"),Bound::Excluded("90"))) {
+        *v*=10;
+    }
+
+    let list=map.iter().map(|Tuple2(_,v)| *v ).collect::<Vec<_>>();
+    assert_eq!(list,vec![20,220,300,40]);
+}
+
+
+#[test]
+#[should_panic]
+fn range_start_greater_than_end(){
+    let map=new_map::<u32,u32>();
+    let _=map.range(20..10);
+}
+
+
+#[test]
+#[should_panic]
+fn range_equal_excluded(){
+    let map=new_map::<u32,u32>();
+    let _=map.range((Bound::Excluded(10),Bound::Excluded(10)));
+}
+
+
+#[test]
+fn from_into_btreemap(){
+    let stdmap=new_stdmap();
+
+    let map:RBTreeMap<u32,u32>=stdmap.clone().into();
+    assert_eq!(map.len(), 4);
+
+    for (key,val) in &stdmap {
+        assert_eq!(map.get(key),Some(val),"key:{:?} value:{:?}",key,val);
+    }
+
+    let back:BTreeMap<u32,u32>=map.into();
+    assert_eq!(back,stdmap);
+}
+
+
+#[test]
+fn from_iter_extend(){
+    let stdmap=new_stdmap();
+
+    let map0=stdmap.clone().into_iter().collect::<RBTreeMap<_,_>>();
+    let map1=stdmap.clone().into_iter().map(Tuple2::from).collect::<RBTreeMap<_,_>>();
+
+    let mut map2=RBTreeMap::new();
+    map2.extend(stdmap.clone());
+
+    let mut map3=RBTreeMap::new();
+    map3.extend(stdmap.clone().into_iter().map(Tuple2::from));
+
+    let expected:RBTreeMap<u32,u32>=stdmap.into();
+    assert_eq!(map0, expected);
+    assert_eq!(map1, expected);
+    assert_eq!(map2, expected);
+    assert_eq!(map3, expected);
+}
+
+
+#[test]
+fn test_serde(){
+    let map=new_map::<String,RString>();
+
+    let json=r##"
+        {
+            "90":"40",
+            "10":"20",
+            "88":"30",
+            "77":"22"
+        }
+    "##;
+
+    let deserialized=serde_json::from_str::<RBTreeMap<String,RString>>(json).unwrap();
+
+    assert_eq!(deserialized,map);
+
+    let serialized=serde_json::to_string(&map).unwrap();
+
+    assert_eq!(serialized,r##"{"10":"20","77":"22","88":"30","90":"40"}"##);
+
+    let redeserialized=serde_json::from_str::<RBTreeMap<String,RString>>(&serialized).unwrap();
+
+    assert_eq!(redeserialized,map);
+}
+
+
+fn assert_is_occupied<K,V>(map:&mut RBTreeMap<K,V>,k:K,v:V)
+where
+    K:Ord+Clone+Debug,
+    V:Clone+Debug+PartialEq,
+{
+    let mut entry=map.entry(k.clone());
+    assert_matches!(REntry::Occupied{..}=entry);
+    assert_eq!(entry.key(), &k);
+    assert_eq!(entry.get().cloned(), Some(v.clone()));
+    assert_eq!(entry.get_mut().cloned(), Some(v.clone()));
+}
+
+fn assert_is_vacant<K,V>(map:&mut RBTreeMap<K,V>,k:K)
+where
+    K:Ord+Clone+Debug,
+    V:Clone+Debug+PartialEq,
+{
+    let mut entry=map.entry(k.clone());
+    assert_matches!(REntry::Vacant{..}=entry);
+    assert_eq!(entry.key(), &k);
+    assert_eq!(entry.get().cloned(), None);
+    assert_eq!(entry.get_mut().cloned(), None);
+}
+
+
+#[test]
+fn existing_is_occupied(){
+    let mut map=new_map::<RString,RString>();
+
+    assert_is_occupied(&mut map,"90".into(),"40".into());
+    assert_is_occupied(&mut map,"10".into(),"20".into());
+    assert_is_occupied(&mut map,"88".into(),"30".into());
+    assert_is_occupied(&mut map,"77".into(),"22".into());
+
+    assert_is_vacant(&mut map,"13".into());
+    assert_is_vacant(&mut map,"14".into());
+}
+
+
+#[test]
+fn entry_methods(){
+    let mut map=new_map::<RString,RString>();
+
+    assert_is_vacant(&mut map,"12".into());
+
+    assert_eq!(
+        *map.entry("12".into()).or_insert("100".into()),
+        "100".into_(RString::T)
+    );
+    assert_is_occupied(&mut map,"12".into(),"100".into());
+
+    assert_eq!(
+        *map.entry("12".into())
+            .and_modify(|v| *v="what".into() )
+            .or_insert_with(||unreachable!()),
+        "what".into_(RString::T)
+    );
+    assert_is_occupied(&mut map,"12".into(),"what".into());
+
+    assert_eq!(
+        *map.entry("13".into()).or_default(),
+        "".into_(RString::T)
+    );
+
+    match map.entry("13".into()) {
+        REntry::Occupied(entry)=>assert_eq!(entry.remove(),"".into_(RString::T)),
+        REntry::Vacant(_)=>unreachable!(),
+    }
+    match map.entry("13".into()) {
+        REntry::Occupied(_)=>unreachable!(),
+        REntry::Vacant(entry)=>assert_eq!(entry.into_key(),"13".into_(RString::T)),
+    }
+    assert_eq!(map.get("13"),None);
+}
@@ -0,0 +1,484 @@
+/*!
+Contains the ffi-safe equivalent of `std::collections::BTreeSet`,and related items.
+*/
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::BTreeSet,
+    fmt::{self,Debug},
+    hash::{Hash,Hasher},
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::RangeBounds,
+};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::{
+    StableAbi,
+    std_types::{
+        btree_map::{self,RBTreeMap},
+        ROption,Tuple2,
+    },
+};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+/**
+
+An ffi-safe ordered set,which wraps `std::collections::BTreeSet<T>`
+(by storing the values as the keys of an `RBTreeMap<T,()>`),
+only requiring the `T:Ord` bound when constructing it.
+
+The values are iterated over in ascending order.
+
+# Example
+
+```
+use abi_stable::std_types::RBTreeSet;
+
+let mut set=vec![8,3,5,13,1].into_iter().collect::<RBTreeSet<u32>>();
+
+assert!(set.insert(2));
+assert!(!set.insert(3));
+
+assert_eq!(set.iter().cloned().collect::<Vec<_>>(),vec![1,2,3,5,8,13]);
+assert_eq!(set.range(3..8).cloned().collect::<Vec<_>>(),vec![3,5]);
+
+assert!(set.remove(&13));
+assert_eq!(set.iter().next_back(),Some(&8));
+
+```
+
+*/
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct RBTreeSet<T>{
+    map:RBTreeMap<T,()>,
+}
+
+
+impl<T> RBTreeSet<T>{
+    /// Constructs an empty RBTreeSet.
+    pub fn new()->RBTreeSet<T>
+    where
+        T:Ord,
+    {
+        RBTreeSet{
+            map:RBTreeMap::new(),
+        }
+    }
+}
+
+
+impl<T> RBTreeSet<T>{
+    /// Returns whether the set contains the value.
+    pub fn contains<Q>(&self,query:&Q)->bool
+    where
+        T:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    pub fn remove<Q>(&mut self,query:&Q)->bool
+    where
+        T:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        self.map.remove(query).is_rsome()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    pub fn take<Q>(&mut self,query:&Q)->ROption<T>
+    where
+        T:Borrow<Q>,
+        Q:Ord+?Sized
+    {
+        self.map.remove_entry(query).map(|x| x.0 )
+    }
+
+    /// Iterates over references to the values in the set that are in the range,
+    /// in ascending order.
+    ///
+    /// This returns an `Iterator<Item= &T >+DoubleEndedIterator+!Send+!Sync+Clone`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end,
+    /// or if the start is equal to the end and both bounds are `Excluded`.
+    pub fn range<Q,R>(&self,range:R)->Iter<'_,T>
+    where
+        T:Borrow<Q>,
+        Q:Ord+?Sized,
+        R:RangeBounds<Q>,
+    {
+        Iter{
+            iter:self.map.range(range),
+        }
+    }
+
+    /// Adds the value to the set,returning whether it wasn't already in the set.
+    ///
+    /// If the set already contained the value,the value in the set is not replaced.
+    pub fn insert(&mut self,value:T)->bool{
+        self.map.insert(value,()).is_rnone()
+    }
+
+    /// Removes all the values in the set.
+    pub fn clear(&mut self){
+        self.map.clear();
+    }
+
+    /// Returns the ammount of values in the set.
+    pub fn len(&self)->usize{
+        self.map.len()
+    }
+
+    /// Returns whether the set contains any values.
+    pub fn is_empty(&self)->bool{
+        self.len()==0
+    }
+
+    /// Iterates over references to the values in the set,in ascending order.
+    ///
+    /// This returns an `Iterator<Item= &T >+DoubleEndedIterator+!Send+!Sync+Clone`
+    pub fn iter(&self)->Iter<'_,T>{
+        Iter{
+            iter:self.map.iter(),
+        }
+    }
+
+    /// Returns whether none of the values in this set are in `other`.
+    pub fn is_disjoint(&self,other:&Self)->bool{
+        let (smaller,larger)=if self.len() <= other.len() { (self,other) }else{ (other,self) };
+        smaller.iter().all(|x| !larger.map.contains_key_p(x) )
+    }
+
+    /// Returns whether all the values in this set are in `other`.
+    pub fn is_subset(&self,other:&Self)->bool{
+        self.len() <= other.len() &&
+        self.iter().all(|x| other.map.contains_key_p(x) )
+    }
+
+    /// Returns whether all the values in `other` are in this set.
+    pub fn is_superset(&self,other:&Self)->bool{
+        other.is_subset(self)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// An RBTreeSet iterator,
+/// implementing `Iterator<Item= &T >+DoubleEndedIterator+!Send+!Sync+Clone`
+#[repr(transparent)]
+#[derive(StableAbi)]
+pub struct Iter<'a,T:'a>{
+    iter:btree_map::Iter<'a,T,()>,
+}
+
+impl<'a,T:'a> Clone for Iter<'a,T>{
+    fn clone(&self)->Self{
+        Iter{
+            iter:self.iter.clone(),
+        }
+    }
+}
+
+impl<'a,T:'a> Iterator for Iter<'a,T>{
+    type Item=&'a T;
+
+    #[inline]
+    fn next(&mut self)->Option<&'a T>{
+        self.iter.next().map(|Tuple2(k,_)| k )
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+impl<'a,T:'a> DoubleEndedIterator for Iter<'a,T>{
+    #[inline]
+    fn next_back(&mut self)->Option<&'a T>{
+        self.iter.next_back().map(|Tuple2(k,_)| k )
+    }
+}
+
+
+/// An iterator that yields all the values of an RBTreeSet in ascending order,
+/// deallocating the set afterwards.
+///
+/// This is an `Iterator<Item= T >+DoubleEndedIterator+!Send+!Sync`
+#[repr(transparent)]
+#[derive(StableAbi)]
+pub struct IntoIter<T>{
+    iter:btree_map::IntoIter<T,()>,
+}
+
+impl<T> Iterator for IntoIter<T>{
+    type Item=T;
+
+    #[inline]
+    fn next(&mut self)->Option<T>{
+        self.iter.next().map(|Tuple2(k,_)| k )
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T>{
+    #[inline]
+    fn next_back(&mut self)->Option<T>{
+        self.iter.next_back().map(|Tuple2(k,_)| k )
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// This returns an `Iterator<Item= T >+DoubleEndedIterator+!Send+!Sync`
+impl<T> IntoIterator for RBTreeSet<T>{
+    type Item=T;
+    type IntoIter=IntoIter<T>;
+
+    fn into_iter(self)->IntoIter<T>{
+        IntoIter{
+            iter:self.map.into_iter(),
+        }
+    }
+}
+
+
+/// This returns an `Iterator<Item= &T >+DoubleEndedIterator+!Send+!Sync+Clone`
+impl<'a,T> IntoIterator for &'a RBTreeSet<T>{
+    type Item=&'a T;
+    type IntoIter=Iter<'a,T>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.iter()
+    }
+}
+
+
+impl<T> From<BTreeSet<T>> for RBTreeSet<T>
+where
+    T:Ord,
+{
+    fn from(set:BTreeSet<T>)->Self{
+        set.into_iter().collect()
+    }
+}
+
+impl<T> Into<BTreeSet<T>> for RBTreeSet<T>
+where
+    T:Ord,
+{
+    fn into(self)->BTreeSet<T>{
+        self.into_iter().collect()
+    }
+}
+
+
+impl<T> FromIterator<T> for RBTreeSet<T>
+where
+    T:Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>
+    {
+        let mut set=Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+
+impl<T> Extend<T> for RBTreeSet<T>{
+    #[inline]
+    fn extend<I>(&mut self,iter: I)
+    where
+        I: IntoIterator<Item = T>
+    {
+        self.map.extend( iter.into_iter().map(|x| (x,()) ) );
+    }
+}
+
+
+impl<T> Default for RBTreeSet<T>
+where
+    T:Ord,
+{
+    fn default()->Self{
+        Self::new()
+    }
+}
+
+
+impl<T> Clone for RBTreeSet<T>
+where
+    T:Clone+Ord,
+{
+    fn clone(&self)->Self{
+        self.iter().cloned().collect()
+    }
+}
+
+
+impl<T> Debug for RBTreeSet<T>
+where
+    T:Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_set()
+         .entries(self.iter())
+         .finish()
+    }
+}
+
+
+impl<T> Eq for RBTreeSet<T>
+where
+    T:Eq,
+{}
+
+
+impl<T> PartialEq for RBTreeSet<T>
+where
+    T:PartialEq,
+{
+    fn eq(&self,other:&Self)->bool{
+        self.len() == other.len() &&
+        self.iter().eq(other.iter())
+    }
+}
+
+
+impl<T> PartialOrd for RBTreeSet<T>
+where
+    T:PartialOrd,
+{
+    fn partial_cmp(&self,other:&Self)->Option<Ordering>{
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+
+impl<T> Ord for RBTreeSet<T>
+where
+    T:Ord,
+{
+    fn cmp(&self,other:&Self)->Ordering{
+        self.iter().cmp(other.iter())
+    }
+}
+
+
+impl<T> Hash for RBTreeSet<T>
+where
+    T:Hash,
+{
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.len().hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+
+mod serde{
+    use super::*;
+
+    use ::serde::{
+        de::{Visitor, SeqAccess},
+        ser::SerializeSeq,
+        Deserialize,Serialize,Deserializer,Serializer,
+    };
+
+
+    struct RBTreeSetVisitor<T> {
+        marker: PhantomData<fn() -> RBTreeSet<T>>
+    }
+
+    impl<T> RBTreeSetVisitor<T> {
+        fn new() -> Self {
+            RBTreeSetVisitor {
+                marker: PhantomData
+            }
+        }
+    }
+
+    impl<'de,T> Visitor<'de> for RBTreeSetVisitor<T>
+    where
+        T: Deserialize<'de>+Ord,
+    {
+        type Value = RBTreeSet<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RBTreeSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut set = RBTreeSet::new();
+
+            while let Some(value) = seq_access.next_element()? {
+                set.insert(value);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de,T> Deserialize<'de> for RBTreeSet<T>
+    where
+        T: Deserialize<'de>+Ord,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RBTreeSetVisitor::new())
+        }
+    }
+
+
+
+    impl<T> Serialize for RBTreeSet<T>
+    where
+        T:Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for value in self.iter() {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+
+
+}
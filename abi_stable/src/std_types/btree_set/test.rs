@@ -0,0 +1,186 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+use crate::std_types::{RNone,RSome,RString};
+
+
+fn new_stdset()->BTreeSet<u32>{
+    vec![90,10,88,77].into_iter().collect()
+}
+
+fn new_set()->RBTreeSet<u32>{
+    vec![90,10,88,77].into_iter().collect()
+}
+
+fn hash_value<T:Hash>(value:&T)->u64{
+    let mut hasher=DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+#[test]
+fn test_new_set(){
+    let mut set=RBTreeSet::new();
+    assert!(set.insert(10));
+    assert!(set.contains(&10));
+    assert!(!set.contains(&11));
+}
+
+
+#[test]
+fn test_default(){
+    let default_=RBTreeSet::<u32>::default();
+    let new_=RBTreeSet::<u32>::new();
+
+    assert_eq!(default_.len(),0);
+    assert_eq!(default_,new_);
+}
+
+
+#[test]
+fn test_eq_ord_hash(){
+    let set=new_set();
+    let clone=set.clone();
+
+    assert_eq!(set,clone);
+    assert_eq!(set.cmp(&clone),Ordering::Equal);
+    assert_eq!(hash_value(&set),hash_value(&clone));
+
+    let stdset=new_stdset();
+    let mut other=set.clone();
+    other.insert(1000);
+    let mut stdother=stdset.clone();
+    stdother.insert(1000);
+
+    assert_ne!(set,other);
+    assert_eq!(set.cmp(&other),stdset.cmp(&stdother));
+
+    let mut other=set.clone();
+    other.remove(&10);
+    let mut stdother=stdset.clone();
+    stdother.remove(&10);
+
+    assert_eq!(set.cmp(&other),stdset.cmp(&stdother));
+    assert_eq!(set.partial_cmp(&other),stdset.partial_cmp(&stdother));
+}
+
+
+#[test]
+fn insert_remove_take(){
+    let mut set=RBTreeSet::<RString>::new();
+
+    assert!(set.insert("foo".into()));
+    assert!(!set.insert("foo".into()));
+    assert!(set.insert("bar".into()));
+    assert_eq!(set.len(),2);
+
+    assert!(set.remove("foo"));
+    assert!(!set.remove("foo"));
+    assert_eq!(set.len(),1);
+
+    assert_eq!(set.take("bar"),RSome(RString::from("bar")));
+    assert_eq!(set.take("bar"),RNone);
+    assert!(set.is_empty());
+}
+
+
+#[test]
+fn clear_len_is_empty(){
+    let mut set=new_set();
+    assert_eq!(set.len(),4);
+    assert!(!set.is_empty());
+
+    set.clear();
+    assert_eq!(set.len(),0);
+    assert!(set.is_empty());
+}
+
+
+#[test]
+fn iteration_order(){
+    let set=new_set();
+
+    assert_eq!(set.iter().cloned().collect::<Vec<_>>(),vec![10,77,88,90]);
+    assert_eq!(set.iter().rev().cloned().collect::<Vec<_>>(),vec![90,88,77,10]);
+    assert_eq!((&set).into_iter().cloned().collect::<Vec<_>>(),vec![10,77,88,90]);
+
+    assert_eq!(set.clone().into_iter().collect::<Vec<_>>(),vec![10,77,88,90]);
+    assert_eq!(set.into_iter().rev().collect::<Vec<_>>(),vec![90,88,77,10]);
+}
+
+
+#[test]
+fn ranges(){
+    let set=new_set();
+    let stdset=new_stdset();
+
+    assert_eq!(
+        set.range(20..89).cloned().collect::<Vec<_>>(),
+        stdset.range(20..89).cloned().collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        set.range(..=88).rev().cloned().collect::<Vec<_>>(),
+        stdset.range(..=88).rev().cloned().collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        set.range(78..).cloned().collect::<Vec<_>>(),
+        stdset.range(78..).cloned().collect::<Vec<_>>(),
+    );
+}
+
+
+#[test]
+#[should_panic]
+fn range_start_greater_than_end(){
+    new_set().range(20..10);
+}
+
+
+#[test]
+fn set_relations(){
+    let set=new_set();
+    let subset:RBTreeSet<u32>=vec![10,88].into_iter().collect();
+    let disjoint:RBTreeSet<u32>=vec![0,1,2].into_iter().collect();
+
+    assert!(subset.is_subset(&set));
+    assert!(!set.is_subset(&subset));
+    assert!(set.is_superset(&subset));
+    assert!(set.is_subset(&set));
+
+    assert!(set.is_disjoint(&disjoint));
+    assert!(disjoint.is_disjoint(&set));
+    assert!(!set.is_disjoint(&subset));
+}
+
+
+#[test]
+fn from_into_btreeset(){
+    let stdset=new_stdset();
+    let set:RBTreeSet<u32>=stdset.clone().into();
+    assert_eq!(set,new_set());
+
+    let back:BTreeSet<u32>=set.into();
+    assert_eq!(back,stdset);
+}
+
+
+#[test]
+fn from_iter_extend(){
+    let mut set=RBTreeSet::new();
+    set.extend(new_stdset());
+    assert_eq!(set,new_set());
+}
+
+
+#[test]
+fn test_serde(){
+    let set=new_set();
+
+    let serialized=serde_json::to_string(&set).unwrap();
+    assert_eq!(serialized,"[10,77,88,90]");
+
+    let deserialized=serde_json::from_str::<RBTreeSet<u32>>("[77,10,90,88,10]").unwrap();
+    assert_eq!(deserialized,set);
+}
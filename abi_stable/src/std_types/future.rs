@@ -0,0 +1,160 @@
+/*!
+Ffi-safe equivalent of `Pin<Box<dyn Future<Output=T>+Send+'a>>`
+and `Pin<Box<dyn Future<Output=T>+'a>>`.
+*/
+
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    task::{Context,Poll},
+};
+
+use crate::{
+    marker_type::{ErasedObject,UnsyncSend,UnsyncUnsend},
+    std_types::{
+        RBox,Tuple2,
+        task::{RContext,RPoll},
+    },
+    utils::transmute_mut_reference,
+};
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+Ffi-safe equivalent of `Pin<Box<dyn Future<Output=T>+'a>>`,
+whose `Send`ness is determined by the `M` type parameter.
+
+The future is polled through an `RContext`,
+which wakes up the task that is polling this `RFuture_<_>`.
+
+# Example
+
+```
+use abi_stable::std_types::{RFuture,RString};
+
+use std::future::Future;
+
+fn greet(name:RString)->RFuture<'static,RString>{
+    RFuture::new(std::future::ready(format!("hello {}",name).into()))
+}
+
+fn assert_future<F:Future<Output=RString>+Send>(_:&F){}
+
+let future=greet("world".into());
+assert_future(&future);
+
+```
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RFuture_<'a,T,M=UnsyncSend>{
+    future:RBox<ErasedObject>,
+    poll:unsafe extern "C" fn(&mut ErasedObject,RContext<'_>)->RPoll<T>,
+    _marker:PhantomData<Tuple2<&'a (),M>>,
+}
+
+/// Ffi safe equivalent to `Pin<Box<dyn Future<Output=T>+Send+'a>>`.
+pub type RFuture<'a,T>=RFuture_<'a,T,UnsyncSend>;
+
+/// Ffi safe equivalent to `Pin<Box<dyn Future<Output=T>+'a>>`.
+pub type UnsyncRFuture<'a,T>=RFuture_<'a,T,UnsyncUnsend>;
+
+
+impl<'a,T,M> RFuture_<'a,T,M>{
+    fn new_inner<F>(future:F)->Self
+    where
+        F:Future<Output=T>+'a,
+    {
+        unsafe{
+            RFuture_{
+                future:mem::transmute::<RBox<F>,RBox<ErasedObject>>(RBox::new(future)),
+                poll:poll_future::<F>,
+                _marker:PhantomData,
+            }
+        }
+    }
+
+    /// Polls the future with an `RContext`.
+    pub fn poll_rcontext(&mut self,cx:RContext<'_>)->RPoll<T>{
+        unsafe{
+            (self.poll)(&mut self.future,cx)
+        }
+    }
+
+    /// Converts this `RFuture_<_>` to `UnsyncRFuture`.
+    pub fn into_unsync(self)->UnsyncRFuture<'a,T>{
+        unsafe{
+            mem::transmute::<RFuture_<'a,T,M>,UnsyncRFuture<'a,T>>(self)
+        }
+    }
+}
+
+
+macro_rules! declare_constructor {
+    (
+        bounds=( $($bounds:tt)* ) ,
+        marker=$marker:ident,
+    ) => (
+        impl<'a,T> RFuture_<'a,T,$marker> {
+            /// Constructs this from a future.
+            pub fn new<F>(future: F) -> Self
+            where
+                F: Future<Output=T> $($bounds)* + 'a,
+            {
+                Self::new_inner(future)
+            }
+        }
+    )
+}
+
+declare_constructor!{
+    bounds=( + Send ) ,
+    marker=UnsyncSend,
+}
+declare_constructor!{
+    bounds=( ) ,
+    marker=UnsyncUnsend,
+}
+
+
+// The future is stored in an `RBox<_>`,
+// so it never moves even if the `RFuture_<_>` does.
+impl<'a,T,M> Unpin for RFuture_<'a,T,M>{}
+
+impl<'a,T,M> Future for RFuture_<'a,T,M>{
+    type Output=T;
+
+    fn poll(mut self:Pin<&mut Self>,cx:&mut Context<'_>)->Poll<T>{
+        self.poll_rcontext(RContext::from_context(cx)).into()
+    }
+}
+
+impl<'a,T,M> fmt::Debug for RFuture_<'a,T,M>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RFuture{..}")
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+unsafe extern "C" fn poll_future<F>(future:&mut ErasedObject,cx:RContext<'_>)->RPoll<F::Output>
+where
+    F:Future,
+{
+    extern_fn_panic_handling!{
+        // The future is never moved out of its `RBox<_>`.
+        let future=Pin::new_unchecked(transmute_mut_reference::<ErasedObject,F>(future));
+        cx.with_context(|cx| future.poll(cx) ).into()
+    }
+}
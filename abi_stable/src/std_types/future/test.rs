@@ -0,0 +1,80 @@
+use super::*;
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::Arc,
+    thread,
+};
+
+use crate::{
+    std_types::RString,
+    test_utils::block_on,
+};
+
+
+fn assert_send<T:Send>(_:&T){}
+
+
+/// A future that returns `value` after being polled `remaining+1` times,
+/// waking up the task from another thread.
+struct Countdown<T>{
+    remaining:u32,
+    value:Option<T>,
+}
+
+impl<T:Unpin> Future for Countdown<T>{
+    type Output=T;
+
+    fn poll(mut self:Pin<&mut Self>,cx:&mut Context<'_>)->Poll<T>{
+        if self.remaining==0 {
+            return Poll::Ready(self.value.take().unwrap());
+        }
+        self.remaining-=1;
+        let waker=cx.waker().clone();
+        thread::spawn(move||{
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+
+#[test]
+fn rfuture(){
+    let future=RFuture::new(Countdown{remaining:3,value:Some(RString::from("hello"))});
+    assert_send(&future);
+    assert_eq!(block_on(future).as_str(),"hello");
+
+    let future=RFuture::new(Countdown{remaining:0,value:Some(10)});
+    assert_eq!(block_on(future.into_unsync()),10);
+}
+
+
+#[test]
+fn unsync_rfuture(){
+    let rc=Rc::new(Cell::new(0));
+    let future=UnsyncRFuture::new(Countdown{remaining:2,value:Some(rc.clone())});
+    block_on(future).set(5);
+    assert_eq!(rc.get(),5);
+}
+
+
+#[test]
+fn drops_future(){
+    let arc=Arc::new(());
+    let future=RFuture::new(Countdown{remaining:2,value:Some(arc.clone())});
+    assert_eq!(Arc::strong_count(&arc),2);
+    drop(future);
+    assert_eq!(Arc::strong_count(&arc),1);
+}
+
+
+#[test]
+fn poll_rcontext(){
+    let mut future=RFuture::new(Countdown{remaining:1,value:Some(7)});
+    let result=block_on(std::future::poll_fn(|cx|{
+        future.poll_rcontext(RContext::from_context(cx)).into_poll()
+    }));
+    assert_eq!(result,7);
+}
@@ -12,9 +12,9 @@ macro_rules! declare_iter_interface {
         interface=$interface:ident;
         type Item=$item:ty;
     ) => (
-        $(#[$attr])*
         #[repr(C)]
         #[derive(StableAbi)]
+        $(#[$attr])*
         pub struct $interface<$k,$v>(PhantomData<Tuple2<$k,$v>>);
 
         impl<$k,$v> $interface<$k,$v>{
@@ -0,0 +1,673 @@
+/*!
+Contains the ffi-safe equivalents of `std::ffi::OsStr` and `std::ffi::OsString`.
+
+On Unix platforms these store the same bytes that `std::os::unix::ffi::OsStrExt` exposes,
+so conversions to and from the std types are lossless.
+
+On other platforms the bytes are UTF-8,
+and converting from an `OsStr` that is not valid unicode replaces the invalid parts with
+`U+FFFD REPLACEMENT CHARACTER`.
+*/
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ffi::{OsStr,OsString},
+    fmt::{self,Debug},
+    hash::{Hash,Hasher},
+    str,
+};
+
+use serde::{
+    de::{self,SeqAccess,Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::std_types::{RSlice,RString,RVec};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests;
+
+
+/**
+Ffi-safe equivalent of `&'a std::ffi::OsStr`,
+a borrowed platform string that is not necessarily valid UTF-8.
+
+# Example
+
+```
+use abi_stable::std_types::ROsStr;
+
+let os_str=ROsStr::from_str("hello");
+
+assert_eq!(os_str.to_str(),Some("hello"));
+assert_eq!(os_str.as_bytes(),b"hello");
+
+```
+*/
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct ROsStr<'a>{
+    inner:RSlice<'a,u8>,
+}
+
+
+impl<'a> ROsStr<'a>{
+    pub const EMPTY: Self = ROsStr {
+        inner: RSlice::EMPTY,
+    };
+
+    /// Constructs an empty `ROsStr<'a>`.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self::EMPTY
+    }
+
+    /// Constructs an `ROsStr<'a>` from its platform-specific bytes.
+    ///
+    /// On non-Unix platforms,
+    /// methods that convert to std types replace invalid UTF-8 with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    #[inline]
+    pub fn from_bytes(bytes:&'a [u8])->Self{
+        ROsStr{
+            inner:bytes.into(),
+        }
+    }
+
+    /// Constructs an `ROsStr<'a>` from a `&'a str`.
+    #[inline]
+    pub fn from_str(s:&'a str)->Self{
+        Self::from_bytes(s.as_bytes())
+    }
+
+    /// Gets the platform-specific bytes of this `ROsStr<'a>`.
+    #[inline]
+    pub fn as_bytes(&self)->&'a [u8]{
+        self.inner.as_slice()
+    }
+
+    /// Gets the platform-specific bytes of this `ROsStr<'a>` as an `RSlice<'a,u8>`.
+    #[inline]
+    pub fn as_rslice(&self)->RSlice<'a,u8>{
+        self.inner
+    }
+
+    /// The length of this `ROsStr<'a>`,in bytes.
+    #[inline]
+    pub const fn len(&self)->usize{
+        self.inner.len()
+    }
+
+    /// Whether this `ROsStr<'a>` is empty.
+    #[inline]
+    pub fn is_empty(&self)->bool{
+        self.len()==0
+    }
+
+    /// Converts this to a `&'a str`,if it is valid UTF-8.
+    pub fn to_str(&self)->Option<&'a str>{
+        str::from_utf8(self.as_bytes()).ok()
+    }
+
+    /// Converts this to a `Cow<'a,str>`,
+    /// replacing invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self)->Cow<'a,str>{
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Converts this to a `Cow<'a,OsStr>`.
+    ///
+    /// This is always borrowed on Unix platforms.
+    pub fn to_os_str(&self)->Cow<'a,OsStr>{
+        bytes_to_os_str(self.as_bytes())
+    }
+
+    /// Converts this to an `OsString`,allocating the string.
+    pub fn to_os_string(&self)->OsString{
+        self.to_os_str().into_owned()
+    }
+
+    /// Converts this to an `ROsString`,allocating the string.
+    pub fn to_ros_string(&self)->ROsString{
+        ROsString::from_vec(self.as_bytes().to_vec())
+    }
+}
+
+
+impl<'a> Default for ROsStr<'a> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<'a> Debug for ROsStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.to_os_str(), f)
+    }
+}
+
+impl<'a> Eq for ROsStr<'a> {}
+
+impl<'a,'b> PartialEq<ROsStr<'b>> for ROsStr<'a> {
+    fn eq(&self,other:&ROsStr<'b>)->bool{
+        self.as_bytes()==other.as_bytes()
+    }
+}
+
+impl<'a> Ord for ROsStr<'a> {
+    fn cmp(&self,other:&Self)->Ordering{
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<'a,'b> PartialOrd<ROsStr<'b>> for ROsStr<'a> {
+    fn partial_cmp(&self,other:&ROsStr<'b>)->Option<Ordering>{
+        self.as_bytes().partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> Hash for ROsStr<'a> {
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.as_bytes().hash(state)
+    }
+}
+
+
+impl<'a> From<&'a str> for ROsStr<'a> {
+    fn from(s:&'a str)->Self{
+        Self::from_str(s)
+    }
+}
+
+impl_from_rust_repr! {
+    #[cfg(unix)]
+    impl['a] From<&'a OsStr> for ROsStr<'a> {
+        fn(this){
+            ROsStr::from_bytes(std::os::unix::ffi::OsStrExt::as_bytes(this))
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl['a] Into<Cow<'a,OsStr>> for ROsStr<'a> {
+        fn(this){
+            this.to_os_str()
+        }
+    }
+}
+
+impl<'a> Into<OsString> for ROsStr<'a> {
+    fn into(self)->OsString{
+        self.to_os_string()
+    }
+}
+
+#[cfg(unix)]
+impl<'a> AsRef<OsStr> for ROsStr<'a> {
+    fn as_ref(&self)->&OsStr{
+        std::os::unix::ffi::OsStrExt::from_bytes(self.as_bytes())
+    }
+}
+
+impl<'a> AsRef<[u8]> for ROsStr<'a> {
+    fn as_ref(&self)->&[u8]{
+        self.as_bytes()
+    }
+}
+
+
+impl<'de> Deserialize<'de> for ROsStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BorrowedVisitor)
+    }
+}
+
+impl<'a> Serialize for ROsStr<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(),serializer)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+Ffi-safe equivalent of `std::ffi::OsString`,
+an owned platform string that is not necessarily valid UTF-8.
+
+# Example
+
+```
+use abi_stable::std_types::ROsString;
+
+use std::ffi::OsString;
+
+let mut os_string=ROsString::from("foo");
+os_string.push("bar");
+
+assert_eq!(os_string.to_str(),Some("foobar"));
+
+let std_os_string:OsString=os_string.into();
+assert_eq!(std_os_string,OsString::from("foobar"));
+
+```
+*/
+#[repr(C)]
+#[derive(Clone, StableAbi)]
+pub struct ROsString{
+    inner:RVec<u8>,
+}
+
+
+impl ROsString{
+    /// Creates a new,empty `ROsString`.
+    pub fn new()->Self{
+        Self::from_vec(RVec::new())
+    }
+
+    /// Creates a new,empty `ROsString`,with the capacity for `cap` bytes.
+    pub fn with_capacity(cap:usize)->Self{
+        Self::from_vec(RVec::with_capacity(cap))
+    }
+
+    /// Constructs an `ROsString` from its platform-specific bytes.
+    ///
+    /// On non-Unix platforms,
+    /// methods that convert to std types replace invalid UTF-8 with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn from_vec<V>(vec:V)->Self
+    where
+        V:Into<RVec<u8>>,
+    {
+        ROsString{
+            inner:vec.into(),
+        }
+    }
+
+    /// Gets the platform-specific bytes of this `ROsString`.
+    #[inline]
+    pub fn as_bytes(&self)->&[u8]{
+        &self.inner
+    }
+
+    /// Converts this into its platform-specific bytes.
+    #[inline]
+    pub fn into_bytes(self)->RVec<u8>{
+        self.inner
+    }
+
+    /// Creates an `ROsStr<'_>` with access to all the bytes of the `ROsString`.
+    #[inline]
+    pub fn as_ros_str(&self)->ROsStr<'_>{
+        ROsStr::from_bytes(self.as_bytes())
+    }
+
+    /// The length of this `ROsString`,in bytes.
+    #[inline]
+    pub const fn len(&self)->usize{
+        self.inner.len()
+    }
+
+    /// The capacity of this `ROsString`,in bytes.
+    #[inline]
+    pub const fn capacity(&self)->usize{
+        self.inner.capacity()
+    }
+
+    /// Whether this `ROsString` is empty.
+    #[inline]
+    pub fn is_empty(&self)->bool{
+        self.len()==0
+    }
+
+    /// Reserves `àdditional` additional capacity for extra bytes.
+    pub fn reserve(&mut self,additional:usize){
+        self.inner.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the `ROsString` to match its length.
+    pub fn shrink_to_fit(&mut self){
+        self.inner.shrink_to_fit();
+    }
+
+    /// Removes all the contents of the `ROsString`.
+    pub fn clear(&mut self){
+        self.inner.clear();
+    }
+
+    /// Appends `s` to this `ROsString`.
+    pub fn push<S>(&mut self,s:S)
+    where
+        S:AsRef<OsStr>,
+    {
+        self.inner.extend_from_copy_slice(&os_str_to_bytes(s.as_ref()));
+    }
+
+    /// Converts this to a `&str`,if it is valid UTF-8.
+    pub fn to_str(&self)->Option<&str>{
+        self.as_ros_str().to_str()
+    }
+
+    /// Converts this to a `Cow<'_,str>`,
+    /// replacing invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self)->Cow<'_,str>{
+        self.as_ros_str().to_string_lossy()
+    }
+
+    /// Converts this into an `RString`,
+    /// returning it back in the `Err` variant if it is not valid UTF-8.
+    pub fn into_string(self)->Result<RString,ROsString>{
+        RString::from_utf8(self.inner)
+            .map_err(|e| ROsString::from_vec(e.into_bytes()) )
+    }
+
+    /// Converts this to an `OsString`,allocating the string.
+    pub fn to_os_string(&self)->OsString{
+        self.as_ros_str().to_os_string()
+    }
+
+    /// Converts this into an `OsString`.
+    ///
+    /// # Allocation
+    ///
+    /// On Unix platforms,
+    /// this only allocates if it is invoked outside of the dynamic library/binary
+    /// that created the `ROsString`.
+    pub fn into_os_string(self)->OsString{
+        bytes_into_os_string(self.inner.into_vec())
+    }
+}
+
+
+impl Default for ROsString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for ROsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_ros_str(), f)
+    }
+}
+
+impl Eq for ROsString {}
+
+impl PartialEq for ROsString {
+    fn eq(&self,other:&Self)->bool{
+        self.as_bytes()==other.as_bytes()
+    }
+}
+
+impl Ord for ROsString {
+    fn cmp(&self,other:&Self)->Ordering{
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl PartialOrd for ROsString {
+    fn partial_cmp(&self,other:&Self)->Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for ROsString {
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.as_bytes().hash(state)
+    }
+}
+
+
+impl_from_rust_repr! {
+    impl[] From<OsString> for ROsString {
+        fn(this){
+            ROsString::from_vec(os_string_into_bytes(this))
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[] Into<OsString> for ROsString {
+        fn(this){
+            this.into_os_string()
+        }
+    }
+}
+
+impl<'a> From<&'a OsStr> for ROsString {
+    fn from(s:&'a OsStr)->Self{
+        ROsString::from_vec(os_str_to_bytes(s).into_owned())
+    }
+}
+
+impl<'a> From<&'a str> for ROsString {
+    fn from(s:&'a str)->Self{
+        ROsString::from_vec(s.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for ROsString {
+    fn from(s:String)->Self{
+        ROsString::from_vec(s.into_bytes())
+    }
+}
+
+impl From<RString> for ROsString {
+    fn from(s:RString)->Self{
+        ROsString::from_vec(s.into_bytes())
+    }
+}
+
+impl<'a> From<ROsStr<'a>> for ROsString {
+    fn from(s:ROsStr<'a>)->Self{
+        s.to_ros_string()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<OsStr> for ROsString {
+    fn as_ref(&self)->&OsStr{
+        std::os::unix::ffi::OsStrExt::from_bytes(self.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for ROsString {
+    fn as_ref(&self)->&[u8]{
+        self.as_bytes()
+    }
+}
+
+
+impl<'de> Deserialize<'de> for ROsString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(OwnedVisitor)
+    }
+}
+
+impl Serialize for ROsString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(),serializer)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+//                              serde helpers
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// Serializes the bytes as a string if they are valid UTF-8,otherwise as bytes.
+pub(super) fn serialize_bytes<S>(bytes:&[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match str::from_utf8(bytes) {
+        Ok(s)=>serializer.serialize_str(s),
+        Err(_)=>serializer.serialize_bytes(bytes),
+    }
+}
+
+
+/// Deserializes an `ROsStr` borrowing from the input,from either a string or bytes.
+pub(super) struct BorrowedVisitor;
+
+impl<'de> Visitor<'de> for BorrowedVisitor {
+    type Value = ROsStr<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a borrowed string or byte array")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsStr::from_str(v))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsStr::from_bytes(v))
+    }
+}
+
+
+/// Deserializes an `ROsString` from either a string,bytes,or a sequence of bytes.
+pub(super) struct OwnedVisitor;
+
+impl<'de> Visitor<'de> for OwnedVisitor {
+    type Value = ROsString;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string or byte array")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsString::from(v))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsString::from(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsString::from_vec(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ROsString::from_vec(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes=RVec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(ROsString::from_vec(bytes))
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+//                     platform-specific conversions
+///////////////////////////////////////////////////////////////////////////////
+
+
+#[cfg(unix)]
+pub(super) fn os_str_to_bytes(s:&OsStr)->Cow<'_,[u8]>{
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(s.as_bytes())
+}
+
+#[cfg(unix)]
+pub(super) fn bytes_to_os_str(bytes:&[u8])->Cow<'_,OsStr>{
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(OsStr::from_bytes(bytes))
+}
+
+#[cfg(unix)]
+pub(super) fn os_string_into_bytes(s:OsString)->Vec<u8>{
+    use std::os::unix::ffi::OsStringExt;
+    s.into_vec()
+}
+
+#[cfg(unix)]
+pub(super) fn bytes_into_os_string(bytes:Vec<u8>)->OsString{
+    use std::os::unix::ffi::OsStringExt;
+    OsString::from_vec(bytes)
+}
+
+
+#[cfg(not(unix))]
+pub(super) fn os_str_to_bytes(s:&OsStr)->Cow<'_,[u8]>{
+    match s.to_string_lossy() {
+        Cow::Borrowed(s)=>Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s)=>Cow::Owned(s.into_bytes()),
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn bytes_to_os_str(bytes:&[u8])->Cow<'_,OsStr>{
+    match String::from_utf8_lossy(bytes) {
+        Cow::Borrowed(s)=>Cow::Borrowed(OsStr::new(s)),
+        Cow::Owned(s)=>Cow::Owned(OsString::from(s)),
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn os_string_into_bytes(s:OsString)->Vec<u8>{
+    match s.into_string() {
+        Ok(s)=>s.into_bytes(),
+        Err(s)=>s.to_string_lossy().into_owned().into_bytes(),
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn bytes_into_os_string(bytes:Vec<u8>)->OsString{
+    match String::from_utf8(bytes) {
+        Ok(s)=>s.into(),
+        Err(e)=>String::from_utf8_lossy(e.as_bytes()).into_owned().into(),
+    }
+}
+
@@ -0,0 +1,124 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+
+/// Bytes that are not valid UTF-8.
+const INVALID_UTF8:&[u8]=b"foo\xFF\xFEbar";
+
+fn hash_value<T:Hash>(value:&T)->u64{
+    let mut hasher=DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+#[test]
+fn ros_str_from_to_str(){
+    let os_str=ROsStr::from_str("hello");
+    assert_eq!(os_str.to_str(),Some("hello"));
+    assert_eq!(os_str.to_string_lossy(),"hello");
+    assert_eq!(os_str.to_os_str(),OsStr::new("hello"));
+    assert_eq!(os_str.to_os_string(),OsString::from("hello"));
+    assert_eq!(os_str.len(),5);
+    assert!(!os_str.is_empty());
+    assert!(ROsStr::empty().is_empty());
+    assert_eq!(ROsStr::default(),ROsStr::EMPTY);
+}
+
+
+#[test]
+fn invalid_utf8(){
+    let os_str=ROsStr::from_bytes(INVALID_UTF8);
+    assert_eq!(os_str.to_str(),None);
+    assert_eq!(os_str.to_string_lossy(),"foo\u{FFFD}\u{FFFD}bar");
+
+    let os_string=os_str.to_ros_string();
+    assert_eq!(os_string.to_str(),None);
+    assert_eq!(os_string.as_bytes(),INVALID_UTF8);
+
+    let err=os_string.clone().into_string().unwrap_err();
+    assert_eq!(err,os_string);
+
+    assert_eq!(
+        ROsString::from("hello").into_string(),
+        Ok(RString::from("hello")),
+    );
+}
+
+
+#[cfg(unix)]
+#[test]
+fn lossless_unix_conversions(){
+    use std::os::unix::ffi::{OsStrExt,OsStringExt};
+
+    let std_os_str=OsStr::from_bytes(INVALID_UTF8);
+
+    let os_str=ROsStr::from(std_os_str);
+    assert_eq!(os_str.as_bytes(),INVALID_UTF8);
+    assert_eq!(&*os_str.to_os_str(),std_os_str);
+    assert_eq!(AsRef::<OsStr>::as_ref(&os_str),std_os_str);
+
+    let os_string=ROsString::from(OsString::from_vec(INVALID_UTF8.to_vec()));
+    assert_eq!(os_string.as_bytes(),INVALID_UTF8);
+    assert_eq!(os_string.clone().into_os_string().into_vec(),INVALID_UTF8);
+    assert_eq!(os_string.to_os_string(),std_os_str);
+    assert_eq!(AsRef::<OsStr>::as_ref(&os_string),std_os_str);
+
+    let mut pushed=ROsString::from("a");
+    pushed.push(std_os_str);
+    assert_eq!(pushed.as_bytes(),&b"afoo\xFF\xFEbar"[..]);
+}
+
+
+#[test]
+fn ros_string_mutation(){
+    let mut os_string=ROsString::with_capacity(4);
+    assert!(os_string.is_empty());
+    assert!(os_string.capacity() >= 4);
+
+    os_string.push("foo");
+    os_string.push(OsString::from("bar"));
+    assert_eq!(os_string.to_str(),Some("foobar"));
+    assert_eq!(os_string.len(),6);
+
+    let capacity=os_string.capacity();
+    os_string.clear();
+    assert!(os_string.is_empty());
+    assert_eq!(os_string.capacity(),capacity);
+    assert_eq!(os_string,ROsString::new());
+
+    os_string.push("baz");
+    assert_eq!(os_string.to_str(),Some("baz"));
+}
+
+
+#[test]
+fn eq_ord_hash(){
+    let a=ROsString::from("aaa");
+    let b=ROsString::from("aab");
+
+    assert_eq!(a,a.clone());
+    assert_eq!(a.as_ros_str(),ROsStr::from_str("aaa"));
+    assert_eq!(hash_value(&a),hash_value(&a.clone()));
+    assert_eq!(hash_value(&a.as_ros_str()),hash_value(&ROsStr::from_str("aaa")));
+
+    assert!(a < b);
+    assert!(a.as_ros_str() < b.as_ros_str());
+    assert_eq!(format!("{:?}",a),format!("{:?}",OsStr::new("aaa")));
+}
+
+
+#[test]
+fn test_serde(){
+    let os_string=ROsString::from("hello");
+    let serialized=serde_json::to_string(&os_string).unwrap();
+    assert_eq!(serialized,r#""hello""#);
+    assert_eq!(serde_json::from_str::<ROsString>(&serialized).unwrap(),os_string);
+    assert_eq!(serde_json::from_str::<ROsStr<'_>>(&serialized).unwrap(),os_string.as_ros_str());
+
+    let invalid=ROsString::from_vec(INVALID_UTF8.to_vec());
+    let serialized=serde_json::to_string(&invalid).unwrap();
+    assert_eq!(serialized,"[102,111,111,255,254,98,97,114]");
+    assert_eq!(serde_json::from_str::<ROsString>(&serialized).unwrap(),invalid);
+}
@@ -0,0 +1,574 @@
+/*!
+Contains the ffi-safe equivalents of `std::path::Path` and `std::path::PathBuf`.
+
+These use the same representation as
+[`ROsStr` and `ROsString`](../os_str/index.html),
+which means that on Unix platforms conversions to and from the std types are lossless.
+*/
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ffi::{OsStr,OsString},
+    fmt::{self,Debug},
+    hash::{Hash,Hasher},
+    mem,
+    path::{Path,PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::std_types::{
+    RString,
+    os_str::{ROsStr,ROsString},
+};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests;
+
+
+/**
+Ffi-safe equivalent of `&'a std::path::Path`.
+
+Like `Path`,equality,ordering,and hashing are based on the components of the path,
+so `RPath::from_str("a//b")==RPath::from_str("a/b")`.
+
+# Example
+
+```
+use abi_stable::std_types::{RPath,RPathBuf};
+
+let path=RPath::from_str("/tmp/foo.txt");
+
+assert!(path.to_path().is_absolute());
+assert_eq!(path.to_str(),Some("/tmp/foo.txt"));
+assert_eq!(path.display().to_string(),"/tmp/foo.txt");
+
+let joined:RPathBuf=RPath::from_str("/tmp").join("bar.txt");
+assert_eq!(joined.as_path().to_str(),Some("/tmp/bar.txt"));
+
+```
+*/
+#[repr(C)]
+#[derive(Copy, Clone, StableAbi)]
+pub struct RPath<'a>{
+    inner:ROsStr<'a>,
+}
+
+
+impl<'a> RPath<'a>{
+    /// Constructs an `RPath<'a>` from an `ROsStr<'a>`.
+    #[inline]
+    pub fn from_ros_str(inner:ROsStr<'a>)->Self{
+        RPath{inner}
+    }
+
+    /// Constructs an `RPath<'a>` from a `&'a str`.
+    #[inline]
+    pub fn from_str(s:&'a str)->Self{
+        Self::from_ros_str(ROsStr::from_str(s))
+    }
+
+    /// Gets the path as an `ROsStr<'a>`.
+    #[inline]
+    pub fn as_ros_str(&self)->ROsStr<'a>{
+        self.inner
+    }
+
+    /// Gets the platform-specific bytes of this path.
+    #[inline]
+    pub fn as_bytes(&self)->&'a [u8]{
+        self.inner.as_bytes()
+    }
+
+    /// Converts this to a `&'a str`,if it is valid UTF-8.
+    pub fn to_str(&self)->Option<&'a str>{
+        self.inner.to_str()
+    }
+
+    /// Converts this to a `Cow<'a,str>`,
+    /// replacing invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self)->Cow<'a,str>{
+        self.inner.to_string_lossy()
+    }
+
+    /// Converts this to a `Cow<'a,Path>`.
+    ///
+    /// This is always borrowed on Unix platforms.
+    pub fn to_path(&self)->Cow<'a,Path>{
+        match self.inner.to_os_str() {
+            Cow::Borrowed(x)=>Cow::Borrowed(Path::new(x)),
+            Cow::Owned(x)=>Cow::Owned(PathBuf::from(x)),
+        }
+    }
+
+    /// Converts this to a `PathBuf`,allocating the path.
+    pub fn to_path_buf(&self)->PathBuf{
+        self.to_path().into_owned()
+    }
+
+    /// Converts this to an `RPathBuf`,allocating the path.
+    pub fn to_rpath_buf(&self)->RPathBuf{
+        RPathBuf::from_ros_string(self.inner.to_ros_string())
+    }
+
+    /// Whether the path is absolute,with the same semantics as `Path::is_absolute`.
+    pub fn is_absolute(&self)->bool{
+        self.to_path().is_absolute()
+    }
+
+    /// Creates an `RPathBuf` with `path` adjoined to this path,
+    /// with the same semantics as `PathBuf::push`.
+    pub fn join<P>(&self,path:P)->RPathBuf
+    where
+        P:AsRef<Path>,
+    {
+        let mut ret=self.to_rpath_buf();
+        ret.push(path);
+        ret
+    }
+
+    /// Returns a type that implements `Display`,
+    /// printing the path with invalid UTF-8 replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn display(&self)->Display<'a>{
+        Display{ path:*self }
+    }
+}
+
+
+impl<'a> Default for RPath<'a> {
+    fn default() -> Self {
+        Self::from_ros_str(ROsStr::EMPTY)
+    }
+}
+
+impl<'a> Debug for RPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.to_path(), f)
+    }
+}
+
+impl<'a> Eq for RPath<'a> {}
+
+impl<'a,'b> PartialEq<RPath<'b>> for RPath<'a> {
+    fn eq(&self,other:&RPath<'b>)->bool{
+        self.to_path()==other.to_path()
+    }
+}
+
+impl<'a> Ord for RPath<'a> {
+    fn cmp(&self,other:&Self)->Ordering{
+        self.to_path().cmp(&other.to_path())
+    }
+}
+
+impl<'a,'b> PartialOrd<RPath<'b>> for RPath<'a> {
+    fn partial_cmp(&self,other:&RPath<'b>)->Option<Ordering>{
+        self.to_path().partial_cmp(&other.to_path())
+    }
+}
+
+impl<'a> Hash for RPath<'a> {
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.to_path().hash(state)
+    }
+}
+
+
+impl<'a> From<&'a str> for RPath<'a> {
+    fn from(s:&'a str)->Self{
+        Self::from_str(s)
+    }
+}
+
+impl<'a> From<ROsStr<'a>> for RPath<'a> {
+    fn from(s:ROsStr<'a>)->Self{
+        Self::from_ros_str(s)
+    }
+}
+
+impl_from_rust_repr! {
+    #[cfg(unix)]
+    impl['a] From<&'a Path> for RPath<'a> {
+        fn(this){
+            RPath::from_ros_str(this.as_os_str().into())
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl['a] Into<Cow<'a,Path>> for RPath<'a> {
+        fn(this){
+            this.to_path()
+        }
+    }
+}
+
+impl<'a> Into<PathBuf> for RPath<'a> {
+    fn into(self)->PathBuf{
+        self.to_path_buf()
+    }
+}
+
+#[cfg(unix)]
+impl<'a> AsRef<Path> for RPath<'a> {
+    fn as_ref(&self)->&Path{
+        Path::new(AsRef::<OsStr>::as_ref(&self.inner))
+    }
+}
+
+#[cfg(unix)]
+impl<'a> AsRef<OsStr> for RPath<'a> {
+    fn as_ref(&self)->&OsStr{
+        self.inner.as_ref()
+    }
+}
+
+
+impl<'de> Deserialize<'de> for RPath<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ROsStr::deserialize(deserializer).map(Self::from_ros_str)
+    }
+}
+
+impl<'a> Serialize for RPath<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+Ffi-safe equivalent of `std::path::PathBuf`.
+
+Like `PathBuf`,equality,ordering,and hashing are based on the components of the path.
+
+# Example
+
+```
+use abi_stable::std_types::RPathBuf;
+
+use std::path::PathBuf;
+
+let mut path=RPathBuf::from("/tmp");
+path.push("foo");
+path.set_extension("txt");
+
+assert_eq!(path.as_path().to_str(),Some("/tmp/foo.txt"));
+
+assert!(path.pop());
+assert_eq!(path.as_path().to_str(),Some("/tmp"));
+
+let std_path:PathBuf=path.into();
+assert_eq!(std_path,PathBuf::from("/tmp"));
+
+```
+*/
+#[repr(C)]
+#[derive(Clone, StableAbi)]
+pub struct RPathBuf{
+    inner:ROsString,
+}
+
+
+impl RPathBuf{
+    /// Creates a new,empty `RPathBuf`.
+    pub fn new()->Self{
+        Self::from_ros_string(ROsString::new())
+    }
+
+    /// Creates a new,empty `RPathBuf`,with the capacity for `cap` bytes.
+    pub fn with_capacity(cap:usize)->Self{
+        Self::from_ros_string(ROsString::with_capacity(cap))
+    }
+
+    /// Constructs an `RPathBuf` from an `ROsString`.
+    #[inline]
+    pub fn from_ros_string(inner:ROsString)->Self{
+        RPathBuf{inner}
+    }
+
+    /// Creates an `RPath<'_>` with access to the entire path.
+    #[inline]
+    pub fn as_path(&self)->RPath<'_>{
+        RPath::from_ros_str(self.inner.as_ros_str())
+    }
+
+    /// Gets the path as an `ROsStr<'_>`.
+    #[inline]
+    pub fn as_ros_str(&self)->ROsStr<'_>{
+        self.inner.as_ros_str()
+    }
+
+    /// Converts this into an `ROsString`.
+    #[inline]
+    pub fn into_ros_string(self)->ROsString{
+        self.inner
+    }
+
+    /// The capacity of this `RPathBuf`,in bytes.
+    #[inline]
+    pub const fn capacity(&self)->usize{
+        self.inner.capacity()
+    }
+
+    /// Removes all the contents of the `RPathBuf`.
+    pub fn clear(&mut self){
+        self.inner.clear();
+    }
+
+    /// Converts this to a `&str`,if it is valid UTF-8.
+    pub fn to_str(&self)->Option<&str>{
+        self.inner.to_str()
+    }
+
+    /// Converts this to a `Cow<'_,str>`,
+    /// replacing invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self)->Cow<'_,str>{
+        self.inner.to_string_lossy()
+    }
+
+    /// Converts this to a `PathBuf`,allocating the path.
+    pub fn to_path_buf(&self)->PathBuf{
+        self.as_path().to_path_buf()
+    }
+
+    /// Converts this into a `PathBuf`.
+    ///
+    /// # Allocation
+    ///
+    /// On Unix platforms,
+    /// this only allocates if it is invoked outside of the dynamic library/binary
+    /// that created the `RPathBuf`.
+    pub fn into_path_buf(self)->PathBuf{
+        self.inner.into_os_string().into()
+    }
+
+    /// Whether the path is absolute,with the same semantics as `Path::is_absolute`.
+    pub fn is_absolute(&self)->bool{
+        self.as_path().is_absolute()
+    }
+
+    /// Returns a type that implements `Display`,
+    /// printing the path with invalid UTF-8 replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn display(&self)->Display<'_>{
+        self.as_path().display()
+    }
+
+    /// Extends this path with `path`,with the same semantics as `PathBuf::push`.
+    pub fn push<P>(&mut self,path:P)
+    where
+        P:AsRef<Path>,
+    {
+        self.with_path_buf(|this| this.push(path) )
+    }
+
+    /// Truncates this path to its parent,with the same semantics as `PathBuf::pop`.
+    pub fn pop(&mut self)->bool{
+        self.with_path_buf(|this| this.pop() )
+    }
+
+    /// Replaces the file name of this path,
+    /// with the same semantics as `PathBuf::set_file_name`.
+    pub fn set_file_name<S>(&mut self,file_name:S)
+    where
+        S:AsRef<OsStr>,
+    {
+        self.with_path_buf(|this| this.set_file_name(file_name) )
+    }
+
+    /// Replaces the extension of this path,
+    /// with the same semantics as `PathBuf::set_extension`.
+    pub fn set_extension<S>(&mut self,extension:S)->bool
+    where
+        S:AsRef<OsStr>,
+    {
+        self.with_path_buf(|this| this.set_extension(extension) )
+    }
+
+    /// Does an operation on this path as a `PathBuf`.
+    fn with_path_buf<F,R>(&mut self,f:F)->R
+    where
+        F:FnOnce(&mut PathBuf)->R,
+    {
+        let mut path=mem::replace(self,RPathBuf::new()).into_path_buf();
+        let ret=f(&mut path);
+        *self=path.into();
+        ret
+    }
+}
+
+
+impl Default for RPathBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for RPathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_path(), f)
+    }
+}
+
+impl Eq for RPathBuf {}
+
+impl PartialEq for RPathBuf {
+    fn eq(&self,other:&Self)->bool{
+        self.as_path()==other.as_path()
+    }
+}
+
+impl Ord for RPathBuf {
+    fn cmp(&self,other:&Self)->Ordering{
+        self.as_path().cmp(&other.as_path())
+    }
+}
+
+impl PartialOrd for RPathBuf {
+    fn partial_cmp(&self,other:&Self)->Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for RPathBuf {
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.as_path().hash(state)
+    }
+}
+
+
+impl_from_rust_repr! {
+    impl[] From<PathBuf> for RPathBuf {
+        fn(this){
+            RPathBuf::from_ros_string(this.into_os_string().into())
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[] Into<PathBuf> for RPathBuf {
+        fn(this){
+            this.into_path_buf()
+        }
+    }
+}
+
+impl<'a> From<&'a Path> for RPathBuf {
+    fn from(path:&'a Path)->Self{
+        RPathBuf::from_ros_string(path.as_os_str().into())
+    }
+}
+
+impl From<OsString> for RPathBuf {
+    fn from(s:OsString)->Self{
+        RPathBuf::from_ros_string(s.into())
+    }
+}
+
+impl From<ROsString> for RPathBuf {
+    fn from(s:ROsString)->Self{
+        RPathBuf::from_ros_string(s)
+    }
+}
+
+impl<'a> From<&'a str> for RPathBuf {
+    fn from(s:&'a str)->Self{
+        RPathBuf::from_ros_string(s.into())
+    }
+}
+
+impl From<String> for RPathBuf {
+    fn from(s:String)->Self{
+        RPathBuf::from_ros_string(s.into())
+    }
+}
+
+impl From<RString> for RPathBuf {
+    fn from(s:RString)->Self{
+        RPathBuf::from_ros_string(s.into())
+    }
+}
+
+impl<'a> From<RPath<'a>> for RPathBuf {
+    fn from(path:RPath<'a>)->Self{
+        path.to_rpath_buf()
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<Path> for RPathBuf {
+    fn as_ref(&self)->&Path{
+        Path::new(AsRef::<OsStr>::as_ref(&self.inner))
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<OsStr> for RPathBuf {
+    fn as_ref(&self)->&OsStr{
+        self.inner.as_ref()
+    }
+}
+
+
+impl<'de> Deserialize<'de> for RPathBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ROsString::deserialize(deserializer).map(Self::from_ros_string)
+    }
+}
+
+impl Serialize for RPathBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// Helper type for printing paths,
+/// replacing invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`.
+///
+/// This is returned by `RPath::display` and `RPathBuf::display`.
+#[derive(Copy, Clone)]
+pub struct Display<'a>{
+    path:RPath<'a>,
+}
+
+impl<'a> fmt::Display for Display<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.path.to_string_lossy(), f)
+    }
+}
+
+impl<'a> Debug for Display<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.path, f)
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+use std::collections::hash_map::DefaultHasher;
+
+
+fn hash_value<T:Hash>(value:&T)->u64{
+    let mut hasher=DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+#[test]
+fn rpath_conversions(){
+    let path=RPath::from_str("foo/bar.txt");
+
+    assert_eq!(path.to_str(),Some("foo/bar.txt"));
+    assert_eq!(path.to_path(),Path::new("foo/bar.txt"));
+    assert_eq!(path.to_path_buf(),PathBuf::from("foo/bar.txt"));
+    assert_eq!(path.to_rpath_buf(),RPathBuf::from("foo/bar.txt"));
+    assert_eq!(path.as_ros_str(),ROsStr::from_str("foo/bar.txt"));
+    assert!(!path.is_absolute());
+
+    let path_buf:PathBuf=path.into();
+    assert_eq!(path_buf,PathBuf::from("foo/bar.txt"));
+}
+
+
+#[test]
+fn component_equality(){
+    let a=RPath::from_str("foo//bar/");
+    let b=RPath::from_str("foo/bar");
+
+    assert_eq!(a,b);
+    assert_eq!(a.cmp(&b),Ordering::Equal);
+    assert_eq!(hash_value(&a),hash_value(&b));
+    assert_eq!(a.to_rpath_buf(),b.to_rpath_buf());
+    assert_eq!(hash_value(&a.to_rpath_buf()),hash_value(&b.to_rpath_buf()));
+
+    assert_ne!(RPath::from_str("foo/bar"),RPath::from_str("foo/baz"));
+    assert!(RPath::from_str("foo/bar") < RPath::from_str("foo/baz"));
+}
+
+
+#[test]
+fn rpath_buf_mutation(){
+    let mut path=RPathBuf::with_capacity(16);
+    path.push("foo");
+    path.push("bar");
+    assert_eq!(path.to_path_buf(),Path::new("foo").join("bar"));
+
+    path.set_file_name("baz");
+    assert_eq!(path.to_path_buf(),Path::new("foo").join("baz"));
+
+    assert!(path.set_extension("txt"));
+    assert_eq!(path.to_path_buf(),Path::new("foo").join("baz.txt"));
+
+    assert!(path.pop());
+    assert_eq!(path.to_path_buf(),PathBuf::from("foo"));
+
+    let joined=path.as_path().join("qux");
+    assert_eq!(joined.into_path_buf(),Path::new("foo").join("qux"));
+
+    let capacity=path.capacity();
+    path.clear();
+    assert_eq!(path.capacity(),capacity);
+    assert_eq!(path,RPathBuf::new());
+
+    path.push("qux");
+    assert_eq!(path.to_path_buf(),PathBuf::from("qux"));
+}
+
+
+#[test]
+fn display_debug(){
+    let path=RPathBuf::from("foo/bar");
+    assert_eq!(path.display().to_string(),"foo/bar");
+    assert_eq!(format!("{:?}",path),format!("{:?}",Path::new("foo/bar")));
+    assert_eq!(format!("{:?}",path.as_path()),format!("{:?}",Path::new("foo/bar")));
+}
+
+
+#[cfg(unix)]
+#[test]
+fn lossless_unix_conversions(){
+    use std::os::unix::ffi::OsStrExt;
+
+    let bytes:&[u8]=b"/tmp/\xFF\xFE.txt";
+    let std_path=Path::new(OsStr::from_bytes(bytes));
+
+    let path=RPath::from(std_path);
+    assert_eq!(path.as_bytes(),bytes);
+    assert_eq!(path.to_str(),None);
+    assert_eq!(path.to_path(),std_path);
+    assert_eq!(AsRef::<Path>::as_ref(&path),std_path);
+    assert!(path.is_absolute());
+    assert_eq!(path.display().to_string(),"/tmp/\u{FFFD}\u{FFFD}.txt");
+
+    let path_buf=RPathBuf::from(std_path.to_path_buf());
+    assert_eq!(path_buf.as_path().as_bytes(),bytes);
+    assert_eq!(AsRef::<Path>::as_ref(&path_buf),std_path);
+    assert_eq!(path_buf.clone().into_path_buf(),std_path);
+
+    let mut with_extension=path_buf.clone();
+    with_extension.set_extension("rs");
+    assert_eq!(with_extension.as_path().as_bytes(),&b"/tmp/\xFF\xFE.rs"[..]);
+}
+
+
+#[test]
+fn test_serde(){
+    let path=RPathBuf::from("foo/bar");
+    let serialized=serde_json::to_string(&path).unwrap();
+    assert_eq!(serialized,r#""foo/bar""#);
+    assert_eq!(serde_json::from_str::<RPathBuf>(&serialized).unwrap(),path);
+    assert_eq!(serde_json::from_str::<RPath<'_>>(&serialized).unwrap(),path.as_path());
+}
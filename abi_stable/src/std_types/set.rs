@@ -0,0 +1,504 @@
+/*!
+Contains the ffi-safe equivalent of `std::collections::HashSet`,and related items.
+*/
+
+use std::{
+    borrow::Borrow,
+    collections::{HashSet,hash_map::RandomState},
+    fmt::{self,Debug},
+    hash::{Hash,BuildHasher},
+    iter::FromIterator,
+    marker::PhantomData,
+};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::{
+    StableAbi,
+    std_types::{
+        map::{self,RHashMap},
+        ROption,Tuple2,
+    },
+};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+/**
+
+An ffi-safe hashset,which wraps `std::collections::HashSet<T,S>`
+(by storing the values as the keys of an `RHashMap<T,(),S>`),
+only requiring the `T:Eq+Hash` bounds when constructing it.
+
+# Example
+
+```
+use abi_stable::std_types::{RHashSet,RString};
+
+let mut set=RHashSet::<RString>::new();
+
+assert!(set.insert("hello".into()));
+assert!(set.insert("world".into()));
+assert!(!set.insert("hello".into()));
+
+assert!(set.contains("hello"));
+assert_eq!(set.len(),2);
+
+assert!(set.remove("world"));
+assert!(!set.contains("world"));
+
+```
+
+*/
+#[derive(StableAbi)]
+#[repr(C)]
+#[sabi(
+    // The hasher doesn't matter
+    unsafe_unconstrained(S),
+)]
+pub struct RHashSet<T,S=RandomState>{
+    map:RHashMap<T,(),S>,
+}
+
+
+impl<T> RHashSet<T,RandomState>{
+    /// Constructs an empty RHashSet.
+    #[inline]
+    pub fn new()->RHashSet<T>
+    where
+        Self:Default
+    {
+        Self::default()
+    }
+
+    /// Constructs an empty RHashSet with the passed capacity.
+    #[inline]
+    pub fn with_capacity(capacity:usize)->RHashSet<T>
+    where
+        Self:Default
+    {
+        let mut this=Self::default();
+        this.reserve(capacity);
+        this
+    }
+}
+
+
+impl<T,S> RHashSet<T,S>{
+    /// Constructs an empty RHashSet with the passed `hash_builder` to hash the values.
+    #[inline]
+    pub fn with_hasher(hash_builder: S) -> RHashSet<T,S>
+    where
+        T:Eq+Hash,
+        S:BuildHasher+Default,
+    {
+        Self::with_capacity_and_hasher(0,hash_builder)
+    }
+
+    /// Constructs an empty RHashSet with the passed capacity,
+    /// and the passed `hash_builder` to hash the values.
+    pub fn with_capacity_and_hasher(
+        capacity: usize,
+        hash_builder: S
+    ) -> RHashSet<T,S>
+    where
+        T:Eq+Hash,
+        S:BuildHasher+Default,
+    {
+        RHashSet{
+            map:RHashMap::with_capacity_and_hasher(capacity,hash_builder),
+        }
+    }
+}
+
+
+impl<T,S> RHashSet<T,S>{
+    /// Returns whether the set contains the value.
+    pub fn contains<Q>(&self,query:&Q)->bool
+    where
+        T:Borrow<Q>,
+        Q:Hash+Eq+?Sized
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set,returning whether it was in the set.
+    pub fn remove<Q>(&mut self,query:&Q)->bool
+    where
+        T:Borrow<Q>,
+        Q:Hash+Eq+?Sized
+    {
+        self.map.remove(query).is_rsome()
+    }
+
+    /// Removes the value from the set,returning it if it was in the set.
+    pub fn take<Q>(&mut self,query:&Q)->ROption<T>
+    where
+        T:Borrow<Q>,
+        Q:Hash+Eq+?Sized
+    {
+        self.map.remove_entry(query).map(|x| x.0 )
+    }
+
+    /// Adds the value to the set,returning whether it wasn't already in the set.
+    ///
+    /// If the set already contained the value,the value in the set is not replaced.
+    pub fn insert(&mut self,value:T)->bool{
+        self.map.insert(value,()).is_rnone()
+    }
+
+    /// Reserves enough space to insert `reserved` extra values.
+    pub fn reserve(&mut self,reserved:usize){
+        self.map.reserve(reserved);
+    }
+
+    /// Removes all the values in the set.
+    pub fn clear(&mut self){
+        self.map.clear();
+    }
+
+    /// Returns the ammount of values in the set.
+    pub fn len(&self)->usize{
+        self.map.len()
+    }
+
+    /// Returns the capacity of the set,the ammount of values it can store without reallocating.
+    ///
+    /// Note that this is a lower bound,since hash sets don't necessarily have an exact capacity.
+    pub fn capacity(&self)->usize{
+        self.map.capacity()
+    }
+
+    /// Returns whether the set contains any values.
+    pub fn is_empty(&self)->bool{
+        self.len()==0
+    }
+
+    /// Iterates over references to the values in the set.
+    ///
+    /// This returns an `Iterator<Item= &T >+!Send+!Sync+Clone`
+    pub fn iter(&self)->Iter<'_,T>{
+        Iter{
+            iter:self.map.iter(),
+        }
+    }
+
+    /// Clears the set,returning an iterator over all the values that were removed.
+    ///
+    /// This returns an `Iterator<Item= T >+!Send+!Sync`
+    pub fn drain(&mut self)->Drain<'_,T>{
+        Drain{
+            iter:self.map.drain(),
+        }
+    }
+
+    /// Returns whether none of the values in this set are in `other`.
+    pub fn is_disjoint(&self,other:&Self)->bool{
+        let (smaller,larger)=if self.len() <= other.len() { (self,other) }else{ (other,self) };
+        smaller.iter().all(|x| !larger.map.contains_key_p(x) )
+    }
+
+    /// Returns whether all the values in this set are in `other`.
+    pub fn is_subset(&self,other:&Self)->bool{
+        self.len() <= other.len() &&
+        self.iter().all(|x| other.map.contains_key_p(x) )
+    }
+
+    /// Returns whether all the values in `other` are in this set.
+    pub fn is_superset(&self,other:&Self)->bool{
+        other.is_subset(self)
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// An RHashSet iterator,
+/// implementing `Iterator<Item= &T >+!Send+!Sync+Clone`
+#[repr(transparent)]
+#[derive(StableAbi)]
+pub struct Iter<'a,T:'a>{
+    iter:map::Iter<'a,T,()>,
+}
+
+impl<'a,T:'a> Clone for Iter<'a,T>{
+    fn clone(&self)->Self{
+        Iter{
+            iter:self.iter.clone(),
+        }
+    }
+}
+
+impl<'a,T:'a> Iterator for Iter<'a,T>{
+    type Item=&'a T;
+
+    #[inline]
+    fn next(&mut self)->Option<&'a T>{
+        self.iter.next().map(|Tuple2(k,_)| k )
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+
+/// An RHashSet iterator,
+/// implementing `Iterator<Item= T >+!Send+!Sync`
+#[repr(transparent)]
+#[derive(StableAbi)]
+pub struct Drain<'a,T:'a>{
+    iter:map::Drain<'a,T,()>,
+}
+
+impl<'a,T:'a> Iterator for Drain<'a,T>{
+    type Item=T;
+
+    #[inline]
+    fn next(&mut self)->Option<T>{
+        self.iter.next().map(|Tuple2(k,_)| k )
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+
+/// An iterator that yields all the values of an RHashSet,
+/// deallocating the hashset afterwards.
+///
+/// This is an `Iterator<Item= T >+!Send+!Sync`
+#[repr(transparent)]
+#[derive(StableAbi)]
+pub struct IntoIter<T>{
+    iter:map::IntoIter<T,()>,
+}
+
+impl<T> Iterator for IntoIter<T>{
+    type Item=T;
+
+    #[inline]
+    fn next(&mut self)->Option<T>{
+        self.iter.next().map(|Tuple2(k,_)| k )
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        self.iter.size_hint()
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// This returns an `Iterator<Item= T >+!Send+!Sync`
+impl<T,S> IntoIterator for RHashSet<T,S>{
+    type Item=T;
+    type IntoIter=IntoIter<T>;
+
+    fn into_iter(self)->IntoIter<T>{
+        IntoIter{
+            iter:self.map.into_iter(),
+        }
+    }
+}
+
+
+/// This returns an `Iterator<Item= &T >+!Send+!Sync+Clone`
+impl<'a,T,S> IntoIterator for &'a RHashSet<T,S>{
+    type Item=&'a T;
+    type IntoIter=Iter<'a,T>;
+
+    fn into_iter(self)->Self::IntoIter{
+        self.iter()
+    }
+}
+
+
+impl<T,S> From<HashSet<T,S>> for RHashSet<T,S>
+where
+    Self:Default
+{
+    fn from(set:HashSet<T,S>)->Self{
+        set.into_iter().collect()
+    }
+}
+
+impl<T,S> Into<HashSet<T,S>> for RHashSet<T,S>
+where
+    T:Eq+Hash,
+    S:BuildHasher+Default,
+{
+    fn into(self)->HashSet<T,S>{
+        self.into_iter().collect()
+    }
+}
+
+
+impl<T,S> FromIterator<T> for RHashSet<T,S>
+where
+    Self:Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>
+    {
+        let mut set=Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+
+impl<T,S> Extend<T> for RHashSet<T,S>{
+    #[inline]
+    fn extend<I>(&mut self,iter: I)
+    where
+        I: IntoIterator<Item = T>
+    {
+        self.map.extend( iter.into_iter().map(|x| (x,()) ) );
+    }
+}
+
+
+impl<T,S> Default for RHashSet<T,S>
+where
+    T:Eq+Hash,
+    S:BuildHasher+Default,
+{
+    fn default()->Self{
+        Self::with_hasher(S::default())
+    }
+}
+
+
+impl<T,S> Clone for RHashSet<T,S>
+where
+    T:Clone,
+    Self:Default
+{
+    fn clone(&self)->Self{
+        self.iter().cloned().collect()
+    }
+}
+
+
+impl<T,S> Debug for RHashSet<T,S>
+where
+    T:Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_set()
+         .entries(self.iter())
+         .finish()
+    }
+}
+
+
+impl<T,S> Eq for RHashSet<T,S>
+where
+    T:Eq,
+{}
+
+
+impl<T,S> PartialEq for RHashSet<T,S>
+where
+    T:PartialEq,
+{
+    fn eq(&self,other:&Self)->bool{
+        self.len() == other.len() &&
+        self.iter().all(|x| other.map.contains_key_p(x) )
+    }
+}
+
+
+mod serde{
+    use super::*;
+
+    use ::serde::{
+        de::{Visitor, SeqAccess},
+        ser::SerializeSeq,
+        Deserialize,Serialize,Deserializer,Serializer,
+    };
+
+
+    struct RHashSetVisitor<T,S> {
+        marker: PhantomData<fn() -> RHashSet<T,S>>
+    }
+
+    impl<T,S> RHashSetVisitor<T,S> {
+        fn new() -> Self {
+            RHashSetVisitor {
+                marker: PhantomData
+            }
+        }
+    }
+
+    impl<'de,T,S> Visitor<'de> for RHashSetVisitor<T,S>
+    where
+        T: Deserialize<'de>,
+        RHashSet<T,S>:Default,
+    {
+        type Value = RHashSet<T,S>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RHashSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let capacity=seq_access.size_hint().unwrap_or(0);
+            let mut set = RHashSet::default();
+            set.reserve(capacity);
+
+            while let Some(value) = seq_access.next_element()? {
+                set.insert(value);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de,T,S> Deserialize<'de> for RHashSet<T,S>
+    where
+        T: Deserialize<'de>,
+        Self:Default,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RHashSetVisitor::new())
+        }
+    }
+
+
+
+    impl<T,S> Serialize for RHashSet<T,S>
+    where
+        T:Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for value in self.iter() {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+
+
+}
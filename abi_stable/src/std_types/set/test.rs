@@ -0,0 +1,148 @@
+use super::*;
+
+use crate::std_types::{RNone,RSome,RString};
+
+
+fn new_stdset()->HashSet<u32>{
+    vec![90,10,88,77].into_iter().collect()
+}
+
+fn new_set()->RHashSet<u32>{
+    vec![90,10,88,77].into_iter().collect()
+}
+
+
+#[test]
+fn test_new_set(){
+    let mut set=RHashSet::new();
+    assert!(set.insert(10));
+    assert!(set.contains(&10));
+    assert!(!set.contains(&11));
+}
+
+
+#[test]
+fn test_default(){
+    let default_=RHashSet::<u32>::default();
+    let new_=RHashSet::<u32>::new();
+
+    assert_eq!(default_.len(),0);
+    assert_eq!(default_,new_);
+}
+
+
+#[test]
+fn test_eq_clone(){
+    let set=new_set();
+    let clone=set.clone();
+
+    assert_eq!(set,clone);
+
+    let mut other=set.clone();
+    other.insert(1000);
+    assert_ne!(set,other);
+
+    let mut other=set.clone();
+    other.remove(&90);
+    other.insert(91);
+    assert_ne!(set,other);
+}
+
+
+#[test]
+fn insert_remove_take(){
+    let mut set=RHashSet::<RString>::new();
+
+    assert!(set.insert("foo".into()));
+    assert!(!set.insert("foo".into()));
+    assert!(set.insert("bar".into()));
+    assert_eq!(set.len(),2);
+
+    assert!(set.remove("foo"));
+    assert!(!set.remove("foo"));
+    assert_eq!(set.len(),1);
+
+    assert_eq!(set.take("bar"),RSome(RString::from("bar")));
+    assert_eq!(set.take("bar"),RNone);
+    assert!(set.is_empty());
+}
+
+
+#[test]
+fn clear_len_is_empty(){
+    let mut set=new_set();
+    assert_eq!(set.len(),4);
+    assert!(!set.is_empty());
+
+    set.clear();
+    assert_eq!(set.len(),0);
+    assert!(set.is_empty());
+}
+
+
+#[test]
+fn iter_drain(){
+    let mut set=new_set();
+
+    let mut list=set.iter().cloned().collect::<Vec<_>>();
+    list.sort();
+    assert_eq!(list,vec![10,77,88,90]);
+
+    let mut list=set.drain().collect::<Vec<_>>();
+    list.sort();
+    assert_eq!(list,vec![10,77,88,90]);
+    assert!(set.is_empty());
+
+    let mut list=new_set().into_iter().collect::<Vec<_>>();
+    list.sort();
+    assert_eq!(list,vec![10,77,88,90]);
+}
+
+
+#[test]
+fn set_relations(){
+    let set=new_set();
+    let subset:RHashSet<u32>=vec![10,88].into_iter().collect();
+    let disjoint:RHashSet<u32>=vec![0,1,2].into_iter().collect();
+
+    assert!(subset.is_subset(&set));
+    assert!(!set.is_subset(&subset));
+    assert!(set.is_superset(&subset));
+    assert!(set.is_subset(&set));
+
+    assert!(set.is_disjoint(&disjoint));
+    assert!(disjoint.is_disjoint(&set));
+    assert!(!set.is_disjoint(&subset));
+}
+
+
+#[test]
+fn from_into_hashset(){
+    let stdset=new_stdset();
+    let set:RHashSet<u32>=stdset.clone().into();
+    assert_eq!(set,new_set());
+
+    let back:HashSet<u32>=set.into();
+    assert_eq!(back,stdset);
+}
+
+
+#[test]
+fn from_iter_extend(){
+    let mut set=RHashSet::new();
+    set.extend(new_stdset());
+    assert_eq!(set,new_set());
+}
+
+
+#[test]
+fn test_serde(){
+    let set=new_set();
+
+    let serialized=serde_json::to_string(&set).unwrap();
+    let deserialized=serde_json::from_str::<RHashSet<u32>>(&serialized).unwrap();
+    assert_eq!(deserialized,set);
+
+    let deserialized=serde_json::from_str::<RHashSet<u32>>("[77,10,90,88,10]").unwrap();
+    assert_eq!(deserialized,set);
+}
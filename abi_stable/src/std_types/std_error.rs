@@ -13,12 +13,17 @@ use std::{
 use core_extensions::prelude::*;
 
 use crate::{
+    abi_stability::{
+        StableAbi,StableTypeId,
+        stable_type_id::{no_stable_type_id,some_stable_type_id},
+    },
     erased_types::{
         c_functions::{adapt_std_fmt, debug_impl, display_impl},
         FormattingMode,
     },
     marker_type::{SyncSend, UnsyncUnsend,UnsyncSend,ErasedObject},
     prefix_type::{PrefixTypeTrait,WithMetadata},
+    sabi_types::MaybeCmp,
     std_types::{
         RBox, RResult, RString,
         utypeid::{UTypeId,new_utypeid}
@@ -82,6 +87,16 @@ impl<M> RBoxError_<M> {
         }
     }
 
+    fn new_stable_inner<T>(value: T) -> Self
+    where
+        T: ErrorTrait + StableAbi + 'static,
+    {
+        Self::new_with_vtable(
+            value,
+            MakeStableRErrorVTable::<T>::LIB_VTABLE.as_prefix()
+        )
+    }
+
     fn new_with_vtable<T>(value: T,vtable:&'static RErrorVTable) -> Self{
         unsafe {
             let value = value
@@ -109,6 +124,16 @@ impl<M> RBoxError_<M> {
         self_id==other_id
     }
 
+    /// Returns the StableTypeId of the error this wraps,
+    /// if it was constructed with the `new_stable` constructor.
+    pub fn stable_type_id(&self)->MaybeCmp<StableTypeId>{
+        self.vtable.stable_type_id()()
+    }
+
+    fn is_stable_type<T:StableAbi+'static>(&self)->bool{
+        self.stable_type_id()==MaybeCmp::Just(StableTypeId::new::<T>())
+    }
+
     /// The address of the `Box<_>` this wraps
     pub fn heap_address(&self)->usize{
         (&*self.value)as *const _ as usize
@@ -165,6 +190,63 @@ impl<M> RBoxError_<M> {
         }
     }
 
+    /// Converts this `RBoxError_<_>` to an `RBox<T>`,
+    /// which can be done in any dynamic library/executable that uses 
+    /// the same version of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This returns `Err(self)` if this was not constructed with 
+    /// the `new_stable` constructor,or if the error it wraps is not a `T`.
+    ///
+    pub fn downcast_stable<T:StableAbi+'static>(self)->Result<RBox<T>,Self>{
+        if self.is_stable_type::<T>() {
+            unsafe{
+                Ok(mem::transmute::<RBox<ErasedObject>, RBox<T>>(self.value))
+            }
+        }else{
+            Err(self)
+        }
+    }
+
+    /// Converts this `&RBoxError_<_>` to an `Option<&T>`,
+    /// which can be done in any dynamic library/executable that uses 
+    /// the same version of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This returns `None` if this was not constructed with 
+    /// the `new_stable` constructor,or if the error it wraps is not a `T`.
+    ///
+    pub fn downcast_stable_ref<T:StableAbi+'static>(&self)->Option<&T>{
+        if self.is_stable_type::<T>() {
+            unsafe{
+                Some(transmute_reference::<ErasedObject,T>(&*self.value))
+            }
+        }else{
+            None
+        }
+    }
+
+    /// Converts this `&mut RBoxError_<_>` to an `Option<&mut T>`,
+    /// which can be done in any dynamic library/executable that uses 
+    /// the same version of the crate that declared `T`.
+    ///
+    /// # Errors
+    ///
+    /// This returns `None` if this was not constructed with 
+    /// the `new_stable` constructor,or if the error it wraps is not a `T`.
+    ///
+    pub fn downcast_stable_mut<T:StableAbi+'static>(&mut self)->Option<&mut T>{
+        if self.is_stable_type::<T>() {
+            unsafe{
+                Some(transmute_mut_reference::<ErasedObject,T>(&mut *self.value))
+            }
+        }else{
+            None
+        }
+    }
+
     /// Casts this `&RBoxError_<_>` to `&UnsyncRBoxError`.
     pub fn as_unsync(&self)->&UnsyncRBoxError{
         unsafe{
@@ -211,6 +293,17 @@ macro_rules! declare_constructor {
             {
                 Self::new_inner(value)
             }
+
+            /// Constructs an RBoxError from an error,
+            /// which can be downcasted with the `downcast_stable*` methods
+            /// in any dynamic library/executable that uses 
+            /// the same version of the crate that declared `T`.
+            pub fn new_stable<T>(value: T) -> Self
+            where
+                T: StableAbi + $($bounds)*,
+            {
+                Self::new_stable_inner(value)
+            }
         }
     )
 }
@@ -331,6 +424,8 @@ struct RErrorVTableVal {
     display: unsafe extern "C" fn(&ErasedObject, FormattingMode, &mut RString) -> RResult<(), ()>,
     #[sabi(last_prefix_field)]
     type_id: extern "C" fn()->UTypeId,
+    #[sabi(missing_field(value="no_stable_type_id"))]
+    stable_type_id: extern "C" fn()->MaybeCmp<StableTypeId>,
 }
 
 ///////////////////
@@ -345,6 +440,27 @@ where T:ErrorTrait+'static
         debug: debug_impl::<T>,
         display: display_impl::<T>,
         type_id: new_utypeid::<T>,
+        stable_type_id: no_stable_type_id,
+    };
+
+    const LIB_VTABLE: &'static WithMetadata<RErrorVTableVal> = {
+        &WithMetadata::new(PrefixTypeTrait::METADATA,Self::VALUE)
+    };
+}
+
+///////////////////
+
+struct MakeStableRErrorVTable<T>(T);
+
+
+impl<T> MakeStableRErrorVTable<T>
+where T:ErrorTrait+StableAbi+'static
+{
+    const VALUE:RErrorVTableVal=RErrorVTableVal{
+        debug: debug_impl::<T>,
+        display: display_impl::<T>,
+        type_id: new_utypeid::<T>,
+        stable_type_id: some_stable_type_id::<T>,
     };
 
     const LIB_VTABLE: &'static WithMetadata<RErrorVTableVal> = {
@@ -364,6 +480,7 @@ where T:?Sized+ErrorTrait+'static
         debug: debug_impl::<Box<T>>,
         display: display_impl::<Box<T>>,
         type_id: new_utypeid::<Box<T>>,
+        stable_type_id: no_stable_type_id,
     };
 
     const LIB_VTABLE: &'static WithMetadata<RErrorVTableVal> = {
@@ -96,6 +96,60 @@ fn downcast() {
 }
 
 
+#[repr(C)]
+#[derive(StableAbi,Debug,Clone,PartialEq)]
+struct StableError{
+    code:u32,
+}
+
+impl Display for StableError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        write!(f,"error code:{}",self.code)
+    }
+}
+
+impl ErrorTrait for StableError{}
+
+
+#[test]
+fn downcast_stable() {
+    let err=StableError{code:3};
+
+    macro_rules! downcast_ {
+        (
+            method=$method:ident,
+            conv=$conv:expr
+        ) => ({
+            let res0=err.clone().piped(RBoxError::new_stable).$method::<StableError>()
+                .piped($conv).is_some();
+            let res1=err.clone().piped(RBoxError::new_stable).$method::<u32>()
+                .piped($conv).is_none();
+            let res2=err.clone().piped(RBoxError::new).$method::<StableError>()
+                .piped($conv).is_none();
+
+            assert!(res0,"This RBoxError could not be downcasted to StableError.");
+
+            assert!(res1,"This RBoxError should only downcast to StableError.");
+            
+            assert!(res2,"This RBoxError was not constructed with `new_stable`.");
+        })
+    }
+
+    downcast_!{method=downcast_stable    ,conv=|x| x.ok() }
+    downcast_!{method=downcast_stable_ref,conv=::std::convert::identity}
+    downcast_!{method=downcast_stable_mut,conv=::std::convert::identity}
+
+    let e0=RBoxError::new_stable(err.clone());
+    check_formatting_equivalence(&err,&e0);
+    assert_eq!(e0.stable_type_id(),MaybeCmp::Just(StableTypeId::new::<StableError>()));
+    assert_eq!(e0.downcast_ref::<StableError>(),Some(&err));
+    match RBoxError::new(err.clone()).stable_type_id() {
+        MaybeCmp::Nothing=>{}
+        MaybeCmp::Just(_)=>panic!("RBoxError::new should not store a StableTypeId"),
+    }
+}
+
+
 #[test]
 fn casts_among_rboxerrors(){
     let err=Stringy::new("hello\n\rworld");
@@ -0,0 +1,383 @@
+/*!
+Ffi-safe equivalents of the types in `std::task`,
+used to poll futures across the ffi boundary.
+*/
+
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    task::{Context,Poll,RawWaker,RawWakerVTable,Waker},
+};
+
+use core_extensions::matches;
+
+use crate::{
+    marker_type::ErasedObject,
+    prefix_type::{PrefixTypeTrait,WithMetadata},
+    std_types::RBox,
+    utils::transmute_reference,
+};
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod test;
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of the `std::task::Poll<_>` type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[repr(u8)]
+#[derive(StableAbi)]
+pub enum RPoll<T> {
+    RReady(T),
+    RPending,
+}
+
+pub use self::RPoll::*;
+
+
+impl<T> RPoll<T> {
+    /// Returns whether `self` is an `RReady`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use abi_stable::std_types::*;
+    ///
+    /// assert_eq!(RReady(10)    .is_ready(),true);
+    /// assert_eq!(RPending::<u32>.is_ready(),false);
+    ///
+    /// ```
+    #[inline]
+    pub fn is_ready(&self)->bool{
+        matches!( RReady{..}=self )
+    }
+
+    /// Returns whether `self` is an `RPending`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use abi_stable::std_types::*;
+    ///
+    /// assert_eq!(RReady(10)    .is_pending(),false);
+    /// assert_eq!(RPending::<u32>.is_pending(),true);
+    ///
+    /// ```
+    #[inline]
+    pub fn is_pending(&self)->bool{
+        matches!( RPending{..}=self )
+    }
+
+    /// Maps the value inside an `RReady`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use abi_stable::std_types::*;
+    ///
+    /// assert_eq!(RReady(10)    .map(|x| x*2 ),RReady(20));
+    /// assert_eq!(RPending::<u32>.map(|x| x*2 ),RPending);
+    ///
+    /// ```
+    #[inline]
+    pub fn map<U,F>(self,f:F)->RPoll<U>
+    where
+        F:FnOnce(T)->U,
+    {
+        match self {
+            RReady(v)=>RReady(f(v)),
+            RPending=>RPending,
+        }
+    }
+
+    /// Converts from `RPoll<T>` to `Poll<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use abi_stable::std_types::*;
+    /// use std::task::Poll;
+    ///
+    /// assert_eq!(RReady(10)    .into_poll(),Poll::Ready(10));
+    /// assert_eq!(RPending::<u32>.into_poll(),Poll::Pending);
+    ///
+    /// ```
+    #[inline]
+    pub fn into_poll(self)->Poll<T>{
+        self.into()
+    }
+}
+
+
+impl_from_rust_repr! {
+    impl[T] From<Poll<T>> for RPoll<T> {
+        fn(this){
+            match this {
+                Poll::Ready(v) => RReady(v),
+                Poll::Pending => RPending,
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<Poll<T>> for RPoll<T> {
+        fn(this){
+            match this {
+                RReady(v) => Poll::Ready(v),
+                RPending => Poll::Pending,
+            }
+        }
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+Ffi-safe equivalent of `std::task::Waker`.
+
+This can be converted to and from a `Waker`,
+waking the `Waker` this was originally constructed from.
+
+*/
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RWaker{
+    waker:RBox<ErasedObject>,
+    vtable:&'static RWakerVTable,
+}
+
+impl RWaker{
+    /// Constructs an `RWaker` from a `Waker`.
+    pub fn new(waker:Waker)->Self{
+        unsafe{
+            RWaker{
+                waker:erase_waker(waker),
+                vtable:WAKER_VTABLE.as_prefix(),
+            }
+        }
+    }
+
+    /// Wakes up the task associated with this `RWaker`.
+    pub fn wake(self){
+        self.wake_by_ref();
+    }
+
+    /// Wakes up the task associated with this `RWaker`,without consuming it.
+    pub fn wake_by_ref(&self){
+        unsafe{
+            (self.vtable.wake_by_ref())(&self.waker)
+        }
+    }
+
+    /// Converts this `RWaker` into a `Waker`.
+    pub fn into_waker(self)->Waker{
+        unsafe{
+            Waker::from_raw(self.into_raw_waker())
+        }
+    }
+
+    fn into_raw_waker(self)->RawWaker{
+        RawWaker::new(
+            Box::into_raw(Box::new(self)) as *const (),
+            &OWNED_RAW_WAKER_VTABLE,
+        )
+    }
+}
+
+impl Clone for RWaker{
+    fn clone(&self)->Self{
+        unsafe{
+            RWaker{
+                waker:(self.vtable.clone_waker())(&self.waker),
+                vtable:self.vtable,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RWaker{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RWaker{..}")
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<Waker> for RWaker {
+        fn(this){
+            RWaker::new(this)
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl Into<Waker> for RWaker {
+        fn(this){
+            this.into_waker()
+        }
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+/**
+Ffi-safe equivalent of `std::task::Context`,
+which borrows the `Waker` of the task that is polling a future.
+
+*/
+#[repr(C)]
+#[derive(Copy,Clone,StableAbi)]
+pub struct RContext<'a>{
+    waker:&'a ErasedObject,
+    vtable:&'static RWakerVTable,
+}
+
+impl<'a> RContext<'a>{
+    /// Constructs an `RContext` from the `Waker` of a task.
+    pub fn from_waker(waker:&'a Waker)->Self{
+        unsafe{
+            RContext{
+                waker:transmute_reference::<Waker,ErasedObject>(waker),
+                vtable:WAKER_VTABLE.as_prefix(),
+            }
+        }
+    }
+
+    /// Constructs an `RContext` from a `Context`.
+    pub fn from_context(cx:&'a Context<'_>)->Self{
+        Self::from_waker(cx.waker())
+    }
+
+    /// Gets an owned `RWaker` for the current task.
+    pub fn clone_waker(&self)->RWaker{
+        unsafe{
+            RWaker{
+                waker:(self.vtable.clone_waker())(self.waker),
+                vtable:self.vtable,
+            }
+        }
+    }
+
+    /// Wakes up the current task.
+    pub fn wake_by_ref(&self){
+        unsafe{
+            (self.vtable.wake_by_ref())(self.waker)
+        }
+    }
+
+    /// Calls `f` with a `Context` that wakes up the same task as this `RContext`.
+    pub fn with_context<F,R>(&self,f:F)->R
+    where
+        F:FnOnce(&mut Context<'_>)->R,
+    {
+        let raw_waker=RawWaker::new(
+            self as *const RContext<'a> as *const (),
+            &BORROWED_RAW_WAKER_VTABLE,
+        );
+        // The waker borrows this RContext,so it must not be dropped.
+        let waker=ManuallyDrop::new(unsafe{ Waker::from_raw(raw_waker) });
+        f(&mut Context::from_waker(&waker))
+    }
+}
+
+impl<'a> fmt::Debug for RContext<'a>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.write_str("RContext{..}")
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_struct="RWakerVTable")))]
+struct RWakerVTableVal{
+    clone_waker:unsafe extern "C" fn(&ErasedObject)->RBox<ErasedObject>,
+    #[sabi(last_prefix_field)]
+    wake_by_ref:unsafe extern "C" fn(&ErasedObject),
+}
+
+const WAKER_VTABLE:&WithMetadata<RWakerVTableVal>={
+    &WithMetadata::new(
+        PrefixTypeTrait::METADATA,
+        RWakerVTableVal{
+            clone_waker,
+            wake_by_ref,
+        }
+    )
+};
+
+
+unsafe fn erase_waker(waker:Waker)->RBox<ErasedObject>{
+    std::mem::transmute::<RBox<Waker>,RBox<ErasedObject>>(RBox::new(waker))
+}
+
+unsafe extern "C" fn clone_waker(waker:&ErasedObject)->RBox<ErasedObject>{
+    extern_fn_panic_handling!{
+        let waker=transmute_reference::<ErasedObject,Waker>(waker);
+        erase_waker(waker.clone())
+    }
+}
+
+unsafe extern "C" fn wake_by_ref(waker:&ErasedObject){
+    extern_fn_panic_handling!{
+        transmute_reference::<ErasedObject,Waker>(waker).wake_by_ref();
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////
+
+
+// The vtable of the `Waker`s constructed from an `RWaker`,
+// whose data pointer is a `Box<RWaker>`.
+static OWNED_RAW_WAKER_VTABLE:RawWakerVTable=RawWakerVTable::new(
+    owned_clone,
+    owned_wake,
+    owned_wake_by_ref,
+    owned_drop,
+);
+
+unsafe fn owned_clone(data:*const ())->RawWaker{
+    (*(data as *const RWaker)).clone().into_raw_waker()
+}
+
+unsafe fn owned_wake(data:*const ()){
+    Box::from_raw(data as *mut RWaker).wake();
+}
+
+unsafe fn owned_wake_by_ref(data:*const ()){
+    (*(data as *const RWaker)).wake_by_ref();
+}
+
+unsafe fn owned_drop(data:*const ()){
+    drop(Box::from_raw(data as *mut RWaker));
+}
+
+
+// The vtable of the `Waker` passed to `RContext::with_context`,
+// whose data pointer is the `RContext`.
+static BORROWED_RAW_WAKER_VTABLE:RawWakerVTable=RawWakerVTable::new(
+    borrowed_clone,
+    borrowed_wake_by_ref,
+    borrowed_wake_by_ref,
+    borrowed_drop,
+);
+
+unsafe fn borrowed_clone(data:*const ())->RawWaker{
+    (*(data as *const RContext<'_>)).clone_waker().into_raw_waker()
+}
+
+unsafe fn borrowed_wake_by_ref(data:*const ()){
+    (*(data as *const RContext<'_>)).wake_by_ref();
+}
+
+unsafe fn borrowed_drop(_:*const ()){}
@@ -0,0 +1,120 @@
+use super::*;
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize,Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use crate::test_utils::block_on;
+
+
+/// A future that is pending the first time it's polled,
+/// waking itself up through an `RContext` or an `RWaker`.
+struct YieldOnce{
+    polled:bool,
+    from_thread:bool,
+}
+
+impl Future for YieldOnce{
+    type Output=u32;
+
+    fn poll(mut self:Pin<&mut Self>,cx:&mut Context<'_>)->Poll<u32>{
+        if self.polled {
+            return Poll::Ready(100);
+        }
+        self.polled=true;
+        let cx=RContext::from_context(cx);
+        if self.from_thread {
+            let waker=cx.clone_waker();
+            thread::spawn(move||{
+                waker.wake();
+            });
+        }else{
+            cx.wake_by_ref();
+        }
+        Poll::Pending
+    }
+}
+
+
+#[test]
+fn rpoll_conversions(){
+    assert_eq!(RPoll::from(Poll::Ready(3)),RReady(3));
+    assert_eq!(RPoll::<u32>::from(Poll::Pending),RPending);
+    assert_eq!(RReady(5).into_poll(),Poll::Ready(5));
+    assert_eq!(RPending::<u32>.into_poll(),Poll::Pending);
+}
+
+
+#[test]
+fn wake_through_rcontext(){
+    assert_eq!(block_on(YieldOnce{polled:false,from_thread:false}),100);
+    assert_eq!(block_on(YieldOnce{polled:false,from_thread:true}),100);
+}
+
+
+#[test]
+fn rwaker_roundtrip(){
+    struct CountingWaker(AtomicUsize);
+
+    static VTABLE:RawWakerVTable=RawWakerVTable::new(clone,wake,wake_by_ref,drop_waker);
+
+    unsafe fn clone(data:*const ())->RawWaker{
+        Arc::increment_strong_count(data as *const CountingWaker);
+        RawWaker::new(data,&VTABLE)
+    }
+    unsafe fn wake(data:*const ()){
+        wake_by_ref(data);
+        drop_waker(data);
+    }
+    unsafe fn wake_by_ref(data:*const ()){
+        (*(data as *const CountingWaker)).0.fetch_add(1,Ordering::SeqCst);
+    }
+    unsafe fn drop_waker(data:*const ()){
+        drop(Arc::from_raw(data as *const CountingWaker));
+    }
+
+    let counter=Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker=unsafe{
+        Waker::from_raw(RawWaker::new(Arc::into_raw(counter.clone()) as *const (),&VTABLE))
+    };
+    let wakes=||counter.0.load(Ordering::SeqCst);
+
+    {
+        let rcx=RContext::from_waker(&waker);
+        rcx.wake_by_ref();
+        assert_eq!(wakes(),1);
+
+        rcx.with_context(|cx| cx.waker().wake_by_ref() );
+        assert_eq!(wakes(),2);
+
+        rcx.with_context(|cx| cx.waker().clone() ).wake();
+        assert_eq!(wakes(),3);
+    }
+
+    let rwaker=RWaker::new(waker.clone());
+    assert_eq!(Arc::strong_count(&counter),3);
+
+    let rwaker2=rwaker.clone();
+    assert_eq!(Arc::strong_count(&counter),4);
+    rwaker2.wake();
+    assert_eq!(wakes(),4);
+    assert_eq!(Arc::strong_count(&counter),3);
+
+    let waker2=rwaker.into_waker();
+    waker2.wake_by_ref();
+    assert_eq!(wakes(),5);
+    let waker3=waker2.clone();
+    assert_eq!(Arc::strong_count(&counter),4);
+    drop(waker2);
+    waker3.wake();
+    assert_eq!(wakes(),6);
+
+    drop(waker);
+    assert_eq!(Arc::strong_count(&counter),1);
+}
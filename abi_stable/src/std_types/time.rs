@@ -1,4 +1,22 @@
-use std::time::Duration;
+use std::{
+    ops::{Add,AddAssign,Sub,SubAssign},
+    time::{Duration,Instant,SystemTime,UNIX_EPOCH},
+};
+
+use serde::{de,Deserialize,Deserializer};
+
+use crate::{
+    globals::initialized_globals,
+    sabi_types::LateStaticRef,
+    utils::leak_value,
+};
+
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests;
+
+
+const NANOS_PER_SEC:u32=1_000_000_000;
 
 /// Ffi-safe equivalent of ::std::time::Duration .
 #[derive(
@@ -49,3 +67,335 @@ impl_into_rust_repr! {
         }
     }
 }
+
+
+impl RDuration {
+    fn as_nanos_i128(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SEC as i128 + self.subsec_nanos as i128
+    }
+
+    /// Returns None if `nanos` is negative or doesn't fit in an RDuration.
+    fn from_nanos_i128(nanos: i128) -> Option<Self> {
+        if nanos < 0 || (nanos / NANOS_PER_SEC as i128) > u64::max_value() as i128 {
+            return None;
+        }
+        Some(RDuration::new(
+            (nanos / NANOS_PER_SEC as i128) as u64,
+            (nanos % NANOS_PER_SEC as i128) as u32,
+        ))
+    }
+}
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of ::std::time::SystemTime .
+///
+/// This is stored as the ammount of seconds and nanoseconds since the unix epoch,
+/// where `seconds` is negative for times before the epoch,
+/// and `subsec_nanos` is always added to `seconds`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, StableAbi)]
+#[repr(C)]
+pub struct RSystemTime {
+    seconds: i64,
+    subsec_nanos: u32,
+}
+
+impl RSystemTime {
+    /// The unix epoch (1970-01-01 00:00:00 UTC).
+    pub const UNIX_EPOCH: RSystemTime = RSystemTime {
+        seconds: 0,
+        subsec_nanos: 0,
+    };
+
+    /// Constructs this RSystemTime from the seconds since the unix epoch
+    /// (negative for times before it) and the nanoseconds inside a second .
+    ///
+    /// # Panics
+    ///
+    /// Panics if carrying the whole seconds in `subsec_nanos` into `seconds` overflows.
+    pub fn new(seconds: i64, subsec_nanos: u32) -> Self {
+        let carry = (subsec_nanos / NANOS_PER_SEC) as i64;
+        Self {
+            seconds: seconds
+                .checked_add(carry)
+                .expect("overflow when constructing RSystemTime"),
+            subsec_nanos: subsec_nanos % NANOS_PER_SEC,
+        }
+    }
+
+    /// Gets the current time of the system clock.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// The ammount of seconds since the unix epoch,negative for times before it.
+    pub const fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// The ammount of nanoseconds added to `seconds()`,always less than 1_000_000_000.
+    pub const fn subsec_nanos(&self) -> u32 {
+        self.subsec_nanos
+    }
+
+    /// Returns the ammount of time elapsed from `earlier` to `self`.
+    ///
+    /// Returns `Err` with the ammount of time from `self` to `earlier`
+    /// if `earlier` is later than `self`.
+    pub fn duration_since(&self, earlier: RSystemTime) -> Result<RDuration, RDuration> {
+        let diff = self.as_nanos_i128() - earlier.as_nanos_i128();
+        // The difference of two RSystemTimes always fits in an RDuration.
+        if diff >= 0 {
+            Ok(RDuration::from_nanos_i128(diff).unwrap())
+        } else {
+            Err(RDuration::from_nanos_i128(-diff).unwrap())
+        }
+    }
+
+    /// Returns the ammount of time elapsed since `self`.
+    ///
+    /// Returns `Err` with the ammount of time until `self` if `self` is in the future.
+    pub fn elapsed(&self) -> Result<RDuration, RDuration> {
+        RSystemTime::now().duration_since(*self)
+    }
+
+    /// Adds `duration` to `self`,returning None on overflow.
+    pub fn checked_add(&self, duration: RDuration) -> Option<RSystemTime> {
+        RSystemTime::from_nanos_i128(self.as_nanos_i128() + duration.as_nanos_i128())
+    }
+
+    /// Subtracts `duration` from `self`,returning None on overflow.
+    pub fn checked_sub(&self, duration: RDuration) -> Option<RSystemTime> {
+        RSystemTime::from_nanos_i128(self.as_nanos_i128() - duration.as_nanos_i128())
+    }
+
+    fn as_nanos_i128(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SEC as i128 + self.subsec_nanos as i128
+    }
+
+    /// Returns None if `nanos` doesn't fit in an RSystemTime.
+    fn from_nanos_i128(nanos: i128) -> Option<Self> {
+        let mut seconds = nanos / NANOS_PER_SEC as i128;
+        let mut subsec_nanos = nanos % NANOS_PER_SEC as i128;
+        if subsec_nanos < 0 {
+            seconds -= 1;
+            subsec_nanos += NANOS_PER_SEC as i128;
+        }
+        if seconds < i64::min_value() as i128 || seconds > i64::max_value() as i128 {
+            return None;
+        }
+        Some(RSystemTime {
+            seconds: seconds as i64,
+            subsec_nanos: subsec_nanos as u32,
+        })
+    }
+}
+
+/// Rejects a `subsec_nanos` of a second or more,
+/// since all the methods of RSystemTime assume that it's less than a second.
+impl<'de> Deserialize<'de> for RSystemTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "RSystemTime")]
+        struct Proxy {
+            seconds: i64,
+            subsec_nanos: u32,
+        }
+
+        let Proxy { seconds, subsec_nanos } = Proxy::deserialize(deserializer)?;
+        if subsec_nanos >= NANOS_PER_SEC {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(subsec_nanos as u64),
+                &"less than 1_000_000_000 subsec_nanos",
+            ));
+        }
+        Ok(RSystemTime { seconds, subsec_nanos })
+    }
+}
+
+impl Add<RDuration> for RSystemTime {
+    type Output = RSystemTime;
+
+    /// # Panics
+    ///
+    /// Panics if the result overflows.
+    fn add(self, duration: RDuration) -> RSystemTime {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to RSystemTime")
+    }
+}
+
+impl AddAssign<RDuration> for RSystemTime {
+    fn add_assign(&mut self, duration: RDuration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<RDuration> for RSystemTime {
+    type Output = RSystemTime;
+
+    /// # Panics
+    ///
+    /// Panics if the result overflows.
+    fn sub(self, duration: RDuration) -> RSystemTime {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from RSystemTime")
+    }
+}
+
+impl SubAssign<RDuration> for RSystemTime {
+    fn sub_assign(&mut self, duration: RDuration) {
+        *self = *self - duration;
+    }
+}
+
+impl_from_rust_repr! {
+    impl From<SystemTime> for RSystemTime {
+        fn(v){
+            let converted=match v.duration_since(UNIX_EPOCH) {
+                Ok(after)=>RSystemTime::UNIX_EPOCH.checked_add(after.into()),
+                Err(e)=>RSystemTime::UNIX_EPOCH.checked_sub(e.duration().into()),
+            };
+            converted.expect("SystemTime is out of the range of RSystemTime")
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl Into<SystemTime> for RSystemTime {
+        fn(this){
+            match this.duration_since(RSystemTime::UNIX_EPOCH) {
+                Ok(after)=>UNIX_EPOCH + after.into(),
+                Err(before)=>UNIX_EPOCH - before.into(),
+            }
+        }
+    }
+}
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+
+/// Ffi-safe equivalent of ::std::time::Instant .
+///
+/// RInstants are measured from a monotonic clock that is shared with
+/// dynamic libraries through the globals of this crate,
+/// so that an RInstant created in a dynamic library can be compared with
+/// one created in the executable that loaded it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, StableAbi)]
+#[repr(C)]
+pub struct RInstant {
+    since_origin: RDuration,
+}
+
+impl RInstant {
+    /// Gets the current time of the shared monotonic clock.
+    pub fn now() -> Self {
+        RInstant {
+            since_origin: initialized_globals().monotonic_clock()(),
+        }
+    }
+
+    /// Returns the ammount of time elapsed from `earlier` to `self`,
+    /// or zero if `earlier` is later than `self`.
+    pub fn duration_since(&self, earlier: RInstant) -> RDuration {
+        self.checked_duration_since(earlier)
+            .unwrap_or(RDuration::new(0, 0))
+    }
+
+    /// Returns the ammount of time elapsed from `earlier` to `self`,
+    /// or None if `earlier` is later than `self`.
+    pub fn checked_duration_since(&self, earlier: RInstant) -> Option<RDuration> {
+        let since_origin: Duration = self.since_origin.into();
+        since_origin
+            .checked_sub(earlier.since_origin.into())
+            .map(RDuration::from)
+    }
+
+    /// Returns the ammount of time elapsed since `self`.
+    pub fn elapsed(&self) -> RDuration {
+        RInstant::now().duration_since(*self)
+    }
+
+    /// Adds `duration` to `self`,returning None on overflow.
+    pub fn checked_add(&self, duration: RDuration) -> Option<RInstant> {
+        let since_origin: Duration = self.since_origin.into();
+        since_origin
+            .checked_add(duration.into())
+            .map(|since_origin| RInstant { since_origin: since_origin.into() })
+    }
+
+    /// Subtracts `duration` from `self`,
+    /// returning None if the result is before the origin of the monotonic clock.
+    pub fn checked_sub(&self, duration: RDuration) -> Option<RInstant> {
+        let since_origin: Duration = self.since_origin.into();
+        since_origin
+            .checked_sub(duration.into())
+            .map(|since_origin| RInstant { since_origin: since_origin.into() })
+    }
+}
+
+impl Add<RDuration> for RInstant {
+    type Output = RInstant;
+
+    /// # Panics
+    ///
+    /// Panics if the result overflows.
+    fn add(self, duration: RDuration) -> RInstant {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to RInstant")
+    }
+}
+
+impl AddAssign<RDuration> for RInstant {
+    fn add_assign(&mut self, duration: RDuration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<RDuration> for RInstant {
+    type Output = RInstant;
+
+    /// # Panics
+    ///
+    /// Panics if the result is before the origin of the monotonic clock.
+    fn sub(self, duration: RDuration) -> RInstant {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from RInstant")
+    }
+}
+
+impl SubAssign<RDuration> for RInstant {
+    fn sub_assign(&mut self, duration: RDuration) {
+        *self = *self - duration;
+    }
+}
+
+impl Sub<RInstant> for RInstant {
+    type Output = RDuration;
+
+    /// Equivalent to `self.duration_since(other)`.
+    fn sub(self, other: RInstant) -> RDuration {
+        self.duration_since(other)
+    }
+}
+
+
+/// The monotonic clock stored in `Globals`,
+/// returning the time elapsed since the first time it was called.
+///
+/// Because dynamic libraries use the `Globals` of the executable that loaded them,
+/// every RInstant is measured from the origin of the executable's clock.
+pub(crate) extern fn monotonic_clock() -> RDuration {
+    extern_fn_panic_handling! {
+        static ORIGIN: LateStaticRef<Instant> = LateStaticRef::new();
+
+        let origin = ORIGIN.init(|| leak_value(Instant::now()));
+        origin.elapsed().into()
+    }
+}
@@ -0,0 +1,178 @@
+use super::*;
+
+
+fn secs(seconds:u64,subsec_nanos:u32)->RDuration{
+    RDuration::new(seconds,subsec_nanos)
+}
+
+
+#[test]
+fn system_time_new(){
+    let time=RSystemTime::new(10,2_500_000_000);
+    assert_eq!(time.seconds(),12);
+    assert_eq!(time.subsec_nanos(),500_000_000);
+
+    assert_eq!(RSystemTime::new(0,0),RSystemTime::UNIX_EPOCH);
+    assert!(RSystemTime::new(-1,999_999_999) < RSystemTime::UNIX_EPOCH);
+}
+
+
+#[test]
+fn system_time_arithmetic(){
+    let time=RSystemTime::new(10,700_000_000);
+
+    assert_eq!(time+secs(1,500_000_000),RSystemTime::new(12,200_000_000));
+    assert_eq!(time-secs(11,0),RSystemTime::new(-1,700_000_000));
+    assert_eq!(time-secs(10,800_000_000),RSystemTime::new(-1,900_000_000));
+
+    let mut assigned=time;
+    assigned+=secs(0,300_000_000);
+    assert_eq!(assigned,RSystemTime::new(11,0));
+    assigned-=secs(1,0);
+    assert_eq!(assigned,RSystemTime::new(10,0));
+
+    let max=RSystemTime::new(i64::max_value(),999_999_999);
+    assert_eq!(max.checked_add(secs(0,1)),None);
+    let min=RSystemTime::new(i64::min_value(),0);
+    assert_eq!(min.checked_sub(secs(0,1)),None);
+    assert_eq!(
+        min.checked_add(secs(u64::max_value(),999_999_999)),
+        Some(max),
+    );
+}
+
+
+#[test]
+fn system_time_duration_since(){
+    let earlier=RSystemTime::new(-3,200_000_000);
+    let later=RSystemTime::new(5,100_000_000);
+
+    assert_eq!(later.duration_since(earlier),Ok(secs(7,900_000_000)));
+    assert_eq!(earlier.duration_since(later),Err(secs(7,900_000_000)));
+    assert_eq!(later.duration_since(later),Ok(secs(0,0)));
+
+    let min=RSystemTime::new(i64::min_value(),0);
+    let max=RSystemTime::new(i64::max_value(),999_999_999);
+    assert_eq!(max.duration_since(min),Ok(secs(u64::max_value(),999_999_999)));
+
+    assert!(RSystemTime::UNIX_EPOCH.elapsed().is_ok());
+}
+
+
+#[test]
+fn system_time_conversions(){
+    let after=UNIX_EPOCH+Duration::new(1_000,250);
+    let rafter=RSystemTime::from(after);
+    assert_eq!(rafter,RSystemTime::new(1_000,250));
+    assert_eq!(Into::<SystemTime>::into(rafter),after);
+
+    let before=UNIX_EPOCH-Duration::new(1_000,250);
+    let rbefore=RSystemTime::from(before);
+    assert_eq!(rbefore,RSystemTime::new(-1_001,999_999_750));
+    assert_eq!(Into::<SystemTime>::into(rbefore),before);
+
+    let now=SystemTime::now();
+    assert_eq!(Into::<SystemTime>::into(RSystemTime::from(now)),now);
+}
+
+
+#[test]
+fn system_time_serde(){
+    let time=RSystemTime::new(-5,100);
+    let serialized=serde_json::to_string(&time).unwrap();
+    assert_eq!(serde_json::from_str::<RSystemTime>(&serialized).unwrap(),time);
+
+    let max_nanos=r#"{"seconds":-5,"subsec_nanos":999999999}"#;
+    assert_eq!(
+        serde_json::from_str::<RSystemTime>(max_nanos).unwrap(),
+        RSystemTime::new(-5,999_999_999),
+    );
+
+    for invalid in &[
+        r#"{"seconds":-5,"subsec_nanos":1000000000}"#,
+        r#"{"seconds":0,"subsec_nanos":4294967295}"#,
+    ] {
+        serde_json::from_str::<RSystemTime>(invalid).unwrap_err();
+    }
+}
+
+
+#[test]
+fn instant_shared_clock(){
+    let first=RInstant::now();
+    let from_globals=RInstant{
+        since_origin:initialized_globals().monotonic_clock()(),
+    };
+    let last=RInstant::now();
+
+    assert!(first <= from_globals);
+    assert!(from_globals <= last);
+    assert_eq!(last-first,last.duration_since(first));
+    assert!(first.elapsed() >= last-first);
+}
+
+
+#[test]
+fn instant_arithmetic(){
+    let instant=RInstant::now();
+    let later=instant+secs(2,500_000_000);
+
+    assert_eq!(later.duration_since(instant),secs(2,500_000_000));
+    assert_eq!(later.checked_duration_since(instant),Some(secs(2,500_000_000)));
+    assert_eq!(instant.checked_duration_since(later),None);
+    assert_eq!(instant.duration_since(later),secs(0,0));
+    assert_eq!(later-secs(2,500_000_000),instant);
+
+    let mut assigned=instant;
+    assigned+=secs(1,0);
+    assigned-=secs(0,500_000_000);
+    assert_eq!(assigned-instant,secs(0,500_000_000));
+
+    assert_eq!(later.checked_add(secs(u64::max_value(),0)),None);
+    assert_eq!(
+        instant.checked_sub(secs(instant.since_origin.seconds()+1,0)),
+        None,
+    );
+}
+
+
+mod globals_v0 {
+    use crate::abi_stability::{
+        abi_checking::AbiInstabilityErrors,
+        stable_abi_trait::AbiInfoWrapper,
+    };
+    use crate::std_types::RResult;
+
+    /// `Globals` as it was before the `monotonic_clock` field was added.
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_struct="Globals")))]
+    pub struct GlobalsVal{
+        #[sabi(last_prefix_field)]
+        pub layout_checking:
+            extern fn(&'static AbiInfoWrapper,&'static AbiInfoWrapper) 
+                -> RResult<(), AbiInstabilityErrors> ,
+    }
+}
+
+
+#[test]
+fn instant_clock_missing_from_globals(){
+    use crate::{
+        abi_stability::abi_checking::check_layout_compatibility_for_ffi,
+        globals::Globals,
+        prefix_type::PrefixTypeTrait,
+        utils::transmute_reference,
+    };
+
+    let old_globals=globals_v0::GlobalsVal{
+        layout_checking:check_layout_compatibility_for_ffi,
+    }.leak_into_prefix();
+
+    let globals:&Globals=unsafe{ transmute_reference(old_globals) };
+    let clock=globals.monotonic_clock();
+    assert_eq!(clock as usize,monotonic_clock as usize);
+
+    let first=clock();
+    assert!(first <= clock());
+}
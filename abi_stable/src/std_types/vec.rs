@@ -7,6 +7,7 @@ use std::{
     mem::{self, ManuallyDrop},
     ops::{Deref, DerefMut, Index, IndexMut},
     ptr,
+    slice,
 };
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -375,11 +376,11 @@ impl<T> RVec<T> {
 
     fn truncate_inner(&mut self, to: usize) {
         let old_length = self.length;
-        self.length = to;
         unsafe {
-            for elem in self.get_unchecked_mut(to..old_length) {
-                ptr::drop_in_place(elem);
-            }
+            let to_drop: *mut [T] = 
+                slice::from_raw_parts_mut(self.as_mut_ptr().add(to), old_length - to);
+            self.length = to;
+            ptr::drop_in_place(to_drop);
         }
     }
 
@@ -714,7 +715,7 @@ impl io::Write for RVec<u8> {
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, StableAbi)]
-enum Exactness {
+pub(super) enum Exactness {
     Exact,
     Above,
 }
@@ -152,7 +152,7 @@ fn truncate() {
         let length = 10;
         let mut list = iter::repeat(pointer.clone())
             .take(length)
-            .collect::<Vec<_>>();
+            .collect::<RVec<_>>();
 
         assert_eq!(Arc::strong_count(&pointer), 1 + length);
         for i in (0..list.len()).rev() {
@@ -163,6 +163,26 @@ fn truncate() {
     }
 }
 
+#[test]
+fn clear() {
+    let pointer = Arc::new(());
+
+    let length = 10;
+    let mut list = iter::repeat(pointer.clone())
+        .take(length)
+        .collect::<RVec<_>>();
+    let capacity = list.capacity();
+
+    assert_eq!(Arc::strong_count(&pointer), 1 + length);
+    list.clear();
+    assert_eq!(Arc::strong_count(&pointer), 1);
+    assert!(list.is_empty());
+    assert_eq!(list.capacity(), capacity);
+
+    list.clear();
+    assert!(list.is_empty());
+}
+
 #[test]
 fn retain(){
     let orig = vec![2, 3, 4 , 5, 6,7,8];
@@ -0,0 +1,1049 @@
+/*!
+Contains the ffi-safe equivalent of `std::collections::VecDeque`,and related items.
+*/
+
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    fmt::{self,Debug},
+    hash::{Hash,Hasher},
+    iter::{Chain,FromIterator},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Bound,Index,IndexMut,RangeBounds},
+    ptr,
+    slice,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[allow(unused_imports)]
+use core_extensions::prelude::*;
+
+use crate::{
+    sabi_types::{ReturnValueEquality},
+    std_types::{
+        RVec,
+        utypeid::{UTypeId,new_utypeid},
+        vec::Exactness,
+    },
+    prefix_type::{PrefixTypeTrait,WithMetadata},
+};
+
+#[cfg(all(test,not(feature="only_new_tests")))]
+mod tests;
+
+
+mod private {
+    use super::*;
+
+    /**
+Ffi-safe equivalent of `std::collections::VecDeque`,
+a double-ended queue implemented with a growable ring buffer.
+
+Growing,shrinking,and deallocating the buffer is done through a vtable,
+so that an `RVecDeque<T>` can be mutated from any dynamic library/executable,
+regardless of which one created it.
+
+# Example
+
+```
+use abi_stable::std_types::RVecDeque;
+
+let mut queue=RVecDeque::new();
+
+queue.push_back(3);
+queue.push_back(5);
+queue.push_front(1);
+
+assert_eq!(queue.iter().cloned().collect::<Vec<_>>(),vec![1,3,5]);
+
+assert_eq!(queue.pop_front(),Some(1));
+assert_eq!(queue.pop_back(),Some(5));
+assert_eq!(queue.pop_back(),Some(3));
+assert_eq!(queue.pop_back(),None);
+
+```
+
+    */
+    #[repr(C)]
+    #[derive(StableAbi)]
+    pub struct RVecDeque<T> {
+        buffer: *mut T,
+        head: usize,
+        pub(super) length: usize,
+        capacity: usize,
+        vtable: *const VecDequeVTable<T>,
+        _marker: PhantomData<T>,
+    }
+
+    impl<T> RVecDeque<T> {
+        #[allow(dead_code)]
+        // Used to test functions that change behavior when the vtable changes
+        pub(super) fn set_vtable_for_testing(mut self) -> Self {
+            self.vtable = VTableGetter::<T>::LIB_VTABLE_FOR_TESTING.as_prefix_raw();
+            self
+        }
+
+        #[inline(always)]
+        pub(super) fn vtable<'a>(&self) -> &'a VecDequeVTable<T>
+        where
+            T: 'a,
+        {
+            unsafe { &*self.vtable }
+        }
+
+        #[inline(always)]
+        pub(super) fn buffer(&self) -> *const T {
+            self.buffer
+        }
+
+        pub(super) fn buffer_mut(&mut self) -> *mut T {
+            self.buffer
+        }
+
+        #[inline(always)]
+        pub(super) fn head(&self) -> usize {
+            self.head
+        }
+
+        #[inline(always)]
+        pub(super) fn set_head(&mut self,head:usize) {
+            self.head=head;
+        }
+
+        /// Returns the ammount of elements the `RVecDeque<T>` can hold without reallocating.
+        pub const fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        /// Constructs a vec to do operations on the underlying buffer,
+        /// after moving all the elements to the start of the buffer.
+        ///
+        /// # Safety
+        ///
+        /// This must not be called outside of functions that get stored in the vtable.
+        pub(super) unsafe fn with_vec<U, F>(&mut self, f: F) -> U
+        where
+            F: FnOnce(&mut Vec<T>) -> U,
+        {
+            self.move_to_buffer_start();
+            let mut old = mem::replace(self, RVecDeque::new()).piped(ManuallyDrop::new);
+            let mut list = Vec::<T>::from_raw_parts(
+                old.buffer_mut(),
+                old.len(),
+                old.capacity()
+            );
+            let ret = f(&mut list);
+            ptr::write(self, list.into());
+            ret
+        }
+    }
+
+    impl<T> From<Vec<T>> for RVecDeque<T> {
+        fn from(this:Vec<T>)->Self{
+            let mut this=ManuallyDrop::new(this);
+            RVecDeque {
+                vtable: VTableGetter::<T>::LIB_VTABLE.as_prefix_raw(),
+                buffer: this.as_mut_ptr(),
+                head: 0,
+                length: this.len(),
+                capacity: this.capacity(),
+                _marker: Default::default(),
+            }
+        }
+    }
+}
+
+pub use self::private::RVecDeque;
+
+
+/// An iterator over references to the elements of an `RVecDeque<T>`,
+/// from front to back.
+pub type Iter<'a,T>=Chain<slice::Iter<'a,T>,slice::Iter<'a,T>>;
+
+/// An iterator over mutable references to the elements of an `RVecDeque<T>`,
+/// from front to back.
+pub type IterMut<'a,T>=Chain<slice::IterMut<'a,T>,slice::IterMut<'a,T>>;
+
+
+impl<T> RVecDeque<T> {
+    /// Creates a new,empty `RVecDeque<T>`.
+    ///
+    /// This function does not allocate.
+    pub fn new() -> Self {
+        Vec::new().into()
+    }
+
+    /// Creates a new,empty `RVecDeque<T>`,with a capacity of `cap`.
+    ///
+    /// This function does not allocate if `cap`==0.
+    pub fn with_capacity(cap: usize) -> Self {
+        Vec::with_capacity(cap).into()
+    }
+
+    /// Returns the ammount of elements in the `RVecDeque<T>`.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Whether the length of the `RVecDeque<T>` is 0.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Converts an index relative to the front of the queue to
+    /// an index into the buffer.
+    #[inline]
+    fn physical_index(&self,index:usize)->usize{
+        let to_end=self.capacity()-self.head();
+        if index < to_end {
+            self.head()+index
+        }else{
+            index-to_end
+        }
+    }
+
+    /// The lengths of the slices returned by `as_slices`.
+    #[inline]
+    fn slice_lengths(&self)->(usize,usize){
+        let to_end=self.capacity()-self.head();
+        if self.length <= to_end {
+            (self.length,0)
+        }else{
+            (to_end,self.length-to_end)
+        }
+    }
+
+    /// Returns the elements of the `RVecDeque<T>`,
+    /// in the order they are stored in the ring buffer.
+    ///
+    /// The first slice contains the elements from the front of the queue,
+    /// the second slice the elements that wrapped around to the start of the buffer.
+    pub fn as_slices(&self)->(&[T],&[T]){
+        let (front_len,back_len)=self.slice_lengths();
+        unsafe{
+            let buffer=self.buffer();
+            (
+                slice::from_raw_parts(buffer.add(self.head()),front_len),
+                slice::from_raw_parts(buffer,back_len),
+            )
+        }
+    }
+
+    /// Returns the elements of the `RVecDeque<T>`,
+    /// in the order they are stored in the ring buffer.
+    ///
+    /// The first slice contains the elements from the front of the queue,
+    /// the second slice the elements that wrapped around to the start of the buffer.
+    pub fn as_mut_slices(&mut self)->(&mut [T],&mut [T]){
+        let (front_len,back_len)=self.slice_lengths();
+        unsafe{
+            let head=self.head();
+            let buffer=self.buffer_mut();
+            (
+                slice::from_raw_parts_mut(buffer.add(head),front_len),
+                slice::from_raw_parts_mut(buffer,back_len),
+            )
+        }
+    }
+
+    /// Rearranges the elements of the `RVecDeque<T>` so that they are stored contiguously,
+    /// returning a slice with all of them.
+    ///
+    /// This does not allocate.
+    pub fn make_contiguous(&mut self)->&mut [T]{
+        let (front_len,back_len)=self.slice_lengths();
+        unsafe{
+            let buffer=self.buffer_mut();
+            if back_len!=0 {
+                // The buffer looks like `[back..,gap..,front..]`,
+                // this moves `front` to the end of `back` and then rotates them in place.
+                ptr::copy(buffer.add(self.head()),buffer.add(back_len),front_len);
+                self.set_head(0);
+                slice::from_raw_parts_mut(buffer,self.length).rotate_left(back_len);
+            }
+            slice::from_raw_parts_mut(buffer.add(self.head()),self.length)
+        }
+    }
+
+    /// Moves all the elements to the start of the buffer,in order.
+    fn move_to_buffer_start(&mut self){
+        self.make_contiguous();
+        let head=self.head();
+        if head!=0 {
+            unsafe{
+                let buffer=self.buffer_mut();
+                ptr::copy(buffer.add(head),buffer,self.length);
+            }
+            self.set_head(0);
+        }
+    }
+
+    /// Returns a reference to the element at the `index` position from the front,
+    /// returns None if self.len() <= index.
+    pub fn get(&self,index:usize)->Option<&T>{
+        if index < self.length {
+            unsafe{ Some(&*self.buffer().add(self.physical_index(index))) }
+        }else{
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at the `index` position from the front,
+    /// returns None if self.len() <= index.
+    pub fn get_mut(&mut self,index:usize)->Option<&mut T>{
+        if index < self.length {
+            unsafe{
+                let index=self.physical_index(index);
+                Some(&mut *self.buffer_mut().add(index))
+            }
+        }else{
+            None
+        }
+    }
+
+    /// Returns a reference to the element at the front,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn front(&self)->Option<&T>{
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the element at the front,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn front_mut(&mut self)->Option<&mut T>{
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the element at the back,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn back(&self)->Option<&T>{
+        self.length.checked_sub(1).and_then(|i| self.get(i) )
+    }
+
+    /// Returns a mutable reference to the element at the back,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn back_mut(&mut self)->Option<&mut T>{
+        match self.length.checked_sub(1) {
+            Some(i)=>self.get_mut(i),
+            None=>None,
+        }
+    }
+
+    /// Appends `value` at the back of the `RVecDeque<T>`.
+    pub fn push_back(&mut self,value:T){
+        if self.length == self.capacity() {
+            self.grow_capacity_to_1();
+        }
+        unsafe{
+            let index=self.physical_index(self.length);
+            ptr::write(self.buffer_mut().add(index),value);
+        }
+        self.length+=1;
+    }
+
+    /// Prepends `value` at the front of the `RVecDeque<T>`.
+    pub fn push_front(&mut self,value:T){
+        if self.length == self.capacity() {
+            self.grow_capacity_to_1();
+        }
+        let head=match self.head() {
+            0=>self.capacity()-1,
+            x=>x-1,
+        };
+        unsafe{
+            ptr::write(self.buffer_mut().add(head),value);
+        }
+        self.set_head(head);
+        self.length+=1;
+    }
+
+    /// Attempts to remove the element at the back,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn pop_back(&mut self)->Option<T>{
+        if self.length == 0 {
+            None
+        }else{
+            self.length-=1;
+            unsafe{
+                let index=self.physical_index(self.length);
+                Some(ptr::read(self.buffer_mut().add(index)))
+            }
+        }
+    }
+
+    /// Attempts to remove the element at the front,
+    /// returns None if the `RVecDeque<T>` is empty.
+    pub fn pop_front(&mut self)->Option<T>{
+        if self.length == 0 {
+            None
+        }else{
+            unsafe{
+                let head=self.head();
+                let ret=ptr::read(self.buffer_mut().add(head));
+                let new_head=self.physical_index(1);
+                self.set_head(new_head);
+                self.length-=1;
+                Some(ret)
+            }
+        }
+    }
+
+    /// Swaps the elements at the `i` and `j` positions from the front.
+    ///
+    /// # Panic
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self,i:usize,j:usize){
+        assert!(
+            i < self.length && j < self.length,
+            "index out of bounds,indices=({},{}) len={} ",
+            i,
+            j,
+            self.length,
+        );
+        unsafe{
+            let i=self.physical_index(i);
+            let j=self.physical_index(j);
+            let buffer=self.buffer_mut();
+            ptr::swap(buffer.add(i),buffer.add(j));
+        }
+    }
+
+    /// Inserts the `value` value at the `index` position from the front,
+    /// moving the elements after it towards the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if self.len() < index.
+    pub fn insert(&mut self,index:usize,value:T){
+        assert!(
+            index <= self.length,
+            "index out of bounds,index={} len={} ",
+            index,
+            self.length
+        );
+        if index < self.length / 2 {
+            self.push_front(value);
+            for i in 0..index {
+                self.swap(i,i+1);
+            }
+        }else{
+            self.push_back(value);
+            for i in (index..self.length-1).rev() {
+                self.swap(i,i+1);
+            }
+        }
+    }
+
+    /// Attempts to remove the element at the `index` position from the front,
+    /// returns None if self.len() <= index.
+    pub fn remove(&mut self,index:usize)->Option<T>{
+        if self.length <= index {
+            return None;
+        }
+        if index < self.length / 2 {
+            for i in (0..index).rev() {
+                self.swap(i,i+1);
+            }
+            self.pop_front()
+        }else{
+            for i in index..self.length-1 {
+                self.swap(i,i+1);
+            }
+            self.pop_back()
+        }
+    }
+
+    /// Truncates the `RVecDeque<T>` to `to` length,removing elements from the back.
+    /// Does nothing if self.len() <= to.
+    ///
+    /// Note:this has no effect on the capacity of the `RVecDeque<T>`.
+    pub fn truncate(&mut self,to:usize){
+        while to < self.length {
+            self.pop_back();
+        }
+    }
+
+    /// Removes all the elements from collection.
+    ///
+    /// Note:this has no effect on the capacity of the `RVecDeque<T>`.
+    pub fn clear(&mut self){
+        self.truncate(0);
+        self.set_head(0);
+    }
+
+    /// Retains only the elements that satisfy the `pred` predicate
+    ///
+    /// This means that a element will be removed if `pred(that_element)`
+    /// returns false.
+    pub fn retain<F>(&mut self,mut pred:F)
+    where F: FnMut(&T) -> bool
+    {
+        let len=self.length;
+        let mut deleted=0;
+        for i in 0..len {
+            if !pred(&self[i]) {
+                deleted+=1;
+            }else if deleted > 0 {
+                self.swap(i-deleted,i);
+            }
+        }
+        self.truncate(len-deleted);
+    }
+
+    /// Whether the `RVecDeque<T>` contains an element equal to `value`.
+    pub fn contains(&self,value:&T)->bool
+    where
+        T:PartialEq,
+    {
+        let (front,back)=self.as_slices();
+        front.contains(value) || back.contains(value)
+    }
+
+    /// Iterates over references to the elements,from front to back.
+    pub fn iter(&self)->Iter<'_,T>{
+        let (front,back)=self.as_slices();
+        front.iter().chain(back.iter())
+    }
+
+    /// Iterates over mutable references to the elements,from front to back.
+    pub fn iter_mut(&mut self)->IterMut<'_,T>{
+        let (front,back)=self.as_mut_slices();
+        front.iter_mut().chain(back.iter_mut())
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    /// This may reserve more than necessary for the additional capacity.
+    pub fn reserve(&mut self,additional:usize){
+        self.resize_capacity(self.len() + additional, Exactness::Above)
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    ///
+    /// Prefer using `reserve` for most situations.
+    pub fn reserve_exact(&mut self,additional:usize){
+        self.resize_capacity(self.len() + additional, Exactness::Exact)
+    }
+
+    /// Shrinks the capacity of the `RVecDeque<T>` to match its length.
+    pub fn shrink_to_fit(&mut self){
+        let vtable = self.vtable();
+        vtable.shrink_to_fit()(self);
+    }
+
+    /// Returns a `Vec<T>` with the elements from front to back,consuming `self`.
+    ///
+    /// # Allocation
+    ///
+    /// If this is invoked outside of the dynamic library/binary that created it,
+    /// it will allocate a new `Vec<T>` and move the data into it.
+    pub fn into_vec(mut self)->Vec<T>{
+        let this_vtable =self.vtable();
+        let other_vtable=VTableGetter::LIB_VTABLE.as_prefix();
+        if ::std::ptr::eq(this_vtable,other_vtable)||
+            this_vtable.type_id()==other_vtable.type_id()
+        {
+            self.move_to_buffer_start();
+            let mut this = ManuallyDrop::new(self);
+            unsafe{
+                Vec::from_raw_parts(this.buffer_mut(), this.len(), this.capacity())
+            }
+        } else {
+            let mut ret=Vec::with_capacity(self.len());
+            while let Some(elem)=self.pop_front() {
+                ret.push(elem);
+            }
+            ret
+        }
+    }
+
+    /// Converts this `RVecDeque<T>` into an `RVec<T>`,
+    /// with the elements from front to back.
+    pub fn into_rvec(self)->RVec<T>{
+        self.into_vec().into()
+    }
+
+    /// Creates a draining iterator that removes the specified range in
+    /// the `RVecDeque<T>` and yields the removed items.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the end of the range is out of bounds or if the start of the range is
+    /// greater than the end of the range.
+    ///
+    /// # Consumption
+    ///
+    /// The elements in the range will be removed even if the iterator
+    /// was dropped before yielding them.
+    pub fn drain<R>(&mut self,range:R)->Drain<'_,T>
+    where
+        R:RangeBounds<usize>,
+    {
+        let len=self.length;
+        let start=match range.start_bound() {
+            Bound::Included(&x)=>x,
+            Bound::Excluded(&x)=>x+1,
+            Bound::Unbounded=>0,
+        };
+        let end=match range.end_bound() {
+            Bound::Included(&x)=>x+1,
+            Bound::Excluded(&x)=>x,
+            Bound::Unbounded=>len,
+        };
+        assert!(start <= end,"drain start greater than end,start={} end={} ",start,end);
+        assert!(end <= len,"drain end out of bounds,end={} len={} ",end,len);
+
+        // Leaks the elements after the drained range if the Drain is leaked.
+        self.length=start;
+
+        Drain{
+            deque:self,
+            drain_start:start,
+            drain_end:end,
+            front:start,
+            back:end,
+            old_len:len,
+        }
+    }
+
+    #[inline]
+    fn grow_capacity_to_1(&mut self) {
+        let vtable = self.vtable();
+        vtable.grow_capacity_to()(self, self.capacity() + 1, Exactness::Above);
+    }
+
+    fn resize_capacity(&mut self, to: usize, exactness: Exactness) {
+        let vtable = self.vtable();
+        if self.capacity() < to {
+            vtable.grow_capacity_to()(self, to, exactness);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+/// An Iterator returned by `RVecDeque::drain` ,
+/// which removes and yields all the elements in a range from the `RVecDeque<T>`.
+pub struct Drain<'a,T>{
+    deque:&'a mut RVecDeque<T>,
+    drain_start:usize,
+    drain_end:usize,
+    front:usize,
+    back:usize,
+    old_len:usize,
+}
+
+impl<'a,T> Iterator for Drain<'a,T>{
+    type Item=T;
+
+    fn next(&mut self)->Option<T>{
+        if self.front==self.back {
+            None
+        }else{
+            unsafe{
+                let index=self.deque.physical_index(self.front);
+                self.front+=1;
+                Some(ptr::read(self.deque.buffer_mut().add(index)))
+            }
+        }
+    }
+
+    fn size_hint(&self)->(usize,Option<usize>){
+        let len=self.back-self.front;
+        (len,Some(len))
+    }
+}
+
+impl<'a,T> DoubleEndedIterator for Drain<'a,T>{
+    fn next_back(&mut self)->Option<T>{
+        if self.front==self.back {
+            None
+        }else{
+            unsafe{
+                self.back-=1;
+                let index=self.deque.physical_index(self.back);
+                Some(ptr::read(self.deque.buffer_mut().add(index)))
+            }
+        }
+    }
+}
+
+impl<'a,T> ExactSizeIterator for Drain<'a,T>{}
+
+impl<'a,T> Drop for Drain<'a,T>{
+    fn drop(&mut self){
+        self.by_ref().for_each(drop);
+
+        let removed=self.drain_end-self.drain_start;
+        let tail_len=self.old_len-self.drain_end;
+        let deque=&mut *self.deque;
+
+        unsafe{
+            let move_elem=|deque:&mut RVecDeque<T>,from:usize,to:usize|{
+                let from=deque.physical_index(from);
+                let to=deque.physical_index(to);
+                let buffer=deque.buffer_mut();
+                ptr::copy_nonoverlapping(buffer.add(from),buffer.add(to),1);
+            };
+
+            if removed==0 {
+                // Nothing to move
+            }else if self.drain_start < tail_len {
+                // Moving the elements before the drained range towards the back
+                for i in (0..self.drain_start).rev() {
+                    move_elem(deque,i,i+removed);
+                }
+                let new_head=deque.physical_index(removed);
+                deque.set_head(new_head);
+            }else{
+                // Moving the elements after the drained range towards the front
+                for i in 0..tail_len {
+                    move_elem(deque,self.drain_end+i,self.drain_start+i);
+                }
+            }
+        }
+        deque.length=self.old_len-removed;
+    }
+}
+
+
+/// An Iterator created by `<RVecDeque<T> as IntoIterator>::into_iter`,
+/// which yields all the elements from the `RVecDeque<T>`,from front to back,
+/// consuming it in the process.
+pub struct IntoIter<T>{
+    deque:RVecDeque<T>,
+}
+
+impl<T> Iterator for IntoIter<T>{
+    type Item=T;
+
+    #[inline]
+    fn next(&mut self)->Option<T>{
+        self.deque.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self)->(usize,Option<usize>){
+        let len=self.deque.len();
+        (len,Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T>{
+    #[inline]
+    fn next_back(&mut self)->Option<T>{
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T>{}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+impl<T> Clone for RVecDeque<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut ret=Vec::with_capacity(self.len());
+        ret.extend(self.iter().cloned());
+        ret.into()
+    }
+}
+
+impl<T> Default for RVecDeque<T> {
+    fn default() -> Self {
+        Vec::new().into()
+    }
+}
+
+impl<T> Debug for RVecDeque<T>
+where
+    T:Debug,
+{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_list()
+         .entries(self.iter())
+         .finish()
+    }
+}
+
+impl<T> Eq for RVecDeque<T>
+where
+    T:Eq,
+{}
+
+impl<T> PartialEq for RVecDeque<T>
+where
+    T:PartialEq,
+{
+    fn eq(&self,other:&Self)->bool{
+        self.len() == other.len() &&
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> PartialOrd for RVecDeque<T>
+where
+    T:PartialOrd,
+{
+    fn partial_cmp(&self,other:&Self)->Option<Ordering>{
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T> Ord for RVecDeque<T>
+where
+    T:Ord,
+{
+    fn cmp(&self,other:&Self)->Ordering{
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Hash for RVecDeque<T>
+where
+    T:Hash,
+{
+    fn hash<H>(&self,state:&mut H)
+    where
+        H:Hasher
+    {
+        self.len().hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for RVecDeque<T>{
+    type Output=T;
+
+    fn index(&self,index:usize)->&T{
+        match self.get(index) {
+            Some(x)=>x,
+            None=>panic!("index out of bounds,index={} len={} ",index,self.len()),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RVecDeque<T>{
+    fn index_mut(&mut self,index:usize)->&mut T{
+        let len=self.len();
+        match self.get_mut(index) {
+            Some(x)=>x,
+            None=>panic!("index out of bounds,index={} len={} ",index,len),
+        }
+    }
+}
+
+
+impl_from_rust_repr! {
+    impl[T] From<VecDeque<T>> for RVecDeque<T>{
+        fn(this){
+            Vec::from(this).into()
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<VecDeque<T>> for RVecDeque<T> {
+        fn(this){
+            this.into_vec().into()
+        }
+    }
+}
+
+impl<T> From<RVec<T>> for RVecDeque<T>{
+    fn from(this:RVec<T>)->Self{
+        this.into_vec().into()
+    }
+}
+
+
+unsafe impl<T> Send for RVecDeque<T> where T: Send {}
+unsafe impl<T> Sync for RVecDeque<T> where T: Sync {}
+
+impl<T> Drop for RVecDeque<T> {
+    fn drop(&mut self) {
+        let vtable = self.vtable();
+        vtable.destructor()(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RVecDeque<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Vec<T>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T> Serialize for RVecDeque<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+
+impl<T> IntoIterator for RVecDeque<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter{
+            deque:self,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RVecDeque<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RVecDeque<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for RVecDeque<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.piped(Vec::from_iter).piped(Self::from)
+    }
+}
+
+impl<T> Extend<T> for RVecDeque<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+/// Dummy type used to create a statically allocated constant that can only be
+/// accessed of the lifetime of T.
+struct VTableGetter<'a, T>(&'a T);
+
+impl<'a, T: 'a> VTableGetter<'a, T> {
+    const DEFAULT_VTABLE:VecDequeVTableVal<T>=VecDequeVTableVal{
+        type_id:ReturnValueEquality{
+            function:new_utypeid::<RVecDeque<()>>
+        },
+        destructor: destructor_deque,
+        grow_capacity_to: grow_capacity_to_deque,
+        shrink_to_fit: shrink_to_fit_deque,
+    };
+
+    // The VTABLE for this type in this executable/library
+    const LIB_VTABLE: &'a WithMetadata<VecDequeVTableVal<T>> =
+        &WithMetadata::new(PrefixTypeTrait::METADATA,Self::DEFAULT_VTABLE);
+
+    // Used to test functions that change behavior based on the vtable being used
+    const LIB_VTABLE_FOR_TESTING: &'a WithMetadata<VecDequeVTableVal<T>> =
+        &WithMetadata::new(
+            PrefixTypeTrait::METADATA,
+            VecDequeVTableVal {
+                type_id:ReturnValueEquality{
+                    function:new_utypeid::<RVecDeque<i32>>
+                },
+                ..Self::DEFAULT_VTABLE
+            }
+        );
+
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_struct="VecDequeVTable")))]
+#[sabi(missing_field(panic))]
+struct VecDequeVTableVal<T> {
+    type_id:ReturnValueEquality<UTypeId>,
+    destructor: extern "C" fn(&mut RVecDeque<T>),
+    grow_capacity_to: extern "C" fn(&mut RVecDeque<T>, usize, Exactness),
+    #[sabi(last_prefix_field)]
+    shrink_to_fit: extern "C" fn(&mut RVecDeque<T>),
+}
+
+
+extern "C" fn destructor_deque<T>(this: &mut RVecDeque<T>) {
+    extern_fn_panic_handling! {
+        unsafe {
+            let (front,back)=this.as_mut_slices();
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+            drop(Vec::from_raw_parts(
+                this.buffer_mut(),
+                0,
+                this.capacity(),
+            ));
+        }
+    }
+}
+
+extern "C" fn grow_capacity_to_deque<T>(
+    this: &mut RVecDeque<T>,
+    to: usize,
+    exactness: Exactness
+) {
+    extern_fn_panic_handling! {
+        unsafe{
+            this.with_vec(|list| {
+                let additional = to.saturating_sub(list.len());
+                match exactness {
+                    Exactness::Above => list.reserve(additional),
+                    Exactness::Exact => list.reserve_exact(additional),
+                }
+            })
+        }
+    }
+}
+
+extern "C" fn shrink_to_fit_deque<T>(this: &mut RVecDeque<T>) {
+    extern_fn_panic_handling! {
+        unsafe{
+            this.with_vec(|list| {
+                list.shrink_to_fit();
+            })
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use super::*;
+
+use std::{iter,sync::Arc};
+
+use crate::std_types::RString;
+
+
+/// Creates a deque whose elements wrap around the end of the buffer.
+fn wrapped_deque(front:&[u32],back:&[u32])->RVecDeque<u32>{
+    let mut deque=RVecDeque::with_capacity(front.len()+back.len());
+    for &elem in back {
+        deque.push_back(elem);
+    }
+    for &elem in front.iter().rev() {
+        deque.push_front(elem);
+    }
+    deque
+}
+
+fn to_vec<T:Clone>(deque:&RVecDeque<T>)->Vec<T>{
+    deque.iter().cloned().collect()
+}
+
+
+#[test]
+fn push_pop(){
+    let mut deque=RVecDeque::new();
+    let mut std_deque=VecDeque::new();
+
+    for i in 0..40 {
+        if i%3==0 {
+            deque.push_front(i);
+            std_deque.push_front(i);
+        }else{
+            deque.push_back(i);
+            std_deque.push_back(i);
+        }
+        assert_eq!(deque.len(),std_deque.len());
+        assert_eq!(deque.front(),std_deque.front());
+        assert_eq!(deque.back(),std_deque.back());
+    }
+    assert_eq!(to_vec(&deque),std_deque.iter().cloned().collect::<Vec<_>>());
+
+    for i in 0..40 {
+        if i%2==0 {
+            assert_eq!(deque.pop_front(),std_deque.pop_front());
+        }else{
+            assert_eq!(deque.pop_back(),std_deque.pop_back());
+        }
+    }
+    assert_eq!(deque.pop_front(),None);
+    assert_eq!(deque.pop_back(),None);
+    assert!(deque.is_empty());
+}
+
+
+#[test]
+fn as_slices(){
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+
+    assert_eq!(deque.capacity(),5);
+    assert_eq!(deque.as_slices(),(&[1,2][..],&[3,4,5][..]));
+    assert_eq!(to_vec(&deque),vec![1,2,3,4,5]);
+
+    {
+        let (front,back)=deque.as_mut_slices();
+        front[0]=10;
+        back[0]=30;
+    }
+    assert_eq!(to_vec(&deque),vec![10,2,30,4,5]);
+
+    assert_eq!(deque.make_contiguous(),&mut [10,2,30,4,5][..]);
+    assert_eq!(deque.as_slices(),(&[10,2,30,4,5][..],&[][..]));
+}
+
+
+#[test]
+fn get_index_swap(){
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+
+    for (i,expected) in (1..=5).enumerate() {
+        assert_eq!(deque.get(i),Some(&expected));
+        assert_eq!(deque[i],expected);
+    }
+    assert_eq!(deque.get(5),None);
+
+    deque[0]=100;
+    *deque.get_mut(4).unwrap()=500;
+    *deque.front_mut().unwrap()+=1;
+    *deque.back_mut().unwrap()+=1;
+    assert_eq!(to_vec(&deque),vec![101,2,3,4,501]);
+
+    deque.swap(0,4);
+    deque.swap(1,3);
+    assert_eq!(to_vec(&deque),vec![501,4,3,2,101]);
+}
+
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds(){
+    let deque=wrapped_deque(&[1,2],&[3,4,5]);
+    let _=deque[5];
+}
+
+
+#[test]
+fn insert_remove(){
+    for index in 0..=5 {
+        let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+        let mut std_deque=VecDeque::from(vec![1,2,3,4,5]);
+
+        deque.insert(index,100);
+        std_deque.insert(index,100);
+        assert_eq!(to_vec(&deque),std_deque.iter().cloned().collect::<Vec<_>>());
+
+        assert_eq!(deque.remove(index),std_deque.remove(index));
+        assert_eq!(to_vec(&deque),vec![1,2,3,4,5]);
+    }
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+    assert_eq!(deque.remove(5),None);
+    assert_eq!(deque.remove(0),Some(1));
+    assert_eq!(deque.remove(3),Some(5));
+    assert_eq!(to_vec(&deque),vec![2,3,4]);
+}
+
+
+#[test]
+fn truncate_clear_retain(){
+    let mut deque=wrapped_deque(&[1,2,3],&[4,5,6,7]);
+
+    deque.retain(|x| x%2==1 );
+    assert_eq!(to_vec(&deque),vec![1,3,5,7]);
+
+    deque.truncate(10);
+    assert_eq!(to_vec(&deque),vec![1,3,5,7]);
+
+    deque.truncate(2);
+    assert_eq!(to_vec(&deque),vec![1,3]);
+
+    deque.clear();
+    assert!(deque.is_empty());
+    assert_eq!(deque.capacity(),7);
+}
+
+
+#[test]
+fn drain(){
+    let original=vec![1,2,3,4,5,6,7,8];
+
+    macro_rules! assert_eq_drain {
+        ($range:expr) => {
+            let range=$range;
+            let mut deque=wrapped_deque(&original[..3],&original[3..]);
+            let mut std_deque=VecDeque::from(original.clone());
+
+            let drained=deque.drain(range.clone()).collect::<Vec<_>>();
+            let std_drained=std_deque.drain(range.clone()).collect::<Vec<_>>();
+            assert_eq!(drained,std_drained);
+            assert_eq!(to_vec(&deque),std_deque.iter().cloned().collect::<Vec<_>>());
+
+            let mut deque=wrapped_deque(&original[..3],&original[3..]);
+            let drained=deque.drain(range.clone()).rev().collect::<Vec<_>>();
+            assert_eq!(drained,std_drained.iter().rev().cloned().collect::<Vec<_>>());
+            assert_eq!(to_vec(&deque),std_deque.iter().cloned().collect::<Vec<_>>());
+        };
+    }
+
+    assert_eq_drain!(..);
+    assert_eq_drain!(..2);
+    assert_eq_drain!(1..3);
+    assert_eq_drain!(2..7);
+    assert_eq_drain!(5..);
+    assert_eq_drain!(6..=6);
+    assert_eq_drain!(4..4);
+}
+
+
+#[test]
+fn drain_drops_elements(){
+    let pointer=Arc::new(());
+    let length=10;
+
+    for &(start,end) in &[(0,3),(2,4),(6,9),(3,10)] {
+        let mut deque=iter::repeat(pointer.clone())
+            .take(length)
+            .collect::<RVecDeque<_>>();
+        deque.pop_front();
+        deque.push_back(pointer.clone());
+
+        {
+            let mut drain=deque.drain(start..end);
+            drain.next();
+        }
+        assert_eq!(deque.len(),length-(end-start));
+        assert_eq!(Arc::strong_count(&pointer),1+length-(end-start));
+    }
+    assert_eq!(Arc::strong_count(&pointer),1);
+}
+
+
+#[test]
+#[should_panic]
+fn drain_out_of_bounds(){
+    wrapped_deque(&[1,2],&[3,4,5]).drain(2..6);
+}
+
+
+#[test]
+fn reserve_while_wrapped(){
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+
+    deque.reserve(10);
+    assert!(deque.capacity() >= 15);
+    assert_eq!(to_vec(&deque),vec![1,2,3,4,5]);
+
+    deque.push_front(0);
+    deque.push_back(6);
+    assert_eq!(to_vec(&deque),vec![0,1,2,3,4,5,6]);
+
+    deque.shrink_to_fit();
+    assert_eq!(deque.capacity(),7);
+    assert_eq!(to_vec(&deque),vec![0,1,2,3,4,5,6]);
+
+    deque.reserve_exact(3);
+    assert_eq!(deque.capacity(),10);
+}
+
+
+#[test]
+fn grow_with_strings(){
+    let mut deque=RVecDeque::<RString>::new();
+    for i in 0..100 {
+        deque.push_front(i.to_string().into());
+        deque.push_back((i+1000).to_string().into());
+    }
+    assert_eq!(deque.len(),200);
+    assert_eq!(deque.front().map(|x| x.as_str() ),Some("99"));
+    assert_eq!(deque.back().map(|x| x.as_str() ),Some("1099"));
+    assert!(deque.contains(&RString::from("50")));
+    assert!(!deque.contains(&RString::from("500")));
+}
+
+
+#[test]
+fn zero_sized(){
+    let mut deque=RVecDeque::new();
+    for _ in 0..10 {
+        deque.push_front(());
+        deque.push_back(());
+    }
+    assert_eq!(deque.len(),20);
+    assert_eq!(deque.drain(5..10).count(),5);
+    assert_eq!(deque.iter().count(),15);
+    assert_eq!(deque.into_vec().len(),15);
+}
+
+
+#[test]
+fn into_vec(){
+    let deque=wrapped_deque(&[1,2],&[3,4,5]);
+    assert_eq!(deque.into_vec(),vec![1,2,3,4,5]);
+
+    let deque=wrapped_deque(&[1,2],&[3,4,5]).set_vtable_for_testing();
+    assert_eq!(deque.into_vec(),vec![1,2,3,4,5]);
+
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+    deque.pop_front();
+    deque.pop_front();
+    assert_eq!(deque.into_rvec(),RVec::from(vec![3,4,5]));
+}
+
+
+#[test]
+fn foreign_vtable_mutation(){
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]).set_vtable_for_testing();
+    for i in 6..20 {
+        deque.push_back(i);
+    }
+    deque.push_front(0);
+    assert_eq!(to_vec(&deque),(0..20).collect::<Vec<_>>());
+}
+
+
+#[test]
+fn into_iter(){
+    let deque=wrapped_deque(&[1,2],&[3,4,5]);
+    assert_eq!(deque.clone().into_iter().collect::<Vec<_>>(),vec![1,2,3,4,5]);
+    assert_eq!(deque.clone().into_iter().rev().collect::<Vec<_>>(),vec![5,4,3,2,1]);
+
+    let mut iter=deque.into_iter();
+    assert_eq!(iter.len(),5);
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(),3);
+
+    let pointer=Arc::new(());
+    let mut deque=RVecDeque::new();
+    deque.push_back(pointer.clone());
+    deque.push_front(pointer.clone());
+    let mut iter=deque.into_iter();
+    iter.next();
+    drop(iter);
+    assert_eq!(Arc::strong_count(&pointer),1);
+}
+
+
+#[test]
+fn iter_mut(){
+    let mut deque=wrapped_deque(&[1,2],&[3,4,5]);
+    for elem in &mut deque {
+        *elem*=10;
+    }
+    assert_eq!(to_vec(&deque),vec![10,20,30,40,50]);
+    assert_eq!((&deque).into_iter().rev().cloned().collect::<Vec<_>>(),vec![50,40,30,20,10]);
+}
+
+
+#[test]
+fn eq_ord_clone(){
+    let deque=wrapped_deque(&[1,2],&[3,4,5]);
+    let contiguous=RVecDeque::from(vec![1,2,3,4,5]);
+
+    assert_eq!(deque,contiguous);
+    assert_eq!(deque.cmp(&contiguous),Ordering::Equal);
+    assert_eq!(deque.clone(),contiguous);
+    assert_eq!(format!("{:?}",deque),format!("{:?}",vec![1,2,3,4,5]));
+
+    let bigger=RVecDeque::from(vec![1,2,3,4,6]);
+    assert!(deque < bigger);
+    assert_ne!(deque,bigger);
+}
+
+
+#[test]
+fn conversions(){
+    let std_deque=VecDeque::from(vec![1,2,3]);
+    let deque=RVecDeque::from(std_deque.clone());
+    assert_eq!(to_vec(&deque),vec![1,2,3]);
+
+    let back:VecDeque<u32>=deque.into();
+    assert_eq!(back,std_deque);
+
+    let deque=RVecDeque::from(RVec::from(vec![4,5]));
+    assert_eq!(to_vec(&deque),vec![4,5]);
+
+    let mut deque=(0..3).collect::<RVecDeque<u32>>();
+    deque.extend(3..6);
+    assert_eq!(to_vec(&deque),vec![0,1,2,3,4,5]);
+}
+
+
+#[test]
+fn test_serde(){
+    let deque=wrapped_deque(&[1,2],&[3,4,5]);
+
+    let serialized=serde_json::to_string(&deque).unwrap();
+    assert_eq!(serialized,"[1,2,3,4,5]");
+
+    let deserialized=serde_json::from_str::<RVecDeque<u32>>(&serialized).unwrap();
+    assert_eq!(deserialized,deque);
+}
@@ -71,3 +71,49 @@ impl Display for Stringy{
 
 impl ErrorTrait for Stringy{}
 
+
+
+//////////////////////////////////////////////////////////////////
+
+
+/// Runs `future` to completion on the current thread,
+/// parking the thread while the future is pending.
+#[cfg(any(rust_1_36,feature="rust_1_36"))]
+pub(crate) fn block_on<F>(future:F)->F::Output
+where
+    F:std::future::Future,
+{
+    use std::{
+        sync::Arc,
+        task::{Context,Poll,RawWaker,RawWakerVTable,Waker},
+        thread::{self,Thread},
+    };
+
+    static VTABLE:RawWakerVTable=RawWakerVTable::new(clone,wake,wake_by_ref,drop_waker);
+
+    unsafe fn clone(data:*const ())->RawWaker{
+        Arc::increment_strong_count(data as *const Thread);
+        RawWaker::new(data,&VTABLE)
+    }
+    unsafe fn wake(data:*const ()){
+        Arc::from_raw(data as *const Thread).unpark();
+    }
+    unsafe fn wake_by_ref(data:*const ()){
+        (*(data as *const Thread)).unpark();
+    }
+    unsafe fn drop_waker(data:*const ()){
+        drop(Arc::from_raw(data as *const Thread));
+    }
+
+    let thread=Arc::into_raw(Arc::new(thread::current())) as *const ();
+    let waker=unsafe{ Waker::from_raw(RawWaker::new(thread,&VTABLE)) };
+    let mut cx=Context::from_waker(&waker);
+
+    let mut future=Box::pin(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(x)=>return x,
+            Poll::Pending=>thread::park(),
+        }
+    }
+}
@@ -12,11 +12,14 @@ use std::{
 use core_extensions::StringExt;
 
 use crate::{
-    abi_stability::stable_abi_trait::{AbiInfo,GetAbiInfo},
+    abi_stability::{
+        extra_checks::ExtraChecks,
+        stable_abi_trait::{AbiInfo,GetAbiInfo},
+    },
     const_utils::empty_slice, sabi_types::VersionStrings, 
     sabi_types::CmpIgnored,
     std_types::{RNone, ROption, RSome, RStr, StaticSlice,StaticStr,RSlice},
-    prefix_type::{FieldAccessibility,IsConditional},
+    prefix_type::{FieldAccessibility,IsAccessible,IsConditional},
     reflection::ModReflMode,
 };
 
@@ -66,6 +69,7 @@ pub use self::{
         TLFunctionRange,
     },
     tl_other::{
+        CustomPrimitive,
         FullType,
         GenericParams,
         LifetimeIndex,
@@ -121,6 +125,8 @@ pub struct TypeLayout {
     pub repr_attr:ReprAttr,
     /// How this type is treated when interpreted as a module.
     pub mod_refl_mode:ModReflMode,
+    /// User-defined checks,run when this is the expected layout in layout checking.
+    pub extra_checks:ROption<ExtraChecks>,
 }
 
 
@@ -210,6 +216,7 @@ impl TypeLayout {
             tag:Tag::null(),
             mod_refl_mode:ModReflMode::Module,
             repr_attr:repr,
+            extra_checks:RNone,
         }
     }
 
@@ -233,6 +240,7 @@ impl TypeLayout {
             tag:Tag::null(),
             mod_refl_mode:ModReflMode::Module,
             repr_attr:ReprAttr::C(RNone),
+            extra_checks:RNone,
         }
     }
 
@@ -257,6 +265,7 @@ impl TypeLayout {
             tag:p.tag,
             mod_refl_mode:p.mod_refl_mode,
             repr_attr:p.repr_attr,
+            extra_checks:p.extra_checks,
         }
     }
 
@@ -298,6 +307,13 @@ impl TypeLayout {
         self.repr_attr=repr_attr;
         self
     }
+
+    /// Sets the user-defined checks of the type,
+    /// which are run when this is the expected layout in layout checking.
+    pub const fn set_extra_checks(mut self,extra_checks:ExtraChecks)->Self{
+        self.extra_checks=RSome(extra_checks);
+        self
+    }
 }
 
 
@@ -33,6 +33,7 @@ pub struct _private_TypeLayoutDerive {
     pub tag:Tag,
     pub mod_refl_mode:ModReflMode,
     pub repr_attr:ReprAttr,
+    pub extra_checks:ROption<ExtraChecks>,
 }
 
 
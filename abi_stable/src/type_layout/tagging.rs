@@ -460,6 +460,14 @@ where I:IntoIterator<Item= (CheckableTag,CheckableTag) >
 
 
 impl CheckableTag{
+    pub(crate) const fn from_variant(variant:CTVariant)->Self{
+        Self{variant}
+    }
+
+    pub(crate) fn variant(&self)->&CTVariant{
+        &self.variant
+    }
+
     pub fn check_compatible(&self,other:&Self)->Result<(),TagErrors>{
         use self::CTVariant as CTV;
 
@@ -784,7 +792,8 @@ impl Display for CheckableTag {
 
 
 /// The error produced when checking `CheckableTag`s.
-#[derive(Debug,Clone,PartialEq)]
+#[repr(C)]
+#[derive(Debug,Clone,PartialEq,StableAbi)]
 pub struct TagErrors{
     expected:CheckableTag,
     found:CheckableTag,
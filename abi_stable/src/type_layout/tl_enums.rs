@@ -133,6 +133,16 @@ macro_rules! declare_tl_discriminants {
                 }
             )*
 
+            /// Gets the value of every discriminant,formatted as a string.
+            pub(crate) fn to_string_vec(&self)->Vec<String>{
+                match self {
+                    $(
+                        TLDiscriminants::$variant(discrs)=>
+                            discrs.as_slice().iter().map(|x| x.to_string() ).collect(),
+                    )*
+                }
+            }
+
             /// Gets the type of a discriminant in this TLDiscriminants.
             pub fn discriminant_repr(&self)->DiscriminantRepr{
                 match self {
@@ -220,7 +230,7 @@ pub enum TLDiscriminant{
 
 /// How the discriminant of an enum is represented.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Serialize, Deserialize)]
 pub enum DiscriminantRepr {
     /// The type of the discriminant for a `#[repr(u8)]`enum
     U8,
@@ -285,7 +295,7 @@ impl IsExhaustive{
 
 /// Properties exclusive to nonexhaustive enums.
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Serialize, Deserialize)]
 pub struct TLNonExhaustive{
     original_size:usize,
     original_alignment:usize,
@@ -15,7 +15,7 @@ use crate::{
 /// Which lifetime is being referenced by a field.
 /// Allows lifetimes to be renamed,so long as the "same" lifetime is being referenced.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Serialize, Deserialize)]
 pub enum LifetimeIndex {
     Static,
     /// Refers to the nth lifetime parameter of the deriving type.
@@ -37,7 +37,13 @@ pub struct TLPrefixType {
     pub first_suffix_field:usize,
     /// Which fields are accessible when the prefix type is instantiated in 
     /// the same dynlib/binary.
+    ///
+    /// This only describes the first 64 fields.
     pub accessible_fields:FieldAccessibility,
+    /// Which fields after the first 64 are accessible when 
+    /// the prefix type is instantiated in the same dynlib/binary,
+    /// each element describes the next 64 fields.
+    pub more_accessible_fields:StaticSlice<FieldAccessibility>,
     /// Which fields in the prefix 
     /// (what comes at and before `#[sabi(last_prefix_field)]`)
     /// are conditionally accessible 
@@ -48,12 +54,27 @@ pub struct TLPrefixType {
 }
 
 
+impl TLPrefixType {
+    /// Whether the field at the `index` position is accessible.
+    pub fn is_accessible(&self,index:usize)->bool{
+        self.accessible_fields
+            .is_accessible_with_more(self.more_accessible_fields.as_slice(),index)
+    }
+
+    /// Iterates over the accessibility of every field.
+    pub fn iter_accessible_fields(&self)->impl ExactSizeIterator<Item=IsAccessible>+Clone{
+        self.accessible_fields
+            .iter_field_count_with_more(self.more_accessible_fields.as_slice(),self.fields.len())
+    }
+}
+
+
 impl Display for TLPrefixType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f,"first_suffix_field:{}",self.first_suffix_field)?;
         write!(f,"accessible_fields:\n    ")?;
         f.debug_list()
-         .entries(self.accessible_fields.iter_field_count(self.fields.len()))
+         .entries(self.iter_accessible_fields())
          .finish()?;
         writeln!(f,)?;
         writeln!(f,"conditional_prefix_fields:\n    {:?}",self.conditional_prefix_fields)?;
@@ -68,7 +89,7 @@ impl Display for TLPrefixType {
 
 /// The `repr(..)` attribute used on a type.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Serialize, Deserialize)]
 pub enum ReprAttr{
     /// This is an Option<NonZeroType>.
     /// In which the size and alignment of the Option<_> is exactly that of its contents.
@@ -364,12 +385,14 @@ impl TLData {
     pub const fn prefix_type(
         first_suffix_field:usize,
         accessible_fields:FieldAccessibility,
+        more_accessible_fields:&'static [FieldAccessibility],
         conditional_prefix_fields:&'static [IsConditional],
         fields: &'static [TLField],
     )->Self{
         TLData::PrefixType(TLPrefixType{
             first_suffix_field,
             accessible_fields,
+            more_accessible_fields:StaticSlice::new(more_accessible_fields),
             conditional_prefix_fields:StaticSlice::new(conditional_prefix_fields),
             fields:TLFieldsOrSlice::from_slice(fields),
         })
@@ -393,12 +416,14 @@ impl TLData {
     pub const fn prefix_type_derive(
         first_suffix_field:usize,
         accessible_fields:FieldAccessibility,
+        more_accessible_fields:&'static [FieldAccessibility],
         conditional_prefix_fields:&'static [IsConditional],
         fields: TLFields,
     )->Self{
         TLData::PrefixType(TLPrefixType{
             first_suffix_field,
             accessible_fields,
+            more_accessible_fields:StaticSlice::new(more_accessible_fields),
             conditional_prefix_fields:StaticSlice::new(conditional_prefix_fields),
             fields:TLFieldsOrSlice::TLFields(fields),
         })
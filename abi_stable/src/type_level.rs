@@ -23,6 +23,10 @@ pub mod bools{
 /// can be converted back into the concrete type they were constructed with.
 pub mod unerasability{
     use crate::{
+        abi_stability::{
+            StableAbi,
+            stable_type_id::{StableTypeId,no_stable_type_id,some_stable_type_id},
+        },
         sabi_types::{MaybeCmp,ReturnValueEquality},
         std_types::utypeid::{UTypeId,no_utypeid,some_utypeid},
     };
@@ -32,6 +36,12 @@ pub mod unerasability{
     #[allow(non_camel_case_types)]
     pub struct TU_Unerasable;
 
+    /// Indicates that a type implements `Any` and `StableAbi`,
+    /// which allows unerasing it in a dynamic library/executable other than 
+    /// the one where it was erased,using its `StableTypeId`.
+    #[allow(non_camel_case_types)]
+    pub struct TU_StableUnerasable;
+
     /// Indicates that a type does not implement `Any`.
     #[allow(non_camel_case_types)]
     pub struct TU_Opaque;
@@ -44,6 +54,9 @@ pub mod unerasability{
     /// Whether the function returns `MaybeCmp::Just(typeid)` is determined by `Self`.
     pub trait GetUTID<T>{
         const UID:ReturnValueEquality<MaybeCmp<UTypeId>>;
+
+        /// Gets a function optionally returning the StableTypeId of `T`.
+        const STABLE_ID:ReturnValueEquality<MaybeCmp<StableTypeId>>;
     }
 
 
@@ -53,12 +66,29 @@ pub mod unerasability{
         const UID:ReturnValueEquality<MaybeCmp<UTypeId>>=ReturnValueEquality{
             function:some_utypeid::<T>
         };
+        const STABLE_ID:ReturnValueEquality<MaybeCmp<StableTypeId>>=ReturnValueEquality{
+            function:no_stable_type_id
+        };
+    }
+
+    impl<T> GetUTID<T> for TU_StableUnerasable
+    where T:StableAbi+'static
+    {
+        const UID:ReturnValueEquality<MaybeCmp<UTypeId>>=ReturnValueEquality{
+            function:some_utypeid::<T>
+        };
+        const STABLE_ID:ReturnValueEquality<MaybeCmp<StableTypeId>>=ReturnValueEquality{
+            function:some_stable_type_id::<T>
+        };
     }
 
     impl<T> GetUTID<T> for TU_Opaque{
         const UID:ReturnValueEquality<MaybeCmp<UTypeId>>=ReturnValueEquality{
             function:no_utypeid
         };
+        const STABLE_ID:ReturnValueEquality<MaybeCmp<StableTypeId>>=ReturnValueEquality{
+            function:no_stable_type_id
+        };
     }
 }
 
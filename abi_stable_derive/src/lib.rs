@@ -106,6 +106,19 @@ disabling it in Continuous Integration so that the
 binary compatibility of a dynamic library is checked at some point before releasing it.
 
 
+# Library metadata
+
+This attribute stores the `LibMetadata` of the library
+(the abi_stable version,and optionally the version and serialized layout of the root module)
+in the `.abi_stable_meta` section of ELF dynamic libraries,
+which can be read without loading the library with 
+`abi_stable::library::lib_metadata_from_path`.
+
+The metadata of the root module is only stored if the build script of the crate calls
+`RootModuleMetadata::new::<RootModuleType>().write_to_out_dir()`,
+which writes the file that this attribute includes.
+
+
 # More examples
 
 For a more detailed example look in the README in the repository for this crate.
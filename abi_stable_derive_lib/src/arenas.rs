@@ -76,6 +76,7 @@ declare_arenas! {
     tokenstream: proc_macro2::TokenStream,
     meta_attr: syn::Meta,
     expr: syn::Expr,
+    blocks: syn::Block,
     strings: String,
     paths: syn::Path,
 }
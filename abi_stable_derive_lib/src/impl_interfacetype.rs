@@ -186,13 +186,13 @@ usable_traits!{
     default=(Default,"::std::default::Default",false,UB::DYN_TRAIT),
     display=(Display,"::std::fmt::Display",false,UB::DYN_TRAIT),
     debug=(Debug,"::std::fmt::Debug",false,UB::ROBJECT_AND_DYN_TRAIT),
-    serialize=(Serialize,"::serde::Serialize",false,UB::DYN_TRAIT),
+    serialize=(Serialize,"::serde::Serialize",false,UB::ROBJECT_AND_DYN_TRAIT),
     eq=(Eq,"::std::cmp::Eq",false,UB::DYN_TRAIT),
     partial_eq=(PartialEq,"::std::cmp::PartialEq",false,UB::DYN_TRAIT),
     ord=(Ord,"::std::cmp::Ord",false,UB::DYN_TRAIT),
     partial_ord=(PartialOrd,"::std::cmp::PartialOrd",false,UB::DYN_TRAIT),
     hash=(Hash,"::std::hash::Hash",false,UB::DYN_TRAIT),
-    deserialize=(Deserialize,"::serde::Deserialize",false,UB::DYN_TRAIT),
+    deserialize=(Deserialize,"::serde::Deserialize",false,UB::ROBJECT_AND_DYN_TRAIT),
     send=(Send,"::std::marker::Send",true ,UB::ROBJECT_AND_DYN_TRAIT),
     sync=(Sync,"::std::marker::Sync",true ,UB::ROBJECT_AND_DYN_TRAIT),
     iterator=(Iterator,"::std::iter::Iterator",false,UB::DYN_TRAIT),
@@ -4,7 +4,16 @@ use syn::Ident;
 
 use proc_macro2::Span;
 
-use abi_stable_shared::mangled_root_module_loader_name;
+use abi_stable_shared::{
+    mangled_lib_metadata_name,
+    mangled_root_module_loader_name,
+    LIB_METADATA_FILE,
+};
+
+use std::{
+    env,
+    path::Path,
+};
 
 
 
@@ -59,9 +68,13 @@ fn mangle_library_getter_inner(mut input:ItemFn)->TokenStream2{
         Span::call_site(),
     );
 
+    let lib_metadata=lib_metadata_tokens(has_root_module_metadata(),vis);
+
     quote!(
         #input
 
+        #lib_metadata
+
         #[no_mangle]
         #vis static #export_name:abi_stable::library::LibHeader={
             use abi_stable::{
@@ -96,6 +109,47 @@ fn mangle_library_getter_inner(mut input:ItemFn)->TokenStream2{
 }
 
 
+/// Whether the build script of the crate being compiled wrote the `RootModuleMetadata`.
+fn has_root_module_metadata()->bool{
+    env::var_os("OUT_DIR")
+        .map_or(false,|out_dir| Path::new(&out_dir).join(LIB_METADATA_FILE).exists() )
+}
+
+
+/// The static that stores the `LibMetadata` of the library in the metadata section.
+fn lib_metadata_tokens(has_root_module_metadata:bool,vis:&syn::Visibility)->TokenStream2{
+    let metadata_name=Ident::new(
+        &mangled_lib_metadata_name(),
+        Span::call_site(),
+    );
+
+    let root_module_bytes=if has_root_module_metadata {
+        let file=format!("/{}",LIB_METADATA_FILE);
+        quote!( include!(concat!(env!("OUT_DIR"),#file)); )
+    }else{
+        quote!(
+            pub const LEN:usize=0;
+            pub const BYTES:[u8;LEN]=[];
+        )
+    };
+
+    quote!(
+        #[cfg(not(any(target_os="macos",target_os="ios",windows)))]
+        mod _sabi_lib_metadata{
+            #root_module_bytes
+        }
+
+        #[cfg(not(any(target_os="macos",target_os="ios",windows)))]
+        #[no_mangle]
+        #[used]
+        #[link_section=".abi_stable_meta"]
+        #vis static #metadata_name:
+            abi_stable::library::LibMetadataSection<[u8;_sabi_lib_metadata::LEN]>=
+                abi_stable::library::LibMetadataSection::new(_sabi_lib_metadata::BYTES);
+    )
+}
+
+
 
 #[cfg(test)]
 mod tests{
@@ -152,4 +206,22 @@ mod tests{
             assert!(str_out.contains(expected_const));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_lib_metadata(){
+        let vis=syn::parse_str::<syn::Visibility>("pub").unwrap();
+
+        for &has_root_module in &[false,true] {
+            let str_out=lib_metadata_tokens(has_root_module,&vis).to_string()
+                .chars()
+                .filter(|c|!c.is_whitespace())
+                .collect::<String>();
+            assert!(str_out.contains("#[link_section=\".abi_stable_meta\"]"));
+            assert!(str_out.contains(&mangled_lib_metadata_name()));
+            assert_eq!(
+                str_out.contains("\"/abi_stable_lib_metadata.rs\""),
+                has_root_module,
+            );
+        }
+    }
+}
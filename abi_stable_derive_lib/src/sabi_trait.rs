@@ -4,6 +4,7 @@ use crate::{
     parse_utils::{parse_str_as_ident},
     my_visibility::{MyVisibility,RelativeVis},
     gen_params_in::{GenParamsIn,InWhat},
+    impl_interfacetype::WhichTrait,
     workaround::token_stream_to_string,
 };
 
@@ -18,6 +19,7 @@ use syn::{
 };
 
 mod attribute_parsing;
+mod class_vtable;
 mod common_tokens;
 mod impl_delegations;
 mod method_where_clause;
@@ -25,6 +27,7 @@ mod lifetime_unelider;
 mod replace_self_path;
 mod trait_definition;
 mod methods_tokenizer;
+mod upcasting;
 
 #[cfg(test)]
 mod tests;
@@ -55,6 +58,7 @@ struct TokenizerParams<'a>{
     trait_to:&'a syn::Ident,
     trait_backend:&'a syn::Ident,
     trait_interface:&'a syn::Ident,
+    trait_class:&'a syn::Ident,
 }
 
 
@@ -82,6 +86,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> TokenStream2 {
     let trait_to    =&parse_str_as_ident(&format!("{}_TO",trait_ident));
     let trait_backend=&parse_str_as_ident(&format!("{}_Backend",trait_ident));
     let trait_interface=&parse_str_as_ident(&format!("{}_Interface",trait_ident));
+    let trait_class=&parse_str_as_ident(&format!("{}_Class",trait_ident));
     
     let mut mod_contents=TokenStream2::default();
 
@@ -99,6 +104,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> TokenStream2 {
         trait_to    ,
         trait_backend,
         trait_interface,
+        trait_class,
     };
 
     first_items(tokenizer_params,&mut mod_contents);
@@ -115,6 +121,12 @@ pub fn derive_sabi_trait(item: ItemTrait) -> TokenStream2 {
 
     impl_delegations::delegated_impls(tokenizer_params,&mut mod_contents);
 
+    upcasting::upcasting_items(tokenizer_params,&mut mod_contents);
+
+    class_vtable::class_vtable_items(tokenizer_params,&mut mod_contents);
+
+    let reexported_class=Some(trait_class).filter(|_| trait_def.has_static_items() );
+
     quote!(
         #[doc(inline)]
         #vis use self::#generated_mod::{
@@ -122,6 +134,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> TokenStream2 {
             #trait_interface,
             #trait_ident,
             #trait_backend,
+            #( #reexported_class, )*
         };
 
         #[allow(explicit_outlives_requirements)]
@@ -299,7 +312,7 @@ There are extra methods on the `obj` field.
             #(#where_preds)*
         {
             #submod_vis obj:#used_trait_object,
-            _marker:__sabi_re::UnsafeIgnoredType< __sabi_re::#send_syncness >,
+            _marker:__sabi_re::PhantomData<__sabi_re::UnsafeIgnoredType< __sabi_re::#send_syncness >>,
         }
 
         const __inside_generated_mod:()={
@@ -327,12 +340,14 @@ There are extra methods on the `obj` field.
 
 
 fn constructor_items<'a>(
-    TokenizerParams{
-        ctokens,totrait_def,submod_vis,trait_ident,trait_to,trait_backend,trait_interface,
-        ..
-    }:TokenizerParams,
+    param:TokenizerParams,
     mod_:&mut TokenStream2,
 ){
+    let TokenizerParams{
+        ctokens,totrait_def,submod_vis,trait_ident,trait_to,trait_backend,trait_interface,
+        ..
+    }=param;
+
     let trait_params=totrait_def.generics_tokenizer(
         InWhat::ItemUse,
         WithAssocTys::No,
@@ -438,11 +453,35 @@ fn constructor_items<'a>(
             &ctokens.ts_empty,
         );
 
+    let user_supertraits=&totrait_def.user_supertraits;
+    let supertrait_ptr_bounds=user_supertraits.iter().map(|x|{
+        let interface=&x.interface;
+        quote!(
+            #interface:__sabi_re::GetSabiTraitVTable<
+                Erasability,_OrigPtr::Target,_ErasedPtr,_OrigPtr
+            >,
+        )
+    });
+    let supertrait_value_bounds=user_supertraits.iter().map(|x|{
+        let interface=&x.interface;
+        quote!(
+            #interface:__sabi_re::GetSabiTraitVTable<
+                Erasability,_Self,__sabi_re::RBox<()>,__sabi_re::RBox<_Self>
+            >,
+        )
+    });
+
+    let class_ptr_bounds=class_vtable::class_vtable_bounds(
+        param,
+        &quote!(Erasability),
+        &quote!(_OrigPtr::Target),
+    );
+
     let shared_docs=format!(
         "
 `erasability` describes whether the trait object can be \
 converted back into the original type or not.
-Its possible values are `TU_Unerasable` and `TU_Opaque`.
+Its possible values are `TU_Unerasable`,`TU_StableUnerasable`,and `TU_Opaque`.
 "
     );
 
@@ -476,6 +515,8 @@ Its possible values are `TU_Unerasable` and `TU_Opaque`.
                         Erasability,_OrigPtr::Target,_ErasedPtr,_OrigPtr
                     >,
                 #extra_constraints_ptr
+                #( #supertrait_ptr_bounds )*
+                #class_ptr_bounds
             {
                 let _erasability=erasability;
                 unsafe{
@@ -484,7 +525,7 @@ Its possible values are `TU_Unerasable` and `TU_Opaque`.
                             ptr,
                             MakeVTable::<#make_vtable_args>::VTABLE
                         ),
-                        _marker:__sabi_re::UnsafeIgnoredType::DEFAULT,
+                        _marker:__sabi_re::PhantomData,
                     }
                 }
             }
@@ -493,7 +534,7 @@ Its possible values are `TU_Unerasable` and `TU_Opaque`.
             #submod_vis fn from_sabi(obj:#trait_backend<#uto_params_use>)->Self{
                 Self{
                     obj,
-                    _marker:__sabi_re::UnsafeIgnoredType::DEFAULT,
+                    _marker:__sabi_re::PhantomData,
                 }
             }
         }
@@ -511,6 +552,7 @@ Its possible values are `TU_Unerasable` and `TU_Opaque`.
                         Erasability,_Self,__sabi_re::RBox<()>,__sabi_re::RBox<_Self>
                     >,
                 #extra_constraints_value
+                #( #supertrait_value_bounds )*
             {
                 Self::from_ptr::<
                     __sabi_re::RBox<_Self>,
@@ -536,9 +578,21 @@ fn trait_and_impl<'a>(
     let lifetime_bounds=&*trait_def.lifetime_bounds;
     let super_traits_a=trait_def.impld_traits.iter().map(|t| &t.bound );
     let super_traits_b=super_traits_a.clone();
+    let user_supertraits_a=trait_def.user_supertraits.iter().map(|t| t.bound );
+    let user_supertraits_b=user_supertraits_a.clone();
 
     let assoc_tys_a=trait_def.assoc_tys.values().map(|x| &x.assoc_ty );
-    
+    let assoc_consts=trait_def.assoc_consts.iter().map(|x| x.item );
+    let static_methods=trait_def.static_methods.iter().map(|method|{
+        let other_attrs=method.other_attrs;
+        let signature=&method.item.sig;
+        let default_=&method.item.default;
+        let semicolon=&method.item.semi_token;
+        quote!(
+            #( #[#other_attrs] )*
+            #signature #default_ #semicolon
+        )
+    });
 
     let erased_ptr_bounds=trait_def.erased_ptr_preds();
 
@@ -548,16 +602,26 @@ fn trait_and_impl<'a>(
         #( #[#other_attrs] )*
         #submod_vis trait #trait_ident<
             #gen_params_trait
-        >: #( #super_traits_a + )* #(#lifetime_bounds+)*
+        >: #( #super_traits_a + )* #( #user_supertraits_a + )* #(#lifetime_bounds+)*
         where 
             #(#where_preds,)*
         {
             #( #assoc_tys_a )*
 
+            #( #assoc_consts )*
+
+            #( #static_methods )*
+
             #methods_tokenizer_def
         }
     ).to_tokens(mod_);
 
+    // The trait object can't implement the static methods/associated constants,
+    // those are accessible through the `Trait_Class` of the trait object instead.
+    if trait_def.has_static_items() {
+        return;
+    }
+
 
     let gen_params_header=
         trait_def.generics_tokenizer(
@@ -585,7 +649,7 @@ fn trait_and_impl<'a>(
         impl<#gen_params_header> #trait_ident<#gen_params_use_trait> 
         for #trait_to<#gen_params_use_to>
         where
-            Self:#( #super_traits_b + )* #(#lifetime_bounds+)* Sized ,
+            Self:#( #super_traits_b + )* #( #user_supertraits_b + )* #(#lifetime_bounds+)* Sized ,
             #erased_ptr_bounds
             #(#where_preds,)*
         {
@@ -605,7 +669,13 @@ fn methods_impls<'a>(
     
     let impl_where_preds=totrait_def.trait_impl_where_preds();
 
-    let super_traits_a=totrait_def.impld_traits.iter().map(|t| &t.bound );
+    // Deserialize is not required here,
+    // since it would require every pointer type to be deserializable
+    // for the inherent methods to be callable.
+    let super_traits_a=totrait_def.impld_traits.iter()
+        .filter(|t| t.which_trait!=WhichTrait::Deserialize )
+        .map(|t| &t.bound );
+    let user_supertraits_a=totrait_def.user_supertraits.iter().map(|t| t.bound );
     
     let lifetime_bounds=&*totrait_def.lifetime_bounds;
     
@@ -627,7 +697,7 @@ fn methods_impls<'a>(
     quote!(
         impl<#gen_params_header> #trait_to<#gen_params_use_to>
         where 
-            Self:#( #super_traits_a + )* #( #lifetime_bounds+ )* Sized ,
+            Self:#( #super_traits_a + )* #( #user_supertraits_a + )* #( #lifetime_bounds+ )* Sized ,
             #impl_where_preds
         {
             #methods_tokenizer_def
@@ -637,7 +707,7 @@ fn methods_impls<'a>(
 
 
 fn declare_vtable<'a>(
-    TokenizerParams{ctokens,vtable_trait_decl,submod_vis,trait_interface,..}:TokenizerParams,
+    TokenizerParams{ctokens,trait_def,vtable_trait_decl,submod_vis,trait_interface,..}:TokenizerParams,
     mod_:&mut TokenStream2,
 ){
     
@@ -664,18 +734,7 @@ fn declare_vtable<'a>(
 
     let methods_tokenizer=vtable_trait_decl.methods_tokenizer(WhichItem::VtableDecl);
 
-    let lifetime_bounds=if vtable_trait_decl.lifetime_bounds.is_empty() {
-        None
-    }else{
-        use std::fmt::Write;
-        let mut lifetime_bounds=String::with_capacity(32);
-        lifetime_bounds.push_str("_Self:");
-        for lt in &*vtable_trait_decl.lifetime_bounds {
-            let _=write!(lifetime_bounds,"{}+",lt);
-        }
-        lifetime_bounds.push_str("Sized");
-        Some(lifetime_bounds)
-    };
+    let lifetime_bounds=uself_lifetime_bounds(vtable_trait_decl);
 
     let trait_interface_use=
         vtable_trait_decl.generics_tokenizer(
@@ -692,6 +751,40 @@ fn declare_vtable<'a>(
     );
     let vtable_bound=format!("{}: ::abi_stable::StableAbi",(&robject_vtable).into_token_stream());
 
+    let supertrait_vtables=vtable_trait_decl.user_supertraits.iter()
+        .map(|x|{
+            let interface=&x.interface;
+            quote!(
+                __sabi_re::StaticRef<
+                    <#interface as __sabi_re::SabiTraitInterface<_ErasedPtr>>::VTable
+                >
+            )
+        })
+        .collect::<Vec<TokenStream2>>();
+    let supertrait_vtable_bounds=supertrait_vtables.iter()
+        .map(|x| format!("{}: ::abi_stable::StableAbi",x.into_token_stream()) );
+    let supertrait_fields=vtable_trait_decl.user_supertraits.iter()
+        .zip(&supertrait_vtables)
+        .map(|(x,vtable_ty)|{
+            let field=&x.vtable_field;
+            quote!( #submod_vis #field:#vtable_ty, )
+        });
+
+    let class_vtable=if trait_def.has_static_items() {
+        let class_vtable_args=vtable_trait_decl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &ctokens.ts_unit,
+        );
+        Some(quote!( __sabi_re::StaticRef<ClassVTable<#class_vtable_args>> ))
+    }else{
+        None
+    };
+    let class_vtable_bound=class_vtable.as_ref()
+        .map(|x| format!("{}: ::abi_stable::StableAbi",x.into_token_stream()) );
+    let class_field=class_vtable.as_ref()
+        .map(|x| quote!( #submod_vis _sabi_class:#x, ) );
+
     quote!(
         #[repr(C)]
         #[derive(abi_stable::StableAbi)]
@@ -699,6 +792,8 @@ fn declare_vtable<'a>(
         #[sabi(missing_field(panic))]
         #( #[sabi(prefix_bound=#lifetime_bounds)] )*
         #[sabi(bound=#vtable_bound)]
+        #( #[sabi(bound=#supertrait_vtable_bounds)] )*
+        #( #[sabi(bound=#class_vtable_bound)] )*
         #(#[#derive_attrs])*
         #submod_vis struct VTableVal<#generics_decl>{
             _sabi_tys: ::std::marker::PhantomData<
@@ -707,6 +802,10 @@ fn declare_vtable<'a>(
 
             _sabi_vtable:#robject_vtable,
 
+            #( #supertrait_fields )*
+
+            #class_field
+
             #methods_tokenizer
         }
     ).to_tokens(mod_);
@@ -715,9 +814,11 @@ fn declare_vtable<'a>(
 
 
 fn vtable_impl<'a>(
-    TokenizerParams{ctokens,vtable_trait_impl,trait_ident,trait_interface,..}:TokenizerParams,
+    param:TokenizerParams,
     mod_:&mut TokenStream2,
 ){
+    let TokenizerParams{ctokens,trait_def,vtable_trait_impl,trait_ident,trait_interface,..}=param;
+
     let struct_decl_generics=
         vtable_trait_impl.generics_tokenizer(
             InWhat::ItemDecl,
@@ -779,6 +880,22 @@ fn vtable_impl<'a>(
 
     let methods_tokenizer=vtable_trait_impl.methods_tokenizer(WhichItem::VtableImpl);
 
+    let supertrait_interfaces_a=vtable_trait_impl.user_supertraits.iter().map(|x| &x.interface );
+    let supertrait_interfaces_b=supertrait_interfaces_a.clone();
+    let supertrait_fields=vtable_trait_impl.user_supertraits.iter().map(|x| &x.vtable_field );
+
+    let class_bounds=class_vtable::class_vtable_bounds(param,&quote!(IA),&quote!(_Self));
+    let class_field=if trait_def.has_static_items() {
+        let makeclass_generics=vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &ctokens.ts_class_params,
+        );
+        Some(quote!( _sabi_class:MakeClassVTable::<#makeclass_generics>::VTABLE, ))
+    }else{
+        None
+    };
+
     quote!(
         struct MakeVTable<#struct_decl_generics>(#dummy_struct_tys);
 
@@ -788,6 +905,11 @@ fn vtable_impl<'a>(
             _Self:#trait_ident<#trait_generics>,
             #trait_interface<#trait_interface_use>:
                 __sabi_re::GetRObjectVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
+            #(
+                #supertrait_interfaces_a:
+                    __sabi_re::GetSabiTraitVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
+            )*
+            #class_bounds
         {
             const TMP0: *const __sabi_re::WithMetadata<
                 VTableVal<#withmetadata_generics>
@@ -795,6 +917,13 @@ fn vtable_impl<'a>(
                 let __vtable=VTableVal{
                     _sabi_tys: ::std::marker::PhantomData,
                     _sabi_vtable:__sabi_re::GetRObjectVTable::ROBJECT_VTABLE,
+                    #(
+                        #supertrait_fields:<
+                            #supertrait_interfaces_b as 
+                            __sabi_re::GetSabiTraitVTable<IA,_Self,_ErasedPtr,_OrigPtr>
+                        >::SABI_TRAIT_VTABLE,
+                    )*
+                    #class_field
                     #(
                         #method_names_a:Self::#method_names_b,
                     )*
@@ -820,6 +949,24 @@ fn vtable_impl<'a>(
 
 
 
+/// Returns the `_Self:'a+'b+Sized` bound for the lifetimes that `_Self` must outlive,
+/// used in the `#[sabi(prefix_bound="...")]` attribute of vtables.
+fn uself_lifetime_bounds(trait_def:&TraitDefinition<'_>)->Option<String>{
+    if trait_def.lifetime_bounds.is_empty() {
+        None
+    }else{
+        use std::fmt::Write;
+        let mut lifetime_bounds=String::with_capacity(32);
+        lifetime_bounds.push_str("_Self:");
+        for lt in &*trait_def.lifetime_bounds {
+            let _=write!(lifetime_bounds,"{}+",lt);
+        }
+        lifetime_bounds.push_str("Sized");
+        Some(lifetime_bounds)
+    }
+}
+
+
 #[derive(Debug,Clone,PartialEq,Eq)]
 pub(crate) enum SelfParam<'a>{
     ByRef{
@@ -834,6 +981,8 @@ pub(crate) enum SelfParam<'a>{
 pub(crate) enum WhichItem{
     Trait,
     TraitImpl,
+    /// The impl of the trait for the trait objects of its subtraits.
+    SubtraitObjectImpl,
     TraitObjectImpl,
     VtableDecl,
     VtableImpl,
@@ -40,6 +40,7 @@ impl<'a> SabiTraitOptions<'a> {
                     this.attrs,
                     this.methods_with_attrs,
                     this.which_object,
+                    this.sabi_supertraits,
                     arenas,
                     ctokens,
                 ),
@@ -90,6 +91,7 @@ struct SabiTraitAttrs<'a> {
     attrs:OwnedDeriveAndOtherAttrs,
     methods_with_attrs:Vec<MethodWithAttrs<'a>>,
     which_object:WhichObject,
+    sabi_supertraits:Vec<Ident>,
 }
 
 
@@ -227,6 +229,17 @@ fn parse_sabi_trait_attr<'a>(
         if word=="use_dyntrait"||word=="use_dyn_trait" => {
             this.which_object=WhichObject::DynTrait;
         }
+        (ParseContext::TraitAttr{..}, Meta::List(list))if list.ident=="sabi_supertraits" => {
+            with_nested_meta("sabi_supertraits", list.nested, |attr|{
+                match attr {
+                    Meta::Word(word)=>this.sabi_supertraits.push(word),
+                    attr=>panic!(
+                        "\nExpected the name of a #[sabi_trait] supertrait,found:\n\t{}\n\n",
+                        attr.into_token_stream(),
+                    ),
+                }
+            });
+        }
         (ParseContext::TraitAttr{..}, attr) => {
             this.attrs.derive_attrs.push(attr);
         }
@@ -0,0 +1,511 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use quote::{ToTokens,quote,quote_spanned};
+
+use crate::{
+    gen_params_in::{InWhat},
+    sabi_trait::{
+        uself_lifetime_bounds,
+        WithAssocTys,
+        TokenizerParams,
+    },
+};
+
+
+/// Generates the class vtable of the trait,
+/// which stores the static methods and associated constants of the trait,
+/// as well as the `Trait_Class` type that wraps it.
+pub(super) fn class_vtable_items<'a>(
+    params:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    if !params.trait_def.has_static_items() {
+        return;
+    }
+
+    declare_class_vtable(params,mod_);
+    class_vtable_impl(params,mod_);
+    class_items(params,mod_);
+}
+
+
+/// The bounds required to construct the class vtable of the trait,
+/// in which `_Self` is `self_ty` and the `IA` parameter is `erasability`.
+///
+/// This is empty if the trait has no static methods or associated constants.
+pub(super) fn class_vtable_bounds<'a>(
+    TokenizerParams{ctokens,trait_def,trait_interface,..}:TokenizerParams<'a>,
+    erasability:&TokenStream2,
+    self_ty:&TokenStream2,
+)->TokenStream2{
+    if !trait_def.has_static_items() {
+        return TokenStream2::new();
+    }
+
+    let trait_interface_use=trait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_empty,
+    );
+    let supertrait_bounds=trait_def.user_supertraits.iter().map(|x|{
+        let interface=&x.interface;
+        quote!(
+            #interface:
+                __sabi_re::GetSabiTraitVTable<
+                    #erasability,#self_ty,__sabi_re::RBox<()>,__sabi_re::RBox<#self_ty>
+                >,
+        )
+    });
+
+    quote!(
+        #trait_interface<#trait_interface_use>:
+            __sabi_re::GetRObjectVTable<
+                #erasability,#self_ty,__sabi_re::RBox<()>,__sabi_re::RBox<#self_ty>
+            >,
+        #( #supertrait_bounds )*
+    )
+}
+
+
+fn declare_class_vtable<'a>(
+    TokenizerParams{ctokens,vtable_trait_decl,submod_vis,..}:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    let generics_decl=vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::No,
+        &ctokens.ts_uself,
+    );
+
+    let mut generics_use0=vtable_trait_decl.generics_tokenizer(
+        InWhat::DummyStruct,
+        WithAssocTys::No,
+        &ctokens.ts_uself,
+    );
+    generics_use0.set_no_bounds();
+
+    let rbox_vtable_args=vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_unit_rbox,
+    );
+
+    let lifetime_bounds=uself_lifetime_bounds(vtable_trait_decl);
+
+    let const_fields=vtable_trait_decl.assoc_consts.iter().map(|assoc_const|{
+        let field=&assoc_const.vtable_field;
+        let ty=assoc_const.ty;
+        quote!( #submod_vis #field:#ty, )
+    });
+
+    let method_fields=vtable_trait_decl.static_methods.iter().map(|method|{
+        let derive_attrs=method.derive_attrs;
+        let name=method.name;
+        let lifetimes=Some(&method.lifetimes).filter(|l| !l.is_empty() );
+        let param_names=method.params.iter().map(|x| x.name );
+        let param_tys=method.params.iter().map(|x| &x.ty );
+        let return_ty=if method.returns_self {
+            Some(quote!( __sabi_re::RBox<()> ))
+        }else{
+            method.output.map(|x| x.into_token_stream() )
+        };
+        quote_spanned!(name.span()=>
+            #(#[#derive_attrs])*
+            #submod_vis #name:
+                #(for< #(#lifetimes,)* >)*
+                unsafe extern "C" fn( #( #param_names:#param_tys, )* ) #(-> #return_ty)*,
+        )
+    });
+
+    quote!(
+        #[repr(C)]
+        #[derive(abi_stable::StableAbi)]
+        #[sabi(kind(Prefix(prefix_struct="ClassVTable")))]
+        #[sabi(missing_field(panic))]
+        #( #[sabi(prefix_bound=#lifetime_bounds)] )*
+        #submod_vis struct ClassVTableVal<#generics_decl>{
+            _sabi_tys: ::std::marker::PhantomData<
+                extern "C" fn(#generics_use0)
+            >,
+
+            // This is opaque because its `StableAbi` bounds would be cyclic
+            // (`VTable` contains a reference to this vtable).
+            // It is the same vtable that this class vtable is stored in,
+            // for `RBox<()>` instead of the pointer of the trait object.
+            #[sabi(unsafe_opaque_field)]
+            _sabi_rbox_vtable:extern "C" fn()->__sabi_re::StaticRef<VTable<#rbox_vtable_args>>,
+
+            #( #const_fields )*
+
+            #( #method_fields )*
+        }
+    ).to_tokens(mod_);
+}
+
+
+fn class_vtable_impl<'a>(
+    params:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    let TokenizerParams{ctokens,vtable_trait_impl,trait_ident,..}=params;
+
+    let struct_decl_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::No,
+        &ctokens.ts_class_params,
+    );
+
+    let dummy_struct_tys=vtable_trait_impl.generics_tokenizer(
+        InWhat::DummyStruct,
+        WithAssocTys::No,
+        &ctokens.ts_class_params,
+    );
+
+    let impl_header_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_class_params,
+    );
+
+    let makeclass_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_class_params,
+    );
+
+    let trait_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.empty_ts
+    );
+
+    let withmetadata_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_uself,
+    );
+
+    let class_vtable_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_unit,
+    );
+
+    let rbox_vtable_args=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_unit_rbox,
+    );
+
+    let makevtable_rbox_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_rbox_getvtable_params,
+    );
+
+    let class_bounds=class_vtable_bounds(params,&quote!(IA),&quote!(_Self));
+
+    let const_fields=vtable_trait_impl.assoc_consts.iter().map(|assoc_const|{
+        let field=&assoc_const.vtable_field;
+        let name=assoc_const.name;
+        quote!( #field:<_Self as __Trait<#trait_generics>>::#name, )
+    });
+
+    let method_names_a=vtable_trait_impl.static_methods.iter().map(|m| m.name );
+    let method_names_b=method_names_a.clone();
+
+    let methods=vtable_trait_impl.static_methods.iter().map(|method|{
+        let name=method.name;
+        let lifetimes=Some(&method.lifetimes).filter(|l| !l.is_empty() );
+        let param_names_a=method.params.iter().map(|x| x.name );
+        let param_names_b=param_names_a.clone();
+        let param_tys=method.params.iter().map(|x| &x.ty );
+        let unsafety=method.unsafety;
+
+        let (return_ty,call)=if method.returns_self {
+            (
+                Some(quote!( __sabi_re::RBox<()> )),
+                quote_spanned!(name.span()=>
+                    __sabi_re::sabi_erased_box(
+                        #unsafety{
+                            <_Self as __Trait<#trait_generics>>::#name(#(#param_names_a,)*)
+                        }
+                    )
+                ),
+            )
+        }else{
+            (
+                method.output.map(|x| x.into_token_stream() ),
+                quote_spanned!(name.span()=>
+                    #unsafety{
+                        <_Self as __Trait<#trait_generics>>::#name(#(#param_names_a,)*)
+                    }
+                ),
+            )
+        };
+
+        quote_spanned!(name.span()=>
+            unsafe extern "C" fn #name #(< #(#lifetimes,)* >)* (
+                #( #param_names_b:#param_tys, )*
+            ) #(-> #return_ty)* {
+                ::abi_stable::extern_fn_panic_handling!{no_early_return;
+                    #call
+                }
+            }
+        )
+    });
+
+    quote!(
+        struct MakeClassVTable<#struct_decl_generics>(#dummy_struct_tys);
+
+
+        impl<#impl_header_generics> MakeClassVTable<#makeclass_generics>
+        where
+            _Self:#trait_ident<#trait_generics>,
+            #class_bounds
+        {
+            const TMP0: *const __sabi_re::WithMetadata<
+                ClassVTableVal<#withmetadata_generics>
+            >={
+                let __vtable=ClassVTableVal{
+                    _sabi_tys: ::std::marker::PhantomData,
+                    _sabi_rbox_vtable:Self::_sabi_rbox_vtable,
+                    #( #const_fields )*
+                    #(
+                        #method_names_a:Self::#method_names_b,
+                    )*
+                };
+                &__sabi_re::WithMetadata::new(
+                    __sabi_re::PrefixTypeTrait::METADATA,
+                    __vtable
+                )
+            };
+
+            const VTABLE:__sabi_re::StaticRef<ClassVTable<#class_vtable_generics>>=unsafe{
+                let __vtable=__sabi_re::StaticRef::from_raw(Self::TMP0);
+                __sabi_re::WithMetadata::staticref_as_prefix(__vtable)
+                    .transmute_ref()
+            };
+
+            extern "C" fn _sabi_rbox_vtable()->__sabi_re::StaticRef<VTable<#rbox_vtable_args>>{
+                MakeVTable::<#makevtable_rbox_generics>::VTABLE
+            }
+
+            #( #methods )*
+        }
+    ).to_tokens(mod_);
+}
+
+
+fn class_items<'a>(
+    params:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    let TokenizerParams{
+        ctokens,totrait_def,submod_vis,trait_ident,trait_to,trait_backend,trait_class,
+        ..
+    }=params;
+
+    let class_decl=totrait_def.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::No,
+        &ctokens.ts_lt,
+    );
+    let class_header=totrait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_lt,
+    );
+    let class_use=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_lt,
+    );
+    let class_vtable_args=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_unit,
+    );
+    let makeclass_generics=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_erasability_uself,
+    );
+    let trait_generics=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.empty_ts
+    );
+    let to_header=totrait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_lt_erasedptr,
+    );
+    let to_use=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_lt_erasedptr,
+    );
+    let to_rbox_use=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_lt_rbox,
+    );
+    let where_preds=&totrait_def.where_preds;
+    let where_preds_b=where_preds;
+    let where_preds_c=where_preds;
+
+    let class_vtable=quote!( __sabi_re::StaticRef<ClassVTable<#class_vtable_args>> );
+    let class_vtable_bound=format!(
+        "{}: ::abi_stable::StableAbi",
+        (&class_vtable).into_token_stream()
+    );
+
+    let class_bounds=class_vtable_bounds(params,&quote!(Erasability),&quote!(_Self));
+
+    let consts=totrait_def.assoc_consts.iter().map(|assoc_const|{
+        let name=assoc_const.name;
+        let field=&assoc_const.vtable_field;
+        let ty=assoc_const.ty;
+        let docs=format!("Gets the value of the `{}` associated constant.",name);
+        quote_spanned!(name.span()=>
+            #[doc=#docs]
+            #[allow(non_snake_case)]
+            #submod_vis fn #name(&self)->#ty{
+                self.vtable.get().#field()
+            }
+        )
+    });
+
+    let methods=totrait_def.static_methods.iter().map(|method|{
+        let name=method.name;
+        let other_attrs=method.other_attrs;
+        let lifetimes=Some(&method.lifetimes).filter(|l| !l.is_empty() );
+        let param_names_a=method.params.iter().map(|x| x.name );
+        let param_names_b=param_names_a.clone();
+        let param_tys=method.params.iter().map(|x| &x.ty );
+        let unsafety=method.unsafety;
+
+        let (return_ty,body)=if method.returns_self {
+            (
+                Some(quote!( #trait_to<#to_rbox_use> )),
+                quote_spanned!(name.span()=>
+                    unsafe{
+                        let ptr=__method(#(#param_names_a,)*);
+                        let vtable=(self.vtable.get()._sabi_rbox_vtable())();
+                        #trait_to::from_sabi(
+                            #trait_backend::with_vtable::<__sabi_re::RBox<()>>(ptr,vtable)
+                        )
+                    }
+                ),
+            )
+        }else{
+            (
+                method.output.map(|x| x.into_token_stream() ),
+                quote_spanned!(name.span()=>
+                    unsafe{
+                        __method(#(#param_names_a,)*)
+                    }
+                ),
+            )
+        };
+
+        quote_spanned!(name.span()=>
+            #(#[#other_attrs])*
+            #submod_vis #unsafety fn #name #(< #(#lifetimes,)* >)* (
+                &self,
+                #( #param_names_b:#param_tys, )*
+            ) #(-> #return_ty)* {
+                let __method=self.vtable.get().#name();
+                #body
+            }
+        )
+    });
+
+    let trait_class_docs=format!(
+        "\
+The class of a type that implements `{trait_}`,
+with its static methods and associated constants.
+
+This can be constructed with `{class}::from_type`,
+or gotten from a trait object with `{trait_to}::sabi_class`.
+",
+        trait_=trait_ident,
+        class=trait_class,
+        trait_to=trait_to,
+    );
+
+    let from_type_docs=format!(
+        "Constructs the class of `_Self`,a type that implements `{trait_}`.
+
+`erasability` describes whether the trait objects returned by the static methods can be \
+converted back into the original type or not.
+Its possible values are `TU_Unerasable`,`TU_StableUnerasable`,and `TU_Opaque`.
+",
+        trait_=trait_ident,
+    );
+
+    quote!(
+        #[doc=#trait_class_docs]
+        #[repr(C)]
+        #[derive(::abi_stable::StableAbi)]
+        #[sabi(bound=#class_vtable_bound)]
+        #submod_vis struct #trait_class<#class_decl>
+        where
+            #(#where_preds,)*
+        {
+            vtable:#class_vtable,
+            _marker:__sabi_re::PhantomData<&'lt ()>,
+        }
+
+        impl<#class_header> Clone for #trait_class<#class_use>
+        where
+            #(#where_preds_b,)*
+        {
+            fn clone(&self)->Self{
+                *self
+            }
+        }
+
+        impl<#class_header> Copy for #trait_class<#class_use>
+        where
+            #(#where_preds_b,)*
+        {}
+
+        impl<#class_header> #trait_class<#class_use>
+        where
+            #(#where_preds_b,)*
+        {
+            #[doc=#from_type_docs]
+            #submod_vis fn from_type<_Self,Erasability>(erasability:Erasability)->Self
+            where
+                _Self:#trait_ident<#trait_generics>+'lt,
+                #class_bounds
+            {
+                let _erasability=erasability;
+                Self{
+                    vtable:MakeClassVTable::<#makeclass_generics>::VTABLE,
+                    _marker:__sabi_re::PhantomData,
+                }
+            }
+
+            #( #consts )*
+
+            #( #methods )*
+        }
+
+        impl<#to_header> #trait_to<#to_use>
+        where
+            #(#where_preds_c,)*
+        {
+            /// Gets the class of the type that this trait object was constructed from,
+            /// with its static methods and associated constants.
+            #submod_vis fn sabi_class(&self)->#trait_class<#class_use>{
+                #trait_class{
+                    vtable:self.obj.sabi_et_vtable()._sabi_class(),
+                    _marker:__sabi_re::PhantomData,
+                }
+            }
+        }
+    ).to_tokens(mod_);
+}
@@ -126,8 +126,15 @@ declare_common_tokens! {
         ts_staticlt_erasedptr="'static,_ErasedPtr,",
         ts_self_erasedptr="_Self,_ErasedPtr,",
         ts_unit_erasedptr="(),_ErasedPtr,",
+        ts_unit="(),",
+        ts_unit_rbox="(),__sabi_re::RBox<()>,",
+        ts_class_params="IA,_Self,",
+        ts_erasability_uself="Erasability,_Self,",
+        ts_rbox_getvtable_params="IA,_Self,__sabi_re::RBox<()>,__sabi_re::RBox<_Self>,",
 
         ts_getvtable_params="IA,_Self,_ErasedPtr,_OrigPtr,",
+        ts_usub="__Sub,",
+        ts_lt_erasedptr_usub="'lt,_ErasedPtr,__Sub,",
         missing_field_option="#[sabi(missing_field(option))]",
     ]
 
@@ -1,11 +1,15 @@
 use proc_macro2::TokenStream as TokenStream2;
 
-use quote::{ToTokens, quote_spanned};
+use quote::{ToTokens, quote, quote_spanned};
+
+use syn::token::Comma;
 
 
 
 use crate::{
+    arenas::AllocMethods,
     gen_params_in::{InWhat},
+    to_token_fn::ToTokenFnMut,
     sabi_trait::{
         WhichSelf,
         WithAssocTys,
@@ -17,7 +21,7 @@ use crate::{
 /// to the wrapped DynTrait or RObject.
 pub(super) fn delegated_impls<'a>(
     TokenizerParams{
-        arenas:_,ctokens,totrait_def,trait_to,trait_backend,trait_interface,
+        arenas,ctokens,totrait_def,trait_to,trait_backend,trait_interface,
         ..
     }:TokenizerParams<'a>,
     mod_:&mut TokenStream2,
@@ -27,13 +31,6 @@ pub(super) fn delegated_impls<'a>(
     let impls=totrait_def.trait_flags;
     let spans=&totrait_def.trait_spans;
 
-    // let gen_params_deser_header=
-    //     totrait_def.generics_tokenizer(
-    //         InWhat::ImplHeader,
-    //         WithAssocTys::Yes(WhichSelf::NoSelf),
-    //         &ctokens.ts_lt_de_erasedptr,
-    //     );
-
     let gen_params_header=
         totrait_def.generics_tokenizer(
             InWhat::ImplHeader,
@@ -120,7 +117,7 @@ pub(super) fn delegated_impls<'a>(
                 fn clone(&self)->Self{
                     Self{
                         obj:std::clone::Clone::clone(&self.obj),
-                        _marker:__sabi_re::UnsafeIgnoredType::DEFAULT,
+                        _marker:__sabi_re::PhantomData,
                     }
                 }
             }
@@ -329,65 +326,72 @@ pub(super) fn delegated_impls<'a>(
         ).to_tokens(mod_);
     }
 
-    // if let Some(deserialize_bound)=&totrait_def.deserialize_bound {
-    //     let deserialize_path=&deserialize_bound.bound.path;
-
-    //     let lifetimes=deserialize_bound.bound.lifetimes.as_ref()
-    //         .map(|x|ToTokenFnMut::new(move|ts|{
-    //             for lt in &x.lifetimes {
-    //                 lt.to_tokens(ts);
-    //                 Comma::default().to_tokens(ts);
-    //             }
-    //         }));
-
-    //     let suffix=&ctokens.ts_lt_erasedptr;
-
-    //     let header_generics=arenas.alloc(quote!( #lifetimes #suffix ));
-
-    //     let gen_params_header=
-    //         totrait_def.generics_tokenizer(
-    //             InWhat::ImplHeader,
-    //             WithAssocTys::Yes(WhichSelf::NoSelf),
-    //             header_generics,
-    //         );
-
-    //     let lifetime_param=deserialize_bound.lifetime;
-
-    //     quote!(
-    //         impl<#gen_params_header> #deserialize_path for #trait_to<#gen_params_use_to>
-    //         where
-    //             #trait_backend<#gen_params_use_to>: #deserialize_path,
-    //             _ErasedPtr:__DerefTrait<Target=()>,
-    //             #(#where_preds,)*
-    //         {
-    //             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    //             where
-    //                 D: ::serde::Deserializer<#lifetime_param>,
-    //             {
-    //                 #trait_backend::<#gen_params_use_to>::deserialize(deserializer)
-    //                     .map(Self::from_sabi)
-    //             }
-    //         }
-    //     ).to_tokens(mod_);
-    // }
+    if let Some(deserialize_bound)=&totrait_def.deserialize_bound {
+        let deserialize_path=&deserialize_bound.bound.path;
+
+        let lifetimes=deserialize_bound.bound.lifetimes.as_ref()
+            .map(|x|ToTokenFnMut::new(move|ts|{
+                for lt in &x.lifetimes {
+                    lt.to_tokens(ts);
+                    Comma::default().to_tokens(ts);
+                }
+            }));
+
+        let suffix=&ctokens.ts_lt_erasedptr;
+
+        let header_generics=arenas.alloc(quote!( #lifetimes #suffix ));
+
+        let gen_params_header=
+            totrait_def.generics_tokenizer(
+                InWhat::ImplHeader,
+                WithAssocTys::Yes(WhichSelf::NoSelf),
+                header_generics,
+            );
+
+        let lifetime_param=deserialize_bound.lifetime;
+
+        quote_spanned!(spans.deserialize=>
+            impl<#gen_params_header> #deserialize_path for #trait_to<#gen_params_use_to>
+            where
+                #trait_interface<#trait_interface_use>:
+                    __sabi_re::DeserializeDyn<#lifetime_param,Self>,
+                <
+                    #trait_interface<#trait_interface_use> as 
+                    __sabi_re::DeserializeDyn<#lifetime_param,Self>
+                >::Proxy:#deserialize_path,
+                #(#where_preds,)*
+            {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<#lifetime_param>,
+                {
+                    __sabi_re::sabi_deserialize_from_proxy::<
+                        #trait_interface<#trait_interface_use>,
+                        Self,
+                        D,
+                    >(deserializer)
+                }
+            }
+        ).to_tokens(mod_);
+    }
     
-    // if impls.serialize{
-    //     quote!(
-    //         impl<#gen_params_header> ::serde::Serialize for #trait_to<#gen_params_use_to>
-    //         where
-    //             #trait_backend<#gen_params_use_to>: ::serde::Serialize,
-    //             _ErasedPtr:__DerefTrait<Target=()>,
-    //             #(#where_preds,)*
-    //         {
-    //             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    //             where
-    //                 S: ::serde::Serializer,
-    //             {
-    //                 self.obj.serialize(serializer)
-    //             }
-    //         }
-    //     ).to_tokens(mod_);
-    // }
+    if impls.serialize{
+        quote_spanned!(spans.serialize=>
+            impl<#gen_params_header> ::serde::Serialize for #trait_to<#gen_params_use_to>
+            where
+                #trait_backend<#gen_params_use_to>: ::serde::Serialize,
+                _ErasedPtr:__DerefTrait<Target=()>,
+                #(#where_preds,)*
+            {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    ::serde::Serialize::serialize(&self.obj,serializer)
+                }
+            }
+        ).to_tokens(mod_);
+    }
 
     if let Some(iter_item)=&totrait_def.iterator_item {
         quote_spanned!(spans.iterator=>
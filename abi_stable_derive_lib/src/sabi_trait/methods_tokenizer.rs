@@ -1,7 +1,13 @@
 use super::*;
 
+use super::trait_definition::{ClosureParam,ClosureKind};
+
 use crate::to_token_fn::ToTokenFnMut;
 
+use proc_macro2::Span;
+
+use syn::Ident;
+
 #[derive(Debug,Copy,Clone)]
 pub struct MethodsTokenizer<'a>{
     pub(crate) trait_def:&'a TraitDefinition<'a>,
@@ -38,6 +44,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
         let (is_method,vis)=match which_item {
             WhichItem::Trait
             |WhichItem::TraitImpl
+            |WhichItem::SubtraitObjectImpl
             =>(true,None),
             WhichItem::TraitObjectImpl=>
                 (true,Some(trait_def.submod_vis)),
@@ -50,6 +57,16 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
 
         let lifetimes=Some(&method.lifetimes).filter(|l| !l.is_empty() );
 
+        // The vtable stores closure parameters type-erased,
+        // so the type parameters only appear in the user-facing items.
+        let type_params=if is_method { &method.type_params[..] }else{ &[] };
+        let generic_params=if lifetimes.is_some()||!type_params.is_empty() {
+            let lifetimes=&method.lifetimes;
+            Some(quote!( < #(#lifetimes,)* #(#type_params,)* > ))
+        }else{
+            None
+        };
+
         // The name of the method in the __Trait trait.
         let method_name=method.name;
         let method_span=method_name.span();
@@ -80,10 +97,55 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     }
                 }
             }));
-        let param_ty     =method.params.iter().map(|param| &param.ty   );
+        let param_ty     =method.params.iter()
+            .map(move|param|ToTokenFnMut::new(move|ts|{
+                match (&param.closure,is_method) {
+                    (Some(closure),false)=>closure_vtable_type(closure,ts),
+                    _=>param.ty.to_tokens(ts),
+                }
+            }));
         let param_names_c=param_names_a.clone();
         let param_names_d=param_names_a.clone();
         let param_names_e=method.params.iter().map(|x| x.pattern );
+
+        // Closure parameters are passed to the vtable as `RFn`/`RFnMut`/`RFnOnce`,
+        // these are the statements and arguments used to construct them.
+        let caller_rebinds=method.params.iter()
+            .map(|param|ToTokenFnMut::new(move|ts|{
+                let name=param.name;
+                match param.closure.as_ref().map(|x| x.kind ) {
+                    Some(ClosureKind::FnOnce)=>quote!( let mut #name=Some(#name); ),
+                    Some(ClosureKind::FnMut)=>quote!( let mut #name=#name; ),
+                    Some(ClosureKind::Fn)|None=>return,
+                }.to_tokens(ts);
+            }));
+        let caller_args=method.params.iter()
+            .map(|param|ToTokenFnMut::new(move|ts|{
+                let name=param.name;
+                match param.closure.as_ref().map(|x| x.kind ) {
+                    Some(ClosureKind::Fn)=>quote!( __sabi_re::RFn::new(&#name) ),
+                    Some(ClosureKind::FnMut)=>quote!( __sabi_re::RFnMut::new(&mut #name) ),
+                    Some(ClosureKind::FnOnce)=>quote!( __sabi_re::RFnOnce::new(&mut #name) ),
+                    None=>quote!( #name ),
+                }.to_tokens(ts);
+            }));
+
+        // The vtable functions wrap the `RFn`/`RFnMut`/`RFnOnce` parameters in closures,
+        // to pass them to the trait method.
+        let callee_rebinds=method.params.iter()
+            .map(|param|ToTokenFnMut::new(move|ts|{
+                let name=param.name;
+                if let Some(ClosureKind::FnMut)=param.closure.as_ref().map(|x| x.kind ) {
+                    quote!( let mut #name=#name; ).to_tokens(ts);
+                }
+            }));
+        let callee_args=method.params.iter()
+            .map(|param|ToTokenFnMut::new(move|ts|{
+                match &param.closure {
+                    Some(closure)=>callee_closure(param.name,closure,ts),
+                    None=>param.name.to_tokens(ts),
+                }
+            }));
         let return_ty=&method.output;
         
         let self_is_sized_bound=Some(&ctokens.self_sized)
@@ -123,7 +185,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
 
             quote_spanned!(method_span=>
                 #(#[#other_attrs])*
-                #vis #unsafety #abi fn #used_name #(< #(#lifetimes,)* >)* (
+                #vis #unsafety #abi fn #used_name #generic_params (
                     #self_param, 
                     #( #param_names_a:#param_ty ,)* 
                 ) #(-> #return_ty )*
@@ -152,22 +214,54 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     self.#method_name(#(#param_names_c,)*)
                 }).to_tokens(ts);
             }
-            (WhichItem::TraitObjectImpl,_)=>{
+            (WhichItem::SubtraitObjectImpl,SelfParam::ByVal)=>{
+                quote_spanned!(method_span=>{
+                    __Trait::#method_name(
+                        __sabi_re::UpcastTo::<_>::sabi_upcast(self),
+                        #(#param_names_c,)*
+                    )
+                }).to_tokens(ts);
+            }
+            (WhichItem::TraitObjectImpl,_)|(WhichItem::SubtraitObjectImpl,_)=>{
+                // The trait objects of subtraits store the vtable of this trait
+                // in their own vtable.
+                let (vtable,erased_ref,erased_mut,ptr_constraint)=
+                    if which_item==WhichItem::SubtraitObjectImpl {
+                        (
+                            quote!(__sabi_re::UpcastTo::<_>::sabi_supertrait_vtable(self).get()),
+                            quote!(__sabi_re::UpcastTo::<_>::sabi_erased_ref(self)),
+                            quote!(__sabi_re::UpcastTo::<_>::sabi_erased_mut(self)),
+                            None,
+                        )
+                    }else{
+                        (
+                            quote!(self.obj.sabi_et_vtable()),
+                            quote!(self.obj.sabi_erased_ref()),
+                            quote!(self.obj.sabi_erased_mut()),
+                            Some(ptr_constraint),
+                        )
+                    };
+
                 let method_call=match &method.self_param {
                     SelfParam::ByRef{is_mutable:false,..}=>{
-                        quote_spanned!(method_span=> 
-                            __method(self.obj.sabi_erased_ref(),#(#param_names_c,)*) 
-                        )
+                        quote_spanned!(method_span=>{
+                            #(#caller_rebinds)*
+                            __method(#erased_ref,#(#caller_args,)*) 
+                        })
                     }
                     SelfParam::ByRef{is_mutable:true,..}=>{
-                        quote_spanned!(method_span=> 
-                            __method(self.obj.sabi_erased_mut(),#(#param_names_c,)*) 
-                        )
+                        quote_spanned!(method_span=>{
+                            #(#caller_rebinds)*
+                            __method(#erased_mut,#(#caller_args,)*) 
+                        })
                     }
                     SelfParam::ByVal=>{
                         quote_spanned!(method_span=>
                             self.obj.sabi_with_value(
-                                move|_self|__method(_self,#(#param_names_c,)*)
+                                move|_self|{
+                                    #(#caller_rebinds)*
+                                    __method(_self,#(#caller_args,)*)
+                                }
                             )
                         )
                     }
@@ -179,7 +273,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                         quote_spanned!(method_span=>
                                 #ptr_constraint
                             {
-                                match self.obj.sabi_et_vtable().#method_name() {
+                                match #vtable.#method_name() {
                                     Some(__method)=>{
                                         unsafe{
                                             #method_call
@@ -199,7 +293,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                         quote_spanned!(method_span=>
                                 #ptr_constraint
                             {
-                                let __method=self.obj.sabi_et_vtable().#method_name();
+                                let __method=#vtable.#method_name();
                                 unsafe{
                                     #method_call
                                 }
@@ -216,8 +310,10 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 quote_spanned!(method_span=>{
                     __sabi_re::sabi_from_ref(
                         _self,
-                        move|_self| 
-                            __Trait::#method_name(_self,#(#param_names_c,)*)
+                        move|_self|{
+                            #(#callee_rebinds)*
+                            __Trait::#method_name(_self,#(#callee_args,)*)
+                        }
                     )
                 }).to_tokens(ts);
             }
@@ -225,20 +321,81 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 quote_spanned!(method_span=>{
                     __sabi_re::sabi_from_mut(
                         _self,
-                        move|_self| 
-                            __Trait::#method_name(_self,#(#param_names_c,)*)
+                        move|_self|{
+                            #(#callee_rebinds)*
+                            __Trait::#method_name(_self,#(#callee_args,)*)
+                        }
                     )
                 }).to_tokens(ts);
             }
             (WhichItem::VtableImpl,SelfParam::ByVal)=>{
                 quote_spanned!(method_span=>{
                     ::abi_stable::extern_fn_panic_handling!{no_early_return;
+                        #(#callee_rebinds)*
                         __Trait::#method_name(
-                            _self.into_inner(),#(#param_names_c,)*
+                            _self.into_inner(),#(#callee_args,)*
                         )
                     }
                 }).to_tokens(ts);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+
+/// Outputs the type of a closure parameter in the vtable.
+fn closure_vtable_type(closure:&ClosureParam,ts: &mut TokenStream2){
+    let rfn=match closure.kind {
+        ClosureKind::Fn=>quote!( __sabi_re::RFn ),
+        ClosureKind::FnMut=>quote!( __sabi_re::RFnMut ),
+        ClosureKind::FnOnce=>quote!( __sabi_re::RFnOnce ),
+    };
+    let params=&closure.params;
+    let tuple=if params.is_empty() {
+        quote!( () )
+    }else{
+        let tuple=tuple_ident(params.len());
+        quote!( __sabi_re::#tuple< #(#params,)* > )
+    };
+    let output=match &closure.output {
+        Some(output)=>output.into_token_stream(),
+        None=>quote!( () ),
+    };
+    quote!( #rfn<'_,#tuple,#output> ).to_tokens(ts);
+}
+
+
+/// Outputs a closure that calls the `RFn`/`RFnMut`/`RFnOnce` in the `name` variable.
+///
+/// The arguments are transmuted because the lifetimes in the parameters 
+/// of the `RFn*` type are bound by the vtable function,
+/// while the closure parameter must accept arguments of any lifetime
+/// (the closure originally passed by the caller does).
+fn callee_closure(name:&Ident,closure:&ClosureParam,ts: &mut TokenStream2){
+    let args=(0..closure.params.len())
+        .map(|i| Ident::new(&format!("__sabi_arg{}",i),Span::call_site()) )
+        .collect::<Vec<Ident>>();
+
+    let tuple=if args.is_empty() {
+        quote!( () )
+    }else{
+        let tuple=tuple_ident(args.len());
+        let args=&args;
+        quote!( __sabi_re::transmute_ignore_size(__sabi_re::#tuple( #(#args,)* )) )
+    };
+
+    let (ref_,call)=match closure.kind {
+        ClosureKind::Fn=>(quote!( & ),quote!( call )),
+        ClosureKind::FnMut=>(quote!( &mut ),quote!( call_mut )),
+        ClosureKind::FnOnce=>(quote!(),quote!( call_once )),
+    };
+    let ref_=Some(ref_).filter(|_| closure.by_ref );
+
+    let args=&args;
+    quote!( #ref_ move|#(#args),*| #name.#call(#tuple) ).to_tokens(ts);
+}
+
+
+fn tuple_ident(len:usize)->Ident{
+    Ident::new(&format!("Tuple{}",len),Span::call_site())
+}
@@ -43,6 +43,7 @@ impl_visit_mut_with!{
     (syn::WherePredicate,VisitMut::visit_where_predicate_mut),
     (TraitItemType,VisitMut::visit_trait_item_type_mut),
     (syn::Type,VisitMut::visit_type_mut),
+    (syn::TypeParam,VisitMut::visit_type_param_mut),
 }
 
 
@@ -9,7 +9,24 @@ fn must_not_pass(){
     let list=vec![
         "
             trait Foo {
-                fn foo();
+                fn foo(x: &Self);
+            }
+        ",
+        "
+            trait Foo {
+                fn foo() -> Option<Self>;
+            }
+        ",
+        "
+            #[sabi(use_dyntrait)]
+            trait Foo {
+                fn foo() -> Self;
+            }
+        ",
+        "
+            trait Foo {
+                type Assoc;
+                const X: usize;
             }
         ",
         "
@@ -29,10 +46,73 @@ fn must_not_pass(){
             }
         ",
         "
-            trait Bar {
-                const X: usize;
+            trait Closures {
+                fn foo(&self, f: impl Fn(&str) -> &str);
             }
+        ",
+        "
+            trait Closures {
+                fn foo(&self, f: impl FnMut(u8, u8, u8, u8, u8));
+            }
+        ",
         "
+            trait Closures {
+                fn foo(&self, f: impl FnMut() + Send);
+            }
+        ",
+        "
+            trait Closures {
+                fn foo(&self, f: &dyn FnMut());
+            }
+        ",
+        "
+            trait Closures {
+                fn foo<T>(&self, f: T);
+            }
+        ",
+        "
+            trait Closures {
+                fn foo<F: Fn()>(&self, f: F, g: F);
+            }
+        ",
+        "
+            trait Closures {
+                fn foo<F: Fn()>(&self, f: Vec<F>);
+            }
+        ",
+        "
+            trait Futures {
+                async fn foo(self) -> u32;
+            }
+        ",
+        "
+            trait Futures {
+                fn foo(self) -> impl Future<Output = u32> + Send;
+            }
+        ",
+        "
+            trait Futures {
+                async fn foo() -> u32;
+            }
+        ",
+        "
+            trait Foo: Named {
+                fn foo(&self);
+            }
+        ",
+        "
+            #[sabi(sabi_supertraits(Named))]
+            trait Foo: Clone {
+                fn foo(&self);
+            }
+        ",
+        "
+            #[sabi(use_dyntrait)]
+            #[sabi(sabi_supertraits(Named))]
+            trait Foo: Named {
+                fn foo(&self);
+            }
+        ",
     ];
     for elem in list {
         must_panic(file_span!(),||{
@@ -46,6 +126,49 @@ fn must_not_pass(){
 #[test]
 fn must_pass(){
     let list=vec![
+        "
+            trait Foo {
+                fn foo();
+            }
+        ",
+        "
+            trait Bar {
+                const X: usize;
+                fn bar(x: usize) -> Self;
+            }
+        ",
+        "
+            trait Serde<'a>: Serialize + Deserialize<'a> {
+                fn serde(&self);
+            }
+        ",
+        "
+            trait SerdeOwned: Serialize + for<'de> Deserialize<'de> {
+                fn serde(&self);
+            }
+        ",
+        "
+            trait Closures {
+                fn foo(&self, f: impl Fn(&str) -> u32);
+                fn bar<F: FnMut(RStr<'_>, u32)>(&mut self, f: F);
+                fn baz(self, f: impl FnOnce() -> RString);
+                fn qux(&self, f: &dyn Fn(u8, u8, u8, u8), g: &mut dyn FnMut());
+            }
+        ",
+        "
+            trait Futures {
+                async fn foo(&self) -> u32;
+                async fn bar(&mut self) { }
+                fn baz(&self) -> impl Future<Output = RString> + Send + '_;
+                fn qux(&self) -> impl std::future::Future<Output = u32> { async { 0 } }
+            }
+        ",
+        "
+            #[sabi(sabi_supertraits(Named,Counter))]
+            trait Plugin: Named + Counter<u32> + Send {
+                fn run(&mut self);
+            }
+        ",
         // uncomment once syn supports const parameters
         // "
         //     trait ConstBaz<const N:usize> {
@@ -53,10 +53,11 @@ pub(crate) struct TraitDefinition<'a>{
     pub(crate) iterator_item:Option<&'a syn::Type>,
     /// The path for the implemented serde::Deserialize trait 
     /// (it may reference some trait lifetime parameter)
-    #[allow(dead_code)]
     pub(crate) deserialize_bound:Option<DeserializeBound<'a>>,
     pub(crate) impld_traits:Vec<TraitImplness<'a>>,
     pub(crate) unimpld_traits:Vec<&'a Ident>,
+    /// The supertraits that are `#[sabi_trait]` traits themselves.
+    pub(crate) user_supertraits:Vec<UserSupertrait<'a>>,
     pub(crate) trait_flags:TraitStruct<bool>,
     pub(crate) trait_spans:TraitStruct<Span>,
     /// The lifetimes declared in the trait generic parameter list that are used in 
@@ -68,6 +69,10 @@ pub(crate) struct TraitDefinition<'a>{
     // The keys use the proginal identifier for the associated type.
     pub(crate) assoc_tys:HashMap<&'a Ident, AssocTyWithIndex>,
     pub(crate) methods:Vec<TraitMethod<'a>>,
+    /// The methods without a `self` receiver,stored in the class vtable.
+    pub(crate) static_methods:Vec<StaticMethod<'a>>,
+    /// The associated constants,stored in the class vtable.
+    pub(crate) assoc_consts:Vec<AssocConst<'a>>,
     pub(crate) has_mut_methods:bool,
     pub(crate) has_val_methods:bool,
     pub(crate) ts_fq_self:&'a TokenStream2,
@@ -83,6 +88,7 @@ impl<'a> TraitDefinition<'a>{
         attrs:OwnedDeriveAndOtherAttrs,
         methods_with_attrs:Vec<MethodWithAttrs<'a>>,
         which_object:WhichObject,
+        sabi_supertraits:Vec<Ident>,
         arenas: &'a Arenas,
         ctokens:&'a CommonTokens,
     )->Self {
@@ -90,10 +96,16 @@ impl<'a> TraitDefinition<'a>{
         let submod_vis=vis.submodule_level(1);
         let mut assoc_tys=HashMap::default();
         let mut methods=Vec::<TraitMethod<'a>>::new();
+        let mut static_methods=Vec::<StaticMethod<'a>>::new();
+        let mut assoc_consts=Vec::<AssocConst<'a>>::new();
 
-        methods_with_attrs.into_iter()
-            .filter_map(|func| TraitMethod::new(func,&trait_.vis,ctokens,arenas) )
-            .extending(&mut methods);
+        for func in methods_with_attrs {
+            if StaticMethod::is_static(func.item) {
+                static_methods.push(StaticMethod::new(func,ctokens,arenas));
+            }else{
+                methods.extend(TraitMethod::new(func,&trait_.vis,ctokens,arenas));
+            }
+        }
 
         /////////////////////////////////////////////////////
         ////         Processing the supertrait bounds
@@ -107,6 +119,7 @@ impl<'a> TraitDefinition<'a>{
         let GetSupertraits{
             impld_traits,
             unimpld_traits,
+            user_supertraits,
             mut lifetime_bounds,
             iterator_item,
             deserialize_bound,
@@ -116,6 +129,7 @@ impl<'a> TraitDefinition<'a>{
             &trait_.supertraits,
             &lifetime_params,
             which_object,
+            &sabi_supertraits,
             arenas,
             ctokens,
         );
@@ -140,6 +154,9 @@ impl<'a> TraitDefinition<'a>{
         for item in &trait_.items {
             match item {
                 TraitItem::Method{..}=>{},
+                TraitItem::Const(assoc_const)=>{
+                    assoc_consts.push(AssocConst::new(assoc_const));
+                },
                 TraitItem::Type(assoc_ty)=>{
                     let with_index=AssocTyWithIndex{
                         index:assoc_ty_index,
@@ -156,6 +173,22 @@ impl<'a> TraitDefinition<'a>{
             }
         }
 
+        let has_static_items=!static_methods.is_empty()||!assoc_consts.is_empty();
+        if has_static_items {
+            if which_object==WhichObject::DynTrait {
+                panic!(
+                    "\nCannot use static methods or associated constants in traits with the \
+                     #[sabi(use_dyntrait)] attribute.\n\n"
+                );
+            }
+            if !assoc_tys.is_empty() {
+                panic!(
+                    "\nCannot use static methods or associated constants in traits with \
+                     associated types.\n\n"
+                );
+            }
+        }
+
         let has_mut_methods=methods.iter()
             .any(|m| matches!(SelfParam::ByRef{is_mutable:true,..}= &m.self_param) );
 
@@ -183,12 +216,15 @@ impl<'a> TraitDefinition<'a>{
             deserialize_bound,
             impld_traits,
             unimpld_traits,
+            user_supertraits,
             trait_flags,
             trait_spans,
             vis,
             submod_vis,
             assoc_tys,
             methods,
+            static_methods,
+            assoc_consts,
             has_mut_methods,
             has_val_methods,
             ts_fq_self:arenas.alloc(ts_fq_self),
@@ -203,7 +239,7 @@ impl<'a> TraitDefinition<'a>{
         let ctokens=self.ctokens;
 
         let replace_with=match which_item {
-            WhichItem::Trait|WhichItem::TraitImpl=>{
+            WhichItem::Trait|WhichItem::TraitImpl|WhichItem::SubtraitObjectImpl=>{
                 return this;
             }
             WhichItem::TraitObjectImpl=>{
@@ -291,6 +327,12 @@ impl<'a> TraitDefinition<'a>{
         where_preds
     }
 
+    /// Whether the trait has static methods or associated constants,
+    /// which are stored in the class vtable.
+    pub fn has_static_items(&self)->bool{
+        !self.static_methods.is_empty()||!self.assoc_consts.is_empty()
+    }
+
     pub fn methods_tokenizer(&self,which_item:WhichItem)->MethodsTokenizer<'_>{
         MethodsTokenizer{
             trait_def:self,
@@ -313,6 +355,9 @@ pub(crate) struct TraitMethod<'a>{
     pub(crate) name:&'a Ident,
     pub(crate) self_param:SelfParam<'a>,
     pub(crate) lifetimes: Vec<&'a LifetimeDef>,
+    /// The type parameters of the method,
+    /// each one is the type of a closure parameter.
+    pub(crate) type_params: Vec<syn::TypeParam>,
     pub(crate) params: Vec<MethodParam<'a>>,
     pub(crate) output: Option<syn::Type>,
     pub(crate) where_clause:MethodWhereClause<'a>,
@@ -333,6 +378,291 @@ pub(crate) struct MethodParam<'a>{
     pub(crate) name:&'a Ident,
     pub(crate) ty:syn::Type,
     pub(crate) pattern:&'a syn::Pat,
+    /// Whether this is a closure parameter,
+    /// passed through the vtable as an `RFn`/`RFnMut`/`RFnOnce`.
+    pub(crate) closure:Option<ClosureParam>,
+}
+
+
+/// A parameter of type `impl Fn*(..)`,`&dyn Fn(..)`,`&mut dyn FnMut(..)`,
+/// or a method type parameter bounded by `Fn*(..)`.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub(crate) struct ClosureParam{
+    pub(crate) kind:ClosureKind,
+    /// Whether the closure is passed as a `&dyn Fn`/`&mut dyn FnMut`.
+    pub(crate) by_ref:bool,
+    pub(crate) params:Vec<syn::Type>,
+    pub(crate) output:Option<syn::Type>,
+}
+
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub(crate) enum ClosureKind{
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
+
+/// The maximum amount of parameters that closure parameters can take.
+const MAX_CLOSURE_PARAMS:usize=4;
+
+
+impl ClosureParam{
+    fn new(
+        ty:&syn::Type,
+        type_params:&[syn::TypeParam],
+        method_name:&Ident,
+    )->Option<Self>{
+        let (bounds,by_ref)=match ty {
+            syn::Type::ImplTrait(x)=>
+                (&x.bounds,false),
+            syn::Type::Reference(ref_)=>match &*ref_.elem {
+                syn::Type::TraitObject(x)=>{
+                    let this=Self::from_bounds(&x.bounds,true,method_name)?;
+                    let is_mutable=ref_.mutability.is_some();
+                    match (this.kind,is_mutable) {
+                        (ClosureKind::Fn,false)|(ClosureKind::FnMut,true)=>{}
+                        _=>panic!(
+                            "\nClosure parameters passed by reference must be either \
+                             `&dyn Fn(..)` or `&mut dyn FnMut(..)`.\n\
+                             Caused by the '{}' method.\n\n",
+                            method_name,
+                        ),
+                    }
+                    return Some(this);
+                }
+                _=>return None,
+            },
+            syn::Type::Path(x) if x.qself.is_none() =>{
+                let type_param=type_params.iter()
+                    .find(|tp| x.path.is_ident(tp.ident.clone()) )?;
+                let this=Self::from_bounds(&type_param.bounds,false,method_name);
+                if this.is_none() {
+                    panic!(
+                        "\nThe '{}' type parameter of the '{}' method must be \
+                         bounded by `Fn(..)`,`FnMut(..)`,or `FnOnce(..)`.\n\n",
+                        type_param.ident,
+                        method_name,
+                    );
+                }
+                return this;
+            }
+            _=>return None,
+        };
+        Self::from_bounds(bounds,by_ref,method_name)
+    }
+
+    fn from_bounds(
+        bounds:&Punctuated<TypeParamBound,syn::token::Add>,
+        by_ref:bool,
+        method_name:&Ident,
+    )->Option<Self>{
+        let (trait_bound,kind)=bounds.iter()
+            .filter_map(|bound| match bound {
+                TypeParamBound::Trait(x)=>Some(x),
+                TypeParamBound::Lifetime(_)=>None,
+            })
+            .filter_map(|trait_bound|{
+                let last=trait_bound.path.segments.iter().last()?;
+                let kind=if last.ident=="Fn" {
+                    ClosureKind::Fn
+                }else if last.ident=="FnMut" {
+                    ClosureKind::FnMut
+                }else if last.ident=="FnOnce" {
+                    ClosureKind::FnOnce
+                }else{
+                    return None;
+                };
+                Some((trait_bound,kind))
+            })
+            .next()?;
+
+        if bounds.len()!=1 {
+            panic!(
+                "\nClosure parameters can only have one \
+                 `Fn(..)`/`FnMut(..)`/`FnOnce(..)` bound.\n\
+                 Caused by the '{}' method.\n\n",
+                method_name,
+            );
+        }
+        if trait_bound.lifetimes.is_some() {
+            panic!(
+                "\nClosure parameters can't use `for<..>` in their bounds,\
+                 use elided lifetimes instead.\n\
+                 Caused by the '{}' method.\n\n",
+                method_name,
+            );
+        }
+
+        let last=trait_bound.path.segments.iter().last().unwrap();
+        let args=match &last.arguments {
+            syn::PathArguments::Parenthesized(x)=>x,
+            _=>return None,
+        };
+
+        if args.inputs.len() > MAX_CLOSURE_PARAMS {
+            panic!(
+                "\nClosure parameters can take at most {} parameters.\n\
+                 Caused by the '{}' method.\n\n",
+                MAX_CLOSURE_PARAMS,
+                method_name,
+            );
+        }
+
+        let output=match &args.output {
+            syn::ReturnType::Default=>None,
+            syn::ReturnType::Type(_,ty)=>{
+                if has_elided_lifetimes(ty) {
+                    panic!(
+                        "\nThe return type of closure parameters can't have elided lifetimes.\n\
+                         Caused by the '{}' method.\n\n",
+                        method_name,
+                    );
+                }
+                Some((**ty).clone())
+            }
+        };
+
+        Some(Self{
+            kind,
+            by_ref,
+            params:args.inputs.iter().cloned().collect(),
+            output,
+        })
+    }
+
+    fn replace_self<F>(&mut self,replace_with:ReplaceWith,mut is_assoc_type: F)
+    where
+        F: FnMut(&Ident) -> Option<ReplaceWith>,
+    {
+        for ty in self.params.iter_mut().chain(self.output.as_mut()) {
+            replace_self_path::replace_self_path(ty,replace_with.clone(),&mut is_assoc_type);
+        }
+    }
+}
+
+
+fn has_elided_lifetimes(ty:&syn::Type)->bool{
+    struct ElidedVisitor{
+        found:bool,
+    }
+
+    impl<'ast> syn::visit::Visit<'ast> for ElidedVisitor{
+        fn visit_type_reference(&mut self, ref_: &'ast syn::TypeReference) {
+            if ref_.lifetime.is_none() {
+                self.found=true;
+            }
+            syn::visit::visit_type_reference(self,ref_);
+        }
+
+        fn visit_lifetime(&mut self, lt: &'ast Lifetime) {
+            if lt.ident=="_" {
+                self.found=true;
+            }
+        }
+    }
+
+    let mut visitor=ElidedVisitor{found:false};
+    syn::visit::Visit::visit_type(&mut visitor,ty);
+    visitor.found
+}
+
+
+/// The future returned by an `async fn`/`-> impl Future<Output=T>` method,
+/// which is returned as an `RFuture<'_,T>`/`UnsyncRFuture<'_,T>` across the ffi boundary.
+struct ReturnedFuture{
+    /// The `RFuture<'_,T>`/`UnsyncRFuture<'_,T>` type.
+    rfuture:syn::Type,
+    /// Whether the method is an `async fn`,
+    /// in which case its default implementation is wrapped in an `async move` block.
+    is_async_fn:bool,
+    /// `RFuture` or `UnsyncRFuture`.
+    constructor:Ident,
+}
+
+impl ReturnedFuture{
+    fn new(method_signature:&syn::MethodSig)->Option<Self>{
+        let name=&method_signature.ident;
+        let is_async_fn=method_signature.asyncness.is_some();
+
+        let (lifetime,is_send,output)=match (&method_signature.decl.output,is_async_fn) {
+            (syn::ReturnType::Default,true)=>
+                (None,true,quote!( () )),
+            (syn::ReturnType::Type(_,ty),true)=>
+                (None,true,ty.into_token_stream()),
+            (syn::ReturnType::Type(_,ty),false)=>{
+                let bounds=match &**ty {
+                    syn::Type::ImplTrait(x)=>&x.bounds,
+                    _=>return None,
+                };
+                let mut lifetime=None;
+                let mut is_send=false;
+                let mut output=None;
+                for bound in bounds {
+                    match bound {
+                        TypeParamBound::Lifetime(lt)=>
+                            lifetime=Some(lt),
+                        TypeParamBound::Trait(x)=>{
+                            let last=x.path.segments.last().map(|x| x.into_value() );
+                            match last {
+                                Some(seg) if seg.ident=="Send" && seg.arguments.is_empty() =>
+                                    is_send=true,
+                                Some(seg) if seg.ident=="Future" =>
+                                    output=future_output_type(&seg.arguments),
+                                _=>return None,
+                            }
+                        }
+                    }
+                }
+                match output {
+                    Some(output)=>(lifetime,is_send,output.into_token_stream()),
+                    None=>return None,
+                }
+            }
+            (syn::ReturnType::Default,false)=>
+                return None,
+        };
+
+        let constructor=if is_send { "RFuture" }else{ "UnsyncRFuture" };
+        let constructor=parse_str_as_ident(constructor);
+        let lifetime=match lifetime {
+            Some(lt)=>lt.into_token_stream(),
+            None=>quote!( '_ ),
+        };
+
+        let rfuture=syn::parse2(quote!( __sabi_re::#constructor<#lifetime,#output> ))
+            .unwrap_or_else(|e|{
+                panic!("\nInvalid return type for the '{}' method:\n{}\n\n",name,e)
+            });
+
+        Some(Self{rfuture,is_async_fn,constructor})
+    }
+
+    /// Wraps the default implementation of the method,
+    /// so that it returns an `RFuture`/`UnsyncRFuture`.
+    fn wrap_block(&self,block:&Block)->Block{
+        let constructor=&self.constructor;
+        let future=if self.is_async_fn {
+            quote!( async move #block )
+        }else{
+            block.into_token_stream()
+        };
+        syn::parse2(quote!({ __sabi_re::#constructor::new(#future) })).unwrap()
+    }
+}
+
+
+/// Gets the `T` in `Future<Output=T>`.
+fn future_output_type(args:&syn::PathArguments)->Option<&syn::Type>{
+    match args {
+        syn::PathArguments::AngleBracketed(x)=>
+            x.args.iter().filter_map(|arg| match arg {
+                syn::GenericArgument::Binding(b) if b.ident=="Output" => Some(&b.ty),
+                _=>None,
+            }).next(),
+        _=>None,
+    }
 }
 
 
@@ -347,20 +677,6 @@ impl<'a> TraitMethod<'a>{
         let decl=&method_signature.decl;
         let name=&method_signature.ident;
 
-        let panic_msg=||{
-            panic!("\n\n\
-                Cannot define #[sabi_trait]traits containing methods \
-                without a `self`/`&self`/`&mut self` receiver \
-                (static methods).\n\
-                Caused by the '{}' method.\n\n\
-            ",
-                method_signature.ident,
-            )
-        };
-        if decl.inputs.is_empty() {
-            panic_msg();
-        }
-
         let mut input_iter=decl.inputs.iter();
 
         let mut self_param=match input_iter.next()? {
@@ -372,14 +688,34 @@ impl<'a> TraitMethod<'a>{
             FnArg::SelfValue{..}=>
                 SelfParam::ByVal,
             FnArg::Captured{..}|FnArg::Inferred{..}|FnArg::Ignored{..}=>
-                panic_msg(),
+                return None,
         };
 
         let mut lifetimes:Vec<&'a syn::LifetimeDef>=decl.generics.lifetimes().collect();
 
-        let output=match &decl.output {
-            syn::ReturnType::Default=>None,
-            syn::ReturnType::Type(_,ty)=>{
+        let returned_future=ReturnedFuture::new(method_signature);
+
+        if returned_future.is_some() && self_param==SelfParam::ByVal {
+            panic!(
+                "\nMethods returning futures must take `self` by reference.\n\
+                 Caused by the '{}' method.\n\n",
+                name,
+            );
+        }
+
+        let output=match (&returned_future,&decl.output) {
+            (Some(returned_future),_)=>{
+                let mut ty=returned_future.rfuture.clone();
+                if let SelfParam::ByRef{lifetime,..}=&mut self_param {
+                    LifetimeUnelider::new(ctokens,lifetime)
+                        .visit_type(&mut ty)
+                        .into_iter()
+                        .extending(&mut lifetimes);
+                }
+                Some(ty)
+            },
+            (None,syn::ReturnType::Default)=>None,
+            (None,syn::ReturnType::Type(_,ty))=>{
                 let mut ty=(**ty).clone();
                 if let SelfParam::ByRef{lifetime,..}=&mut self_param {
                     LifetimeUnelider::new(ctokens,lifetime)
@@ -391,7 +727,60 @@ impl<'a> TraitMethod<'a>{
             },
         };
 
-        let default=mwa.item.default.as_ref().map(|block| DefaultMethod{block} );
+        let default=mwa.item.default.as_ref().map(|block|{
+            let block=match &returned_future {
+                Some(returned_future)=>arena.alloc(returned_future.wrap_block(block)),
+                None=>block,
+            };
+            DefaultMethod{block}
+        });
+
+        let type_params:Vec<syn::TypeParam>=decl.generics.type_params().cloned().collect();
+
+        let params:Vec<MethodParam<'a>>=input_iter
+            .enumerate()
+            .map(|(param_i,param)|{
+                let (pattern,ty)=match param {
+                    FnArg::SelfRef{..}|FnArg::SelfValue{..}|FnArg::Inferred{..}=>
+                        unreachable!(),
+                    FnArg::Captured(x)=>{
+                        (&x.pat,&x.ty)
+                    },
+                    FnArg::Ignored(ty)=>
+                        (&ctokens.ignored_pat,ty),
+                };
+
+                let param_name=format!("param_{}",param_i);
+                let mut param_name=syn::parse_str::<Ident>(&param_name).unwrap();
+                param_name.set_span(param.span());
+                MethodParam{
+                    name:arena.alloc(param_name),
+                    ty:ty.clone(),
+                    pattern,
+                    closure:ClosureParam::new(ty,&type_params,name),
+                }
+            })
+            .collect();
+
+        // Type parameters are only supported as the type of a single closure parameter,
+        // because the vtable stores the closure type-erased.
+        for type_param in &type_params {
+            let uses=params.iter()
+                .filter(|param| param.closure.is_some() )
+                .filter(|param| match &param.ty {
+                    syn::Type::Path(x)=>x.path.is_ident(type_param.ident.clone()),
+                    _=>false,
+                })
+                .count();
+            if uses!=1 {
+                panic!(
+                    "\nThe '{}' type parameter of the '{}' method must be \
+                     the type of exactly one parameter.\n\n",
+                    type_param.ident,
+                    name,
+                );
+            }
+        }
 
         Some(Self{
             item:&mwa.item,
@@ -402,30 +791,9 @@ impl<'a> TraitMethod<'a>{
             other_attrs:arena.alloc(mwa.attrs.other_attrs),
             name,
             lifetimes,
+            type_params,
             self_param,
-            params:input_iter
-                .enumerate()
-                .map(|(param_i,param)|{
-                    let (pattern,ty)=match param {
-                        FnArg::SelfRef{..}|FnArg::SelfValue{..}|FnArg::Inferred{..}=>
-                            unreachable!(),
-                        FnArg::Captured(x)=>{
-                            (&x.pat,&x.ty)
-                        },
-                        FnArg::Ignored(ty)=>
-                            (&ctokens.ignored_pat,ty),
-                    };
-
-                    let name=format!("param_{}",param_i);
-                    let mut name=syn::parse_str::<Ident>(&name).unwrap();
-                    name.set_span(param.span());
-                    MethodParam{
-                        name:arena.alloc(name),
-                        ty:ty.clone(),
-                        pattern,
-                    }
-                })
-                .collect(),
+            params,
             output,
             where_clause:decl.generics.where_clause.as_ref()
                 .map(|wc| MethodWhereClause::new(wc,ctokens) )
@@ -451,6 +819,152 @@ impl<'a> TraitMethod<'a>{
                 &mut is_assoc_type
             );
         }
+        for closure in self.params.iter_mut().filter_map(|x| x.closure.as_mut() ) {
+            closure.replace_self(replace_with.clone(),&mut is_assoc_type);
+        }
+        for type_param in &mut self.type_params {
+            replace_self_path::replace_self_path(
+                type_param,
+                replace_with.clone(),
+                &mut is_assoc_type
+            );
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+
+
+/// A method without a `self` receiver.
+#[derive(Debug,Clone)]
+pub(crate) struct StaticMethod<'a>{
+    pub(crate) item:&'a syn::TraitItemMethod,
+    pub(crate) unsafety: Option<&'a Unsafe>,
+    pub(crate) derive_attrs:&'a [Meta],
+    pub(crate) other_attrs:&'a [Meta],
+    pub(crate) name:&'a Ident,
+    pub(crate) lifetimes: Vec<&'a LifetimeDef>,
+    pub(crate) params: Vec<MethodParam<'a>>,
+    /// The return type,`None` if it's `Self` or `()`.
+    pub(crate) output: Option<&'a syn::Type>,
+    /// Whether the method returns `Self`,
+    /// which is returned as a trait object from the class vtable.
+    pub(crate) returns_self:bool,
+}
+
+
+impl<'a> StaticMethod<'a>{
+    pub fn is_static(item:&syn::TraitItemMethod)->bool{
+        match item.sig.decl.inputs.iter().next() {
+            Some(FnArg::SelfRef{..})|Some(FnArg::SelfValue{..})=>false,
+            _=>true,
+        }
+    }
+
+    pub fn new(
+        mwa:MethodWithAttrs<'a>,
+        ctokens:&'a CommonTokens,
+        arena:&'a Arenas,
+    )->Self {
+        let method_signature=&mwa.item.sig;
+        let decl=&method_signature.decl;
+        let name=&method_signature.ident;
+
+        if decl.generics.type_params().next().is_some() {
+            panic!(
+                "\nCannot define generic static methods in #[sabi_trait] traits.\n\
+                 Caused by the '{}' method.\n\n",
+                name,
+            );
+        }
+
+        if method_signature.asyncness.is_some() {
+            panic!(
+                "\nCannot define async static methods in #[sabi_trait] traits.\n\
+                 Caused by the '{}' method.\n\n",
+                name,
+            );
+        }
+
+        let assert_no_self=|ty:&syn::Type|{
+            let mut ty=ty.clone();
+            replace_self_path::replace_self_path(&mut ty,ReplaceWith::Remove,|_| None );
+        };
+
+        let (output,returns_self)=match &decl.output {
+            syn::ReturnType::Default=>(None,false),
+            syn::ReturnType::Type(_,ty) if **ty==ctokens.self_ty =>(None,true),
+            syn::ReturnType::Type(_,ty)=>{
+                assert_no_self(ty);
+                (Some(&**ty),false)
+            },
+        };
+
+        let params=decl.inputs.iter()
+            .enumerate()
+            .map(|(param_i,param)|{
+                let (pattern,ty)=match param {
+                    FnArg::SelfRef{..}|FnArg::SelfValue{..}|FnArg::Inferred{..}=>
+                        unreachable!(),
+                    FnArg::Captured(x)=>{
+                        (&x.pat,&x.ty)
+                    },
+                    FnArg::Ignored(ty)=>
+                        (&ctokens.ignored_pat,ty),
+                };
+                assert_no_self(ty);
+
+                let name=format!("param_{}",param_i);
+                let mut name=syn::parse_str::<Ident>(&name).unwrap();
+                name.set_span(param.span());
+                MethodParam{
+                    name:arena.alloc(name),
+                    ty:ty.clone(),
+                    pattern,
+                    closure:None,
+                }
+            })
+            .collect();
+
+        Self{
+            item:&mwa.item,
+            unsafety:method_signature.unsafety.as_ref(),
+            derive_attrs:arena.alloc(mwa.attrs.derive_attrs),
+            other_attrs:arena.alloc(mwa.attrs.other_attrs),
+            name,
+            lifetimes:decl.generics.lifetimes().collect(),
+            params,
+            output,
+            returns_self,
+        }
+    }
+}
+
+
+/// An associated constant.
+#[derive(Debug,Clone)]
+pub(crate) struct AssocConst<'a>{
+    pub(crate) item:&'a syn::TraitItemConst,
+    pub(crate) name:&'a Ident,
+    pub(crate) ty:&'a syn::Type,
+    /// The field in the class vtable that stores the constant.
+    pub(crate) vtable_field:Ident,
+}
+
+
+impl<'a> AssocConst<'a>{
+    pub fn new(item:&'a syn::TraitItemConst)->Self{
+        let mut ty=item.ty.clone();
+        replace_self_path::replace_self_path(&mut ty,ReplaceWith::Remove,|_| None );
+
+        let vtable_field=format!("_sabi_const_{}",item.ident.to_string().to_lowercase());
+        Self{
+            item,
+            name:&item.ident,
+            ty:&item.ty,
+            vtable_field:parse_str_as_ident(&vtable_field),
+        }
     }
 }
 
@@ -538,6 +1052,7 @@ pub(crate) struct DeserializeBound<'a>{
 struct GetSupertraits<'a>{
     impld_traits:Vec<TraitImplness<'a>>,
     unimpld_traits:Vec<&'a Ident>,
+    user_supertraits:Vec<UserSupertrait<'a>>,
     lifetime_bounds:Vec<&'a Lifetime>,
     iterator_item:Option<&'a syn::Type>,
     deserialize_bound:Option<DeserializeBound<'a>>,
@@ -546,6 +1061,61 @@ struct GetSupertraits<'a>{
 }
 
 
+/// A supertrait that is a `#[sabi_trait]` trait.
+#[derive(Debug,Clone)]
+pub(crate) struct UserSupertrait<'a>{
+    /// The supertrait bound,as written in the trait declaration.
+    pub(crate) bound:&'a syn::TraitBound,
+    /// The `Trait_Interface` type generated for the supertrait.
+    pub(crate) interface:syn::Path,
+    /// The vtable field that stores the vtable of the supertrait.
+    pub(crate) vtable_field:Ident,
+}
+
+
+impl<'a> UserSupertrait<'a>{
+    fn new(bound:&'a syn::TraitBound,index:usize,which_object:WhichObject)->Self{
+        use syn::{GenericArgument,PathArguments};
+
+        if which_object==WhichObject::DynTrait {
+            panic!(
+                "\nCannot use #[sabi_trait] traits as supertraits of traits with the \
+                 #[sabi(use_dyntrait)] attribute:\n\t{}\n\n",
+                bound.into_token_stream(),
+            );
+        }
+
+        let mut interface=bound.path.clone();
+        let last=interface.segments.last_mut().unwrap().into_value();
+        match &last.arguments {
+            PathArguments::None=>{}
+            PathArguments::AngleBracketed(args)=>{
+                let has_bindings=args.args.iter()
+                    .any(|arg| matches!(GenericArgument::Binding{..}|GenericArgument::Constraint{..}=arg) );
+                if has_bindings {
+                    panic!(
+                        "\nCannot use #[sabi_trait] supertraits with associated types:\n\t{}\n\n",
+                        bound.into_token_stream(),
+                    );
+                }
+            }
+            PathArguments::Parenthesized{..}=>panic!(
+                "\nUnexpected supertrait bound:\n\t{}\n\n",
+                bound.into_token_stream(),
+            ),
+        }
+        last.ident=parse_str_as_ident(&format!("{}_Interface",last.ident));
+        last.ident.set_span(bound.span());
+
+        UserSupertrait{
+            bound,
+            interface,
+            vtable_field:parse_str_as_ident(&format!("_sabi_supertrait_{}",index)),
+        }
+    }
+}
+
+
 #[derive(Debug,Clone)]
 pub(crate) struct TraitImplness<'a>{
     pub(crate) which_trait:WhichTrait,
@@ -561,6 +1131,7 @@ fn get_supertraits<'a,I>(
     supertraits: I,
     lifetime_params:&HashSet<&'a Lifetime>,
     which_object:WhichObject,
+    sabi_supertraits:&[Ident],
     arenas: &'a Arenas,
     _ctokens:&'a CommonTokens,
 )->GetSupertraits<'a>
@@ -582,9 +1153,10 @@ where
         }
     });
 
+    let mut user_supertraits=Vec::new();
     let mut lifetime_bounds=Vec::new();
     let mut iterator_item=None;
-    let deserialize_bound=None;
+    let mut deserialize_bound=None;
 
     for supertrait_bound in supertraits{
         match supertrait_bound {
@@ -595,6 +1167,12 @@ where
                 };
                 let trait_ident=&last_path_component.ident;
 
+                if sabi_supertraits.contains(trait_ident) {
+                    let index=user_supertraits.len();
+                    user_supertraits.push(UserSupertrait::new(trait_bound,index,which_object));
+                    continue;
+                }
+
                 match trait_map.get(&trait_ident) {
                     Some(&which_trait)=>{
                         let usable_by=which_trait.usable_by();
@@ -643,22 +1221,20 @@ where
                                 iterator_item=iterator_item.or(iter_item);
                             }
                             WhichTrait::Deserialize=>{
-                                // deserialize_bound=deserialize_bound.or(Some(
-                                //     DeserializeBound{
-                                //         bound:trait_bound
-                                //             .clone()
-                                //             .piped(|x| arenas.alloc(x) ),
-                                //         lifetime:
-                                //             extract_deserialize_lifetime(
-                                //                 last_path_component,
-                                //                 arenas
-                                //             ),
-                                //     }
-                                // ));
-                                panic!("Deserialize is not currently supported.");
-                            }
-                            WhichTrait::Serialize=>{
-                                panic!("Serialize is not currently supported.");
+                                // Using the bound as written by the user,
+                                // since `Deserialize` requires a lifetime argument.
+                                trait_struct.deserialize.bound=trait_bound.clone();
+
+                                deserialize_bound=deserialize_bound.or(Some(
+                                    DeserializeBound{
+                                        bound:arenas.alloc(trait_bound.clone()),
+                                        lifetime:
+                                            extract_deserialize_lifetime(
+                                                last_path_component,
+                                                arenas
+                                            ),
+                                    }
+                                ));
                             }
                             WhichTrait::Eq|WhichTrait::PartialOrd=>{
                                 set_impld(&mut trait_struct.partial_eq,span);
@@ -684,7 +1260,9 @@ where
                             .collect::<Vec<String>>();
 
                         panic!(
-                            "Unexpected supertrait bound:\n\t{}\nExpected one of:\n{}\n", 
+                            "Unexpected supertrait bound:\n\t{}\nExpected one of:\n{}\n\
+                             #[sabi_trait] supertraits must be listed in the \
+                             #[sabi(sabi_supertraits(...))] attribute.\n",
                             supertrait_bound.into_token_stream(),
                             list.join("/"),
                         );
@@ -742,9 +1320,25 @@ where
     }
 
 
+    for supertrait in sabi_supertraits {
+        let is_supertrait=user_supertraits.iter().any(|x|{
+            x.bound.path.segments.last()
+                .map_or(false,|last| last.into_value().ident==*supertrait )
+        });
+        if !is_supertrait {
+            panic!(
+                "\n'{}' is in the #[sabi(sabi_supertraits(...))] attribute \
+                 but is not a supertrait of the trait.\n\n",
+                supertrait,
+            );
+        }
+    }
+
+
     GetSupertraits{
         impld_traits,
         unimpld_traits,
+        user_supertraits,
         lifetime_bounds,
         iterator_item,
         deserialize_bound,
@@ -803,7 +1397,6 @@ fn type_as_iter_path_arguments(ty:syn::Type)->syn::PathArguments{
 
 
 /// Extracts the lifetime in `Deserialize<'lt>` out of a path component.
-#[allow(dead_code)]
 fn extract_deserialize_lifetime<'a>(
     last_path_component:&syn::PathSegment,
     arenas:&'a Arenas,
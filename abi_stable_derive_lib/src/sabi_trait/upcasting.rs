@@ -0,0 +1,265 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use quote::{ToTokens,quote};
+
+use crate::{
+    gen_params_in::{InWhat},
+    sabi_trait::{
+        class_vtable,
+        WhichItem,
+        WhichObject,
+        WhichSelf,
+        WithAssocTys,
+        TokenizerParams,
+    },
+};
+
+/// Generates the code that allows using the trait as a supertrait of other
+/// `#[sabi_trait]` traits,
+/// and the code that converts the trait object into those of its `#[sabi_trait]` supertraits.
+pub(super) fn upcasting_items<'a>(
+    params:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    let TokenizerParams{ctokens,trait_def,totrait_def,trait_to,..}=params;
+
+    // Traits with associated types can't be implemented for the trait objects of subtraits,
+    // since the associated types would be unconstrained.
+    if totrait_def.which_object==WhichObject::RObject && trait_def.assoc_tys.is_empty() {
+        supertrait_items(params,mod_);
+    }
+
+    let gen_params_header=
+        totrait_def.generics_tokenizer(
+            InWhat::ImplHeader,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &ctokens.ts_lt_erasedptr,
+        );
+    let gen_params_use_to=
+        totrait_def.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &ctokens.ts_lt_erasedptr,
+        );
+
+    let impl_where_preds=totrait_def.trait_impl_where_preds();
+
+    for supertrait in &totrait_def.user_supertraits {
+        let interface=&supertrait.interface;
+        let vtable_field=&supertrait.vtable_field;
+
+        quote!(
+            unsafe impl<#gen_params_header> __sabi_re::UpcastTo<#interface>
+            for #trait_to<#gen_params_use_to>
+            where
+                #interface:__sabi_re::SabiTraitObject<'lt,_ErasedPtr>,
+                #impl_where_preds
+            {
+                type Target=<#interface as __sabi_re::SabiTraitObject<'lt,_ErasedPtr>>::Object;
+
+                type ErasedPtr=_ErasedPtr;
+
+                fn sabi_upcast(self)->Self::Target{
+                    let vtable=self.obj.sabi_et_vtable().#vtable_field();
+                    let obj=unsafe{ self.obj.sabi_into_supertrait(vtable) };
+                    <#interface as __sabi_re::SabiTraitObject<'lt,_ErasedPtr>>::sabi_from_robject(obj)
+                }
+
+                fn sabi_supertrait_vtable(&self)->__sabi_re::StaticRef<
+                    <#interface as __sabi_re::SabiTraitInterface<_ErasedPtr>>::VTable
+                >
+                where
+                    #interface:__sabi_re::SabiTraitInterface<_ErasedPtr>
+                {
+                    self.obj.sabi_et_vtable().#vtable_field()
+                }
+
+                fn sabi_erased_ref(&self)->&__ErasedObject<()>
+                where
+                    _ErasedPtr:__DerefTrait<Target=()>
+                {
+                    self.obj.sabi_erased_ref()
+                }
+
+                fn sabi_erased_mut(&mut self)->&mut __ErasedObject<()>
+                where
+                    _ErasedPtr:__DerefMutTrait<Target=()>
+                {
+                    self.obj.sabi_erased_mut()
+                }
+            }
+        ).to_tokens(mod_);
+    }
+}
+
+
+/// Generates the code that allows using the trait as a supertrait of other
+/// `#[sabi_trait]` traits.
+fn supertrait_items<'a>(
+    params:TokenizerParams<'a>,
+    mod_:&mut TokenStream2,
+){
+    let TokenizerParams{
+        ctokens,trait_def,totrait_def,vtable_trait_impl,
+        trait_ident,trait_to,trait_backend,trait_interface,
+        ..
+    }=params;
+
+    let trait_interface_use=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_empty,
+    );
+
+    let erasedptr_header=totrait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_erasedptr,
+    );
+    let vtable_args=totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_unit_erasedptr,
+    );
+
+    quote!(
+        unsafe impl<#erasedptr_header> __sabi_re::SabiTraitInterface<_ErasedPtr>
+        for #trait_interface<#trait_interface_use>
+        {
+            type VTable=VTable<#vtable_args>;
+        }
+    ).to_tokens(mod_);
+
+
+    let gen_params_header=
+        totrait_def.generics_tokenizer(
+            InWhat::ImplHeader,
+            WithAssocTys::No,
+            &ctokens.ts_lt_erasedptr,
+        );
+    let gen_params_use_to=
+        totrait_def.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &ctokens.ts_lt_erasedptr,
+        );
+    let where_preds=&totrait_def.where_preds;
+
+    quote!(
+        unsafe impl<#gen_params_header> __sabi_re::SabiTraitObject<'lt,_ErasedPtr>
+        for #trait_interface<#trait_interface_use>
+        where
+            #(#where_preds,)*
+        {
+            type Object=#trait_to<#gen_params_use_to>;
+
+            fn sabi_from_robject(obj:#trait_backend<#gen_params_use_to>)->Self::Object{
+                #trait_to::from_sabi(obj)
+            }
+        }
+    ).to_tokens(mod_);
+
+
+    let getvtable_header=vtable_trait_impl.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_getvtable_params,
+    );
+    let makevtable_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.ts_getvtable_params,
+    );
+    let trait_generics=vtable_trait_impl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.empty_ts
+    );
+    let supertrait_interfaces=vtable_trait_impl.user_supertraits.iter().map(|x| &x.interface );
+    let class_bounds=class_vtable::class_vtable_bounds(params,&quote!(IA),&quote!(_Self));
+
+    quote!(
+        unsafe impl<#getvtable_header>
+            __sabi_re::GetSabiTraitVTable<IA,_Self,_ErasedPtr,_OrigPtr>
+        for #trait_interface<#trait_interface_use>
+        where
+            _Self:#trait_ident<#trait_generics>,
+            #trait_interface<#trait_interface_use>:
+                __sabi_re::GetRObjectVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
+            #(
+                #supertrait_interfaces:
+                    __sabi_re::GetSabiTraitVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
+            )*
+            #class_bounds
+        {
+            const SABI_TRAIT_VTABLE:__sabi_re::StaticRef<VTable<#vtable_args>>=
+                MakeVTable::<#makevtable_generics>::VTABLE;
+        }
+    ).to_tokens(mod_);
+
+
+    let from_header=totrait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_lt_erasedptr_usub,
+    );
+    let where_preds=&totrait_def.where_preds;
+
+    quote!(
+        impl<#from_header> ::std::convert::From<__Sub> for #trait_to<#gen_params_use_to>
+        where
+            __Sub:__sabi_re::UpcastTo<#trait_interface<#trait_interface_use>,Target=Self>,
+            #(#where_preds,)*
+        {
+            fn from(this:__Sub)->Self{
+                __sabi_re::UpcastTo::sabi_upcast(this)
+            }
+        }
+    ).to_tokens(mod_);
+
+
+    // The trait objects of subtraits can't implement the static methods/associated constants
+    // of this trait.
+    if trait_def.has_static_items() {
+        return;
+    }
+
+    let sub_header=trait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::No,
+        &ctokens.ts_usub,
+    );
+    let trait_generics=trait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.empty_ts
+    );
+    let super_traits=trait_def.impld_traits.iter().map(|t| &t.bound );
+    let user_supertraits=trait_def.user_supertraits.iter().map(|t| t.bound );
+    let lifetime_bounds=&*trait_def.lifetime_bounds;
+    let where_preds=&trait_def.where_preds;
+    let methods_tokenizer=trait_def.methods_tokenizer(WhichItem::SubtraitObjectImpl);
+
+    let upcast_to=quote!(
+        __sabi_re::UpcastTo<#trait_interface<#trait_interface_use>>
+    );
+    let erased_ptr_bounds=match (trait_def.has_mut_methods,trait_def.has_val_methods) {
+        (false,false)=>quote!( __DerefTrait<Target=()> ),
+        (false,true )=>quote!( __DerefTrait<Target=()>+__sabi_re::OwnedPointer<Target=()> ),
+        (true ,false)=>quote!( __DerefMutTrait<Target=()> ),
+        (true ,true )=>quote!( __DerefMutTrait<Target=()>+__sabi_re::OwnedPointer<Target=()> ),
+    };
+
+    quote!(
+        impl<#sub_header> #trait_ident<#trait_generics> for __Sub
+        where
+            __Sub:#upcast_to,
+            <__Sub as #upcast_to>::Target:#trait_ident<#trait_generics>,
+            <__Sub as #upcast_to>::ErasedPtr:#erased_ptr_bounds,
+            Self:#( #super_traits + )* #( #user_supertraits + )* #(#lifetime_bounds+)* Sized ,
+            #(#where_preds,)*
+        {
+            #methods_tokenizer
+        }
+    ).to_tokens(mod_);
+}
@@ -145,6 +145,23 @@ pub(crate) fn derive(mut data: DeriveInput) -> TokenStream2 {
         }
     });
 
+    let extra_checks_opt=&config.extra_checks;
+    let extra_checks=ToTokenFnMut::new(move|ts|{
+        match &extra_checks_opt {
+            Some(extra_checks)=>{
+                quote!(
+                    _sabi_reexports::RSome(_sabi_reexports::ExtraChecks{
+                        check:#extra_checks,
+                    })
+                ).to_tokens(ts);
+            }
+            None=>{
+                quote!( _sabi_reexports::RNone )
+                    .to_tokens(ts);
+            }
+        }
+    });
+
     let nonexhaustive_items=tokenize_nonexhaustive_items(&module,ds,config,ctokens);
     let nonexhaustive_tokens=tokenize_enum_info(ds,config,ctokens);
 
@@ -198,6 +215,9 @@ pub(crate) fn derive(mut data: DeriveInput) -> TokenStream2 {
                         <#name #ty_generics as 
                             _sabi_reexports::PrefixTypeTrait
                         >::PT_FIELD_ACCESSIBILITY,
+                        <#name #ty_generics as 
+                            _sabi_reexports::PrefixTypeTrait
+                        >::PT_MORE_FIELD_ACCESSIBILITY,
                         <#name #ty_generics as 
                             _sabi_reexports::PrefixTypeTrait
                         >::PT_COND_PREFIX_FIELDS,
@@ -379,6 +399,7 @@ pub(crate) fn derive(mut data: DeriveInput) -> TokenStream2 {
                             tag:#tags,
                             mod_refl_mode:#mod_refl_mode,
                             repr_attr:#repr,
+                            extra_checks:#extra_checks,
                         }
                     )
                 };
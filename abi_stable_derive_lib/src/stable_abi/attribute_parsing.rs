@@ -58,6 +58,9 @@ pub(crate) struct StableAbiOptions<'a> {
 
     pub(crate) tags:Option<syn::Expr>,
 
+    /// The function used to do extra checks in the layout checker.
+    pub(crate) extra_checks:Option<syn::Expr>,
+
     /// A hashset of the fields whose contents are opaque 
     /// (there are still some minimal checks going on).
     pub(crate) opaque_fields:FieldMap<bool>,
@@ -236,6 +239,7 @@ impl<'a> StableAbiOptions<'a> {
             changed_types:this.changed_types,
             override_field_accessor:this.override_field_accessor,
             tags:this.tags,
+            extra_checks:this.extra_checks,
             impl_interfacetype:this.impl_interfacetype,
             phantom_fields,
             phantom_type_params:this.phantom_type_params,
@@ -257,6 +261,7 @@ struct StableAbiAttrs<'a> {
 
     tags:Option<syn::Expr>,
 
+    extra_checks:Option<syn::Expr>,
 
     first_suffix_field:FirstSuffixField,
     default_on_missing_fields:OnMissingField<'a>,
@@ -535,6 +540,28 @@ Tag:\n\t{}\n",
             };
             this.tags=Some(bound);
         }
+        (
+            ParseContext::TypeAttr{..},
+            Meta::NameValue(MetaNameValue{lit:Lit::Str(ref unparsed_func),ref ident,..})
+        )if ident=="extra_checks" =>
+        {
+            if this.extra_checks.is_some() {
+                panic!(
+                    "\n\nCannot specify multiple extra_checks functions:\n\t{}\n\n",
+                    unparsed_func.value(),
+                );
+            }
+
+            let func=match unparsed_func.parse::<syn::Expr>() {
+                Ok(v)=>v,
+                Err(e)=>panic!(
+                    "\n\nInvalid extra_checks expression:\n\t{}\nError:\n\t{}\n\n",
+                    unparsed_func.value(),
+                    e
+                ),
+            };
+            this.extra_checks=Some(func);
+        }
         (ParseContext::TypeAttr{name},Meta::List(list)) => {
             if list.ident == "override" {
                 with_nested_meta("override", list.nested, |attr| {
@@ -182,10 +182,6 @@ pub(crate) fn prefix_type_tokenizer<'a>(
             _=>return,
         };
 
-        if struct_.fields.len() > 64 {
-            panic!("\n\n`#[sabi(kind(Prefix(..)))]` structs cannot have more than 64 fields\n\n");
-        }
-
         // let repr_attrs=ToTokenFnMut::new(move|ts|{
         //     for list in &config.repr_attrs {
         //         ct.pound.to_tokens(ts);
@@ -224,6 +220,13 @@ Call the `PrefixTypeTrait::leak_into_prefix` method on the
 which converts it to `&{name}{generics}`,
 leaking it in the process.
 
+### Creating an owned runtime value
+
+Using `abi_stable::prefix_type::{{PrefixBox,PrefixArc}}`.<br>
+Call `PrefixBox::new` or `PrefixArc::new` with a 
+`{deriving_name}{generics}` value,
+which moves it to the heap and returns a pointer that dereferences to `{name}{generics}`.
+
 ### Creating a compiletime-constant
 
 Using `abi_stable::prefix_type::{{PrefixTypeTrait,WithMetadata}}`.<br>
@@ -425,11 +428,26 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
 
                     let field_mask_ident=&field_mask_idents[field_i];
 
+                    // The accessibility of the first 64 fields is stored in the
+                    // `WithMetadata` header,the rest is stored in the layout.
+                    let acc_bits=if field_i < 64 {
+                        quote_spanned!(field_span=>
+                            self.inner._prefix_type_field_acc.bits()
+                        )
+                    }else{
+                        let word=field_i/64-1;
+                        quote_spanned!(field_span=>
+                            self.inner._prefix_type_layout.more_accessible_fields.as_slice()
+                                .get(#word)
+                                .map_or(0,|x| x.bits() )
+                        )
+                    };
+
                     conditional_accessors.push(quote_spanned!{field_span=>
                         #vis fn #getter_name(&self)->#return_ty
                         #field_where_clause #( #field_bounds+ )*
                         {
-                            let acc_bits=self.inner._prefix_type_field_acc.bits();
+                            let acc_bits=#acc_bits;
                             let val=if (Self::#field_mask_ident & acc_bits)==0 {
                                 #else_
                             }else{
@@ -453,9 +471,61 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
                 })
                 .collect::<Vec<(usize,&syn::Expr)>>();
 
-        let disabled_field_indices=conditional_fields.iter().map(|&(field_i,_)| field_i );
+        let disabled_field_indices=conditional_fields.iter()
+            .filter(|&&(field_i,_)| field_i < 64 )
+            .map(|&(field_i,_)| field_i );
+
+        let enable_field_if=conditional_fields.iter()
+            .filter(|&&(field_i,_)| field_i < 64 )
+            .map(|&(_,cond)| cond );
+
+        // The accessibility of the fields after the first 64,
+        // each element of the slice describes the next 64 fields.
+        let more_field_accessibility=(64..field_count).step_by(64)
+            .map(|first_field|{
+                let word_field_count=field_count-first_field;
+                let (disabled_field_indices,enable_field_if):(Vec<usize>,Vec<&syn::Expr>)=
+                    conditional_fields.iter()
+                        .filter(|&&(field_i,_)| first_field<=field_i && field_i<first_field+64 )
+                        .map(|&(field_i,cond)| (field_i-first_field,cond) )
+                        .unzip();
+                quote!(
+                    __FieldAccessibility::with_field_count(#word_field_count)
+                    #(
+                        .set_accessibility(
+                            #disabled_field_indices,
+                            __IsAccessible::new(#enable_field_if)
+                        )
+                    )*
+                )
+            });
+
+        let field_masks=(0..field_count)
+            .map(|field_i|{
+                let bit=(field_i%64) as u32;
+                if field_i < 64 {
+                    quote!( (1<<#bit) & Self::__AB_PTT_FIELD_ACCESSIBILTIY_MASK )
+                }else{
+                    let word=field_i/64-1;
+                    quote!(
+                        (1<<#bit) & 
+                        <#deriving_name #ty_generics as 
+                            #module::_sabi_reexports::PrefixTypeTrait 
+                        >::PT_MORE_FIELD_ACCESSIBILITY[#word].bits()
+                    )
+                }
+            });
+
+        let mut pt_layout_ident=parse_str_as_ident(&format!("__sabi_PT_LAYOUT{}",deriving_name));
+        pt_layout_ident.set_span(deriving_name.span());
 
-        let enable_field_if=conditional_fields.iter().map(|&(_,cond)| cond );
+        let pt_layout=if field_count > 64 {
+            quote!(
+                &#pt_layout_ident.with_more_accessible_fields(Self::PT_MORE_FIELD_ACCESSIBILITY)
+            )
+        }else{
+            quote!( &#pt_layout_ident )
+        };
 
         let field_name_list=struct_.fields.iter()
             .map(|x| x.ident().to_string() )
@@ -467,21 +537,17 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
             .take(prefix.first_suffix_field.field_pos)
             .map(|f| prefix.fields[f].is_conditional() );
 
-        let field_i_a=0u8..;
-        let field_i_b=0u8..;
-
-        let mut pt_layout_ident=parse_str_as_ident(&format!("__sabi_PT_LAYOUT{}",deriving_name));
-        pt_layout_ident.set_span(deriving_name.span());
+        let field_i_a=0usize..;
 
         quote!(
 
             #[allow(non_upper_case_globals)]
-            const #pt_layout_ident:&'static #module::__PTStructLayout ={
+            const #pt_layout_ident:#module::__PTStructLayout ={
                 use #module::_sabi_reexports::renamed::{
                     __PTStructLayout,__PTStructLayoutParams,
                 };
 
-                &__PTStructLayout::new(__PTStructLayoutParams{
+                __PTStructLayout::new(__PTStructLayoutParams{
                     name:#stringified_deriving_name,
                     generics:#stringified_generics,
                     package: env!("CARGO_PKG_NAME"),
@@ -513,6 +579,16 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
                         )
                     )*
                 };
+
+                const PT_MORE_FIELD_ACCESSIBILITY:
+                    &'static [#module::_sabi_reexports::FieldAccessibility]=
+                {
+                    use self::#module::_sabi_reexports::{
+                        FieldAccessibility as __FieldAccessibility,
+                        IsAccessible as __IsAccessible,
+                    };
+                    &[ #( #more_field_accessibility ,)* ]
+                };
                 
                 const PT_COND_PREFIX_FIELDS:&'static [#module::_sabi_reexports::IsConditional]={
                     use #module::_sabi_reexports::IsConditional as __IsConditional;
@@ -522,7 +598,7 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
                     ]
                 };
 
-                const PT_LAYOUT:&'static #module::__PTStructLayout =#pt_layout_ident;
+                const PT_LAYOUT:&'static #module::__PTStructLayout =#pt_layout;
 
                 type Prefix=#prefix_struct #ty_generics;
             }
@@ -538,7 +614,7 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
                 )*
 
                 #(
-                    const #field_index_for:u8=
+                    const #field_index_for:usize=
                         #field_i_a;
                 )*
 
@@ -570,7 +646,7 @@ then use the `as_prefix` method at runtime to cast it to `&{name}{generics}`.
 
                 #(
                     const #field_mask_idents:u64=
-                        (1<<#field_i_b) & Self::__AB_PTT_FIELD_ACCESSIBILTIY_MASK;
+                        #field_masks;
                 )*
 
                 /// Accessor to get the layout of the type.
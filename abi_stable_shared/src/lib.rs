@@ -69,3 +69,15 @@ pub fn mangled_root_module_loader_name()->String{
 }
 
 
+
+
+/// Gets the name of the static that `#[export_root_module]` stores the 
+/// metadata of an abi_stable library in.
+pub fn mangled_lib_metadata_name()->String{
+    mangle_ident("lib_metadata","library metadata")
+}
+
+
+/// The name of the file,in the `OUT_DIR` directory of the build script,
+/// that `#[export_root_module]` includes in the metadata of the library.
+pub const LIB_METADATA_FILE:&str="abi_stable_lib_metadata.rs";
@@ -0,0 +1,9 @@
+use abi_stable::library::RootModuleMetadata;
+
+use testing_interface_0::TestingMod;
+
+fn main(){
+    RootModuleMetadata::new::<TestingMod>()
+        .write_to_out_dir()
+        .unwrap();
+}
@@ -10,9 +10,11 @@ use testing_interface_0::{
 use abi_stable::{
     export_root_module,
     extern_fn_panic_handling, 
+    erased_types::interfaces::CloneInterface,
     prefix_type::PrefixTypeTrait,
     traits::{IntoReprC},
     std_types::{RStr,RBox,RVec,RArc, RString}, 
+    DynTrait,
 };
 #[allow(unused_imports)]
 use core_extensions::{SelfOps};
@@ -32,6 +34,7 @@ pub fn get_library() -> &'static TestingMod {
         prefix_types_tests:PrefixTypeMod0Val{
             field_a:123,
         }.leak_into_prefix(),
+        new_clonable,
     }.leak_into_prefix()
 }
 
@@ -64,3 +67,10 @@ pub extern "C" fn for_tests()->ForTests{
         }
     }
 }
+
+
+pub extern "C" fn new_clonable()->DynTrait<'static,RBox<()>,CloneInterface>{
+    extern_fn_panic_handling!{
+        DynTrait::from_any_value(RString::from("hello"),CloneInterface)
+    }
+}
@@ -13,12 +13,16 @@ and then all the modules inside of the library.
 */
 
 
+use std::sync::atomic::{AtomicUsize,Ordering};
+
 use abi_stable::{
     StableAbi,
     package_version_strings,
-    library::RootModule,
+    erased_types::interfaces::CloneInterface,
+    library::{LibraryError,RootModule},
     sabi_types::VersionStrings,
     std_types::{RBox, RStr, RString,RVec,RArc},
+    DynTrait,
 };
 
 
@@ -30,9 +34,18 @@ impl RootModule for TestingMod {
     const BASE_NAME: &'static str = "testing";
     const NAME: &'static str = "testing";
     const VERSION_STRINGS: VersionStrings = package_version_strings!();
+
+    fn initialization(self: &'static Self) -> Result<&'static Self, LibraryError> {
+        INITIALIZATION_COUNT.fetch_add(1,Ordering::SeqCst);
+        Ok(self)
+    }
 }
 
 
+/// How many times `TestingMod::initialization` was called.
+pub static INITIALIZATION_COUNT:AtomicUsize=AtomicUsize::new(0);
+
+
 #[repr(C)]
 #[derive(StableAbi)] 
 #[sabi(kind(Prefix(prefix_struct="TestingMod")))]
@@ -44,6 +57,9 @@ pub struct TestingModVal {
 
     /// An module used in prefix-type tests.
     pub prefix_types_tests:&'static PrefixTypeMod0,
+
+    /// Used to test that `DynTrait`s keep the library that created them loaded.
+    pub new_clonable:extern "C" fn()->DynTrait<'static,RBox<()>,CloneInterface>,
 }
 
 
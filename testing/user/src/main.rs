@@ -1,18 +1,22 @@
 use std::{
     path::{Path,PathBuf},
     io,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::Ordering,
+    },
 };
 
 
 use core_extensions::SelfOps;
 
 use abi_stable::{
+    abi_stability::layout_cache,
     std_types::{RString,RVec,RArc,RBox},
-    library::RootModule,
+    library::{LibrarySuffix,LoadedLibrary,PluginRegistry,RawLibrary,RootModule},
 };
 
-use testing_interface_0::{TestingMod,PrefixTypeMod0,PrefixTypeMod1};
+use testing_interface_0::{TestingMod,PrefixTypeMod0,PrefixTypeMod1,INITIALIZATION_COUNT};
 
 
 
@@ -46,12 +50,39 @@ unsafe fn transmute_reference<T,U>(ref_:&T)->&U{
 
 
 fn main()-> io::Result<()> {
+    let layout_cache_path=std::env::temp_dir()
+        .join(format!("testing_layout_cache_{}.txt",std::process::id()));
+    layout_cache::enable_layout_check_cache(&layout_cache_path)?;
+
     let library_path=compute_library_path().unwrap();
     let mods=TestingMod::load_from_directory(&library_path)
         .unwrap_or_else(|e| panic!("{}", e) );
+
+    // Loading the library stored the successful layout check in the cache.
+    let cached=std::fs::read_to_string(&layout_cache_path)?;
+    assert_eq!(cached.lines().count(),1,"{}",cached);
+    layout_cache::disable_layout_check_cache();
+    let _=std::fs::remove_file(&layout_cache_path);
     
     run_dynamic_library_tests(mods);
 
+    run_instance_tests(&library_path,mods);
+
+    run_unloadable_library_tests(&library_path);
+
+    run_plugin_registry_tests(&library_path);
+
+    #[cfg(target_os="linux")]
+    run_library_unloading_tests(&library_path);
+
+    #[cfg(target_os="linux")]
+    run_lib_metadata_tests(&library_path);
+
+    println!();
+    println!(".-------------------------.");
+    println!("|     tests succeeded!    |");
+    println!("'-------------------------'");
+
     Ok(())
 }
 
@@ -114,9 +145,164 @@ pub fn run_dynamic_library_tests(mods:&'static TestingMod){
         );
     }
     
-    println!();
-    println!(".-------------------------.");
-    println!("|     tests succeeded!    |");
-    println!("'-------------------------'");
+}
+
+
+/// Tests loading the library as an instance,
+/// which isn't stored in the statics of TestingMod.
+pub fn run_instance_tests(library_path:&Path,mods:&'static TestingMod){
+    let instance=TestingMod::load_instance_from_directory(library_path)
+        .unwrap_or_else(|e| panic!("{}", e) );
+    let instance_again=TestingMod::load_instance_from_directory(library_path)
+        .unwrap_or_else(|e| panic!("{}", e) );
+
+    // The library was already loaded by `load_from_directory`,
+    // so it's not initialized again.
+    assert_eq!(INITIALIZATION_COUNT.load(Ordering::SeqCst),1);
+    assert!(std::ptr::eq(instance,instance_again));
+
+    // The same library is only loaded once,so this is the same root module.
+    assert!(std::ptr::eq(instance,mods));
+    assert!(std::ptr::eq(TestingMod::get_module().unwrap(),mods));
+    assert_eq!(instance.prefix_types_tests().field_a(),123);
+}
+
+
+/// Tests loading the library as an unloadable library,
+/// which is independent from the root module loaded with `load_from_directory`.
+pub fn run_unloadable_library_tests(library_path:&Path){
+    let library=unsafe{
+        TestingMod::load_unloadable_from_directory(library_path)
+            .unwrap_or_else(|e| panic!("{}", e) )
+    };
+    assert_eq!(LoadedLibrary::handle_count(&library),1);
+    assert_eq!(library.prefix_types_tests().field_a(),123);
+
+    let val=library.pin(library.for_tests()());
+    assert_eq!(LoadedLibrary::handle_count(&library),2);
+
+    let clone=library.clone();
+    assert_eq!(LoadedLibrary::handle_count(&library),3);
+    drop(clone);
+    drop(library);
+
+    assert_eq!(&**val.arc,"hello");
+    assert_eq!(&*val.string,"what the foo.");
+
+    drop(val);
+}
+
+
+/// Tests loading the library as a plugin,with a `PluginRegistry`.
+pub fn run_plugin_registry_tests(library_path:&Path){
+    use std::fs;
+
+    let directory=std::env::temp_dir()
+        .join(format!("testing_plugin_registry_{}",std::process::id()));
+    let _=fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    let original=TestingMod::get_library_path(library_path);
+    let plugin=directory.join(original.file_name().unwrap());
+    fs::copy(&original,&plugin).unwrap();
+    
+    let broken=RawLibrary::path_in_directory(&directory,"broken",LibrarySuffix::NoSuffix);
+    fs::write(&broken,b"not a dynamic library").unwrap();
+
+    let mut registry=PluginRegistry::<TestingMod>::new(LibrarySuffix::NoSuffix);
+    registry.add_directory(directory.clone());
+
+    let report=registry.scan();
+
+    let loaded=report.loaded.iter().map(|p| p.path().to_owned() ).collect::<Vec<_>>();
+    assert_eq!(loaded,vec![plugin.clone()]);
+    assert_eq!(report.loaded[0].base_name(),TestingMod::BASE_NAME);
+    assert_eq!(report.loaded[0].module().prefix_types_tests().field_a(),123);
+
+    let errors=report.errors.iter().map(|e| e.path.clone() ).collect::<Vec<_>>();
+    assert_eq!(errors,vec![broken]);
+
+    let module=registry.get(TestingMod::BASE_NAME).unwrap();
+    assert!(std::ptr::eq(module,report.loaded[0].module()));
+    assert_eq!(registry.plugins().len(),1);
+
+    // Already loaded plugins are not loaded again.
+    let rescan=registry.scan();
+    assert!(rescan.loaded.is_empty());
+    assert_eq!(rescan.errors.len(),1);
+    assert_eq!(registry.plugins().len(),1);
+
+    let _=fs::remove_dir_all(&directory);
+}
+
+
+/// Tests reading the metadata that `#[export_root_module]` stores in the library,
+/// including the metadata of the root module written by the build script of the library.
+#[cfg(target_os="linux")]
+pub fn run_lib_metadata_tests(library_path:&Path){
+    use abi_stable::library::{AbiHeader,lib_metadata_from_path};
+
+    let path=TestingMod::get_library_path(library_path);
+    let metadata=lib_metadata_from_path(&path)
+        .unwrap_or_else(|e| panic!("{}", e) );
+
+    assert!(metadata.abi_header.is_compatible(&AbiHeader::VALUE));
+
+    let root_module=metadata.root_module.expect("the build script writes the metadata");
+    assert_eq!(root_module.base_name,TestingMod::BASE_NAME);
+    assert_eq!(root_module.name,TestingMod::NAME);
+    assert_eq!(root_module.version,TestingMod::VERSION_STRINGS.version.as_str());
+
+    let layout=root_module.layout.expect("abi_stable enables serde_json by default");
+    assert!(layout.contains("greeter"),"{}",layout);
+}
+
+
+/// Tests that an unloadable library is unloaded once every handle to it,
+/// and every `DynTrait` it created,are dropped.
+#[cfg(target_os="linux")]
+pub fn run_library_unloading_tests(library_path:&Path){
+    use std::{env,fs,process};
+
+    // A copy of the library is loaded,
+    // since the original is never unloaded after `load_from_directory` loads it.
+    let original=TestingMod::get_library_path(library_path);
+    let copy=env::temp_dir().join(format!(
+        "{}_{}",
+        process::id(),
+        original.file_name().unwrap().to_string_lossy(),
+    ));
+    fs::copy(&original,&copy).unwrap();
+    let copy=fs::canonicalize(&copy).unwrap();
+
+    let library=unsafe{
+        TestingMod::load_unloadable_from_file(&copy)
+            .unwrap_or_else(|e| panic!("{}", e) )
+    };
+    assert!(is_library_mapped(&copy));
+
+    let object=library.new_clonable()();
+    assert_eq!(LoadedLibrary::handle_count(&library),2);
+
+    let clone=object.clone();
+    assert_eq!(LoadedLibrary::handle_count(&library),3);
+
+    drop(library);
+    drop(object);
+    assert!(is_library_mapped(&copy));
+
+    drop(clone);
+    assert!(!is_library_mapped(&copy),"the library was not unloaded");
+
+    fs::remove_file(&copy).unwrap();
+}
+
+
+/// Whether the dynamic library at `path` is mapped into the memory of this process.
+#[cfg(target_os="linux")]
+fn is_library_mapped(path:&Path)->bool{
+    std::fs::read_to_string("/proc/self/maps")
+        .unwrap()
+        .contains(&*path.to_string_lossy())
 }
 
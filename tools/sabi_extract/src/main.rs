@@ -5,13 +5,20 @@ use std::{
 
 
 use abi_stable::{
-    //abi_stability::check_layout_compatibility,
-    reflection::export_module::MRItem,
+    abi_stability::{
+        AbiInfoWrapper,
+        layout_diff::{LayoutDiff,diff_layouts},
+        layout_snapshot::LayoutSnapshot,
+    },
+    reflection::{ModReflMode,export_module::MRItem},
     library::lib_header_from_path,
+    type_layout::{TLData,TLField,TypeLayout},
 };
 
 use core_extensions::SelfOps;
 
+use serde_json::{json,Value as JsonValue};
+
 use structopt::StructOpt;
 
 
@@ -45,6 +52,73 @@ with the typenames of each function parameter/return type.
         #[structopt(long = "--compact",)]
         compact_json:bool
     },
+
+/**
+Outputs a json snapshot of the layout of the root module of an abi_stable library,
+which can be passed to the `diff` subcommand in place of the library.
+*/
+    #[structopt(name = "snapshot")]
+    #[structopt(author="_")]
+    Snapshot {
+        /// The path to the library.
+        library_path:PathBuf,
+
+        /// Which file to output the snapshot to,outputs it to stdout by default.
+        #[structopt(short = "o",long="out-file")]
+        #[structopt(parse(from_os_str))]
+        output_file:Option<PathBuf>,
+    },
+
+/**
+Compares the root module of two versions of an abi_stable library,
+reporting the breaking and compatible changes of the newer version.
+
+The older version can be a json file outputted by the `snapshot` subcommand,
+which is checked the same way as a library.
+
+The older version can also be a json file outputted by the `mods` subcommand,
+in which case only the module structure is compared,
+and added items are only compatible if they are fields of a prefix type
+that come after the last prefix field.
+
+This exits with an error code if there are breaking changes.
+*/
+    #[structopt(name = "diff")]
+    #[structopt(author="_")]
+    Diff {
+        /// The path to the older version of the library,
+        /// or to a json file outputted by the `snapshot` or `mods` subcommands.
+        old_path:PathBuf,
+
+        /// The path to the newer version of the library.
+        new_path:PathBuf,
+
+        /// Whether to output the report as json.
+        #[structopt(long = "--json",)]
+        output_json:bool,
+    },
+}
+
+
+/// The changes between two versions of a library.
+#[derive(Default)]
+struct DiffReport{
+    breaking:Vec<String>,
+    compatible:Vec<String>,
+}
+
+impl DiffReport{
+    fn from_layout_diff(diff:&LayoutDiff)->Self{
+        DiffReport{
+            breaking:diff.errors.iter()
+                .flat_map(|errs| errs.errors.iter() )
+                .map(|err| err.to_string() )
+                .collect(),
+            compatible:diff.compatible_changes.iter()
+                .map(|change| change.to_string() )
+                .collect(),
+        }
+    }
 }
 
 
@@ -79,15 +153,7 @@ version of abi_stable to be loaded successfully.
 
     match opts {
         Command::Modules{library_path,output_file,output_stdout,compact_json}=>{
-            let lib_header=lib_header_from_path(library_path.as_ref()).unwrap();
-
-            let abi_info=lib_header.layout().unwrap_or_else(||{
-                println!(
-                    "The dynamic library does not support reflection:\n    {}",
-                    library_path.display(),
-                );
-                std::process::exit(1);
-            });
+            let abi_info=library_layout(&library_path);
 
             let root_mod=MRItem::from_abi_info(abi_info.get().layout);
 
@@ -110,7 +176,212 @@ version of abi_stable to be loaded successfully.
                 println!("{}", json );
             }
         }
+        Command::Snapshot{library_path,output_file}=>{
+            let snapshot=LayoutSnapshot::new(library_layout(&library_path));
+            let json=serde_json::to_string_pretty(&snapshot).unwrap();
+
+            match &output_file {
+                Some(output_file)=>if let Err(e)=fs::write(output_file,json) {
+                    panic!(
+                        "Error writing to file:\n{}\nError:\n{}\n", 
+                        output_file.display(),
+                        e,
+                    );
+                },
+                None=>println!("{}", json ),
+            }
+        }
+        Command::Diff{old_path,new_path,output_json}=>{
+            let new_layout=library_layout(&new_path);
+
+            let is_json=old_path.extension().map_or(false,|ext| ext=="json" );
+            let report=if is_json {
+                let old_json=fs::read_to_string(&old_path)
+                    .map_err(|e| e.to_string() )
+                    .and_then(|s| serde_json::from_str::<JsonValue>(&s).map_err(|e| e.to_string()) )
+                    .unwrap_or_else(|e|{
+                        println!("Could not read the json file:\n    {}\n{}",old_path.display(),e);
+                        std::process::exit(1);
+                    });
+                if old_json.get("types").is_some() {
+                    let old_snapshot=serde_json::from_value::<LayoutSnapshot>(old_json)
+                        .unwrap_or_else(|e|{
+                            println!("Invalid layout snapshot:\n    {}\n{}",old_path.display(),e);
+                            std::process::exit(1);
+                        });
+                    let diff=old_snapshot.diff(new_layout).unwrap_or_else(|e|{
+                        println!("Could not check the layout snapshot:\n    {}\n{}",old_path.display(),e);
+                        std::process::exit(1);
+                    });
+                    DiffReport::from_layout_diff(&diff)
+                }else{
+                    let new_json=MRItem::from_abi_info(new_layout.get().layout)
+                        .piped_ref(serde_json::to_value)
+                        .unwrap();
+                    let mut report=DiffReport::default();
+                    let new_layout=Some(new_layout.get().layout);
+                    diff_module_json(&old_json,&new_json,new_layout,"",&mut report);
+                    report
+                }
+            }else{
+                DiffReport::from_layout_diff(&diff_layouts(library_layout(&old_path),new_layout))
+            };
+
+            if output_json {
+                let json=json!({
+                    "breaking":report.breaking,
+                    "compatible":report.compatible,
+                });
+                println!("{}",serde_json::to_string_pretty(&json).unwrap());
+            }else{
+                println!("Breaking changes:{}",report.breaking.len());
+                for change in &report.breaking {
+                    println!("{}",change);
+                }
+                println!("Compatible changes:{}",report.compatible.len());
+                for change in &report.compatible {
+                    println!("    {}",change);
+                }
+            }
+
+            if !report.breaking.is_empty() {
+                std::process::exit(1);
+            }
+        }
+    }
+
+
+}
+
+
+/// Gets the layout of the root module of the library at `library_path`,
+/// exiting the process if it could not be loaded or does not include its layout.
+fn library_layout(library_path:&PathBuf)->&'static AbiInfoWrapper{
+    let lib_header=lib_header_from_path(library_path.as_ref()).unwrap_or_else(|e|{
+        println!("Could not load the dynamic library:\n    {}\n{}",library_path.display(),e);
+        std::process::exit(1);
+    });
+
+    lib_header.layout().unwrap_or_else(||{
+        println!(
+            "The dynamic library does not support reflection:\n    {}",
+            library_path.display(),
+        );
+        std::process::exit(1);
+    })
+}
+
+
+/// Compares the module structure of two versions of a library,
+/// in the json format outputted by the `mods` subcommand.
+///
+/// `new_layout` is the layout of the newer version of the item,
+/// used to determine whether added items are fields of a prefix type.
+///
+/// Removing an item or changing its type is a breaking change.
+/// Adding an item is only a compatible change if it is a field of a prefix type,
+/// after the last prefix field and after every field of the older version.
+fn diff_module_json(
+    old:&JsonValue,
+    new:&JsonValue,
+    new_layout:Option<&'static TypeLayout>,
+    path:&str,
+    report:&mut DiffReport,
+){
+    if old["type_"]!=new["type_"] {
+        report.breaking.push(format!(
+            "{}:changed type from '{}' to '{}'",
+            display_path(path),
+            old["type_"].as_str().unwrap_or(""),
+            new["type_"].as_str().unwrap_or(""),
+        ));
+        return;
     }
 
+    let empty=Vec::new();
+    let old_items=old["items"].as_array().unwrap_or(&empty);
+    let new_items=new["items"].as_array().unwrap_or(&empty);
+    let item_path=|item:&JsonValue|{
+        format!("{}::{}",path,item["item_name"].as_str().unwrap_or(""))
+    };
+    let find_item=|items:&[JsonValue],name:&JsonValue|{
+        items.iter().find(|item| item["item_name"]==*name ).cloned()
+    };
+
+    let (new_fields,prefix_field_count)=match new_layout.and_then(module_fields) {
+        Some((fields,prefix_field_count))=>(fields,prefix_field_count),
+        None=>(Vec::new(),None),
+    };
+    let field_index=|item:&JsonValue|{
+        let name=item["item_name"].as_str()?;
+        new_fields.iter().position(|field| field.name.as_str()==name )
+    };
+
+    for old_item in old_items {
+        match find_item(new_items,&old_item["item_name"]) {
+            Some(new_item)=>{
+                let new_item_layout=field_index(&new_item)
+                    .map(|i| new_fields[i] )
+                    .filter(|field| !field.is_function )
+                    .map(|field| field.abi_info.get().layout );
+                diff_module_json(old_item,&new_item,new_item_layout,&item_path(old_item),report)
+            }
+            None=>report.breaking.push(format!("{}:removed",display_path(&item_path(old_item)))),
+        }
+    }
+
+    let last_old_field=old_items.iter().filter_map(|item| field_index(item) ).max();
+
+    for new_item in new_items {
+        if find_item(old_items,&new_item["item_name"]).is_some() {
+            continue;
+        }
+        let added_path=item_path(new_item);
+        let added_path=display_path(&added_path);
+        match (prefix_field_count,field_index(new_item)) {
+            (Some(prefix_field_count),Some(index))
+            if index>=prefix_field_count && last_old_field.map_or(true,|last| index>last) =>{
+                report.compatible.push(format!("{}:added",added_path));
+            }
+            (Some(prefix_field_count),Some(index)) if index<prefix_field_count=>{
+                report.breaking.push(format!("{}:added as a prefix field",added_path));
+            }
+            (Some(_),_)=>{
+                report.breaking.push(format!(
+                    "{}:added before a field of the older version",
+                    added_path,
+                ));
+            }
+            (None,_)=>{
+                report.breaking.push(format!(
+                    "{}:added to a type that is not a prefix type",
+                    added_path,
+                ));
+            }
+        }
+    }
+}
+
+
+/// Gets the fields of a type that the `mods` subcommand outputs as a module,
+/// and the amount of prefix fields if it is a prefix type.
+fn module_fields(layout:&'static TypeLayout)->Option<(Vec<TLField>,Option<usize>)>{
+    match layout.mod_refl_mode {
+        ModReflMode::Module=>match layout.data {
+            TLData::Struct{fields}=>
+                Some((fields.get_fields().collect(),None)),
+            TLData::PrefixType(prefix)=>
+                Some((prefix.fields.get_fields().collect(),Some(prefix.first_suffix_field))),
+            _=>None,
+        },
+        ModReflMode::Opaque=>None,
+        ModReflMode::DelegateDeref{phantom_field_index}=>{
+            let delegate_to=layout.phantom_fields[phantom_field_index];
+            module_fields(delegate_to.abi_info.get().layout)
+        }
+    }
+}
 
+fn display_path(path:&str)->&str{
+    if path.is_empty() { "root" }else{ path.trim_start_matches("::") }
 }